@@ -1,13 +1,39 @@
 use clap::{Parser, ValueEnum};
 use graphviz_rust::cmd::{CommandArg, Format};
 use graphviz_rust::exec_dot;
-use oxigraph::io::{RdfFormat, RdfSerializer};
-use oxigraph::model::{Quad, TripleRef};
-use shacl::{InferenceConfig, Source, Validator, ValidatorBuilder};
+use oxigraph::io::{JsonLdProfileSet, RdfFormat, RdfSerializer};
+use oxigraph::model::{Literal, NamedNode, Quad, Term, TripleRef};
+use serde_json::{json, Value};
+use shacl::conformance::run_test_suite_filtered;
+use shacl::types::{CustomSeverityConformance, DuplicateShapePolicy, QueryDataset, Severity};
+use shacl::{
+    diff_heatmaps, FetchPolicy, GraphLoadError, HeatEntry, HeatEntryKind, InferenceConfig,
+    MessageCatalog, Redaction, ShapesVersionRequirement, Source, TestFilter, TestStatus,
+    TestStatusKind, ValidationOptions, Validator, ValidatorBuilder,
+};
 use std::collections::HashMap;
 use std::fs;
-use std::io::{self, Write};
-use std::path::PathBuf;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Exit status used when a shapes or data file is not valid RDF, as opposed
+/// to a graph that parses but violates its shapes.
+const INVALID_RDF_EXIT_CODE: i32 = 2;
+
+/// Exit status used when `--fail-on` finds a result at or above its severity.
+const NON_CONFORMING_EXIT_CODE: i32 = 1;
+
+/// Exit status used when `--self-check` finds the emitted report malformed.
+const MALFORMED_REPORT_EXIT_CODE: i32 = 3;
+
+/// The W3C SHACL test suite bundled with the source tree.
+const BUNDLED_TEST_SUITE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../lib/tests/test-suite/manifest.ttl"
+);
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -20,16 +46,24 @@ struct Cli {
 #[clap(group(
     clap::ArgGroup::new("shapes_source")
         .required(true)
-        .args(&["shapes_file", "shapes_graph"]),
+        .args(&["shapes_file", "shapes_graph", "declared_shapes"]),
 ))]
 struct ShapesSourceCli {
-    /// Path to the shapes file
+    /// Path to a shapes file, or - for stdin (repeatable; the files are merged into one shapes graph)
     #[arg(short, long, value_name = "FILE")]
-    shapes_file: Option<PathBuf>,
+    shapes_file: Vec<PathBuf>,
 
-    /// URI of the shapes graph
+    /// URI of a shapes graph (repeatable)
     #[arg(long, value_name = "URI")]
-    shapes_graph: Option<String>,
+    shapes_graph: Vec<String>,
+
+    /// Use the shapes graphs the data graph declares with sh:shapesGraph
+    #[arg(long)]
+    declared_shapes: bool,
+
+    /// Parse shapes files as FORMAT instead of by their extension (stdin defaults to turtle)
+    #[arg(long, value_name = "FORMAT")]
+    shapes_format: Option<InputFormat>,
 }
 
 #[derive(Parser, Debug)]
@@ -39,13 +73,75 @@ struct ShapesSourceCli {
         .args(&["data_file", "data_graph"]),
 ))]
 struct DataSourceCli {
-    /// Path to the data file
+    /// Path to a data file, or - for stdin (repeatable; the files are merged into one data graph)
     #[arg(short, long, value_name = "FILE")]
-    data_file: Option<PathBuf>,
+    data_file: Vec<PathBuf>,
 
-    /// URI of the data graph
+    /// URI of a data graph (repeatable)
     #[arg(long, value_name = "URI")]
-    data_graph: Option<String>,
+    data_graph: Vec<String>,
+
+    /// Parse data files as FORMAT instead of by their extension (stdin defaults to turtle)
+    #[arg(long, value_name = "FORMAT")]
+    data_format: Option<InputFormat>,
+}
+
+/// RDF syntaxes accepted for shapes and data files.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum InputFormat {
+    Turtle,
+    #[value(alias = "ntriples")]
+    NTriples,
+    #[value(alias = "rdfxml")]
+    RdfXml,
+    #[value(alias = "jsonld")]
+    JsonLd,
+    Trig,
+}
+
+impl InputFormat {
+    fn rdf_format(self) -> RdfFormat {
+        match self {
+            InputFormat::Turtle => RdfFormat::Turtle,
+            InputFormat::NTriples => RdfFormat::NTriples,
+            InputFormat::RdfXml => RdfFormat::RdfXml,
+            InputFormat::JsonLd => RdfFormat::JsonLd {
+                profile: JsonLdProfileSet::empty(),
+            },
+            InputFormat::Trig => RdfFormat::TriG,
+        }
+    }
+}
+
+/// Returns the source to load for `file`: the file itself, or its content in `format` when
+/// it is `-` (stdin, Turtle by default) or `format` overrides its extension.
+fn input_source(
+    file: &Path,
+    format: Option<InputFormat>,
+    role: &str,
+    read_stdin: &mut bool,
+) -> Result<Source, Box<dyn std::error::Error>> {
+    let from_stdin = file == Path::new("-");
+    if !from_stdin && format.is_none() {
+        return Ok(Source::File(file.to_path_buf()));
+    }
+    let data = if from_stdin {
+        if *read_stdin {
+            return Err("Only one shapes or data file can be read from stdin".into());
+        }
+        *read_stdin = true;
+        let mut data = Vec::new();
+        io::stdin()
+            .read_to_end(&mut data)
+            .map_err(|e| format!("Failed to read {} from stdin: {}", role, e))?;
+        data
+    } else {
+        fs::read(file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?
+    };
+    Ok(Source::Bytes {
+        data,
+        format: format.unwrap_or(InputFormat::Turtle).rdf_format(),
+    })
 }
 
 #[derive(Parser, Debug)]
@@ -58,12 +154,76 @@ struct CommonArgs {
     /// Skip invalid SHACL constructs (log and continue)
     #[arg(long)]
     skip_invalid_rules: bool,
+
+    /// Report a focus node instead of validating it when a path yields more than N value nodes that its constraints need all at once
+    #[arg(long, value_name = "N")]
+    max_value_nodes: Option<usize>,
+
+    /// Let results with custom (non sh:Info/sh:Warning/sh:Violation) severities leave conformance unaffected
+    #[arg(long)]
+    ignore_custom_severities: bool,
+
+    /// Require the shapes graph's owl:versionInfo to match, e.g. 1.2.0, '>=1.2,<2' or '<VERSION_IRI>'
+    #[arg(long, value_name = "REQUIREMENT")]
+    expect_shapes_version: Option<ShapesVersionRequirement>,
+
+    /// Also validate the shapes of modules in the shapes graph's owl:imports closure
+    #[arg(long)]
+    include_imports: bool,
+
+    /// Load the custom constraint components declared in FILE, a component library kept apart from the shapes (repeatable)
+    #[arg(long, value_name = "FILE")]
+    component_library: Vec<PathBuf>,
+
+    /// Write engine result messages in LOCALE, e.g. de or de-CH, using the catalogs in --messages-dir
+    #[arg(long, value_name = "LOCALE", requires = "messages_dir")]
+    locale: Option<String>,
+
+    /// Directory of <locale>.messages catalogs for --locale
+    #[arg(long, value_name = "DIR", requires = "locale")]
+    messages_dir: Option<PathBuf>,
+
+    /// Fail instead of warning when an owl:imports module defines a shape differently
+    #[arg(long)]
+    error_on_duplicate_shapes: bool,
+
+    /// Let SPARQL constraints query the union of all loaded graphs instead of only the data graph
+    #[arg(long)]
+    union_default_graph: bool,
+
+    /// Skolemize shapes-graph blank nodes under this base IRI instead of <graph>/.well-known/skolem/
+    #[arg(long, value_name = "IRI")]
+    shapes_skolem_base: Option<String>,
+
+    /// Skolemize data-graph blank nodes under this base IRI instead of <graph>/.well-known/skolem/
+    #[arg(long, value_name = "IRI")]
+    data_skolem_base: Option<String>,
+
+    /// Request http(s) graphs up to N times, backing off between attempts
+    #[arg(long, value_name = "N")]
+    fetch_attempts: Option<u32>,
+
+    /// Cache fetched http(s) graphs in DIR for conditional requests and offline fallback
+    #[arg(long, value_name = "DIR")]
+    fetch_cache_dir: Option<PathBuf>,
+
+    /// Validate only the triples produced by the SPARQL CONSTRUCT/DESCRIBE query in FILE
+    #[arg(long, value_name = "FILE")]
+    data_subset_query: Option<PathBuf>,
 }
 
 #[derive(Parser)]
 struct GraphvizArgs {
     #[clap(flatten)]
     common: CommonArgs,
+
+    /// Only render this shape and the shapes it references
+    #[arg(long, value_name = "IRI", value_parser = parse_shape_iri)]
+    shape: Option<NamedNode>,
+
+    /// How many levels of shape references to follow from --shape
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "shape")]
+    depth: usize,
 }
 
 #[derive(Parser)]
@@ -83,6 +243,8 @@ enum ValidateOutputFormat {
     Dump,
     RdfXml,
     NTriples,
+    Trig,
+    NQuads,
 }
 
 #[derive(Parser)]
@@ -129,6 +291,156 @@ struct ValidateArgs {
     /// Include shapes/components that did not execute in the heatmap (requires --pdf-heatmap)
     #[arg(long, requires = "pdf_heatmap")]
     pdf_heatmap_all: bool,
+
+    /// Attach metadata to the report as PREDICATE_IRI=VALUE (repeatable), e.g. a request id
+    #[arg(long = "report-metadata", value_name = "PREDICATE_IRI=VALUE", value_parser = parse_report_metadata)]
+    report_metadata: Vec<(NamedNode, String)>,
+
+    /// Check the emitted report against the bundled SHACL validation-report shapes
+    #[arg(long)]
+    self_check: bool,
+
+    /// Collapse the results of any focus node with more than N results into one aggregated result
+    #[arg(long, value_name = "N")]
+    aggregate_results_over: Option<usize>,
+
+    /// With --format dump, print up to N types and path values of each result's focus node from the data graph
+    #[arg(long, value_name = "N")]
+    focus_context: Option<usize>,
+
+    /// Named graph holding the report in dataset formats (trig, n-quads)
+    #[arg(long, value_name = "IRI", value_parser = parse_report_graph)]
+    report_graph: Option<NamedNode>,
+
+    /// Derive the report's blank node labels from this seed
+    #[arg(long, value_name = "N")]
+    blank_node_seed: Option<u64>,
+
+    /// Show blank focus nodes and values as the skolem IRIs the validator used
+    #[arg(long)]
+    keep_skolem_iris: bool,
+
+    /// Replace literal focus nodes and values in the report, and in the messages that repeat them
+    #[arg(long, value_enum, value_name = "MODE")]
+    redact: Option<RedactArg>,
+
+    /// With --redact, also replace IRIs with hashed urn:shacl-rs:redacted: IRIs
+    #[arg(long, requires = "redact")]
+    redact_iris: bool,
+
+    /// With --redact, salt prepended to every hashed value
+    #[arg(long, value_name = "SALT", requires = "redact")]
+    redact_salt: Option<String>,
+
+    /// Stop validating once the report holds N results
+    #[arg(long, value_name = "N")]
+    max_errors: Option<usize>,
+
+    /// Stop validating at the first result
+    #[arg(long)]
+    fail_fast: bool,
+
+    /// Leave results of this severity out of the report (repeatable)
+    #[arg(long, value_enum, value_name = "SEVERITY")]
+    ignore_severity: Vec<SeverityArg>,
+
+    /// Exit with status 1 if any result has this severity or a more severe one
+    #[arg(long, value_enum, value_name = "SEVERITY")]
+    fail_on: Option<SeverityArg>,
+
+    /// Only validate the targets of this shape (repeatable)
+    #[arg(long = "shape", value_name = "IRI", value_parser = parse_shape_iri)]
+    shapes: Vec<NamedNode>,
+
+    /// Skip sh:sparql constraints and SPARQL-based constraint components
+    #[arg(long)]
+    no_sparql_constraints: bool,
+
+    /// Explain failed sh:node, sh:and, sh:or and sh:xone constraints with nested sh:detail results
+    #[arg(long)]
+    details: bool,
+
+    /// Stop validating after this many seconds
+    #[arg(long, value_name = "SECONDS")]
+    timeout: Option<f64>,
+
+    /// Append a JSON line per constraint evaluation to this file
+    #[arg(long, value_name = "FILE")]
+    audit_log: Option<PathBuf>,
+
+    /// Skip focus nodes this cache file records as conforming, and update it
+    #[arg(long, value_name = "FILE")]
+    persistent_cache: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum RedactArg {
+    /// Replace values with [redacted]
+    Mask,
+    /// Replace values with their SHA-256 hash
+    Hash,
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+enum SeverityArg {
+    Info,
+    Warning,
+    Violation,
+}
+
+impl From<SeverityArg> for Severity {
+    fn from(severity: SeverityArg) -> Self {
+        match severity {
+            SeverityArg::Info => Severity::Info,
+            SeverityArg::Warning => Severity::Warning,
+            SeverityArg::Violation => Severity::Violation,
+        }
+    }
+}
+
+fn parse_shape_iri(raw: &str) -> Result<NamedNode, String> {
+    NamedNode::new(raw).map_err(|e| format!("Invalid shape IRI '{}': {}", raw, e))
+}
+
+fn validation_options(args: &ValidateArgs) -> Result<ValidationOptions, String> {
+    let mut options = ValidationOptions::new()
+        .with_fail_fast(args.fail_fast)
+        .with_sparql_constraints(!args.no_sparql_constraints)
+        .with_result_details(args.details);
+    if let Some(limit) = args.max_errors {
+        options = options.with_max_errors(limit);
+    }
+    for severity in &args.ignore_severity {
+        options = options.with_ignored_severity(severity.clone().into());
+    }
+    if !args.shapes.is_empty() {
+        options = options.with_shapes(args.shapes.iter().cloned().map(Term::from));
+    }
+    if let Some(seconds) = args.timeout {
+        let timeout = Duration::try_from_secs_f64(seconds)
+            .map_err(|e| format!("Invalid timeout {}: {}", seconds, e))?;
+        options = options.with_timeout(timeout);
+    }
+    if let Some(path) = &args.audit_log {
+        options = options.with_audit_log(path);
+    }
+    if let Some(path) = &args.persistent_cache {
+        options = options.with_persistent_cache(path);
+    }
+    Ok(options)
+}
+
+fn parse_report_graph(raw: &str) -> Result<NamedNode, String> {
+    NamedNode::new(raw).map_err(|e| format!("Invalid report graph IRI '{}': {}", raw, e))
+}
+
+fn parse_report_metadata(raw: &str) -> Result<(NamedNode, String), String> {
+    let (predicate, value) = raw
+        .split_once('=')
+        .ok_or_else(|| format!("Expected PREDICATE_IRI=VALUE, got '{}'", raw))?;
+    let predicate = NamedNode::new(predicate)
+        .map_err(|e| format!("Invalid metadata predicate IRI '{}': {}", predicate, e))?;
+    Ok((predicate, value.to_string()))
 }
 
 #[derive(Parser)]
@@ -181,6 +493,74 @@ struct InferenceArgs {
 struct HeatArgs {
     #[clap(flatten)]
     common: CommonArgs,
+
+    /// Print the execution count and time of every shape and component as JSON, for `heat-diff`
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Parser)]
+struct HeatDiffArgs {
+    /// JSON heatmap of the earlier run (from `heat --json`)
+    #[arg(value_name = "BEFORE")]
+    before: PathBuf,
+
+    /// JSON heatmap of the later run (from `heat --json`)
+    #[arg(value_name = "AFTER")]
+    after: PathBuf,
+
+    /// Only print the N elements whose time changed most
+    #[arg(long, value_name = "N")]
+    top: Option<usize>,
+}
+
+#[derive(Parser)]
+struct ProfileArgs {
+    #[clap(flatten)]
+    common: CommonArgs,
+
+    /// Only print the N slowest shapes and components
+    #[arg(long, value_name = "N")]
+    top: Option<usize>,
+}
+
+#[derive(Parser)]
+struct StatsArgs {
+    #[clap(flatten)]
+    common: CommonArgs,
+}
+
+#[derive(Parser)]
+struct ShapesArgs {
+    #[clap(flatten)]
+    common: CommonArgs,
+
+    /// Only list shapes that no targeted shape reaches
+    #[arg(long)]
+    unreachable: bool,
+}
+
+#[derive(Parser)]
+struct ComponentsArgs {
+    #[clap(flatten)]
+    common: CommonArgs,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+enum DocumentFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+#[derive(Parser)]
+struct DocumentArgs {
+    #[clap(flatten)]
+    common: CommonArgs,
+
+    /// Output format of the documentation
+    #[arg(long, value_enum, default_value_t = DocumentFormat::Markdown)]
+    format: DocumentFormat,
 }
 
 #[derive(Parser)]
@@ -207,6 +587,46 @@ struct PdfHeatmapArgs {
     all: bool,
 }
 
+#[derive(Parser)]
+struct ConformanceArgs {
+    /// Root manifest of the test suite to run (defaults to the bundled W3C suite)
+    #[arg(long, value_name = "FILE")]
+    manifest: Option<PathBuf>,
+
+    /// Also list every failing test and why it failed
+    #[arg(long)]
+    failures: bool,
+
+    /// Only run the tests of this section and those below it, e.g. core/property (repeatable)
+    #[arg(long, value_name = "SECTION")]
+    section: Vec<String>,
+
+    /// Only run the tests whose name contains this text (repeatable)
+    #[arg(long, value_name = "TEXT")]
+    test: Vec<String>,
+
+    /// Only report the tests that ended with this status (repeatable)
+    #[arg(long, value_enum, value_name = "STATUS")]
+    status: Vec<StatusArg>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum StatusArg {
+    Passed,
+    Failed,
+    Errored,
+}
+
+impl From<StatusArg> for TestStatusKind {
+    fn from(status: StatusArg) -> Self {
+        match status {
+            StatusArg::Passed => TestStatusKind::Passed,
+            StatusArg::Failed => TestStatusKind::Failed,
+            StatusArg::Errored => TestStatusKind::Errored,
+        }
+    }
+}
+
 #[derive(Parser)]
 struct TraceArgs {
     #[clap(flatten)]
@@ -221,6 +641,19 @@ enum Commands {
     Pdf(PdfArgs),
     /// Validate the data against the shapes and output a frequency table of component invocations
     Heat(HeatArgs),
+    /// Compare two `heat --json` exports and list the shapes and components whose cost changed most
+    #[command(name = "heat-diff")]
+    HeatDiff(HeatDiffArgs),
+    /// Validate the data and print the time spent in each shape and component, slowest first
+    Profile(ProfileArgs),
+    /// Compare the datatypes/classes observed along each property path with what the shapes expect
+    Stats(StatsArgs),
+    /// List the parsed shapes and whether each is reachable from a targeted shape
+    Shapes(ShapesArgs),
+    /// List the custom constraint components of the shapes graph, their parameters and the validator used for node and property shapes
+    Components(ComponentsArgs),
+    /// Render documentation of the node shapes, their targets and property shapes as Markdown or HTML
+    Document(DocumentArgs),
     /// Validate the data and output a graphviz heatmap of the shape graph
     #[command(name = "graphviz-heatmap")]
     GraphvizHeatmap(GraphvizHeatmapArgs),
@@ -230,30 +663,112 @@ enum Commands {
     /// Validate the data against the shapes
     Validate(ValidateArgs),
     /// Run SHACL rule inference without performing validation
+    #[command(visible_alias = "infer")]
     Inference(InferenceArgs),
     /// Print the execution traces for debugging
     Trace(TraceArgs),
+    /// Run the W3C SHACL test suite and print a pass/fail matrix per manifest section
+    Conformance(ConformanceArgs),
 }
 
 fn get_validator(common: &CommonArgs) -> Result<Validator, Box<dyn std::error::Error>> {
-    let shapes_source = if let Some(path) = &common.shapes.shapes_file {
-        Source::File(path.clone())
-    } else {
-        Source::Graph(common.shapes.shapes_graph.clone().unwrap())
-    };
+    get_validator_with_options(common, ValidationOptions::default())
+}
 
-    let data_source = if let Some(path) = &common.data.data_file {
-        Source::File(path.clone())
-    } else {
-        Source::Graph(common.data.data_graph.clone().unwrap())
+fn get_validator_with_options(
+    common: &CommonArgs,
+    options: ValidationOptions,
+) -> Result<Validator, Box<dyn std::error::Error>> {
+    let mut read_stdin = false;
+    let mut sources = |files: &[PathBuf],
+                       graphs: &[String],
+                       format: Option<InputFormat>,
+                       role: &str|
+     -> Result<Vec<Source>, Box<dyn std::error::Error>> {
+        let mut sources = Vec::new();
+        for file in files {
+            sources.push(input_source(file, format, role, &mut read_stdin)?);
+        }
+        sources.extend(graphs.iter().cloned().map(Source::Graph));
+        Ok(sources)
     };
-
-    ValidatorBuilder::new()
-        .with_shapes_source(shapes_source)
-        .with_data_source(data_source)
+    let shapes_sources = sources(
+        &common.shapes.shapes_file,
+        &common.shapes.shapes_graph,
+        common.shapes.shapes_format,
+        "shapes",
+    )?;
+    let data_sources = sources(
+        &common.data.data_file,
+        &common.data.data_graph,
+        common.data.data_format,
+        "data",
+    )?;
+
+    let mut builder = ValidatorBuilder::new()
+        .with_shapes_sources(shapes_sources)
+        .with_data_sources(data_sources)
         .with_skip_invalid_rules(common.skip_invalid_rules)
-        .build()
-        .map_err(|e| format!("Error creating validator: {}", e).into())
+        .with_validation_options(options)
+        .with_skolem_bases(
+            common.shapes_skolem_base.clone(),
+            common.data_skolem_base.clone(),
+        );
+    if let Some(limit) = common.max_value_nodes {
+        builder = builder.with_max_value_nodes(limit);
+    }
+    if let Some(requirement) = &common.expect_shapes_version {
+        builder = builder.with_expected_shapes_version(requirement.clone());
+    }
+    if common.include_imports {
+        builder = builder.with_shapes_imports(true);
+    }
+    if !common.component_library.is_empty() {
+        builder = builder.with_component_library_sources(
+            common.component_library.iter().cloned().map(Source::File),
+        );
+    }
+    if let (Some(locale), Some(dir)) = (&common.locale, &common.messages_dir) {
+        builder = builder.with_message_catalog(MessageCatalog::for_locale(dir, locale)?);
+    }
+    if common.shapes.declared_shapes {
+        builder = builder.with_declared_shapes_graphs(true);
+    }
+    if common.error_on_duplicate_shapes {
+        builder = builder.with_duplicate_shape_policy(DuplicateShapePolicy::Error);
+    }
+    if common.union_default_graph {
+        builder = builder.with_query_dataset(QueryDataset::Union);
+    }
+    if common.fetch_attempts.is_some() || common.fetch_cache_dir.is_some() {
+        let mut policy = FetchPolicy::new();
+        if let Some(attempts) = common.fetch_attempts {
+            policy = policy.with_max_attempts(attempts);
+        }
+        if let Some(dir) = &common.fetch_cache_dir {
+            policy = policy.with_cache_dir(dir);
+        }
+        builder = builder.with_fetch_policy(policy);
+    }
+    if let Some(path) = &common.data_subset_query {
+        let query = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read data subset query {}: {}", path.display(), e))?;
+        builder = builder.with_data_subset_query(query);
+    }
+    if common.ignore_custom_severities {
+        builder = builder.with_custom_severity_conformance(CustomSeverityConformance::Ignored);
+    }
+
+    match builder.build() {
+        Ok(validator) => Ok(validator),
+        Err(e) => {
+            if let Some(load_error) = e.downcast_ref::<GraphLoadError>() {
+                eprintln!("{}", load_error);
+                process::exit(INVALID_RDF_EXIT_CODE);
+            }
+            Err(format!("Error creating validator: {}", e).into())
+        }
+    }
 }
 
 fn build_inference_config(
@@ -278,6 +793,103 @@ fn build_inference_config(
     config
 }
 
+fn heat_kind_name(kind: HeatEntryKind) -> &'static str {
+    match kind {
+        HeatEntryKind::NodeShape => "NodeShape",
+        HeatEntryKind::PropertyShape => "PropertyShape",
+        HeatEntryKind::Component => "Component",
+    }
+}
+
+fn heatmap_to_json(entries: &[HeatEntry]) -> Value {
+    Value::Array(
+        entries
+            .iter()
+            .map(|entry| {
+                json!({
+                    "id": entry.id,
+                    "iri": entry.iri.to_string(),
+                    "label": entry.label,
+                    "kind": heat_kind_name(entry.kind),
+                    "invocations": entry.invocations,
+                    "duration_secs": entry.duration.as_secs_f64(),
+                })
+            })
+            .collect(),
+    )
+}
+
+fn read_heatmap_json(path: &PathBuf) -> Result<Vec<HeatEntry>, String> {
+    let raw = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let value: Value = serde_json::from_str(&raw)
+        .map_err(|e| format!("{} is not valid JSON: {}", path.display(), e))?;
+    let entries = value
+        .as_array()
+        .ok_or_else(|| format!("{} is not a JSON array of heatmap entries", path.display()))?;
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let field = |name: &str| {
+                entry.get(name).ok_or_else(|| {
+                    format!("{} entry {} has no \"{}\"", path.display(), index, name)
+                })
+            };
+            let text = |name: &str| {
+                field(name)?.as_str().ok_or_else(|| {
+                    format!(
+                        "{} entry {}: \"{}\" is not a string",
+                        path.display(),
+                        index,
+                        name
+                    )
+                })
+            };
+            let kind = match text("kind")? {
+                "NodeShape" => HeatEntryKind::NodeShape,
+                "PropertyShape" => HeatEntryKind::PropertyShape,
+                "Component" => HeatEntryKind::Component,
+                other => {
+                    return Err(format!(
+                        "{} entry {}: unknown kind \"{}\"",
+                        path.display(),
+                        index,
+                        other
+                    ))
+                }
+            };
+            let iri = Term::from_str(text("iri")?)
+                .map_err(|e| format!("{} entry {}: invalid iri: {}", path.display(), index, e))?;
+            let invocations = field("invocations")?.as_u64().ok_or_else(|| {
+                format!(
+                    "{} entry {}: \"invocations\" is not a count",
+                    path.display(),
+                    index
+                )
+            })?;
+            let duration = field("duration_secs")?
+                .as_f64()
+                .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                .ok_or_else(|| {
+                    format!(
+                        "{} entry {}: \"duration_secs\" is not a duration",
+                        path.display(),
+                        index
+                    )
+                })?;
+            Ok(HeatEntry {
+                id: text("id")?.to_string(),
+                iri,
+                label: text("label")?.to_string(),
+                kind,
+                invocations: invocations as usize,
+                duration,
+            })
+        })
+        .collect()
+}
+
 fn serialize_quads_to_turtle(quads: &[Quad]) -> Result<Vec<u8>, String> {
     let mut serializer = RdfSerializer::from_format(RdfFormat::Turtle).for_writer(Vec::new());
     for quad in quads {
@@ -302,7 +914,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     match cli.command {
         Commands::Graphviz(args) => {
             let validator = get_validator(&args.common)?;
-            let dot_string = validator.to_graphviz()?;
+            let dot_string = match &args.shape {
+                Some(shape) => {
+                    validator.to_graphviz_for_shape(&shape.clone().into(), args.depth)?
+                }
+                None => validator.to_graphviz()?,
+            };
             println!("{}", dot_string);
         }
         Commands::Pdf(args) => {
@@ -326,7 +943,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("PDF generated at: {}", args.output_file.display());
         }
         Commands::Validate(args) => {
-            let validator = get_validator(&args.common)?;
+            let validator = get_validator_with_options(&args.common, validation_options(&args)?)?;
             let (report, inference_outcome) = if args.run_inference {
                 let config = build_inference_config(
                     args.inference_min_iterations,
@@ -342,6 +959,53 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             } else {
                 (validator.validate(), None)
             };
+            let report = args
+                .report_metadata
+                .iter()
+                .fold(report, |report, (predicate, value)| {
+                    report.with_metadata(predicate.clone(), Literal::new_simple_literal(value))
+                });
+            let report = match args.report_graph.clone() {
+                Some(graph) => report.with_report_graph(graph),
+                None => report,
+            };
+            let report = match args.blank_node_seed {
+                Some(seed) => report.with_blank_node_seed(seed),
+                None => report,
+            }
+            .with_skolem_iris(args.keep_skolem_iris);
+            let report = match args.aggregate_results_over {
+                Some(threshold) => report.with_focus_node_aggregation(threshold),
+                None => report,
+            };
+            let report = match args.focus_context {
+                Some(limit) => report.with_focus_node_context(limit),
+                None => report,
+            };
+            let report = match args.redact {
+                Some(mode) => {
+                    let redaction = match mode {
+                        RedactArg::Mask => Redaction::mask(),
+                        RedactArg::Hash => Redaction::hash(),
+                    };
+                    report.with_redaction(
+                        redaction
+                            .with_iris(args.redact_iris)
+                            .with_salt(args.redact_salt.clone().unwrap_or_default()),
+                    )
+                }
+                None => report,
+            };
+
+            if let Some(reason) = report.stop_reason() {
+                eprintln!(
+                    "Validation stopped early ({}); the report is incomplete",
+                    reason
+                );
+            }
+            for feature in validator.unsupported_features() {
+                eprintln!("Warning: {}", feature);
+            }
 
             if let Some(outcome) = inference_outcome {
                 eprintln!(
@@ -366,6 +1030,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     let report_str = report.to_rdf(RdfFormat::NTriples)?;
                     println!("{}", report_str);
                 }
+                ValidateOutputFormat::Trig => {
+                    let report_str = report.to_rdf(RdfFormat::TriG)?;
+                    println!("{}", report_str);
+                }
+                ValidateOutputFormat::NQuads => {
+                    let report_str = report.to_rdf(RdfFormat::NQuads)?;
+                    println!("{}", report_str);
+                }
             }
 
             if args.graphviz {
@@ -386,6 +1058,23 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     .map_err(|e| format!("Graphviz execution error: {}", e))?;
                 println!("PDF heatmap generated at: {}", pdf_path.display());
             }
+
+            if args.self_check {
+                let issues = report.self_check()?;
+                if !issues.is_empty() {
+                    eprintln!("Report self-check found {} problem(s):", issues.len());
+                    for issue in &issues {
+                        eprintln!("  {}", issue);
+                    }
+                    process::exit(MALFORMED_REPORT_EXIT_CODE);
+                }
+            }
+
+            if let Some(threshold) = args.fail_on.clone() {
+                if !report.conforms_with(&threshold.into()) {
+                    process::exit(NON_CONFORMING_EXIT_CODE);
+                }
+            }
         }
         Commands::Inference(args) => {
             let validator = get_validator(&args.common)?;
@@ -451,15 +1140,70 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             let validator = get_validator(&args.common)?;
             let report = validator.validate();
 
-            let frequencies: HashMap<(String, String, String), usize> =
-                report.get_component_frequencies();
+            if args.json {
+                let json = serde_json::to_string_pretty(&heatmap_to_json(&report.heatmap()))?;
+                println!("{}", json);
+            } else {
+                let frequencies: HashMap<(String, String, String), usize> =
+                    report.get_component_frequencies();
 
-            let mut sorted_frequencies: Vec<_> = frequencies.into_iter().collect();
-            sorted_frequencies.sort_by(|a, b| b.1.cmp(&a.1));
+                let mut sorted_frequencies: Vec<_> = frequencies.into_iter().collect();
+                sorted_frequencies.sort_by_key(|entry| std::cmp::Reverse(entry.1));
 
-            println!("ID\tLabel\tType\tInvocations");
-            for ((id, label, item_type), count) in sorted_frequencies {
-                println!("{}\t{}\t{}\t{}", id, label, item_type, count);
+                println!("ID\tLabel\tType\tInvocations");
+                for ((id, label, item_type), count) in sorted_frequencies {
+                    println!("{}\t{}\t{}\t{}", id, label, item_type, count);
+                }
+            }
+        }
+        Commands::HeatDiff(args) => {
+            let before = read_heatmap_json(&args.before)?;
+            let after = read_heatmap_json(&args.after)?;
+            let deltas = diff_heatmaps(&before, &after);
+            let shown = args.top.unwrap_or(deltas.len());
+
+            println!("Type\tLabel\tInvocations\tTime\tChange\tRelative");
+            for delta in deltas.iter().take(shown) {
+                println!("{}", delta);
+            }
+        }
+        Commands::Profile(args) => {
+            let validator = get_validator(&args.common)?;
+            let report = validator.validate();
+            let timings = report.get_component_timings();
+            let shown = args.top.unwrap_or(timings.len());
+
+            println!("Type\tLabel\tInvocations\tTotal\tMean");
+            for timing in timings.iter().take(shown) {
+                println!("{}", timing);
+            }
+        }
+        Commands::Stats(args) => {
+            let validator = get_validator(&args.common)?;
+            for statistics in validator.path_type_statistics()? {
+                println!("{}", statistics);
+            }
+        }
+        Commands::Shapes(args) => {
+            let validator = get_validator(&args.common)?;
+            for reachability in validator.shape_reachability() {
+                if args.unreachable && reachability.is_reachable() {
+                    continue;
+                }
+                println!("{}", reachability);
+            }
+        }
+        Commands::Components(args) => {
+            let validator = get_validator(&args.common)?;
+            for component in validator.custom_components() {
+                println!("{}", component);
+            }
+        }
+        Commands::Document(args) => {
+            let validator = get_validator(&args.common)?;
+            match args.format {
+                DocumentFormat::Markdown => print!("{}", validator.to_markdown()),
+                DocumentFormat::Html => print!("{}", validator.to_html()),
             }
         }
         Commands::GraphvizHeatmap(args) => {
@@ -502,6 +1246,38 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             report.print_traces();
         }
+        Commands::Conformance(args) => {
+            let manifest = args
+                .manifest
+                .unwrap_or_else(|| PathBuf::from(BUNDLED_TEST_SUITE));
+            let mut filter = TestFilter::new();
+            for section in args.section {
+                filter = filter.with_section(section);
+            }
+            for name in args.test {
+                filter = filter.with_name(name);
+            }
+            for status in args.status {
+                filter = filter.with_status(status.into());
+            }
+            let report = run_test_suite_filtered(&manifest, &filter)?;
+
+            println!("Section\tPassed\tFailed\tErrors\tTotal\tPass rate");
+            for section in report.sections() {
+                println!("{}", section);
+            }
+            println!("{}", report.totals());
+
+            if args.failures {
+                for outcome in report.failures() {
+                    let reason = match &outcome.status {
+                        TestStatus::Failed(reason) | TestStatus::Errored(reason) => reason,
+                        TestStatus::Passed => continue,
+                    };
+                    eprintln!("{}\t{}\t{}", outcome.section, outcome.name, reason);
+                }
+            }
+        }
     }
     Ok(())
 }
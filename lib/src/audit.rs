@@ -0,0 +1,168 @@
+//! Append-only audit log of constraint evaluations.
+//!
+//! A run with [`ValidationOptions::with_audit_log`](crate::ValidationOptions::with_audit_log)
+//! appends one JSON object per line to the log file for every constraint it evaluates,
+//! including constraints evaluated while checking nested shapes. Unlike execution traces,
+//! which explain the results of one report and live in memory, the log records which
+//! checks ran against which nodes, whether they passed, and survives the process. Entries
+//! are never rewritten; each run is told apart by its `run` timestamp and numbers its
+//! entries in `seq` from 0.
+//!
+//! Entry fields, with terms in N-Triples syntax:
+//!
+//! - `run`, `seq`: the run's start time (`xsd:dateTime`) and the entry's position in it
+//! - `shape`: the shape the constraint belongs to
+//! - `component`: the constraint component, e.g.
+//!   `<http://www.w3.org/ns/shacl#MinCountConstraintComponent>`
+//! - `focus_node`, `path` and `value_nodes`: the evaluation's inputs; `path` is a SPARQL
+//!   property path, or `null` for node shapes
+//! - `outcome`: `pass`, `fail` or `error`
+//! - `failed_values`: the value nodes of failures that name one
+//! - `error`: the message of an evaluation that could not complete, otherwise `null`
+
+use crate::context::{Context, ValidationContext};
+use crate::runtime::{Component, ComponentValidationResult};
+use crate::types::ComponentID;
+use oxigraph::model::Term;
+use oxsdatatypes::DateTime;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+pub(crate) struct AuditLog {
+    writer: BufWriter<File>,
+    run: String,
+    seq: u64,
+    /// The first write error; later entries are dropped and the run fails with it.
+    error: Option<String>,
+}
+
+impl AuditLog {
+    /// Opens `path` for appending, creating it if needed.
+    pub(crate) fn open(path: &Path) -> Result<Self, String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open audit log {}: {}", path.display(), e))?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            run: DateTime::now().to_string(),
+            seq: 0,
+            error: None,
+        })
+    }
+
+    /// Checks that the log at `path` can be opened for appending, creating it if needed.
+    pub(crate) fn check(path: &Path) -> Result<(), String> {
+        Self::open(path).map(|_| ())
+    }
+
+    /// Appends the entry for one evaluation of `component` in context `c`.
+    pub(crate) fn record(
+        &mut self,
+        component: &Component,
+        component_id: ComponentID,
+        c: &Context,
+        result: &Result<Vec<ComponentValidationResult>, String>,
+        context: &ValidationContext,
+    ) {
+        if self.error.is_some() {
+            return;
+        }
+        let shape = c
+            .source_shape()
+            .get_term(context)
+            .map(|term| json_string(&term.to_string()))
+            .unwrap_or_else(|| "null".to_string());
+        let path = c
+            .result_path()
+            .and_then(|path| path.to_sparql_path().ok())
+            .map(|path| json_string(&path))
+            .unwrap_or_else(|| "null".to_string());
+        let value_nodes = json_terms(c.value_nodes().into_iter().flatten());
+        let (outcome, failed_values, error) = match result {
+            Ok(results) => {
+                let failed: Vec<&Term> = results
+                    .iter()
+                    .filter_map(|result| match result {
+                        ComponentValidationResult::Fail(_, failure) => {
+                            failure.failed_value_node.as_ref()
+                        }
+                        ComponentValidationResult::Pass(_) => None,
+                    })
+                    .collect();
+                let failed_any = results
+                    .iter()
+                    .any(|result| matches!(result, ComponentValidationResult::Fail(..)));
+                let outcome = if failed_any { "fail" } else { "pass" };
+                (outcome, json_terms(failed), "null".to_string())
+            }
+            Err(e) => ("error", "[]".to_string(), json_string(e)),
+        };
+        let line = format!(
+            "{{\"run\":{},\"seq\":{},\"shape\":{},\"component\":{},\"component_id\":{},\"focus_node\":{},\"path\":{},\"value_nodes\":{},\"outcome\":\"{}\",\"failed_values\":{},\"error\":{}}}\n",
+            json_string(&self.run),
+            self.seq,
+            shape,
+            json_string(&component.component_type().to_string()),
+            component_id.0,
+            json_string(&c.focus_node().to_string()),
+            path,
+            value_nodes,
+            outcome,
+            failed_values,
+            error,
+        );
+        self.seq += 1;
+        if let Err(e) = self.writer.write_all(line.as_bytes()) {
+            self.error = Some(format!("Failed to write audit log: {}", e));
+        }
+    }
+
+    /// Flushes the log, returning the first error met while writing it.
+    pub(crate) fn finish(mut self) -> Result<(), String> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.writer
+            .flush()
+            .map_err(|e| format!("Failed to write audit log: {}", e))
+    }
+}
+
+fn json_terms<'a>(terms: impl IntoIterator<Item = &'a Term>) -> String {
+    let items: Vec<String> = terms
+        .into_iter()
+        .map(|term| json_string(&term.to_string()))
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_strings_escape_quotes_and_control_characters() {
+        assert_eq!(json_string("\"a\"\\\n\u{1}"), "\"\\\"a\\\"\\\\\\n\\u0001\"");
+    }
+}
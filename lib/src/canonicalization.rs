@@ -1,12 +1,14 @@
+use crate::named_nodes::{RDF, SHACL};
 use log::debug;
 use oxigraph::model::{
-    BlankNode, Graph, GraphNameRef, NamedNode, NamedOrBlankNode as Subject,
-    NamedOrBlankNodeRef as SubjectRef, Quad, Term, TermRef, Triple,
+    BlankNode, BlankNodeRef, Graph, GraphNameRef, NamedNode, NamedNodeRef,
+    NamedOrBlankNode as Subject, NamedOrBlankNodeRef as SubjectRef, Quad, Term, TermRef, Triple,
+    TripleRef,
 };
 use oxigraph::store::{StorageError, Store};
 use petgraph::graph::{DiGraph, NodeIndex};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Converts an `oxigraph::model::Graph` to a `petgraph::graph::DiGraph`.
 ///
@@ -82,11 +84,101 @@ fn log_diff(g1: &Graph, g2: &Graph) {
     }
 }
 
+/// Normalizes a validation report graph for comparison with the `expected` report,
+/// following the comparison rules of the W3C SHACL test suite:
+///
+/// - `sh:resultMessage` triples are dropped unless `expected` has some, as messages are
+///   left to implementations;
+/// - `sh:detail` triples, and the nested results only they reach, are dropped unless
+///   `expected` has some, as reporting details is optional;
+/// - a `sh:ValidationReport` named by an IRI becomes a blank node, so named and anonymous
+///   reports compare equal.
+///
+/// Normalizing `expected` against itself only renames its report node.
+pub fn normalize_report(report: &Graph, expected: &Graph) -> Graph {
+    let sh = SHACL::get();
+    let has =
+        |predicate: NamedNodeRef<'_>| expected.iter().any(|triple| triple.predicate == predicate);
+    let keep_messages = has(sh.result_message);
+    let keep_details = has(sh.detail);
+
+    // Blank nodes reachable only through sh:detail: the nested results and their paths.
+    let mut detail_nodes: HashSet<BlankNodeRef<'_>> = HashSet::new();
+    if !keep_details {
+        let mut pending: Vec<BlankNodeRef<'_>> = report
+            .triples_for_predicate(sh.detail)
+            .filter_map(|triple| match triple.object {
+                TermRef::BlankNode(node) => Some(node),
+                _ => None,
+            })
+            .collect();
+        while let Some(node) = pending.pop() {
+            if !detail_nodes.insert(node) {
+                continue;
+            }
+            for triple in report.triples_for_subject(node) {
+                if let TermRef::BlankNode(object) = triple.object {
+                    pending.push(object);
+                }
+            }
+        }
+    }
+
+    let report_nodes: HashMap<NamedNodeRef<'_>, BlankNode> = report
+        .subjects_for_predicate_object(RDF::get().type_, sh.validation_report)
+        .filter_map(|subject| match subject {
+            SubjectRef::NamedNode(node) => Some((node, BlankNode::default())),
+            SubjectRef::BlankNode(_) => None,
+        })
+        .collect();
+
+    let mut normalized = Graph::new();
+    for triple in report.iter() {
+        if (!keep_messages && triple.predicate == sh.result_message)
+            || (!keep_details && triple.predicate == sh.detail)
+        {
+            continue;
+        }
+        if let SubjectRef::BlankNode(node) = triple.subject {
+            if detail_nodes.contains(&node) {
+                continue;
+            }
+        }
+        let subject = match triple.subject {
+            SubjectRef::NamedNode(node) => match report_nodes.get(&node) {
+                Some(blank) => SubjectRef::from(blank.as_ref()),
+                None => triple.subject,
+            },
+            SubjectRef::BlankNode(_) => triple.subject,
+        };
+        let object = match triple.object {
+            TermRef::NamedNode(node) => match report_nodes.get(&node) {
+                Some(blank) => TermRef::from(blank.as_ref()),
+                None => triple.object,
+            },
+            _ => triple.object,
+        };
+        normalized.insert(TripleRef::new(subject, triple.predicate, object));
+    }
+    normalized
+}
+
+/// Checks whether a validation report matches the `expected` report: both are normalized
+/// with [`normalize_report`] and then compared with [`are_isomorphic`]. Skolem IRIs in
+/// `actual` should be turned back into blank nodes first, see [`deskolemize_graph`].
+pub fn compare_reports(actual: &Graph, expected: &Graph) -> bool {
+    are_isomorphic(
+        &normalize_report(actual, expected),
+        &normalize_report(expected, expected),
+    )
+}
+
 /// Creates a canonical version of a graph by replacing blank node identifiers
 /// with deterministic, content-based identifiers according to RDFC-1.0.
 ///
 /// This allows for meaningful comparison of graphs that contain blank nodes.
-pub(crate) fn to_canonical_graph(graph: &Graph) -> Graph {
+/// Blank nodes are labelled `c14n0`, `c14n1`, ... so isomorphic graphs come out equal.
+pub fn to_canonical_graph(graph: &Graph) -> Graph {
     let bnode_labels = rdfc10::canonicalize(graph);
 
     if bnode_labels.is_empty() {
@@ -120,6 +212,18 @@ pub(crate) fn to_canonical_graph(graph: &Graph) -> Graph {
     canonical_graph
 }
 
+/// Serializes a graph as canonical N-Quads: the triples of [`to_canonical_graph`], one per
+/// line in the default graph, sorted by code point. Isomorphic graphs serialize to the same
+/// string, so it can be hashed or diffed to compare reports.
+pub fn to_canonical_nquads(graph: &Graph) -> String {
+    let mut lines: Vec<String> = to_canonical_graph(graph)
+        .iter()
+        .map(|triple| format!("{} .\n", triple))
+        .collect();
+    lines.sort();
+    lines.concat()
+}
+
 mod rdfc10 {
     use super::*;
     use std::collections::{BTreeMap, HashMap, HashSet};
@@ -524,6 +628,28 @@ pub(crate) fn skolemize(
     Ok(())
 }
 
+/// Returns the skolem base IRI used for a graph unless the caller configured another one:
+/// `{graph_iri}/.well-known/skolem/`.
+pub fn default_skolem_base(graph_iri: &str) -> String {
+    format!("{}/.well-known/skolem/", graph_iri.trim_end_matches('/'))
+}
+
+/// Skolemizes a single triple using the same naming scheme as [`skolemize`], so triples
+/// loaded incrementally line up with graphs skolemized in place.
+pub(crate) fn skolemize_triple(triple: TripleRef<'_>, base_iri: &str) -> Triple {
+    let skolem =
+        |bn: BlankNodeRef<'_>| NamedNode::new_unchecked(format!("{}{}", base_iri, bn.as_str()));
+    let subject = match triple.subject {
+        SubjectRef::BlankNode(bn) => Subject::from(skolem(bn)),
+        other => other.into_owned(),
+    };
+    let object = match triple.object {
+        TermRef::BlankNode(bn) => Term::from(skolem(bn)),
+        other => other.into_owned(),
+    };
+    Triple::new(subject, triple.predicate.into_owned(), object)
+}
+
 /// Replaces skolem IRIs in a graph with blank nodes (Deskolemization).
 ///
 /// This is the reverse operation of `skolemize`. It looks for IRIs that start with
@@ -590,6 +716,7 @@ pub fn deskolemize_graph(graph: &Graph, base_iri: &str) -> Graph {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use oxigraph::io::{RdfFormat, RdfParser};
     use oxigraph::model::vocab::rdf;
     use oxigraph::model::{BlankNode, NamedNode, NamedOrBlankNode as Subject, Term, Triple};
 
@@ -689,4 +816,84 @@ mod tests {
 
         assert!(!are_isomorphic(&g1, &g2));
     }
+
+    #[test]
+    fn canonical_nquads_are_equal_for_isomorphic_graphs() {
+        let p = iri("http://example.org/p");
+        let graph = |labels: [&str; 2]| {
+            let mut graph = Graph::new();
+            let a = BlankNode::new_unchecked(labels[0]);
+            let b = BlankNode::new_unchecked(labels[1]);
+            graph.insert(Triple::new(a.clone(), p.clone(), Term::from(b.clone())).as_ref());
+            graph.insert(
+                Triple::new(b, p.clone(), Term::from(iri("http://example.org/o"))).as_ref(),
+            );
+            graph
+        };
+        let nquads = to_canonical_nquads(&graph(["x", "y"]));
+        assert_eq!(nquads, to_canonical_nquads(&graph(["y", "x"])));
+        assert!(nquads.contains("_:c14n0"), "{}", nquads);
+        let lines: Vec<&str> = nquads.lines().collect();
+        let mut sorted = lines.clone();
+        sorted.sort();
+        assert_eq!(lines, sorted);
+        assert!(lines.iter().all(|line| line.ends_with(" .")));
+    }
+
+    #[test]
+    fn long_lists_are_canonicalized_without_search() {
+        let list = |len: usize, prefix: &str| {
+            let mut graph = Graph::new();
+            let cells: Vec<BlankNode> = (0..len)
+                .map(|i| BlankNode::new_unchecked(format!("{}{}", prefix, i)))
+                .collect();
+            for (i, cell) in cells.iter().enumerate() {
+                let item = iri(&format!("http://example.org/item{}", i));
+                graph.insert(Triple::new(cell.clone(), rdf::FIRST, Term::from(item)).as_ref());
+                let rest = cells
+                    .get(i + 1)
+                    .map(|next| Term::from(next.clone()))
+                    .unwrap_or_else(|| Term::from(rdf::NIL));
+                graph.insert(Triple::new(cell.clone(), rdf::REST, rest).as_ref());
+            }
+            graph
+        };
+        assert!(are_isomorphic(&list(500, "a"), &list(500, "b")));
+        assert!(!are_isomorphic(&list(500, "a"), &list(499, "b")));
+    }
+
+    fn report(turtle: &str) -> Graph {
+        let prefixes = "@prefix sh: <http://www.w3.org/ns/shacl#> .\n\
+                        @prefix ex: <http://example.org/> .\n";
+        let document = format!("{}{}", prefixes, turtle);
+        let mut graph = Graph::new();
+        for quad in RdfParser::from_format(RdfFormat::Turtle).for_slice(document.as_bytes()) {
+            let quad = quad.unwrap();
+            graph.insert(TripleRef::new(&quad.subject, &quad.predicate, &quad.object));
+        }
+        graph
+    }
+
+    #[test]
+    fn compare_reports_ignores_unexpected_messages_details_and_report_names() {
+        let expected = report(
+            "[] a sh:ValidationReport ; sh:conforms false ;
+                sh:result [ sh:focusNode ex:a ; sh:sourceConstraintComponent sh:NodeConstraintComponent ] .",
+        );
+        let actual = report(
+            "ex:report a sh:ValidationReport ; sh:conforms false ;
+                sh:result [ sh:focusNode ex:a ; sh:sourceConstraintComponent sh:NodeConstraintComponent ;
+                    sh:resultMessage \"ex:a does not conform\" ;
+                    sh:detail [ sh:focusNode ex:a ; sh:resultPath [ sh:inversePath ex:p ] ] ] .",
+        );
+        assert!(!are_isomorphic(&actual, &expected));
+        assert!(compare_reports(&actual, &expected));
+
+        let with_message = report(
+            "[] a sh:ValidationReport ; sh:conforms false ;
+                sh:result [ sh:focusNode ex:a ; sh:sourceConstraintComponent sh:NodeConstraintComponent ;
+                    sh:resultMessage \"another message\" ] .",
+        );
+        assert!(!compare_reports(&actual, &with_message));
+    }
 }
@@ -0,0 +1,355 @@
+//! Running the W3C SHACL test suite against this build.
+//!
+//! Every `sht:Validate` test reachable from a manifest is run the way the repository's
+//! own manifest tests run it: the report must agree on `sh:conforms` and, once skolem
+//! IRIs are turned back into blank nodes, match the expected report under the test
+//! suite's comparison rules (see [`compare_reports`]). Tests are
+//! grouped into sections by the directory of their manifest relative to the root
+//! manifest, such as `core/property` or `sparql/pre-binding`, so the summary shows which
+//! areas of the specification are supported. A [`TestFilter`] narrows a run to some
+//! sections, test names or outcomes.
+
+use crate::canonicalization::{compare_reports, default_skolem_base, deskolemize_graph};
+use crate::test_utils::{collect_test_cases, TestCase};
+use crate::{Source, ValidatorBuilder};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+/// How a single test case fared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestStatus {
+    Passed,
+    /// The report disagreed with the expected one.
+    Failed(String),
+    /// The shapes or data could not be loaded, or validation panicked.
+    Errored(String),
+}
+
+impl TestStatus {
+    /// The kind of status, without its reason.
+    pub fn kind(&self) -> TestStatusKind {
+        match self {
+            TestStatus::Passed => TestStatusKind::Passed,
+            TestStatus::Failed(_) => TestStatusKind::Failed,
+            TestStatus::Errored(_) => TestStatusKind::Errored,
+        }
+    }
+}
+
+/// A [`TestStatus`] without its reason, for filtering outcomes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TestStatusKind {
+    Passed,
+    Failed,
+    Errored,
+}
+
+/// Selects which tests of a suite are run and which outcomes are reported. Each criterion
+/// left empty accepts everything; otherwise a test must match one of its values.
+#[derive(Debug, Clone, Default)]
+pub struct TestFilter {
+    sections: Vec<String>,
+    names: Vec<String>,
+    statuses: Vec<TestStatusKind>,
+}
+
+impl TestFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs the tests of `section` and the sections below it, e.g. `core` or
+    /// `core/property`.
+    pub fn with_section(mut self, section: impl Into<String>) -> Self {
+        self.sections.push(section.into());
+        self
+    }
+
+    /// Runs the tests whose name contains `name`.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.names.push(name.into());
+        self
+    }
+
+    /// Reports the tests that ended with `status`. Other tests are still run.
+    pub fn with_status(mut self, status: TestStatusKind) -> Self {
+        self.statuses.push(status);
+        self
+    }
+
+    /// Returns whether the test `name` of `section` is run.
+    pub fn selects(&self, section: &str, name: &str) -> bool {
+        let in_section = self.sections.is_empty()
+            || self.sections.iter().any(|prefix| {
+                let prefix = prefix.trim_end_matches('/');
+                section == prefix
+                    || section
+                        .strip_prefix(prefix)
+                        .is_some_and(|rest| rest.starts_with('/'))
+            });
+        let named = self.names.is_empty() || self.names.iter().any(|n| name.contains(n.as_str()));
+        in_section && named
+    }
+
+    /// Returns whether an outcome with `status` is reported.
+    pub fn reports(&self, status: &TestStatus) -> bool {
+        self.statuses.is_empty() || self.statuses.contains(&status.kind())
+    }
+}
+
+/// The status of one test case and where it came from.
+#[derive(Debug, Clone)]
+pub struct TestOutcome {
+    /// The section the test belongs to, e.g. `core/property`.
+    pub section: String,
+    /// The test's `rdfs:label`.
+    pub name: String,
+    /// The manifest that defines the test.
+    pub manifest: PathBuf,
+    pub status: TestStatus,
+}
+
+/// Pass/fail counts of one section of the suite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionSummary {
+    pub section: String,
+    pub passed: usize,
+    pub failed: usize,
+    pub errored: usize,
+}
+
+impl SectionSummary {
+    fn new(section: &str) -> Self {
+        Self {
+            section: section.to_string(),
+            passed: 0,
+            failed: 0,
+            errored: 0,
+        }
+    }
+
+    fn count(&mut self, status: &TestStatus) {
+        match status {
+            TestStatus::Passed => self.passed += 1,
+            TestStatus::Failed(_) => self.failed += 1,
+            TestStatus::Errored(_) => self.errored += 1,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.passed + self.failed + self.errored
+    }
+
+    /// Share of the section's tests that passed, from 0 to 1.
+    pub fn pass_rate(&self) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.passed as f64 / total as f64,
+        }
+    }
+}
+
+impl fmt::Display for SectionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{:.1}%",
+            self.section,
+            self.passed,
+            self.failed,
+            self.errored,
+            self.total(),
+            self.pass_rate() * 100.0
+        )
+    }
+}
+
+/// The outcomes of a test suite run.
+#[derive(Debug, Clone, Default)]
+pub struct ConformanceReport {
+    /// Outcomes sorted by section, then manifest and test name.
+    pub outcomes: Vec<TestOutcome>,
+}
+
+impl ConformanceReport {
+    /// Per-section counts, sorted by section.
+    pub fn sections(&self) -> Vec<SectionSummary> {
+        let mut sections: BTreeMap<&str, SectionSummary> = BTreeMap::new();
+        for outcome in &self.outcomes {
+            sections
+                .entry(outcome.section.as_str())
+                .or_insert_with(|| SectionSummary::new(&outcome.section))
+                .count(&outcome.status);
+        }
+        sections.into_values().collect()
+    }
+
+    /// Counts over the whole suite, in a section named `total`.
+    pub fn totals(&self) -> SectionSummary {
+        let mut totals = SectionSummary::new("total");
+        for outcome in &self.outcomes {
+            totals.count(&outcome.status);
+        }
+        totals
+    }
+
+    /// The tests that failed or errored.
+    pub fn failures(&self) -> impl Iterator<Item = &TestOutcome> {
+        self.outcomes
+            .iter()
+            .filter(|outcome| outcome.status != TestStatus::Passed)
+    }
+}
+
+/// Runs every test reachable from the manifest at `manifest_path`.
+pub fn run_test_suite(manifest_path: &Path) -> Result<ConformanceReport, String> {
+    run_test_suite_filtered(manifest_path, &TestFilter::default())
+}
+
+/// Runs the tests reachable from the manifest at `manifest_path` that `filter` selects,
+/// and reports the outcomes it keeps.
+pub fn run_test_suite_filtered(
+    manifest_path: &Path,
+    filter: &TestFilter,
+) -> Result<ConformanceReport, String> {
+    let root = manifest_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to open {}: {}", manifest_path.display(), e))?;
+    let root_dir = root.parent().unwrap_or(Path::new("")).to_path_buf();
+
+    let mut outcomes: Vec<TestOutcome> = collect_test_cases(&root)?
+        .into_iter()
+        .filter_map(|(manifest, test)| {
+            let section = section_of(&root_dir, &manifest);
+            if !filter.selects(&section, &test.name) {
+                return None;
+            }
+            Some(TestOutcome {
+                section,
+                status: run_test_case(&test),
+                name: test.name,
+                manifest,
+            })
+        })
+        .filter(|outcome| filter.reports(&outcome.status))
+        .collect();
+    outcomes.sort_by(|a, b| a.section.cmp(&b.section));
+    Ok(ConformanceReport { outcomes })
+}
+
+/// Runs one test case. A panic during validation is reported as an error.
+pub fn run_test_case(test: &TestCase) -> TestStatus {
+    match panic::catch_unwind(AssertUnwindSafe(|| check_test_case(test))) {
+        Ok(status) => status,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            TestStatus::Errored(format!("validation panicked: {}", message))
+        }
+    }
+}
+
+fn check_test_case(test: &TestCase) -> TestStatus {
+    let validator = match ValidatorBuilder::new()
+        .with_shapes_source(Source::File(test.shapes_graph_path.clone()))
+        .with_data_source(Source::File(test.data_graph_path.clone()))
+        .build()
+    {
+        Ok(validator) => validator,
+        Err(e) => return TestStatus::Errored(format!("failed to build validator: {}", e)),
+    };
+    let report = validator.validate();
+    if report.conforms() != test.conforms {
+        return TestStatus::Failed(format!(
+            "expected sh:conforms {}, got {}",
+            test.conforms,
+            report.conforms()
+        ));
+    }
+
+    let mut report_graph = report.to_graph();
+    for graph in [validator.data_graph_iri(), validator.shapes_graph_iri()] {
+        report_graph = deskolemize_graph(&report_graph, &default_skolem_base(graph.as_str()));
+    }
+    if compare_reports(&report_graph, &test.expected_report) {
+        TestStatus::Passed
+    } else {
+        TestStatus::Failed("the report differs from the expected report".to_string())
+    }
+}
+
+/// Returns the directory of `manifest` relative to `root_dir`, with `/` separators; tests
+/// of the root manifest itself are in the section `.`.
+fn section_of(root_dir: &Path, manifest: &Path) -> String {
+    let dir = manifest.parent().unwrap_or(manifest);
+    let relative = dir.strip_prefix(root_dir).unwrap_or(dir);
+    let section = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    if section.is_empty() {
+        ".".to_string()
+    } else {
+        section
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections_are_manifest_directories_relative_to_the_root() {
+        let root = Path::new("/suite");
+        assert_eq!(
+            section_of(root, Path::new("/suite/core/property/minCount-001.ttl")),
+            "core/property"
+        );
+        assert_eq!(section_of(root, Path::new("/suite/manifest.ttl")), ".");
+    }
+
+    #[test]
+    fn summaries_count_each_status() {
+        let outcome = |section: &str, status| TestOutcome {
+            section: section.to_string(),
+            name: "test".to_string(),
+            manifest: PathBuf::new(),
+            status,
+        };
+        let report = ConformanceReport {
+            outcomes: vec![
+                outcome("core/node", TestStatus::Passed),
+                outcome("core/node", TestStatus::Failed("differs".to_string())),
+                outcome("sparql/node", TestStatus::Errored("panicked".to_string())),
+            ],
+        };
+        let sections = report.sections();
+        assert_eq!(sections.len(), 2);
+        assert_eq!((sections[0].passed, sections[0].failed), (1, 1));
+        assert_eq!(sections[0].to_string(), "core/node\t1\t1\t0\t2\t50.0%");
+        assert_eq!(report.totals().total(), 3);
+        assert_eq!(report.failures().count(), 2);
+    }
+
+    #[test]
+    fn filters_match_sections_below_a_prefix_and_name_substrings() {
+        let filter = TestFilter::new()
+            .with_section("core/")
+            .with_name("minCount");
+        assert!(filter.selects("core/property", "Test of sh:minCount 001"));
+        assert!(filter.selects("core", "minCount at the root"));
+        assert!(!filter.selects("corelike", "minCount"));
+        assert!(!filter.selects("core/property", "Test of sh:maxCount 001"));
+        assert!(TestFilter::new().selects("sparql/node", "anything"));
+
+        let failed = TestFilter::new().with_status(TestStatusKind::Failed);
+        assert!(failed.reports(&TestStatus::Failed("differs".to_string())));
+        assert!(!failed.reports(&TestStatus::Passed));
+    }
+}
@@ -0,0 +1,440 @@
+//! Human-readable documentation of the shapes graph for data modelers: each named node
+//! shape with its description, targets and a table of its property shapes.
+
+use super::model::ShapesModel;
+use crate::model::components::ComponentDescriptor;
+use crate::named_nodes::SHACL;
+use crate::shape::{ShapeKind, ShapeView};
+use crate::types::{Path, Target, ID};
+use oxigraph::model::{NamedNodeRef, NamedOrBlankNodeRef, Term};
+
+/// A node shape as it is documented.
+struct ShapeDoc {
+    term: String,
+    name: Option<String>,
+    description: Option<String>,
+    deactivated: bool,
+    targets: Vec<String>,
+    properties: Vec<PropertyDoc>,
+    constraints: Vec<String>,
+}
+
+/// A row of a node shape's property table.
+struct PropertyDoc {
+    path: String,
+    name: Option<String>,
+    description: Option<String>,
+    cardinality: String,
+    value_type: Vec<String>,
+    constraints: Vec<String>,
+}
+
+/// Renders the named node shapes of `model` as a Markdown document.
+pub(crate) fn render_shapes_markdown(model: &ShapesModel) -> String {
+    let mut out = String::from("# Shapes\n");
+    for shape in document_shapes(model) {
+        out.push_str(&format!("\n## `{}`", shape.term));
+        if let Some(name) = &shape.name {
+            out.push_str(&format!(" ({})", markdown_text(name)));
+        }
+        out.push('\n');
+        if shape.deactivated {
+            out.push_str("\n*Deactivated.*\n");
+        }
+        if let Some(description) = &shape.description {
+            out.push_str(&format!("\n{}\n", markdown_text(description)));
+        }
+        if !shape.targets.is_empty() {
+            out.push_str(&format!("\nTargets: {}\n", shape.targets.join(", ")));
+        }
+        if !shape.properties.is_empty() {
+            out.push_str(
+                "\n| Property | Name | Cardinality | Type | Constraints | Description |\n",
+            );
+            out.push_str("|---|---|---|---|---|---|\n");
+            for property in &shape.properties {
+                out.push_str(&format!(
+                    "| `{}` | {} | {} | {} | {} | {} |\n",
+                    markdown_cell(&property.path),
+                    markdown_cell(property.name.as_deref().unwrap_or("")),
+                    property.cardinality,
+                    markdown_cell(&property.value_type.join(", ")),
+                    markdown_cell(&property.constraints.join("; ")),
+                    markdown_cell(property.description.as_deref().unwrap_or("")),
+                ));
+            }
+        }
+        if !shape.constraints.is_empty() {
+            out.push_str("\nConstraints:\n\n");
+            for constraint in &shape.constraints {
+                out.push_str(&format!("- {}\n", constraint));
+            }
+        }
+    }
+    out
+}
+
+/// Renders the named node shapes of `model` as a standalone HTML page.
+pub(crate) fn render_shapes_html(model: &ShapesModel) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Shapes</title>\n</head>\n<body>\n<h1>Shapes</h1>\n",
+    );
+    for shape in document_shapes(model) {
+        out.push_str("<section>\n");
+        out.push_str(&format!("<h2><code>{}</code>", html_text(&shape.term)));
+        if let Some(name) = &shape.name {
+            out.push_str(&format!(" ({})", html_text(name)));
+        }
+        out.push_str("</h2>\n");
+        if shape.deactivated {
+            out.push_str("<p><em>Deactivated.</em></p>\n");
+        }
+        if let Some(description) = &shape.description {
+            out.push_str(&format!("<p>{}</p>\n", html_text(description)));
+        }
+        if !shape.targets.is_empty() {
+            out.push_str(&format!(
+                "<p>Targets: {}</p>\n",
+                html_text(&shape.targets.join(", "))
+            ));
+        }
+        if !shape.properties.is_empty() {
+            out.push_str("<table>\n<tr><th>Property</th><th>Name</th><th>Cardinality</th><th>Type</th><th>Constraints</th><th>Description</th></tr>\n");
+            for property in &shape.properties {
+                out.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    html_text(&property.path),
+                    html_text(property.name.as_deref().unwrap_or("")),
+                    property.cardinality,
+                    html_text(&property.value_type.join(", ")),
+                    html_text(&property.constraints.join("; ")),
+                    html_text(property.description.as_deref().unwrap_or("")),
+                ));
+            }
+            out.push_str("</table>\n");
+        }
+        if !shape.constraints.is_empty() {
+            out.push_str("<p>Constraints:</p>\n<ul>\n");
+            for constraint in &shape.constraints {
+                out.push_str(&format!("<li>{}</li>\n", html_text(constraint)));
+            }
+            out.push_str("</ul>\n");
+        }
+        out.push_str("</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Collects the node shapes named by IRIs, ordered by term. Anonymous node shapes are
+/// described where they are referenced.
+fn document_shapes(model: &ShapesModel) -> Vec<ShapeDoc> {
+    ShapeView::all(model)
+        .into_iter()
+        .filter(|view| matches!(view.kind(), ShapeKind::Node(_)))
+        .filter(|view| matches!(view.term(), Term::NamedNode(_)))
+        .map(|view| {
+            let term = view.term();
+            let properties = view
+                .property_shapes()
+                .iter()
+                .map(|property| property_doc(model, property))
+                .collect();
+            let constraints = view
+                .constraints()
+                .filter(|(_, descriptor)| {
+                    !matches!(descriptor, ComponentDescriptor::Property { .. })
+                })
+                .filter_map(|(_, descriptor)| describe_constraint(model, descriptor))
+                .collect();
+            ShapeDoc {
+                term: model.prefixes.compact(&term),
+                name: shape_literal(model, &term, SHACL::get().name),
+                description: shape_literal(model, &term, SHACL::get().description),
+                deactivated: view.is_deactivated(),
+                targets: view
+                    .targets()
+                    .iter()
+                    .map(|target| describe_target(model, target))
+                    .collect(),
+                properties,
+                constraints,
+            }
+        })
+        .collect()
+}
+
+fn property_doc(model: &ShapesModel, property: &ShapeView<'_>) -> PropertyDoc {
+    let term = property.term();
+    let path = match property.path() {
+        Some(Path::Simple(predicate)) => model.prefixes.compact(predicate),
+        _ => match property.kind() {
+            ShapeKind::Property(shape) => shape.sparql_path(),
+            ShapeKind::Node(_) => String::new(),
+        },
+    };
+    let mut min_count = 0;
+    let mut max_count = None;
+    let mut value_type = Vec::new();
+    let mut constraints = Vec::new();
+    for (_, descriptor) in property.constraints() {
+        match descriptor {
+            ComponentDescriptor::MinCount { min_count: min } => min_count = *min,
+            ComponentDescriptor::MaxCount { max_count: max } => max_count = Some(*max),
+            ComponentDescriptor::Datatype { datatype } => {
+                value_type.push(model.prefixes.compact(datatype))
+            }
+            ComponentDescriptor::Class { class } => {
+                value_type.push(format!("instance of {}", model.prefixes.compact(class)))
+            }
+            ComponentDescriptor::NodeKind { node_kind } => {
+                value_type.push(model.prefixes.compact(node_kind))
+            }
+            ComponentDescriptor::DatatypeIn { datatypes } => {
+                value_type.push(format!("one of {}", terms_text(model, datatypes)))
+            }
+            ComponentDescriptor::Node { shape } => {
+                value_type.push(format!("conforms to {}", shape_text(model, shape)))
+            }
+            other => constraints.extend(describe_constraint(model, other)),
+        }
+    }
+    let cardinality = match max_count {
+        Some(max) => format!("{}..{}", min_count, max),
+        None => format!("{}..*", min_count),
+    };
+    PropertyDoc {
+        path,
+        name: shape_literal(model, &term, SHACL::get().name),
+        description: shape_literal(model, &term, SHACL::get().description),
+        cardinality,
+        value_type,
+        constraints,
+    }
+}
+
+/// Describes a constraint in a short phrase; `None` for constraints that say nothing,
+/// such as `sh:closed false`.
+fn describe_constraint(model: &ShapesModel, descriptor: &ComponentDescriptor) -> Option<String> {
+    let term = |term: &Term| term_text(model, term);
+    let described = match descriptor {
+        ComponentDescriptor::Node { shape } => format!("conforms to {}", shape_text(model, shape)),
+        ComponentDescriptor::Property { shape } => match ShapeView::property(model, shape) {
+            Some(property) => format!("property {}", property_doc(model, &property).path),
+            None => "property".to_string(),
+        },
+        ComponentDescriptor::QualifiedValueShape {
+            shape,
+            min_count,
+            max_count,
+            disjoint,
+        } => {
+            let count = match (min_count, max_count) {
+                (Some(min), Some(max)) => format!("{} to {}", min, max),
+                (Some(min), None) => format!("at least {}", min),
+                (None, Some(max)) => format!("at most {}", max),
+                (None, None) => "any number of".to_string(),
+            };
+            let disjoint = if disjoint.unwrap_or(false) {
+                " and no sibling shape"
+            } else {
+                ""
+            };
+            format!(
+                "{} values conform to {}{}",
+                count,
+                shape_text(model, shape),
+                disjoint
+            )
+        }
+        ComponentDescriptor::Class { class } => format!("instance of {}", term(class)),
+        ComponentDescriptor::Datatype { datatype } => format!("datatype {}", term(datatype)),
+        ComponentDescriptor::DatatypeIn { datatypes } => {
+            format!("datatype one of {}", terms_text(model, datatypes))
+        }
+        ComponentDescriptor::NodeKind { node_kind } => format!("node kind {}", term(node_kind)),
+        ComponentDescriptor::MinCount { min_count } => format!("at least {} values", min_count),
+        ComponentDescriptor::MaxCount { max_count } => format!("at most {} values", max_count),
+        ComponentDescriptor::MinExclusive { value } => format!("> {}", term(value)),
+        ComponentDescriptor::MinInclusive { value } => format!(">= {}", term(value)),
+        ComponentDescriptor::MaxExclusive { value } => format!("< {}", term(value)),
+        ComponentDescriptor::MaxInclusive { value } => format!("<= {}", term(value)),
+        ComponentDescriptor::MinLength { length } => format!("length >= {}", length),
+        ComponentDescriptor::MaxLength { length } => format!("length <= {}", length),
+        ComponentDescriptor::Pattern { pattern, flags } => match flags {
+            Some(flags) if !flags.is_empty() => {
+                format!("matches /{}/ (flags {})", pattern, flags)
+            }
+            _ => format!("matches /{}/", pattern),
+        },
+        ComponentDescriptor::LanguageIn { languages } => {
+            format!("language one of {}", languages.join(", "))
+        }
+        ComponentDescriptor::UniqueLang { enabled } => {
+            if !enabled {
+                return None;
+            }
+            "one value per language".to_string()
+        }
+        ComponentDescriptor::Equals { property } => format!("equals {}", term(property)),
+        ComponentDescriptor::Disjoint { property } => {
+            format!("disjoint with {}", term(property))
+        }
+        ComponentDescriptor::LessThan { property } => format!("less than {}", term(property)),
+        ComponentDescriptor::LessThanOrEquals { property } => {
+            format!("less than or equal to {}", term(property))
+        }
+        ComponentDescriptor::Not { shape } => {
+            format!("does not conform to {}", shape_text(model, shape))
+        }
+        ComponentDescriptor::And { shapes } => {
+            format!("conforms to all of {}", shapes_text(model, shapes))
+        }
+        ComponentDescriptor::Or { shapes } => {
+            format!("conforms to at least one of {}", shapes_text(model, shapes))
+        }
+        ComponentDescriptor::Xone { shapes } => {
+            format!("conforms to exactly one of {}", shapes_text(model, shapes))
+        }
+        ComponentDescriptor::Closed {
+            closed,
+            ignored_properties,
+        } => {
+            if !closed {
+                return None;
+            }
+            if ignored_properties.is_empty() {
+                "closed".to_string()
+            } else {
+                format!("closed, ignoring {}", terms_text(model, ignored_properties))
+            }
+        }
+        ComponentDescriptor::HasValue { value } => format!("has value {}", term(value)),
+        ComponentDescriptor::In { values } => format!("one of {}", terms_text(model, values)),
+        ComponentDescriptor::Sparql { constraint_node } => {
+            match shape_literal(model, constraint_node, SHACL::get().message) {
+                Some(message) => format!("SPARQL constraint: {}", message),
+                None => "SPARQL constraint".to_string(),
+            }
+        }
+        ComponentDescriptor::Custom {
+            definition,
+            parameter_values,
+        } => {
+            let mut parameters: Vec<String> = parameter_values
+                .iter()
+                .map(|(parameter, values)| {
+                    format!(
+                        "{} {}",
+                        model.prefixes.compact(&parameter.clone().into()),
+                        terms_text(model, values)
+                    )
+                })
+                .collect();
+            parameters.sort();
+            format!(
+                "{} ({})",
+                model.prefixes.compact(&definition.iri.clone().into()),
+                parameters.join(", ")
+            )
+        }
+    };
+    Some(described)
+}
+
+fn describe_target(model: &ShapesModel, target: &Target) -> String {
+    match target {
+        Target::Class(class) => format!("instances of {}", term_text(model, class)),
+        Target::Node(node) => format!("node {}", term_text(model, node)),
+        Target::SubjectsOf(predicate) => {
+            format!("subjects of {}", term_text(model, predicate))
+        }
+        Target::ObjectsOf(predicate) => format!("objects of {}", term_text(model, predicate)),
+        Target::Advanced(_) => "custom target".to_string(),
+    }
+}
+
+/// A term as modelers write it: literals by their lexical form, IRIs as prefixed names.
+fn term_text(model: &ShapesModel, term: &Term) -> String {
+    match term {
+        Term::Literal(literal) => format!("\"{}\"", literal.value()),
+        other => model.prefixes.compact(other),
+    }
+}
+
+fn terms_text(model: &ShapesModel, terms: &[Term]) -> String {
+    terms
+        .iter()
+        .map(|term| term_text(model, term))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A referenced node shape: its name in the shapes graph, or "an anonymous shape".
+fn shape_text(model: &ShapesModel, shape: &ID) -> String {
+    match ShapeView::node(model, shape).map(|view| view.term()) {
+        Some(term @ Term::NamedNode(_)) => model.prefixes.compact(&term),
+        _ => "an anonymous shape".to_string(),
+    }
+}
+
+fn shapes_text(model: &ShapesModel, shapes: &[ID]) -> String {
+    shapes
+        .iter()
+        .map(|shape| shape_text(model, shape))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The value of `predicate` on `subject` in the shapes graph, preferring untagged and
+/// English literals.
+fn shape_literal(
+    model: &ShapesModel,
+    subject: &Term,
+    predicate: NamedNodeRef<'_>,
+) -> Option<String> {
+    let subject = match subject {
+        Term::NamedNode(node) => NamedOrBlankNodeRef::NamedNode(node.as_ref()),
+        Term::BlankNode(node) => NamedOrBlankNodeRef::BlankNode(node.as_ref()),
+        _ => return None,
+    };
+    let mut literals: Vec<(u8, String)> = model
+        .store()
+        .quads_for_pattern(
+            Some(subject),
+            Some(predicate),
+            None,
+            Some(model.shape_graph_iri_ref()),
+        )
+        .filter_map(Result::ok)
+        .filter_map(|quad| match quad.object {
+            Term::Literal(literal) => {
+                let rank = match literal.language() {
+                    None => 0,
+                    Some(language) if language.starts_with("en") => 1,
+                    Some(_) => 2,
+                };
+                Some((rank, literal.value().to_string()))
+            }
+            _ => None,
+        })
+        .collect();
+    literals.sort();
+    literals.into_iter().next().map(|(_, value)| value)
+}
+
+/// Collapses whitespace, so multi-line descriptions stay one paragraph.
+fn markdown_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn markdown_cell(text: &str) -> String {
+    markdown_text(text).replace('|', "\\|")
+}
+
+fn html_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
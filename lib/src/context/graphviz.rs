@@ -2,9 +2,10 @@ use super::model::ShapesModel;
 use super::validation::ValidationContext;
 use crate::model::components::ComponentDescriptor;
 use crate::runtime::build_component_from_descriptor;
-use crate::types::TraceItem;
+use crate::shape::{ShapeKind, ShapeView};
+use crate::types::{Target, TraceItem};
 use oxigraph::model::Term;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 pub(crate) fn sanitize_graphviz_string(input: &str) -> String {
     input.chars().filter(|c| c.is_alphanumeric()).collect()
@@ -47,12 +48,18 @@ pub(crate) fn render_shapes_graphviz(model: &ShapesModel) -> Result<String, Stri
             .get_term(*shape.identifier())
             .ok_or_else(|| format!("Missing term for nodeshape ID: {:?}", shape.identifier()))?
             .clone();
-        let name_label = format_term_for_label(&name);
+        let name_label = model.prefixes.label(&name);
         dot_string.push_str(&format!(
             "  {} [label=\"NodeShape\\n{}\"];\n",
             shape.identifier().to_graphviz_id(),
             name_label
         ));
+        push_target_nodes(
+            &mut dot_string,
+            model,
+            &shape.identifier().to_graphviz_id(),
+            &shape.targets,
+        );
         for comp in shape.constraints() {
             dot_string.push_str(&format!(
                 "    {} -> {};\n",
@@ -86,6 +93,12 @@ pub(crate) fn render_shapes_graphviz(model: &ShapesModel) -> Result<String, Stri
             pshape.identifier().to_graphviz_id(),
             path_label
         ));
+        push_target_nodes(
+            &mut dot_string,
+            model,
+            &pshape.identifier().to_graphviz_id(),
+            &pshape.targets,
+        );
         for comp in pshape.constraints() {
             dot_string.push_str(&format!(
                 "    {} -> {};\n",
@@ -128,6 +141,113 @@ pub(crate) fn render_shapes_graphviz(model: &ShapesModel) -> Result<String, Stri
     Ok(dot_string)
 }
 
+/// Renders `root`, its constraints and the shapes they reference, following references
+/// at most `max_depth` levels away from `root`. Property shapes count as part of the
+/// shape declaring them, so they never use up depth. Shapes past the bound are drawn
+/// dashed and not expanded.
+pub(crate) fn render_shape_graphviz(
+    model: &ShapesModel,
+    root: ShapeView<'_>,
+    max_depth: usize,
+) -> Result<String, String> {
+    let mut dot_string = String::new();
+    dot_string.push_str("digraph {\n");
+
+    let mut drawn: HashSet<String> = HashSet::new();
+    let mut queue: VecDeque<(ShapeView<'_>, usize)> = VecDeque::new();
+    drawn.insert(root.graphviz_id());
+    queue.push_back((root, 0));
+    while let Some((shape, depth)) = queue.pop_front() {
+        dot_string.push_str(&format!(
+            "  {} [label=\"{}\"];\n",
+            shape.graphviz_id(),
+            shape_graphviz_label(model, &shape)
+        ));
+        push_target_nodes(
+            &mut dot_string,
+            model,
+            &shape.graphviz_id(),
+            shape.targets(),
+        );
+        for (comp_id, descriptor) in shape.constraints() {
+            let component = build_component_from_descriptor(descriptor);
+            dot_string.push_str(&format!(
+                "  {} [label=\"{}\"];\n",
+                comp_id.to_graphviz_id(),
+                component.label()
+            ));
+            dot_string.push_str(&format!(
+                "    {} -> {};\n",
+                shape.graphviz_id(),
+                comp_id.to_graphviz_id()
+            ));
+            let next_depth = match descriptor {
+                ComponentDescriptor::Property { .. } => depth,
+                _ => depth + 1,
+            };
+            for referenced in shape.referenced_shapes(descriptor) {
+                dot_string.push_str(&format!(
+                    "    {} -> {};\n",
+                    comp_id.to_graphviz_id(),
+                    referenced.graphviz_id()
+                ));
+                if !drawn.insert(referenced.graphviz_id()) {
+                    continue;
+                }
+                if next_depth <= max_depth {
+                    queue.push_back((referenced, next_depth));
+                } else {
+                    dot_string.push_str(&format!(
+                        "  {} [label=\"{}\", style=dashed];\n",
+                        referenced.graphviz_id(),
+                        shape_graphviz_label(model, &referenced)
+                    ));
+                }
+            }
+        }
+    }
+    dot_string.push_str("}\n");
+    Ok(dot_string)
+}
+
+/// Draws each target of the shape `shape_id` as a node holding the target's term, with an
+/// edge to the shape labelled by the target predicate.
+fn push_target_nodes(
+    dot_string: &mut String,
+    model: &ShapesModel,
+    shape_id: &str,
+    targets: &[Target],
+) {
+    for (index, target) in targets.iter().enumerate() {
+        let (predicate, term) = match target {
+            Target::Class(term) => ("sh:targetClass", term),
+            Target::Node(term) => ("sh:targetNode", term),
+            Target::SubjectsOf(term) => ("sh:targetSubjectsOf", term),
+            Target::ObjectsOf(term) => ("sh:targetObjectsOf", term),
+            Target::Advanced(term) => ("sh:target", term),
+        };
+        let target_id = format!("{}_t{}", shape_id, index);
+        dot_string.push_str(&format!(
+            "  {} [label=\"{}\", shape=box, style=rounded];\n",
+            target_id,
+            model.prefixes.label(term)
+        ));
+        dot_string.push_str(&format!(
+            "    {} -> {} [label=\"{}\", style=dotted];\n",
+            target_id, shape_id, predicate
+        ));
+    }
+}
+
+fn shape_graphviz_label(model: &ShapesModel, shape: &ShapeView<'_>) -> String {
+    match shape.kind() {
+        ShapeKind::Node(_) => format!("NodeShape\\n{}", model.prefixes.label(&shape.term())),
+        ShapeKind::Property(property) => {
+            format!("PropertyShape\\nPath: {}", property.sparql_path())
+        }
+    }
+}
+
 pub(crate) fn render_heatmap_graphviz(
     context: &ValidationContext,
     include_all_nodes: bool,
@@ -179,7 +299,7 @@ pub(crate) fn render_heatmap_graphviz(
             .get_term(*shape.identifier())
             .ok_or_else(|| format!("Missing term for nodeshape ID: {:?}", shape.identifier()))?
             .clone();
-        let name_label = format_term_for_label(&name);
+        let name_label = context.model.prefixes.label(&name);
         dot_string.push_str(&format!(
             "  {} [label=\"NodeShape\\n{}\\n({:.2}%) ({}/{})\", fillcolor=\"{}\"];\n",
             shape.identifier().to_graphviz_id(),
@@ -1,10 +1,12 @@
+pub(crate) mod documentation;
 pub(crate) mod graphviz;
 pub(crate) mod ids;
 pub(crate) mod model;
 pub(crate) mod validation;
 
+pub(crate) use documentation::{render_shapes_html, render_shapes_markdown};
 pub(crate) use graphviz::{
-    format_term_for_label, render_heatmap_graphviz, render_shapes_graphviz,
+    format_term_for_label, render_heatmap_graphviz, render_shape_graphviz, render_shapes_graphviz,
     sanitize_graphviz_string,
 };
 #[allow(unused_imports)]
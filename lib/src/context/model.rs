@@ -1,13 +1,21 @@
 #![allow(deprecated)]
 use super::ids::IDLookupTable;
+use crate::canonicalization::default_skolem_base;
+use crate::messages::MessageCatalog;
+use crate::model::components::sparql::CustomConstraintComponentDefinition;
 use crate::model::{
     components::ComponentDescriptor, ComponentTemplateDefinition, Rule, ShapeTemplateDefinition,
 };
 use crate::optimize::Optimizer;
 use crate::parser;
+use crate::prefix_map::PrefixMap;
 use crate::shape::{NodeShape, PropertyShape};
 use crate::sparql::SparqlServices;
-use crate::types::{ComponentID, PropShapeID, RuleID, ID};
+use crate::types::{
+    ComponentID, CustomSeverityConformance, DuplicateShapePolicy, PropShapeID, QueryDataset,
+    RuleID, ID,
+};
+use crate::unsupported::UnsupportedFeature;
 use log::info;
 use ontoenv::api::OntoEnv;
 use ontoenv::ontology::OntologyLocation;
@@ -30,6 +38,41 @@ pub struct FeatureToggles {
     #[allow(dead_code)]
     pub enable_rules: bool,
     pub skip_invalid_rules: bool,
+    /// Maximum number of value nodes a path may produce for one focus node.
+    pub max_value_nodes: Option<usize>,
+    /// Skolem base IRI for the shapes graph; derived from the graph IRI when unset.
+    pub shape_skolem_base: Option<String>,
+    /// Skolem base IRI for data graphs; derived from the graph IRI when unset.
+    pub data_skolem_base: Option<String>,
+    /// Whether blank nodes of data graphs are skolemized. When not, the data graph holds
+    /// the caller's blank nodes and changes are applied as given.
+    pub skolemize_data: bool,
+    /// Whether results with a custom severity IRI make a report non-conforming.
+    pub custom_severity_conformance: CustomSeverityConformance,
+    /// How conflicting shape definitions in imported graphs are handled.
+    pub duplicate_shape_policy: DuplicateShapePolicy,
+    /// Whether the `owl:imports` closure was merged into the shapes graph.
+    pub shapes_imports: bool,
+    /// The default graph of SPARQL constraint queries.
+    pub query_dataset: QueryDataset,
+    /// Templates of the engine's own result messages.
+    pub messages: MessageCatalog,
+}
+
+impl FeatureToggles {
+    /// Returns the skolem base IRI for the shapes graph `shape_graph_iri`.
+    pub(crate) fn shape_skolem_base_for(&self, shape_graph_iri: &NamedNode) -> String {
+        self.shape_skolem_base
+            .clone()
+            .unwrap_or_else(|| default_skolem_base(shape_graph_iri.as_str()))
+    }
+
+    /// Returns the skolem base IRI for the data graph `data_graph_iri`.
+    pub(crate) fn data_skolem_base_for(&self, data_graph_iri: &NamedNode) -> String {
+        self.data_skolem_base
+            .clone()
+            .unwrap_or_else(|| default_skolem_base(data_graph_iri.as_str()))
+    }
 }
 
 impl Default for FeatureToggles {
@@ -38,6 +81,15 @@ impl Default for FeatureToggles {
             enable_af: true,
             enable_rules: true,
             skip_invalid_rules: false,
+            max_value_nodes: None,
+            shape_skolem_base: None,
+            data_skolem_base: None,
+            skolemize_data: true,
+            custom_severity_conformance: CustomSeverityConformance::default(),
+            duplicate_shape_policy: DuplicateShapePolicy::default(),
+            query_dataset: QueryDataset::default(),
+            shapes_imports: false,
+            messages: MessageCatalog::default(),
         }
     }
 }
@@ -66,8 +118,12 @@ impl OriginalValueIndex {
         Self::default()
     }
 
-    pub fn from_path(path: &Path, skolem_base: Option<&str>) -> Result<Self, Box<dyn Error>> {
-        let mut index = Self::new();
+    /// Adds the literals of the data file at `path` to the index.
+    pub fn extend_from_path(
+        &mut self,
+        path: &Path,
+        skolem_base: Option<&str>,
+    ) -> Result<(), Box<dyn Error>> {
         let extension = path
             .extension()
             .and_then(|ext| ext.to_str())
@@ -81,7 +137,7 @@ impl OriginalValueIndex {
 
         let format = match format {
             Some(f) => f,
-            None => return Ok(index),
+            None => return Ok(()),
         };
 
         let file = File::open(path)?;
@@ -89,14 +145,38 @@ impl OriginalValueIndex {
         let parser = RdfParser::from_format(format).without_named_graphs();
         for quad in parser.for_reader(reader) {
             let triple = quad?;
-            index.record_triple(
+            self.record_triple(
                 triple.subject,
                 triple.predicate.clone(),
                 triple.object,
                 skolem_base,
             );
         }
-        Ok(index)
+        Ok(())
+    }
+
+    /// Adds the literals of an in-memory Turtle or N-Triples document to the index; other
+    /// formats are skipped.
+    pub fn extend_from_slice(
+        &mut self,
+        data: &[u8],
+        format: RdfFormat,
+        skolem_base: Option<&str>,
+    ) -> Result<(), Box<dyn Error>> {
+        if !matches!(format, RdfFormat::Turtle | RdfFormat::NTriples) {
+            return Ok(());
+        }
+        let parser = RdfParser::from_format(format).without_named_graphs();
+        for quad in parser.for_slice(data) {
+            let triple = quad?;
+            self.record_triple(
+                triple.subject,
+                triple.predicate.clone(),
+                triple.object,
+                skolem_base,
+            );
+        }
+        Ok(())
     }
 
     fn record_triple(
@@ -194,6 +274,11 @@ pub struct ShapesModel {
     #[allow(dead_code)]
     pub(crate) features: FeatureToggles,
     pub(crate) original_values: Option<OriginalValueIndex>,
+    pub(crate) prefixes: PrefixMap,
+    /// Constructs of the shapes graph the engine does not implement.
+    pub(crate) unsupported_features: Vec<UnsupportedFeature>,
+    /// Custom constraint components declared in the shapes graph, sorted by IRI.
+    pub(crate) custom_components: Vec<CustomConstraintComponentDefinition>,
 }
 
 impl ShapesModel {
@@ -213,10 +298,7 @@ impl ShapesModel {
         let dummy_data_graph_iri = NamedNode::new("urn:dummy:data_graph")?;
         let store = env.io().store().clone();
 
-        let shape_graph_base_iri = format!(
-            "{}/.well-known/skolem/",
-            shape_graph_iri.as_str().trim_end_matches('/')
-        );
+        let shape_graph_base_iri = default_skolem_base(shape_graph_iri.as_str());
         info!(
             "Skolemizing shape graph <{}> with base IRI <{}>",
             shape_graph_iri, shape_graph_base_iri
@@ -253,6 +335,11 @@ impl ShapesModel {
         optimizer.optimize()?;
         info!("Finished parsing shapes and optimizing context");
         let final_ctx = optimizer.finish();
+        let prefixes = PrefixMap::for_shapes(
+            &final_ctx.store,
+            &final_ctx.env,
+            final_ctx.shape_graph_iri_ref(),
+        );
 
         Ok(ShapesModel {
             nodeshape_id_lookup: final_ctx.nodeshape_id_lookup,
@@ -273,7 +360,10 @@ impl ShapesModel {
             env: final_ctx.env,
             sparql: final_ctx.sparql.clone(),
             features: final_ctx.features.clone(),
+            prefixes,
             original_values: final_ctx.original_values,
+            unsupported_features: final_ctx.unsupported_features,
+            custom_components: final_ctx.custom_components,
         })
     }
 
@@ -336,6 +426,8 @@ pub(crate) struct ParsingContext {
     #[allow(dead_code)]
     pub(crate) features: FeatureToggles,
     pub(crate) original_values: Option<OriginalValueIndex>,
+    pub(crate) unsupported_features: Vec<UnsupportedFeature>,
+    pub(crate) custom_components: Vec<CustomConstraintComponentDefinition>,
 }
 
 impl ParsingContext {
@@ -372,6 +464,8 @@ impl ParsingContext {
             sparql: Rc::new(SparqlServices::new()),
             features,
             original_values,
+            unsupported_features: Vec::new(),
+            custom_components: Vec::new(),
         }
     }
 
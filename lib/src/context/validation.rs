@@ -1,37 +1,63 @@
-use super::graphviz::format_term_for_label;
 use super::model::ShapesModel;
+use crate::audit::AuditLog;
+use crate::canonicalization::skolemize_triple;
+use crate::incremental::QuadChange;
+use crate::messages::MessageCatalog;
 use crate::model::components::sparql::CustomConstraintComponentDefinition;
 use crate::model::components::ComponentDescriptor;
+use crate::report::{ConformanceCacheStats, VALUE_NODE_LIMIT_COMPONENT};
 use crate::runtime::engine::build_custom_constraint_component;
-use crate::runtime::{build_component_from_descriptor, Component, CustomConstraintComponent};
+use crate::runtime::{
+    build_component_from_descriptor, Component, ConformanceMode, ConformanceReport,
+    CustomConstraintComponent, ValidationFailure,
+};
 use crate::types::{ComponentID, Path as PShapePath, PropShapeID, TraceItem, ID};
-use oxigraph::model::{GraphNameRef, NamedNode, NamedNodeRef, Term};
-use std::cell::RefCell;
+use crate::validate::VALUE_NODE_LIMIT_COMPONENT_ID;
+use oxigraph::model::{
+    BlankNode, Graph, GraphNameRef, NamedNode, NamedNodeRef, Term, Triple, TripleRef,
+};
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::fmt;
 use std::hash::Hash;
 use std::rc::Rc;
+use std::time::Duration;
 
 pub struct ValidationContext {
     pub(crate) model: Rc<ShapesModel>,
     pub(crate) data_graph_iri: NamedNode,
-    data_graph_skolem_base: String,
+    /// `None` when the data graph's blank nodes are not skolemized.
+    data_graph_skolem_base: Option<String>,
     shape_graph_skolem_base: String,
     pub(crate) execution_traces: RefCell<Vec<Vec<TraceItem>>>,
     pub(crate) components: HashMap<ComponentID, Component>,
     pub(crate) advanced_target_cache: RefCell<HashMap<Term, Vec<Term>>>,
+    /// Failures of custom component evaluations within the current run, so shapes that
+    /// instantiate a component with the same parameters do not re-run its query for the
+    /// same focus and value nodes. Keyed by `CustomConstraintComponent::invocation_key`.
+    pub(crate) invocation_cache: RefCell<HashMap<String, Vec<ValidationFailure>>>,
+    /// Outcomes of checking a node against a node shape within the current run, so nested
+    /// `sh:node`/logical constraints reaching the same pair do not evaluate it again.
+    pub(crate) conformance_cache: RefCell<HashMap<(Term, ID), ConformanceReport>>,
+    pub(crate) conformance_cache_stats: Cell<ConformanceCacheStats>,
+    /// Wall-clock time spent in each shape/component, including nested evaluations.
+    pub(crate) execution_durations: RefCell<HashMap<TraceItem, Duration>>,
+    /// Whether SPARQL-based constraints run; switched off for a run by `ValidationOptions`.
+    pub(crate) sparql_constraints_enabled: Cell<bool>,
+    /// Whether failures of shape-based constraints keep the nested failures behind them;
+    /// switched on for a run by `ValidationOptions`.
+    pub(crate) result_details_enabled: Cell<bool>,
+    /// Where constraint evaluations are logged; opened for a run by `ValidationOptions`.
+    pub(crate) audit_log: RefCell<Option<AuditLog>>,
 }
 
 impl ValidationContext {
     pub(crate) fn new(model: Rc<ShapesModel>, data_graph_iri: NamedNode) -> Self {
-        let data_graph_skolem_base = format!(
-            "{}/.well-known/skolem/",
-            data_graph_iri.as_str().trim_end_matches('/')
-        );
-        let shape_graph_skolem_base = format!(
-            "{}/.well-known/skolem/",
-            model.shape_graph_iri.as_str().trim_end_matches('/')
-        );
+        let data_graph_skolem_base = model
+            .features
+            .skolemize_data
+            .then(|| model.features.data_skolem_base_for(&data_graph_iri));
+        let shape_graph_skolem_base = model.features.shape_skolem_base_for(&model.shape_graph_iri);
         let mut custom_cache: HashMap<String, CustomConstraintComponent> = HashMap::new();
         let components = model
             .component_descriptors
@@ -62,6 +88,13 @@ impl ValidationContext {
             execution_traces: RefCell::new(Vec::new()),
             components,
             advanced_target_cache: RefCell::new(HashMap::new()),
+            invocation_cache: RefCell::new(HashMap::new()),
+            conformance_cache: RefCell::new(HashMap::new()),
+            conformance_cache_stats: Cell::new(ConformanceCacheStats::default()),
+            execution_durations: RefCell::new(HashMap::new()),
+            sparql_constraints_enabled: Cell::new(true),
+            result_details_enabled: Cell::new(false),
+            audit_log: RefCell::new(None),
         }
     }
 
@@ -69,6 +102,113 @@ impl ValidationContext {
         GraphNameRef::NamedNode(self.data_graph_iri.as_ref())
     }
 
+    /// Atomically replaces the contents of the data graph with `graph`.
+    ///
+    /// The old triples are removed and the new ones inserted in a single store transaction,
+    /// blank nodes are skolemized against the data graph's skolem base (unless data
+    /// skolemization is off), and per-run state
+    /// is reset so the next validation only observes the new data.
+    pub(crate) fn replace_data_graph(&self, graph: &Graph) -> Result<(), String> {
+        self.write_data_graph(graph, true)
+    }
+
+    /// Adds the triples of `graph` to the data graph, keeping what is already there.
+    pub(crate) fn extend_data_graph(&self, graph: &Graph) -> Result<(), String> {
+        self.write_data_graph(graph, false)
+    }
+
+    /// Removes every triple from the data graph.
+    pub(crate) fn clear_data_graph(&self) -> Result<(), String> {
+        self.write_data_graph(&Graph::new(), true)
+    }
+
+    /// Removes the data graph from the store altogether, for scratch graphs that must not
+    /// outlive a batch.
+    pub(crate) fn remove_data_graph(&self) -> Result<(), String> {
+        self.model
+            .store()
+            .remove_named_graph(self.data_graph_iri.as_ref())
+            .map_err(|e| format!("Failed to remove data graph: {}", e))?;
+        self.reset_run_state();
+        Ok(())
+    }
+
+    fn write_data_graph(&self, graph: &Graph, clear_first: bool) -> Result<(), String> {
+        let data_graph = self.data_graph_iri_ref();
+        let mut transaction = self
+            .model
+            .store()
+            .start_transaction()
+            .map_err(|e| format!("Failed to start data graph transaction: {}", e))?;
+        if clear_first {
+            transaction
+                .clear_graph(data_graph)
+                .map_err(|e| format!("Failed to clear data graph: {}", e))?;
+        }
+        for triple in graph.iter() {
+            let triple = self.skolemize_data_triple(triple);
+            transaction.insert(triple.as_ref().in_graph(data_graph));
+        }
+        transaction
+            .commit()
+            .map_err(|e| format!("Failed to commit data graph: {}", e))?;
+        self.reset_run_state();
+        Ok(())
+    }
+
+    /// Applies `changes` to the data graph in a single store transaction and returns the
+    /// changed triples, with blank nodes skolemized as in [`Self::replace_data_graph`].
+    pub(crate) fn apply_data_changes(&self, changes: &[QuadChange]) -> Result<Vec<Triple>, String> {
+        let data_graph = self.data_graph_iri_ref();
+        let mut transaction = self
+            .model
+            .store()
+            .start_transaction()
+            .map_err(|e| format!("Failed to start data graph transaction: {}", e))?;
+        let mut changed = Vec::with_capacity(changes.len());
+        for change in changes {
+            let triple = change.data_triple(self)?;
+            let triple = self.skolemize_data_triple(triple.as_ref());
+            let quad = triple.as_ref().in_graph(data_graph);
+            match change {
+                QuadChange::Added(_) => transaction.insert(quad),
+                QuadChange::Removed(_) => transaction.remove(quad),
+            };
+            changed.push(triple);
+        }
+        transaction
+            .commit()
+            .map_err(|e| format!("Failed to commit data graph changes: {}", e))?;
+        self.reset_run_state();
+        Ok(changed)
+    }
+
+    /// Drops execution traces and cached target selections from previous runs.
+    pub(crate) fn reset_run_state(&self) {
+        self.execution_traces.borrow_mut().clear();
+        self.advanced_target_cache.borrow_mut().clear();
+        self.clear_result_caches();
+        self.execution_durations.borrow_mut().clear();
+    }
+
+    /// Drops cached custom component and shape conformance outcomes, which are only
+    /// valid while the data graph is unchanged, and resets the conformance cache counters.
+    pub(crate) fn clear_result_caches(&self) {
+        self.invocation_cache.borrow_mut().clear();
+        self.conformance_cache.borrow_mut().clear();
+        self.conformance_cache_stats
+            .set(ConformanceCacheStats::default());
+    }
+
+    /// Adds `elapsed` to the time attributed to `item`.
+    pub(crate) fn record_duration(&self, item: TraceItem, elapsed: Duration) {
+        *self
+            .execution_durations
+            .borrow_mut()
+            .entry(item)
+            .or_default() += elapsed;
+    }
+
     pub(crate) fn new_trace(&self) -> usize {
         let mut traces = self.execution_traces.borrow_mut();
         traces.push(Vec::new());
@@ -79,6 +219,21 @@ impl ValidationContext {
         self.components.get(id)
     }
 
+    /// Returns the `sh:sourceConstraintComponent` of results produced under `id`, including
+    /// the engine's own results that no registered component produces.
+    pub(crate) fn component_type(&self, id: &ComponentID) -> Option<NamedNode> {
+        if *id == VALUE_NODE_LIMIT_COMPONENT_ID {
+            return Some(NamedNode::new_unchecked(VALUE_NODE_LIMIT_COMPONENT));
+        }
+        self.get_component(id)
+            .map(|component| component.component_type())
+    }
+
+    /// The catalog engine-generated result messages are rendered from.
+    pub(crate) fn messages(&self) -> &MessageCatalog {
+        &self.model.features.messages
+    }
+
     pub(crate) fn cached_advanced_target(&self, selector: &Term) -> Option<Vec<Term>> {
         self.advanced_target_cache.borrow().get(selector).cloned()
     }
@@ -89,8 +244,75 @@ impl ValidationContext {
             .insert(selector.clone(), nodes.to_vec());
     }
 
+    pub(crate) fn cached_invocation(&self, key: &str) -> Option<Vec<ValidationFailure>> {
+        self.invocation_cache.borrow().get(key).cloned()
+    }
+
+    pub(crate) fn store_invocation(&self, key: String, failures: Vec<ValidationFailure>) {
+        self.invocation_cache.borrow_mut().insert(key, failures);
+    }
+
+    /// Looks up the outcome of checking `node` against the node shape `shape`, counting
+    /// the lookup as a cache hit or miss.
+    pub(crate) fn cached_conformance(&self, node: &Term, shape: ID) -> Option<ConformanceReport> {
+        let cached = self
+            .conformance_cache
+            .borrow()
+            .get(&(node.clone(), shape))
+            .cloned();
+        let mut stats = self.conformance_cache_stats.get();
+        if cached.is_some() {
+            stats.hits += 1;
+        } else {
+            stats.misses += 1;
+        }
+        self.conformance_cache_stats.set(stats);
+        cached
+    }
+
+    pub(crate) fn store_conformance(&self, node: Term, shape: ID, outcome: ConformanceReport) {
+        self.conformance_cache
+            .borrow_mut()
+            .insert((node, shape), outcome);
+    }
+
     pub(crate) fn is_data_skolem_iri(&self, node: NamedNodeRef<'_>) -> bool {
-        node.as_str().starts_with(&self.data_graph_skolem_base)
+        self.data_graph_skolem_base
+            .as_ref()
+            .is_some_and(|base| node.as_str().starts_with(base))
+    }
+
+    /// Returns the blank node a data-graph skolem IRI stands in for, labelled as in the
+    /// loaded data graph; other terms are returned unchanged.
+    pub(crate) fn deskolemize_data_term(&self, term: &Term) -> Term {
+        let (Term::NamedNode(node), Some(base)) = (term, &self.data_graph_skolem_base) else {
+            return term.clone();
+        };
+        node.as_str()
+            .strip_prefix(base.as_str())
+            .and_then(|label| BlankNode::new(label).ok())
+            .map(Term::from)
+            .unwrap_or_else(|| term.clone())
+    }
+
+    /// Returns the skolem IRI standing in for a data-graph blank node; the inverse of
+    /// [`Self::deskolemize_data_term`].
+    pub(crate) fn skolemize_data_term(&self, term: &Term) -> Term {
+        match (term, &self.data_graph_skolem_base) {
+            (Term::BlankNode(node), Some(base)) => {
+                NamedNode::new_unchecked(format!("{}{}", base, node.as_str())).into()
+            }
+            _ => term.clone(),
+        }
+    }
+
+    /// Returns `triple` as stored in the data graph: with skolemized blank nodes, unless
+    /// data skolemization is off.
+    fn skolemize_data_triple(&self, triple: TripleRef<'_>) -> Triple {
+        match &self.data_graph_skolem_base {
+            Some(base) => skolemize_triple(triple, base),
+            None => triple.into_owned(),
+        }
     }
 
     pub(crate) fn is_shape_skolem_iri(&self, node: NamedNodeRef<'_>) -> bool {
@@ -107,7 +329,7 @@ impl ValidationContext {
                     .get_term(*id)
                     .map_or_else(
                         || format!("Unknown NodeShape ID: {:?}", id),
-                        format_term_for_label,
+                        |term| self.model.prefixes.label(term),
                     );
                 (label, "NodeShape".to_string())
             }
@@ -200,10 +422,14 @@ pub(crate) struct Context {
     focus_node: Term,
     pub(crate) result_path: Option<PShapePath>,
     value_nodes: Option<Vec<Term>>,
+    /// Number of value nodes when they were evaluated in chunks and are not kept in
+    /// `value_nodes`.
+    value_node_count: Option<usize>,
     value: Option<Term>,
     source_shape: SourceShape,
     trace_index: usize,
     pub source_constraint: Option<Term>,
+    conformance_mode: ConformanceMode,
 }
 
 impl PartialEq for Context {
@@ -211,6 +437,7 @@ impl PartialEq for Context {
         self.focus_node == other.focus_node
             && self.result_path == other.result_path
             && self.value_nodes == other.value_nodes
+            && self.value_node_count == other.value_node_count
             && self.value == other.value
             && self.source_shape == other.source_shape
             && self.source_constraint == other.source_constraint
@@ -242,10 +469,12 @@ impl Context {
             focus_node,
             result_path,
             value_nodes,
+            value_node_count: None,
             source_shape,
             value: None,
             trace_index,
             source_constraint: None,
+            conformance_mode: ConformanceMode::Report,
         }
     }
 
@@ -277,6 +506,16 @@ impl Context {
         self.value_nodes.as_mut()
     }
 
+    /// Returns the number of value nodes, including those evaluated in chunks.
+    pub(crate) fn value_node_count(&self) -> usize {
+        self.value_node_count
+            .unwrap_or_else(|| self.value_nodes.as_ref().map_or(0, Vec::len))
+    }
+
+    pub(crate) fn set_value_node_count(&mut self, count: usize) {
+        self.value_node_count = Some(count);
+    }
+
     pub(crate) fn source_shape(&self) -> SourceShape {
         self.source_shape.clone()
     }
@@ -288,4 +527,12 @@ impl Context {
     pub(crate) fn set_trace_index(&mut self, index: usize) {
         self.trace_index = index;
     }
+
+    pub(crate) fn conformance_mode(&self) -> ConformanceMode {
+        self.conformance_mode
+    }
+
+    pub(crate) fn set_conformance_mode(&mut self, mode: ConformanceMode) {
+        self.conformance_mode = mode;
+    }
 }
@@ -0,0 +1,132 @@
+//! Stable, engine-level error codes for validation results.
+//!
+//! Every result is produced by a constraint component, and every component maps to a
+//! code such as `MinCountViolation` for `sh:MinCountConstraintComponent`. Codes depend
+//! only on the component IRI — not on messages or SHACL-AF annotations — and distinct
+//! components never share a code, so downstream systems can branch on them.
+
+use oxigraph::model::{NamedNode, NamedNodeRef, Term};
+use std::fmt;
+
+const CONSTRAINT_COMPONENT_SUFFIX: &str = "ConstraintComponent";
+
+/// Namespaces whose components get unqualified codes: SHACL itself and the engine's own.
+const SHORT_CODE_NAMESPACES: [&str; 2] = ["http://www.w3.org/ns/shacl#", "urn:shacl-rs:"];
+
+/// The code of one validation result, derived from its `sh:sourceConstraintComponent`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ErrorCode(String);
+
+impl ErrorCode {
+    /// Returns the code for a constraint component IRI.
+    ///
+    /// The code is the IRI's local name with its `ConstraintComponent` suffix replaced by
+    /// `Violation`: `sh:MinCountConstraintComponent` becomes `MinCountViolation`. Codes of
+    /// components outside SHACL and the engine's `urn:shacl-rs:` namespace keep their
+    /// namespace, so that custom components with the same local name stay apart: a custom
+    /// `<http://example.com/ns/PostcodeConstraintComponent>` becomes
+    /// `http://example.com/ns/PostcodeViolation`.
+    pub fn for_component(component: NamedNodeRef<'_>) -> Self {
+        let iri = component.as_str();
+        let local = iri
+            .rsplit(['#', '/', ':'])
+            .find(|segment| !segment.is_empty())
+            .unwrap_or(iri);
+        let namespace = &iri[..iri.rfind(local).unwrap_or(0)];
+        let stem = local
+            .strip_suffix(CONSTRAINT_COMPONENT_SUFFIX)
+            .filter(|stem| !stem.is_empty())
+            .unwrap_or(local);
+        if SHORT_CODE_NAMESPACES.contains(&namespace) {
+            ErrorCode(format!("{}Violation", stem))
+        } else {
+            ErrorCode(format!("{}{}Violation", namespace, stem))
+        }
+    }
+
+    /// Returns the code as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The error code of one result in a [`crate::ValidationReport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultCode {
+    /// The result's focus node.
+    pub focus_node: Term,
+    /// The component that produced the result.
+    pub component: NamedNode,
+    /// The code derived from `component`.
+    pub code: ErrorCode,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(iri: &str) -> String {
+        ErrorCode::for_component(NamedNodeRef::new_unchecked(iri)).to_string()
+    }
+
+    #[test]
+    fn core_components_map_to_violation_codes() {
+        assert_eq!(
+            code("http://www.w3.org/ns/shacl#MinCountConstraintComponent"),
+            "MinCountViolation"
+        );
+        assert_eq!(
+            code("http://www.w3.org/ns/shacl#QualifiedMinCountConstraintComponent"),
+            "QualifiedMinCountViolation"
+        );
+        assert_eq!(
+            code("http://www.w3.org/ns/shacl#SPARQLConstraintComponent"),
+            "SPARQLViolation"
+        );
+    }
+
+    #[test]
+    fn engine_components_use_their_local_name() {
+        assert_eq!(
+            code("urn:shacl-rs:DatatypeInConstraintComponent"),
+            "DatatypeInViolation"
+        );
+        assert_eq!(
+            code("urn:shacl-rs:ValueNodeLimit"),
+            "ValueNodeLimitViolation"
+        );
+    }
+
+    #[test]
+    fn custom_components_keep_their_namespace() {
+        assert_eq!(
+            code("http://example.com/ns/PostcodeConstraintComponent"),
+            "http://example.com/ns/PostcodeViolation"
+        );
+        assert_eq!(
+            code("urn:example:Postcode"),
+            "urn:example:PostcodeViolation"
+        );
+        assert_eq!(
+            code("http://example.com/ns#ConstraintComponent"),
+            "http://example.com/ns#ConstraintComponentViolation"
+        );
+    }
+
+    #[test]
+    fn custom_components_with_the_same_local_name_get_distinct_codes() {
+        let ours = code("http://example.com/ns#PostcodeConstraintComponent");
+        let theirs = code("http://example.org/other#PostcodeConstraintComponent");
+        assert_ne!(ours, theirs);
+        assert_ne!(
+            ours,
+            code("http://www.w3.org/ns/shacl#PostcodeConstraintComponent")
+        );
+    }
+}
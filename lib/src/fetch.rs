@@ -0,0 +1,389 @@
+//! Fetching remote graphs with retries and an on-disk cache.
+//!
+//! By default a `Source::Graph` URL is fetched by OntoEnv in a single attempt, so one
+//! flaky request fails the whole run. With a [`FetchPolicy`] set on the `ValidatorBuilder`,
+//! `http` and `https` sources are fetched here instead: failed requests are retried with
+//! exponential backoff and, when a cache directory is configured, every download is kept
+//! there. Later fetches send `If-None-Match` / `If-Modified-Since` for the cached copy, and
+//! if every attempt fails because of the network or the server, the cached copy is used.
+//! Without a cache directory, each download goes to a temporary file that is removed once
+//! the graph is loaded.
+
+use log::warn;
+use reqwest::blocking::Client;
+use reqwest::header::{
+    ACCEPT, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use reqwest::StatusCode;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use tempfile::{NamedTempFile, TempPath};
+use xxhash_rust::xxh3::xxh3_64;
+
+const ACCEPT_RDF: &str = "text/turtle, application/rdf+xml;q=0.9, application/n-triples;q=0.8, \
+                          application/ld+json;q=0.7";
+
+/// How remote graphs are fetched: retries, backoff, request timeout and cache location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    timeout: Duration,
+    cache_dir: Option<PathBuf>,
+}
+
+impl Default for FetchPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+            timeout: Duration::from_secs(30),
+            cache_dir: None,
+        }
+    }
+}
+
+impl FetchPolicy {
+    /// Creates a policy with three attempts, backoff from 0.5s up to 8s, a 30s request
+    /// timeout and no cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how many times a graph is requested before giving up (at least once).
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Sets the wait before the first retry; it doubles after every failed attempt, up to
+    /// `max`.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Sets the timeout of each request.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Keeps downloaded graphs in `dir`, enabling conditional requests and the fallback to
+    /// the cached copy when a graph cannot be fetched.
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    fn backoff(&self, failed_attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failed_attempts.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Returns `true` for URLs fetched under a [`FetchPolicy`]; other locations are left to
+/// OntoEnv.
+pub(crate) fn is_remote(location: &str) -> bool {
+    location.starts_with("http://") || location.starts_with("https://")
+}
+
+/// A fetched graph on disk. Without a cache directory it is a temporary file, deleted when
+/// this is dropped.
+pub(crate) struct FetchedGraph {
+    path: PathBuf,
+    _temp: Option<TempPath>,
+}
+
+impl FetchedGraph {
+    fn cached(path: PathBuf) -> Self {
+        Self { path, _temp: None }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Fetches `url` according to `policy` and returns the local file holding the graph.
+pub(crate) fn fetch_graph(url: &str, policy: &FetchPolicy) -> Result<FetchedGraph, String> {
+    if let Some(dir) = &policy.cache_dir {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create cache directory {}: {}", dir.display(), e))?;
+    }
+    let cached = policy
+        .cache_dir
+        .as_ref()
+        .and_then(|dir| CacheEntry::read(dir, url));
+    let client = Client::builder()
+        .timeout(policy.timeout)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut last_error = String::new();
+    for attempt in 1..=policy.max_attempts {
+        match request(&client, url, cached.as_ref()) {
+            Ok(Response::NotModified) => {
+                if let Some(entry) = cached {
+                    return Ok(FetchedGraph::cached(entry.path));
+                }
+                return Err(format!(
+                    "{} answered 304 Not Modified to a plain request",
+                    url
+                ));
+            }
+            Ok(Response::Body(download)) => {
+                return match &policy.cache_dir {
+                    Some(dir) => download.write(dir, url).map(FetchedGraph::cached),
+                    None => download.write_temporary(),
+                }
+            }
+            Err(Failure::Permanent(error)) => {
+                return Err(format!("Failed to fetch {}: {}", url, error))
+            }
+            Err(Failure::Transient(error)) => {
+                last_error = error;
+                if attempt < policy.max_attempts {
+                    let wait = policy.backoff(attempt);
+                    warn!(
+                        "Fetching {} failed (attempt {} of {}): {}; retrying in {:?}",
+                        url, attempt, policy.max_attempts, last_error, wait
+                    );
+                    thread::sleep(wait);
+                }
+            }
+        }
+    }
+
+    match cached {
+        Some(entry) => {
+            warn!(
+                "Fetching {} failed after {} attempt(s) ({}); using the cached copy {}",
+                url,
+                policy.max_attempts,
+                last_error,
+                entry.path.display()
+            );
+            Ok(FetchedGraph::cached(entry.path))
+        }
+        None => Err(format!(
+            "Failed to fetch {} after {} attempt(s): {}",
+            url, policy.max_attempts, last_error
+        )),
+    }
+}
+
+enum Response {
+    NotModified,
+    Body(Download),
+}
+
+enum Failure {
+    /// Network errors, timeouts, 5xx, 408 and 429: worth retrying.
+    Transient(String),
+    /// Any other error status: retrying will not help.
+    Permanent(String),
+}
+
+struct Download {
+    bytes: Vec<u8>,
+    extension: &'static str,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+fn request(client: &Client, url: &str, cached: Option<&CacheEntry>) -> Result<Response, Failure> {
+    let mut request = client.get(url).header(ACCEPT, ACCEPT_RDF);
+    if let Some(entry) = cached {
+        if let Some(etag) = &entry.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &entry.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+    let response = request
+        .send()
+        .map_err(|e| Failure::Transient(e.to_string()))?;
+    let status = response.status();
+    if status == StatusCode::NOT_MODIFIED {
+        return Ok(Response::NotModified);
+    }
+    if !status.is_success() {
+        let error = format!("HTTP {}", status);
+        return Err(
+            if status.is_server_error()
+                || status == StatusCode::REQUEST_TIMEOUT
+                || status == StatusCode::TOO_MANY_REQUESTS
+            {
+                Failure::Transient(error)
+            } else {
+                Failure::Permanent(error)
+            },
+        );
+    }
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header(ETAG);
+    let last_modified = header(LAST_MODIFIED);
+    let extension = header(CONTENT_TYPE)
+        .as_deref()
+        .and_then(extension_for_content_type)
+        .or_else(|| extension_for_url(url))
+        .unwrap_or("ttl");
+    let bytes = response
+        .bytes()
+        .map_err(|e| Failure::Transient(e.to_string()))?
+        .to_vec();
+    Ok(Response::Body(Download {
+        bytes,
+        extension,
+        etag,
+        last_modified,
+    }))
+}
+
+fn extension_for_content_type(content_type: &str) -> Option<&'static str> {
+    let media_type = content_type.split(';').next()?.trim().to_ascii_lowercase();
+    match media_type.as_str() {
+        "text/turtle" | "application/x-turtle" => Some("ttl"),
+        "application/rdf+xml" => Some("rdf"),
+        "application/n-triples" => Some("nt"),
+        "application/n-quads" => Some("nq"),
+        "application/trig" => Some("trig"),
+        "application/ld+json" => Some("jsonld"),
+        _ => None,
+    }
+}
+
+fn extension_for_url(url: &str) -> Option<&'static str> {
+    let path = url.split(['?', '#']).next()?;
+    let extension = path.rsplit_once('.')?.1.to_ascii_lowercase();
+    ["ttl", "rdf", "owl", "nt", "nq", "trig", "jsonld"]
+        .into_iter()
+        .find(|known| *known == extension)
+}
+
+/// A downloaded graph and the validators needed to request it conditionally.
+struct CacheEntry {
+    path: PathBuf,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl CacheEntry {
+    fn key(url: &str) -> String {
+        format!("{:016x}", xxh3_64(url.as_bytes()))
+    }
+
+    /// Reads the cached copy of `url`, if there is a usable one.
+    fn read(dir: &Path, url: &str) -> Option<Self> {
+        let meta = fs::read_to_string(dir.join(format!("{}.meta", Self::key(url)))).ok()?;
+        let mut entry = CacheEntry {
+            path: PathBuf::new(),
+            etag: None,
+            last_modified: None,
+        };
+        let mut cached_url = None;
+        for (name, value) in meta.lines().filter_map(|line| line.split_once(": ")) {
+            match name {
+                "url" => cached_url = Some(value.to_string()),
+                "file" => entry.path = dir.join(value),
+                "etag" => entry.etag = Some(value.to_string()),
+                "last-modified" => entry.last_modified = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        (cached_url.as_deref() == Some(url) && entry.path.is_file()).then_some(entry)
+    }
+}
+
+impl Download {
+    /// Writes the graph and its metadata to the cache directory `dir` and returns the
+    /// graph's path.
+    fn write(self, dir: &Path, url: &str) -> Result<PathBuf, String> {
+        let key = CacheEntry::key(url);
+        let file_name = format!("{}.{}", key, self.extension);
+        let path = dir.join(&file_name);
+        write_atomically(dir, &path, &self.bytes)?;
+
+        let mut meta = format!("url: {}\nfile: {}\n", url, file_name);
+        if let Some(etag) = &self.etag {
+            meta.push_str(&format!("etag: {}\n", etag));
+        }
+        if let Some(last_modified) = &self.last_modified {
+            meta.push_str(&format!("last-modified: {}\n", last_modified));
+        }
+        let meta_path = dir.join(format!("{}.meta", key));
+        write_atomically(dir, &meta_path, meta.as_bytes())?;
+        Ok(path)
+    }
+
+    /// Writes the graph to a temporary file of its own.
+    fn write_temporary(self) -> Result<FetchedGraph, String> {
+        let mut file = tempfile::Builder::new()
+            .prefix("shacl-rs-fetch-")
+            .suffix(&format!(".{}", self.extension))
+            .tempfile()
+            .map_err(|e| format!("Failed to create temporary file: {}", e))?;
+        file.write_all(&self.bytes)
+            .map_err(|e| format!("Failed to write {}: {}", file.path().display(), e))?;
+        let temp = file.into_temp_path();
+        Ok(FetchedGraph {
+            path: temp.to_path_buf(),
+            _temp: Some(temp),
+        })
+    }
+}
+
+/// Replaces `path` with `contents` through a temporary file in `dir`, so concurrent runs
+/// never read a partly written file.
+fn write_atomically(dir: &Path, path: &Path, contents: &[u8]) -> Result<(), String> {
+    let error = |e: std::io::Error| format!("Failed to write {}: {}", path.display(), e);
+    let mut file = NamedTempFile::new_in(dir).map_err(error)?;
+    file.write_all(contents).map_err(error)?;
+    file.persist(path).map_err(|e| error(e.error))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        let policy =
+            FetchPolicy::new().with_backoff(Duration::from_millis(100), Duration::from_millis(350));
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff(40), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn picks_file_extensions_from_content_type_then_url() {
+        assert_eq!(
+            extension_for_content_type("text/turtle; charset=utf-8"),
+            Some("ttl")
+        );
+        assert_eq!(extension_for_content_type("text/html"), None);
+        assert_eq!(
+            extension_for_url("http://example.org/a/b.owl?x=1"),
+            Some("owl")
+        );
+        assert_eq!(extension_for_url("http://example.org/a/b"), None);
+    }
+}
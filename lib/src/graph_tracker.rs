@@ -0,0 +1,139 @@
+//! Bookkeeping for validating only the named graphs of a store that changed.
+//!
+//! Ingestion pipelines that write one named graph per batch can keep a [`GraphTracker`]
+//! next to their store and call `Validator::validate_new_graphs` after every write: only
+//! graphs whose name is new, or whose content digest differs from the one recorded at
+//! the previous call, are validated.
+
+use oxigraph::model::{Graph, GraphNameRef, NamedNode, NamedOrBlankNode};
+use oxigraph::store::Store;
+use std::collections::HashMap;
+use xxhash_rust::xxh3::xxh3_64;
+
+/// Remembers the content digest of every named graph validated so far.
+#[derive(Debug, Clone, Default)]
+pub struct GraphTracker {
+    digests: HashMap<NamedNode, u64>,
+}
+
+impl GraphTracker {
+    /// Creates a tracker that has not seen any graph yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if `graph` was validated with its current content `digest`.
+    pub fn is_current(&self, graph: &NamedNode, digest: u64) -> bool {
+        self.digests.get(graph) == Some(&digest)
+    }
+
+    /// Returns the digest recorded for `graph`, if it was validated before.
+    pub fn digest(&self, graph: &NamedNode) -> Option<u64> {
+        self.digests.get(graph).copied()
+    }
+
+    /// Returns the number of graphs with a recorded digest.
+    pub fn len(&self) -> usize {
+        self.digests.len()
+    }
+
+    /// Returns `true` if no graph has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.digests.is_empty()
+    }
+
+    /// Forgets `graph`, so it is validated again at the next call.
+    pub fn forget(&mut self, graph: &NamedNode) {
+        self.digests.remove(graph);
+    }
+
+    pub(crate) fn record(&mut self, graph: NamedNode, digest: u64) {
+        self.digests.insert(graph, digest);
+    }
+
+    pub(crate) fn retain_graphs(&mut self, present: &[NamedNode]) {
+        self.digests.retain(|graph, _| present.contains(graph));
+    }
+}
+
+/// A named graph read from a store, with a digest of its content.
+pub(crate) struct StoredGraph {
+    pub(crate) name: NamedNode,
+    pub(crate) graph: Graph,
+    pub(crate) digest: u64,
+}
+
+/// Reads every named graph of `store`, ordered by name.
+pub(crate) fn read_named_graphs(store: &Store) -> Result<Vec<StoredGraph>, String> {
+    let mut names = Vec::new();
+    for name in store.named_graphs() {
+        match name.map_err(|e| format!("Failed to list named graphs: {}", e))? {
+            NamedOrBlankNode::NamedNode(name) => names.push(name),
+            NamedOrBlankNode::BlankNode(_) => {}
+        }
+    }
+    names.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+    let mut graphs = Vec::with_capacity(names.len());
+    for name in names {
+        let mut graph = Graph::new();
+        for quad in store.quads_for_pattern(
+            None,
+            None,
+            None,
+            Some(GraphNameRef::NamedNode(name.as_ref())),
+        ) {
+            let quad = quad.map_err(|e| format!("Failed to read graph {}: {}", name, e))?;
+            graph.insert(quad.as_ref());
+        }
+        let digest = graph_digest(&graph);
+        graphs.push(StoredGraph {
+            name,
+            graph,
+            digest,
+        });
+    }
+    Ok(graphs)
+}
+
+/// Order-independent digest of a graph's triples.
+fn graph_digest(graph: &Graph) -> u64 {
+    graph
+        .iter()
+        .map(|triple| xxh3_64(triple.to_string().as_bytes()))
+        .fold(graph.len() as u64, u64::wrapping_add)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxigraph::model::{Literal, NamedNodeRef, TripleRef};
+
+    fn graph(values: &[&str]) -> Graph {
+        let mut graph = Graph::new();
+        for value in values {
+            graph.insert(TripleRef::new(
+                NamedNodeRef::new_unchecked("urn:s"),
+                NamedNodeRef::new_unchecked("urn:p"),
+                &Literal::new_simple_literal(*value),
+            ));
+        }
+        graph
+    }
+
+    #[test]
+    fn digest_ignores_insertion_order_and_tracks_content() {
+        assert_eq!(
+            graph_digest(&graph(&["a", "b"])),
+            graph_digest(&graph(&["b", "a"]))
+        );
+        assert_ne!(
+            graph_digest(&graph(&["a", "b"])),
+            graph_digest(&graph(&["a", "c"]))
+        );
+        assert_ne!(
+            graph_digest(&graph(&["a"])),
+            graph_digest(&graph(&["a", "b"]))
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Comparing the heatmaps of two validation runs.
+//!
+//! `ValidationReport::heatmap` records how often each node shape, property shape and
+//! constraint component ran and how long it took. [`diff_heatmaps`] lines up two such
+//! heatmaps (for example before and after editing the shapes, or on two versions of the
+//! data) and orders the elements by how much their cost changed.
+
+use crate::report::{HeatEntry, HeatEntryKind};
+use oxigraph::model::Term;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+/// The change in cost of one shapes-graph element between two runs.
+///
+/// Elements are matched by kind, IRI and label, since the IDs of a [`HeatEntry`] are only
+/// stable within one validator. An element that ran in only one of the runs has zero
+/// invocations and no time on the other side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatDelta {
+    pub kind: HeatEntryKind,
+    /// The shape's IRI, or the constraint component type for components.
+    pub iri: Term,
+    pub label: String,
+    pub invocations_before: usize,
+    pub invocations_after: usize,
+    pub duration_before: Duration,
+    pub duration_after: Duration,
+}
+
+impl HeatDelta {
+    /// Change in wall-clock time, in seconds; negative when the element got cheaper.
+    pub fn duration_change(&self) -> f64 {
+        self.duration_after.as_secs_f64() - self.duration_before.as_secs_f64()
+    }
+
+    /// Change in the number of invocations; negative when the element ran less often.
+    pub fn invocation_change(&self) -> i64 {
+        self.invocations_after as i64 - self.invocations_before as i64
+    }
+
+    /// Relative change in wall-clock time, or `None` if the element took no time before.
+    pub fn relative_duration_change(&self) -> Option<f64> {
+        let before = self.duration_before.as_secs_f64();
+        (before > 0.0).then(|| self.duration_change() / before)
+    }
+}
+
+impl fmt::Display for HeatDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let relative = self
+            .relative_duration_change()
+            .map(|change| format!("{:+.1}%", change * 100.0))
+            .unwrap_or_else(|| "new".to_string());
+        write!(
+            f,
+            "{:?}\t{}\t{} -> {}\t{:.3}ms -> {:.3}ms\t{:+.3}ms\t{}",
+            self.kind,
+            self.label,
+            self.invocations_before,
+            self.invocations_after,
+            self.duration_before.as_secs_f64() * 1000.0,
+            self.duration_after.as_secs_f64() * 1000.0,
+            self.duration_change() * 1000.0,
+            relative,
+        )
+    }
+}
+
+/// Matches the elements of two heatmaps and returns their changes, largest absolute change
+/// in time first (ties broken by the change in invocations, then by label).
+pub fn diff_heatmaps(before: &[HeatEntry], after: &[HeatEntry]) -> Vec<HeatDelta> {
+    let mut deltas: BTreeMap<(String, String, String), HeatDelta> = BTreeMap::new();
+    for entry in before {
+        let delta = delta_for(&mut deltas, entry);
+        delta.invocations_before += entry.invocations;
+        delta.duration_before += entry.duration;
+    }
+    for entry in after {
+        let delta = delta_for(&mut deltas, entry);
+        delta.invocations_after += entry.invocations;
+        delta.duration_after += entry.duration;
+    }
+
+    let mut deltas: Vec<HeatDelta> = deltas.into_values().collect();
+    deltas.sort_by(|a, b| {
+        b.duration_change()
+            .abs()
+            .total_cmp(&a.duration_change().abs())
+            .then_with(|| {
+                b.invocation_change()
+                    .unsigned_abs()
+                    .cmp(&a.invocation_change().unsigned_abs())
+            })
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    deltas
+}
+
+fn delta_for<'a>(
+    deltas: &'a mut BTreeMap<(String, String, String), HeatDelta>,
+    entry: &HeatEntry,
+) -> &'a mut HeatDelta {
+    let key = (
+        format!("{:?}", entry.kind),
+        entry.iri.to_string(),
+        entry.label.clone(),
+    );
+    deltas.entry(key).or_insert_with(|| HeatDelta {
+        kind: entry.kind,
+        iri: entry.iri.clone(),
+        label: entry.label.clone(),
+        invocations_before: 0,
+        invocations_after: 0,
+        duration_before: Duration::ZERO,
+        duration_after: Duration::ZERO,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxigraph::model::NamedNode;
+
+    fn entry(label: &str, kind: HeatEntryKind, invocations: usize, millis: u64) -> HeatEntry {
+        HeatEntry {
+            id: format!("{}-id", label),
+            iri: NamedNode::new_unchecked(format!("http://example.org/{}", label)).into(),
+            label: label.to_string(),
+            kind,
+            invocations,
+            duration: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn orders_elements_by_the_size_of_their_time_change() {
+        let before = vec![
+            entry("Cheap", HeatEntryKind::Component, 10, 5),
+            entry("Slow", HeatEntryKind::Component, 10, 20),
+            entry("Gone", HeatEntryKind::NodeShape, 3, 4),
+        ];
+        let after = vec![
+            entry("Cheap", HeatEntryKind::Component, 10, 6),
+            entry("Slow", HeatEntryKind::Component, 40, 120),
+            entry("New", HeatEntryKind::PropertyShape, 2, 8),
+        ];
+        let deltas = diff_heatmaps(&before, &after);
+        let labels: Vec<&str> = deltas.iter().map(|d| d.label.as_str()).collect();
+        assert_eq!(labels, vec!["Slow", "New", "Gone", "Cheap"]);
+
+        assert_eq!(deltas[0].invocation_change(), 30);
+        let relative = deltas[0].relative_duration_change().unwrap();
+        assert!((relative - 5.0).abs() < 1e-9);
+        assert_eq!(deltas[1].invocations_before, 0);
+        assert_eq!(deltas[1].relative_duration_change(), None);
+        assert_eq!(deltas[2].invocations_after, 0);
+        assert!(deltas[2].duration_change() < 0.0);
+    }
+
+    #[test]
+    fn elements_match_by_kind_iri_and_label_not_id() {
+        let mut renumbered = entry("Same", HeatEntryKind::Component, 1, 1);
+        renumbered.id = "Component(c99)".to_string();
+        let deltas = diff_heatmaps(
+            &[entry("Same", HeatEntryKind::Component, 1, 1)],
+            &[renumbered],
+        );
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].duration_change(), 0.0);
+    }
+}
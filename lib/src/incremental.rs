@@ -0,0 +1,167 @@
+//! Re-validation of the focus nodes affected by a changeset.
+//!
+//! Editors and other interactive tools change a few triples at a time. Instead of running
+//! every shape over the whole data graph again, `Validator::revalidate` applies a list of
+//! [`QuadChange`]s and validates only the focus nodes whose results can depend on them;
+//! `ValidationReport::merge_revalidation` then folds that partial report into the previous
+//! full one. When the data was changed some other way, `Validator::revalidate_subjects`
+//! takes the changed subjects instead and merges into the previous report itself.
+//!
+//! Core constraints only look at triples reachable from a focus node along the edges of
+//! the data graph (forwards, or in both directions when a path uses `sh:inversePath`), so
+//! a changed triple can only affect focus nodes connected to its subject or object.
+//! SPARQL-based constraints, custom components and advanced targets can query arbitrary
+//! data; when the shapes use any of them, every focus node is re-validated.
+
+use crate::context::ValidationContext;
+use crate::model::components::ComponentDescriptor;
+use crate::types::{Path, Target};
+use oxigraph::model::{GraphName, NamedOrBlankNodeRef, Quad, Term, Triple};
+use std::collections::{HashSet, VecDeque};
+
+/// One change to the validator's data graph.
+///
+/// Quads in the default graph are applied to the data graph; quads naming the data graph
+/// explicitly are accepted as well.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuadChange {
+    /// The quad was added.
+    Added(Quad),
+    /// The quad was removed.
+    Removed(Quad),
+}
+
+impl QuadChange {
+    fn quad(&self) -> &Quad {
+        match self {
+            QuadChange::Added(quad) | QuadChange::Removed(quad) => quad,
+        }
+    }
+
+    /// Returns the changed triple, or an error if the quad belongs to another graph.
+    pub(crate) fn data_triple(&self, context: &ValidationContext) -> Result<Triple, String> {
+        let quad = self.quad();
+        match &quad.graph_name {
+            GraphName::DefaultGraph => {}
+            GraphName::NamedNode(name) if *name == context.data_graph_iri => {}
+            other => {
+                return Err(format!(
+                    "Change {} targets graph {}, not the data graph {}",
+                    quad, other, context.data_graph_iri
+                ))
+            }
+        }
+        Ok(Triple::new(
+            quad.subject.clone(),
+            quad.predicate.clone(),
+            quad.object.clone(),
+        ))
+    }
+}
+
+/// Returns the focus nodes whose results may change because of `changed` triples (already
+/// applied to the data graph), or `None` if any focus node may be affected.
+pub(crate) fn affected_focus_nodes(
+    context: &ValidationContext,
+    changed: &[Triple],
+) -> Option<HashSet<Term>> {
+    focus_nodes_reaching(
+        context,
+        changed
+            .iter()
+            .flat_map(|triple| [Term::from(triple.subject.clone()), triple.object.clone()]),
+    )
+}
+
+/// Returns the focus nodes whose results may depend on the triples of `nodes`, or `None` if
+/// any focus node may be affected.
+pub(crate) fn focus_nodes_reaching(
+    context: &ValidationContext,
+    nodes: impl IntoIterator<Item = Term>,
+) -> Option<HashSet<Term>> {
+    if reads_arbitrary_data(context) {
+        return None;
+    }
+    let both_directions = context
+        .model
+        .prop_shapes
+        .values()
+        .any(|shape| has_inverse_step(shape.path()));
+
+    let mut affected: HashSet<Term> = HashSet::new();
+    let mut pending: VecDeque<Term> = VecDeque::new();
+    for node in nodes {
+        if affected.insert(node.clone()) {
+            pending.push_back(node);
+        }
+    }
+
+    let store = context.model.store();
+    let data_graph = context.data_graph_iri_ref();
+    while let Some(node) = pending.pop_front() {
+        let mut neighbours: Vec<Term> = store
+            .quads_for_pattern(None, None, Some(node.as_ref()), Some(data_graph))
+            .filter_map(Result::ok)
+            .map(|quad| quad.subject.into())
+            .collect();
+        if both_directions {
+            if let Some(subject) = as_subject(&node) {
+                neighbours.extend(
+                    store
+                        .quads_for_pattern(Some(subject), None, None, Some(data_graph))
+                        .filter_map(Result::ok)
+                        .map(|quad| quad.object),
+                );
+            }
+        }
+        for neighbour in neighbours {
+            if affected.insert(neighbour.clone()) {
+                pending.push_back(neighbour);
+            }
+        }
+    }
+    Some(affected)
+}
+
+/// Whether any shape uses a constraint or target that can depend on triples not reachable
+/// from the focus node.
+fn reads_arbitrary_data(context: &ValidationContext) -> bool {
+    let model = &context.model;
+    let advanced_target = |targets: &[Target]| {
+        targets
+            .iter()
+            .any(|target| matches!(target, Target::Advanced(_)))
+    };
+    model.component_descriptors.values().any(|descriptor| {
+        matches!(
+            descriptor,
+            ComponentDescriptor::Sparql { .. } | ComponentDescriptor::Custom { .. }
+        )
+    }) || model
+        .node_shapes
+        .values()
+        .any(|shape| advanced_target(&shape.targets))
+        || model
+            .prop_shapes
+            .values()
+            .any(|shape| advanced_target(&shape.targets))
+}
+
+fn has_inverse_step(path: &Path) -> bool {
+    match path {
+        Path::Simple(_) => false,
+        Path::Inverse(_) => true,
+        Path::Sequence(paths) | Path::Alternative(paths) => paths.iter().any(has_inverse_step),
+        Path::ZeroOrMore(inner) | Path::OneOrMore(inner) | Path::ZeroOrOne(inner) => {
+            has_inverse_step(inner)
+        }
+    }
+}
+
+fn as_subject(term: &Term) -> Option<NamedOrBlankNodeRef<'_>> {
+    match term {
+        Term::NamedNode(node) => Some(node.into()),
+        Term::BlankNode(node) => Some(node.into()),
+        _ => None,
+    }
+}
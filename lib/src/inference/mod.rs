@@ -204,6 +204,7 @@ impl<'a> InferenceEngine<'a> {
 
         for iteration in 1..=self.config.max_iterations {
             self.context.advanced_target_cache.borrow_mut().clear();
+            self.context.clear_result_caches();
             iterations_executed = iteration;
             let added_this_round = self.apply_rules_once(&mut inferred_quads)?;
             total_added += added_this_round;
@@ -879,6 +880,34 @@ ex:rect2 a ex:Rectangle .
         }
     }
 
+    #[test]
+    fn validator_infer_runs_the_default_configuration() {
+        let shapes = r#"@prefix sh: <http://www.w3.org/ns/shacl#> .
+@prefix ex: <http://example.com/ns#> .
+
+ex:RectangleShape a sh:NodeShape ;
+    sh:targetClass ex:Rectangle ;
+    sh:rule [
+        a sh:TripleRule ;
+        sh:subject sh:this ;
+        sh:predicate ex:tag ;
+        sh:object "rectangle" ;
+    ] .
+"#;
+
+        let data = r#"@prefix ex: <http://example.com/ns#> .
+
+ex:rect1 a ex:Rectangle .
+"#;
+
+        let validator = build_validator(shapes, data);
+        let outcome = validator.infer().expect("inference");
+        assert_eq!(outcome.triples_added, 1);
+        assert!(outcome.converged);
+        // Everything is materialized, so inferring again adds nothing.
+        assert_eq!(validator.infer().expect("inference").triples_added, 0);
+    }
+
     #[test]
     fn blank_node_guard_errors() {
         let shapes = r#"@prefix sh: <http://www.w3.org/ns/shacl#> .
@@ -2,89 +2,381 @@
 #![deny(clippy::all)]
 
 // Publicly visible items
+pub mod conformance;
+pub mod error_code;
+pub mod fetch;
+pub mod graph_tracker;
+pub mod heat_diff;
+pub mod incremental;
 pub mod inference;
+pub mod load;
+pub mod messages;
 pub mod model;
+pub mod options;
+pub mod prefix_map;
+pub mod reachability;
+pub mod redaction;
+pub mod self_check;
 pub mod shape;
+pub mod stats;
 pub mod types;
+pub mod unsupported;
+pub mod version;
 
+pub use conformance::{
+    ConformanceReport, SectionSummary, TestFilter, TestOutcome, TestStatus, TestStatusKind,
+};
+pub use error_code::{ErrorCode, ResultCode};
+pub use fetch::FetchPolicy;
+pub use graph_tracker::GraphTracker;
+pub use heat_diff::{diff_heatmaps, HeatDelta};
+pub use incremental::QuadChange;
 pub use inference::{InferenceConfig, InferenceError, InferenceOutcome};
-pub use report::ValidationReport;
+pub use load::{GraphLoadError, SyntaxIssue};
+pub use messages::MessageCatalog;
+pub use model::components::sparql::CustomConstraintComponentDefinition;
+pub use options::{StopReason, ValidationOptions};
+pub use prefix_map::PrefixMap;
+pub use reachability::ShapeReachability;
+pub use redaction::Redaction;
+pub use report::{
+    ComponentFrequency, ComponentTiming, ConformanceCacheStats, FocusNodeContext, HeatEntry,
+    HeatEntryKind, ValidationReport, ValidationResult, AGGREGATED_RESULTS_COMPONENT,
+    DEFAULT_REPORT_GRAPH, RESULT_COUNT, VALUE_NODE_LIMIT_COMPONENT, VIOLATED_COMPONENT,
+};
+pub use self_check::{ReportChecker, ReportIssue};
+pub use stats::{ObservedKind, ObservedType, PathTypeStatistics};
+pub use unsupported::{UnsupportedFeature, UnsupportedKind, UNSUPPORTED_FEATURE};
+pub use version::{ShapesVersion, ShapesVersionRequirement};
 
 // Internal modules.
+pub(crate) mod audit;
 pub mod canonicalization;
 pub(crate) mod context;
 pub(crate) mod named_nodes;
 pub(crate) mod optimize;
 pub(crate) mod parser;
+pub(crate) mod persistent_cache;
 pub(crate) mod report;
 pub(crate) mod runtime;
 pub(crate) mod sparql;
 pub mod test_utils; // Often pub for integration tests
 pub(crate) mod validate;
 
-use crate::canonicalization::skolemize;
+use crate::canonicalization::{default_skolem_base, skolemize};
 use crate::context::model::{FeatureToggles, OriginalValueIndex};
 use crate::context::{
-    render_heatmap_graphviz, render_shapes_graphviz, ParsingContext, ShapesModel, ValidationContext,
+    render_heatmap_graphviz, render_shape_graphviz, render_shapes_graphviz, render_shapes_html,
+    render_shapes_markdown, ParsingContext, ShapesModel, ValidationContext,
 };
+use crate::named_nodes::{OWL, SHACL};
 use crate::optimize::Optimizer;
 use crate::parser as shacl_parser;
+use crate::shape::ShapeView;
+use crate::types::{CustomSeverityConformance, DuplicateShapePolicy, QueryDataset};
 use log::info;
-use ontoenv::api::OntoEnv;
+use ontoenv::api::{OntoEnv, ResolveTarget};
 use ontoenv::config::Config;
 use ontoenv::ontology::OntologyLocation;
 use ontoenv::options::{Overwrite, RefreshStrategy};
-use oxigraph::model::{GraphName, GraphNameRef, NamedNode, Quad};
+use oxigraph::io::RdfFormat;
+use oxigraph::model::vocab::rdf;
+use oxigraph::model::{
+    BlankNode, Graph, GraphName, GraphNameRef, NamedNode, NamedNodeRef, NamedOrBlankNode, Quad,
+    QuadRef, Term, Triple,
+};
+use oxigraph::sparql::{QueryResults, SparqlEvaluator};
 use oxigraph::store::Store;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
-/// Represents the source of shapes or data, which can be either a local file or a named graph from an `OntoEnv`.
-#[derive(Debug)]
+/// Named graph that `Validator::validate_each` loads each payload into.
+const SCRATCH_DATA_GRAPH: &str = "urn:shacl-rs:scratch-data";
+
+/// Represents the source of shapes or data: a local file, a named graph from an `OntoEnv`,
+/// or an in-memory document.
+///
+/// In-memory sources, JSON-LD files and single graphs of dataset files are parsed by the
+/// engine and inserted into OntoEnv's store directly; no temporary files are written.
+/// Without an `owl:Ontology` declaration an in-memory graph is named
+/// `urn:shacl-rs:source:` followed by a hash of its content.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Source {
-    /// A local file path.
+    /// A local file path. The format is inferred from the extension; `.jsonld` files are
+    /// read as JSON-LD.
     File(PathBuf),
     /// The URI of a named graph.
     Graph(String),
+    /// A serialized graph in the given format, such as a JSON-LD API response.
+    Bytes { data: Vec<u8>, format: RdfFormat },
+    /// A Turtle document held in memory, handy for tests and embedding applications.
+    InlineTurtle(String),
+    /// One named graph of a TriG, N-Quads or JSON-LD file. Triples in the file's other
+    /// graphs are ignored; the graph is loaded under `graph_iri` unless it declares an
+    /// `owl:Ontology`.
+    FileGraph { path: PathBuf, graph_iri: String },
+}
+
+/// Names an in-memory source that declares no `owl:Ontology` after its content:
+/// `urn:shacl-rs:source:` followed by its SHA-256 digest.
+fn content_graph_name(data: &[u8]) -> NamedNode {
+    NamedNode::new_unchecked(format!("urn:shacl-rs:source:{:x}", Sha256::digest(data)))
+}
+
+/// Returns the graph name used for a shapes or data file that does not declare an
+/// `owl:Ontology`: the `file://` IRI of its absolute, canonicalized path.
+pub fn synthetic_graph_name(path: &Path) -> NamedNode {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    OntologyLocation::File(path).to_iri()
+}
+
+impl Source {
+    /// Returns true when both sources name the same file or graph.
+    fn same_location(&self, other: &Source) -> bool {
+        match (self, other) {
+            (Source::File(a), Source::File(b)) => same_file(a, b),
+            (
+                Source::FileGraph {
+                    path: a,
+                    graph_iri: graph_a,
+                },
+                Source::FileGraph {
+                    path: b,
+                    graph_iri: graph_b,
+                },
+            ) => graph_a == graph_b && same_file(a, b),
+            (Source::Graph(a), Source::Graph(b)) => a == b,
+            (Source::Bytes { .. }, Source::Bytes { .. })
+            | (Source::InlineTurtle(_), Source::InlineTurtle(_)) => self == other,
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "file {}", path.display()),
+            Source::Graph(uri) => write!(f, "graph <{}>", uri),
+            Source::Bytes { data, format } => {
+                write!(f, "{} bytes of {}", data.len(), format.name())
+            }
+            Source::InlineTurtle(turtle) => write!(f, "{} bytes of inline Turtle", turtle.len()),
+            Source::FileGraph { path, graph_iri } => {
+                write!(f, "graph <{}> of file {}", graph_iri, path.display())
+            }
+        }
+    }
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    a == b || matches!((a.canonicalize(), b.canonicalize()), (Ok(a), Ok(b)) if a == b)
+}
+
+/// Lists sources for error messages.
+fn describe_sources(sources: &[Source]) -> String {
+    sources
+        .iter()
+        .map(Source::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 /// Configurable builder for constructing `Validator` instances.
 pub struct ValidatorBuilder {
-    shapes_source: Option<Source>,
-    data_source: Option<Source>,
+    shapes_sources: Vec<Source>,
+    data_sources: Vec<Source>,
+    existing_store: Option<ExistingStore>,
     env_config: Option<Config>,
     skolemize_shapes: bool,
     skolemize_data: bool,
     enable_af: bool,
     enable_rules: bool,
     skip_invalid_rules: bool,
+    max_value_nodes: Option<usize>,
+    shape_skolem_base: Option<String>,
+    data_skolem_base: Option<String>,
+    custom_severity_conformance: CustomSeverityConformance,
+    duplicate_shape_policy: DuplicateShapePolicy,
+    query_dataset: QueryDataset,
+    expected_shapes_version: Option<ShapesVersionRequirement>,
+    validation_options: ValidationOptions,
+    fetch_policy: Option<FetchPolicy>,
+    data_subset_query: Option<String>,
+    shapes_imports: bool,
+    declared_shapes_graphs: bool,
+    component_library_sources: Vec<Source>,
+    message_catalog: MessageCatalog,
+}
+
+/// Graphs of an application-owned store, validated in place.
+struct ExistingStore {
+    store: Store,
+    shapes_graph: NamedNode,
+    data_graph: NamedNode,
 }
 
 impl ValidatorBuilder {
     /// Creates a new builder with default configuration.
     pub fn new() -> Self {
         Self {
-            shapes_source: None,
-            data_source: None,
+            shapes_sources: Vec::new(),
+            data_sources: Vec::new(),
+            existing_store: None,
             env_config: None,
             skolemize_shapes: true,
             skolemize_data: true,
             enable_af: true,
             enable_rules: true,
             skip_invalid_rules: false,
+            max_value_nodes: None,
+            shape_skolem_base: None,
+            data_skolem_base: None,
+            custom_severity_conformance: CustomSeverityConformance::default(),
+            duplicate_shape_policy: DuplicateShapePolicy::default(),
+            query_dataset: QueryDataset::default(),
+            expected_shapes_version: None,
+            validation_options: ValidationOptions::default(),
+            fetch_policy: None,
+            data_subset_query: None,
+            shapes_imports: false,
+            declared_shapes_graphs: false,
+            component_library_sources: Vec::new(),
+            message_catalog: MessageCatalog::default(),
         }
     }
 
     /// Sets the shapes source used for validation.
     pub fn with_shapes_source(mut self, source: Source) -> Self {
-        self.shapes_source = Some(source);
+        self.shapes_sources = vec![source];
         self
     }
 
     /// Sets the data source used for validation.
     pub fn with_data_source(mut self, source: Source) -> Self {
-        self.data_source = Some(source);
+        self.data_sources = vec![source];
+        self
+    }
+
+    /// Sets several shapes sources (for example an ontology and profile shapes kept in
+    /// separate modules) that are merged into one shapes graph.
+    ///
+    /// The shapes graph is named after the first source. Blank nodes of the other sources
+    /// are renamed while merging, so equal blank node labels in different files stay
+    /// distinct.
+    pub fn with_shapes_sources(mut self, sources: impl IntoIterator<Item = Source>) -> Self {
+        self.shapes_sources = sources.into_iter().collect();
+        self
+    }
+
+    /// Sets several data sources that are merged into one data graph, named after the first
+    /// source, in the same way as [`with_shapes_sources`](Self::with_shapes_sources).
+    pub fn with_data_sources(mut self, sources: impl IntoIterator<Item = Source>) -> Self {
+        self.data_sources = sources.into_iter().collect();
+        self
+    }
+
+    /// Validates graphs of an existing store instead of loading shapes and data sources.
+    ///
+    /// Both graphs must be named graphs present in the store. The application's graphs are
+    /// never rewritten: unless skolemization is disabled for both graphs with
+    /// [`with_skolemization`](Self::with_skolemization) (as [`Validator::from_store`] does),
+    /// they are copied into a private in-memory store when the validator is built and
+    /// skolemized there. Without skolemization (or a
+    /// [data subset](Self::with_data_subset_query)) the store is shared, not copied, and
+    /// validation reads the graphs as they are when `validate` runs.
+    pub fn with_store(
+        mut self,
+        store: Store,
+        shapes_graph: GraphNameRef<'_>,
+        data_graph: GraphNameRef<'_>,
+    ) -> Result<Self, String> {
+        let named = |graph: GraphNameRef<'_>, label: &str| match graph {
+            GraphNameRef::NamedNode(name) => Ok(name.into_owned()),
+            other => Err(format!(
+                "The {} graph must be a named graph, got {}",
+                label, other
+            )),
+        };
+        self.existing_store = Some(ExistingStore {
+            shapes_graph: named(shapes_graph, "shapes")?,
+            data_graph: named(data_graph, "data")?,
+            store,
+        });
+        Ok(self)
+    }
+
+    /// Fetches `http`/`https` graph sources with `policy` (retries with backoff, conditional
+    /// requests and a fallback to a cached copy) instead of a single OntoEnv request.
+    ///
+    /// A fetched graph is named after its `owl:Ontology` IRI, or after its URL when it
+    /// declares none.
+    pub fn with_fetch_policy(mut self, policy: FetchPolicy) -> Self {
+        self.fetch_policy = Some(policy);
+        self
+    }
+
+    /// Includes the `owl:imports` closure of the shapes graph, so shapes defined in imported
+    /// modules are validated as well. Off by default, which parses the shapes graph alone.
+    ///
+    /// Imports already loaded by OntoEnv (for example from its configured locations) are
+    /// used as they are; others are loaded from their IRI, with the
+    /// [fetch policy](Self::with_fetch_policy) when one is set. `build` fails when an import
+    /// cannot be loaded. Not supported together with [`with_store`](Self::with_store).
+    pub fn with_shapes_imports(mut self, enabled: bool) -> Self {
+        self.shapes_imports = enabled;
+        self
+    }
+
+    /// Takes the shapes from the graphs the data graph names with `sh:shapesGraph` instead
+    /// of from shapes sources. Off by default.
+    ///
+    /// Declared graphs are loaded like [`Source::Graph`]s (`file://` IRIs as files) and
+    /// merged into one shapes graph, so the data is validated against their union. A data
+    /// graph that declares itself is validated against itself. `build` fails when shapes
+    /// sources are also given or the data graph declares no shapes graph. Not supported
+    /// together with [`with_store`](Self::with_store).
+    pub fn with_declared_shapes_graphs(mut self, enabled: bool) -> Self {
+        self.declared_shapes_graphs = enabled;
+        self
+    }
+
+    /// Loads custom constraint components from library graphs kept apart from the shapes
+    /// graph, such as an organization's shared component library.
+    ///
+    /// Each source is loaded as a graph of its own. The `sh:ConstraintComponent`s it declares,
+    /// with their parameters and validators, are copied into the shapes graph so shapes can
+    /// use them; the rest of the library is not validated. `build` fails when a library
+    /// declares no component. Not supported together with [`with_store`](Self::with_store).
+    pub fn with_component_library_sources(
+        mut self,
+        sources: impl IntoIterator<Item = Source>,
+    ) -> Self {
+        self.component_library_sources = sources.into_iter().collect();
+        self
+    }
+
+    /// Renders the engine's own result messages (a result's `engine_message`, also printed
+    /// by `dump`) from `catalog` instead of in English. See the [`messages`] module for
+    /// the catalog file format.
+    pub fn with_message_catalog(mut self, catalog: MessageCatalog) -> Self {
+        self.message_catalog = catalog;
+        self
+    }
+
+    /// Narrows the data graph to the triples produced by a SPARQL `CONSTRUCT` or `DESCRIBE`
+    /// query before validating.
+    ///
+    /// The query runs once, when the validator is built, with the loaded data graph as its
+    /// default graph; its result replaces the data graph. Extracting only the part of a large
+    /// dataset that the shapes look at is usually far cheaper than validating all of it.
+    /// With [`with_store`](Self::with_store) the application's store is left untouched: the
+    /// shapes graph and the subset are copied into a new in-memory store.
+    pub fn with_data_subset_query(mut self, query: impl Into<String>) -> Self {
+        self.data_subset_query = Some(query.into());
         self
     }
 
@@ -101,6 +393,19 @@ impl ValidatorBuilder {
         self
     }
 
+    /// Overrides the base IRIs blank nodes are skolemized under. `None` keeps the default
+    /// `{graph_iri}/.well-known/skolem/` for that graph.
+    ///
+    /// Use this when the data already contains skolem IRIs under the default base (for
+    /// example when it was exported from a store that skolemizes), since those IRIs would
+    /// otherwise be treated as blank nodes. Skolemization itself is switched off per graph
+    /// with [`ValidatorBuilder::with_skolemization`].
+    pub fn with_skolem_bases(mut self, shapes: Option<String>, data: Option<String>) -> Self {
+        self.shape_skolem_base = shapes;
+        self.data_skolem_base = data;
+        self
+    }
+
     /// Enables or disables SHACL AF extensions.
     pub fn with_af_enabled(mut self, enabled: bool) -> Self {
         self.enable_af = enabled;
@@ -119,35 +424,313 @@ impl ValidatorBuilder {
         self
     }
 
+    /// Caps how many value nodes a property path may produce for a single focus node.
+    ///
+    /// Property shapes whose constraints check value nodes one at a time or only count
+    /// them (`sh:class`, `sh:datatype`, `sh:node`, `sh:minCount`, ...) evaluate their value
+    /// nodes in chunks and are not capped. The others need every value node at once; for
+    /// them a focus node over the cap yields one validation result, with
+    /// [`VALUE_NODE_LIMIT_COMPONENT`] as its `sh:sourceConstraintComponent`, instead of
+    /// having its constraints evaluated, which keeps dense `sh:zeroOrMorePath` fan-outs
+    /// from exhausting memory. Unlimited by default.
+    pub fn with_max_value_nodes(mut self, limit: usize) -> Self {
+        self.max_value_nodes = Some(limit);
+        self
+    }
+
+    /// Chooses whether results with a custom severity IRI make a report non-conforming.
+    ///
+    /// By default every result does, as the SHACL specification requires. With
+    /// `CustomSeverityConformance::Ignored`, such results are still reported (with their
+    /// original `sh:resultSeverity`) but `conforms()` and `sh:conforms` disregard them.
+    pub fn with_custom_severity_conformance(mut self, policy: CustomSeverityConformance) -> Self {
+        self.custom_severity_conformance = policy;
+        self
+    }
+
+    /// Chooses how to handle a shape that a graph in the shapes graph's `owl:imports`
+    /// closure defines differently. Only the shapes graph's definition is used, so by
+    /// default such conflicts are logged as warnings; `DuplicateShapePolicy::Error` makes
+    /// `build` fail instead.
+    pub fn with_duplicate_shape_policy(mut self, policy: DuplicateShapePolicy) -> Self {
+        self.duplicate_shape_policy = policy;
+        self
+    }
+
+    /// Chooses the default graph of SPARQL-based constraint queries.
+    ///
+    /// By default it is exactly the data graph, so a query cannot match triples of the
+    /// shapes graph (or of other graphs in the store) unless it names them with
+    /// `GRAPH $shapesGraph`. `QueryDataset::Union` queries the union of all graphs instead,
+    /// which earlier versions did unconditionally.
+    pub fn with_query_dataset(mut self, dataset: QueryDataset) -> Self {
+        self.query_dataset = dataset;
+        self
+    }
+
+    /// Requires the shapes graph to declare a matching `owl:versionInfo` or
+    /// `owl:versionIRI`; `build` fails before parsing any shapes otherwise.
+    pub fn with_expected_shapes_version(mut self, requirement: ShapesVersionRequirement) -> Self {
+        self.expected_shapes_version = Some(requirement);
+        self
+    }
+
+    /// Sets the [`ValidationOptions`] used by `Validator::validate` and the other
+    /// validation methods. `build` fails if they name a shape that is not in the shapes graph.
+    pub fn with_validation_options(mut self, options: ValidationOptions) -> Self {
+        self.validation_options = options;
+        self
+    }
+
     /// Builds a `Validator` from the configured options.
     pub fn build(self) -> Result<Validator, Box<dyn Error>> {
         let Self {
-            shapes_source,
-            data_source,
+            shapes_sources,
+            data_sources,
+            existing_store,
             env_config,
             skolemize_shapes,
             skolemize_data,
             enable_af,
             enable_rules,
             skip_invalid_rules,
+            max_value_nodes,
+            shape_skolem_base,
+            data_skolem_base,
+            custom_severity_conformance,
+            duplicate_shape_policy,
+            query_dataset,
+            expected_shapes_version,
+            validation_options,
+            fetch_policy,
+            data_subset_query,
+            shapes_imports,
+            declared_shapes_graphs,
+            component_library_sources,
+            message_catalog,
         } = self;
 
-        let shapes_source =
-            shapes_source.ok_or_else(|| "shapes source must be specified".to_string())?;
-        let data_source = data_source.ok_or_else(|| "data source must be specified".to_string())?;
-
         let config = match env_config {
             Some(config) => config,
             None => Self::default_config()?,
         };
-
         let mut env: OntoEnv = OntoEnv::init(config, false)?;
-        let shapes_graph_iri = Self::add_source(&mut env, &shapes_source, "shapes")?;
-        let data_graph_iri = Self::add_source(&mut env, &data_source, "data")?;
-        let store = env.io().store().clone();
 
-        Self::maybe_skolemize_graph("shape", &store, &shapes_graph_iri, skolemize_shapes)?;
-        Self::maybe_skolemize_graph("data", &store, &data_graph_iri, skolemize_data)?;
+        // Without skolemization or a data subset, the application's store is used as is.
+        let shares_store = existing_store.is_some()
+            && data_subset_query.is_none()
+            && !skolemize_shapes
+            && !skolemize_data;
+        let (store, shapes_graph_iri, data_graph_iri) = match existing_store {
+            Some(existing) => {
+                if !shapes_sources.is_empty() || !data_sources.is_empty() {
+                    return Err(
+                        "an existing store cannot be combined with shapes or data sources".into(),
+                    );
+                }
+                if shapes_imports {
+                    return Err(
+                        "shapes imports cannot be resolved for graphs of an existing store".into(),
+                    );
+                }
+                if declared_shapes_graphs {
+                    return Err(
+                        "declared shapes graphs cannot be loaded for graphs of an existing store"
+                            .into(),
+                    );
+                }
+                if !component_library_sources.is_empty() {
+                    return Err(
+                        "component libraries cannot be merged into graphs of an existing store"
+                            .into(),
+                    );
+                }
+                let ExistingStore {
+                    store,
+                    shapes_graph,
+                    data_graph,
+                } = existing;
+                for (label, graph) in [("shapes", &shapes_graph), ("data", &data_graph)] {
+                    if !store.contains_named_graph(graph)? {
+                        return Err(format!("The store has no {} graph {}", label, graph).into());
+                    }
+                }
+                if let Some(requirement) = &expected_shapes_version {
+                    let declared = version::read_shapes_version(&store, &shapes_graph);
+                    if !requirement.matches(&declared) {
+                        return Err(format!(
+                            "Shapes graph {} declares {}, but {} is required",
+                            shapes_graph, declared, requirement
+                        )
+                        .into());
+                    }
+                }
+                // Skolemization and data subsets rewrite graphs, which must never happen to
+                // the application's own graphs; they work on private copies instead.
+                if data_subset_query.is_some() || skolemize_shapes || skolemize_data {
+                    let copy = Store::new()?;
+                    Self::copy_graph(&store, &copy, &shapes_graph)?;
+                    match &data_subset_query {
+                        Some(query) => Self::subset_data_graph(&store, &copy, &data_graph, query)?,
+                        None => Self::copy_graph(&store, &copy, &data_graph)?,
+                    }
+                    (copy, shapes_graph, data_graph)
+                } else {
+                    (store, shapes_graph, data_graph)
+                }
+            }
+            None => {
+                if data_sources.is_empty() {
+                    return Err("data source must be specified".into());
+                }
+                // Declared shapes graphs are only known once the data is loaded.
+                let (shapes_sources, loaded_data_graphs) = if declared_shapes_graphs {
+                    if !shapes_sources.is_empty() {
+                        return Err(
+                            "shapes sources cannot be combined with declared shapes graphs".into(),
+                        );
+                    }
+                    let data_graphs =
+                        Self::add_sources(&mut env, &data_sources, "data", fetch_policy.as_ref())?;
+                    let declared = Self::declared_shapes_sources(
+                        env.io().store(),
+                        &data_graphs[0],
+                        &data_sources,
+                    )?;
+                    (declared, Some(data_graphs))
+                } else {
+                    (shapes_sources, None)
+                };
+                if shapes_sources.is_empty() {
+                    return Err("shapes source must be specified".into());
+                }
+
+                let shapes_graphs =
+                    Self::add_sources(&mut env, &shapes_sources, "shapes", fetch_policy.as_ref())?;
+                let shapes_graph_iri = shapes_graphs[0].clone();
+                if let Some(requirement) = &expected_shapes_version {
+                    let declared =
+                        version::read_shapes_version(env.io().store(), &shapes_graph_iri);
+                    if !requirement.matches(&declared) {
+                        return Err(format!(
+                            "Shapes source {} (graph {}) declares {}, but {} is required",
+                            describe_sources(&shapes_sources),
+                            shapes_graph_iri,
+                            declared,
+                            requirement
+                        )
+                        .into());
+                    }
+                }
+                let data_graphs = match loaded_data_graphs {
+                    Some(data_graphs) => data_graphs,
+                    None => {
+                        Self::add_sources(&mut env, &data_sources, "data", fetch_policy.as_ref())?
+                    }
+                };
+                let data_graph_iri = data_graphs[0].clone();
+                let same_sources = shapes_sources.len() == data_sources.len()
+                    && shapes_sources
+                        .iter()
+                        .zip(&data_sources)
+                        .all(|(shapes, data)| shapes.same_location(data));
+                if !same_sources {
+                    if let Some(shared) = data_graphs.iter().find(|g| shapes_graphs.contains(g)) {
+                        return Err(format!(
+                            "Shapes source {} and data source {} both resolve to graph {}, so loading the \
+                             data replaced the shapes. Give the files distinct owl:Ontology IRIs, or use the \
+                             same source for both to validate a graph against itself.",
+                            describe_sources(&shapes_sources),
+                            describe_sources(&data_sources),
+                            shared
+                        )
+                        .into());
+                    }
+                }
+                let store = env.io().store().clone();
+                if shapes_imports {
+                    let imported = Self::load_shapes_imports(
+                        &mut env,
+                        &shapes_graph_iri,
+                        &data_graph_iri,
+                        fetch_policy.as_ref(),
+                    )?;
+                    let skolem_base = shape_skolem_base
+                        .clone()
+                        .unwrap_or_else(|| default_skolem_base(shapes_graph_iri.as_str()));
+                    shacl_parser::imports::merge_imports(
+                        &store,
+                        &shapes_graph_iri,
+                        &imported,
+                        &skolem_base,
+                    )?;
+                }
+                for source in &component_library_sources {
+                    let library = Self::add_source(
+                        &mut env,
+                        source,
+                        "component library",
+                        fetch_policy.as_ref(),
+                    )?;
+                    if library == shapes_graph_iri || library == data_graph_iri {
+                        return Err(format!(
+                            "Component library {} resolves to graph {}, which is already the shapes \
+                             or data graph; give the library its own owl:Ontology IRI.",
+                            source, library
+                        )
+                        .into());
+                    }
+                    let components = shacl_parser::component_libraries::merge_component_library(
+                        &store,
+                        &shapes_graph_iri,
+                        &library,
+                    )?;
+                    info!(
+                        "Loaded {} custom constraint components from library {}",
+                        components.len(),
+                        library
+                    );
+                }
+                if let Some(query) = &data_subset_query {
+                    if shapes_graph_iri == data_graph_iri {
+                        return Err(format!(
+                            "The data subset query would also narrow the shapes graph {}; \
+                             load the shapes from a separate source.",
+                            shapes_graph_iri
+                        )
+                        .into());
+                    }
+                    Self::subset_data_graph(&store, &store, &data_graph_iri, query)?;
+                }
+                (store, shapes_graph_iri, data_graph_iri)
+            }
+        };
+
+        let features = FeatureToggles {
+            enable_af,
+            enable_rules,
+            skip_invalid_rules,
+            max_value_nodes,
+            shape_skolem_base,
+            data_skolem_base,
+            skolemize_data,
+            custom_severity_conformance,
+            duplicate_shape_policy,
+            shapes_imports,
+            query_dataset,
+            messages: message_catalog,
+        };
+        if skolemize_shapes {
+            let base = features.shape_skolem_base_for(&shapes_graph_iri);
+            Self::skolemize_graph("shape", &store, &shapes_graph_iri, &base)?;
+        }
+        let data_skolem_base = if skolemize_data {
+            let base = features.data_skolem_base_for(&data_graph_iri);
+            Self::skolemize_graph("data", &store, &data_graph_iri, &base)?;
+            Some(base)
+        } else {
+            None
+        };
 
         info!(
             "Optimizing store with shape graph <{}> and data graph <{}>",
@@ -160,24 +743,26 @@ impl ValidatorBuilder {
             )))
         })?;
 
-        let data_skolem_base = if skolemize_data {
-            Some(Self::skolem_base(&data_graph_iri))
-        } else {
-            None
-        };
-        let original_values = match &data_source {
-            Source::File(path) => {
-                let base_ref = data_skolem_base.as_deref();
-                Some(OriginalValueIndex::from_path(path, base_ref)?)
+        let mut original_values: Option<OriginalValueIndex> = None;
+        for source in &data_sources {
+            match source {
+                Source::File(path) => original_values
+                    .get_or_insert_with(OriginalValueIndex::new)
+                    .extend_from_path(path, data_skolem_base.as_deref())?,
+                Source::Bytes { data, format } => original_values
+                    .get_or_insert_with(OriginalValueIndex::new)
+                    .extend_from_slice(data, *format, data_skolem_base.as_deref())?,
+                Source::InlineTurtle(turtle) => original_values
+                    .get_or_insert_with(OriginalValueIndex::new)
+                    .extend_from_slice(
+                        turtle.as_bytes(),
+                        RdfFormat::Turtle,
+                        data_skolem_base.as_deref(),
+                    )?,
+                Source::Graph(_) | Source::FileGraph { .. } => {}
             }
-            Source::Graph(_) => None,
-        };
+        }
 
-        let features = FeatureToggles {
-            enable_af,
-            enable_rules,
-            skip_invalid_rules,
-        };
         let model = Self::build_shapes_model(
             env,
             store,
@@ -187,7 +772,18 @@ impl ValidatorBuilder {
             original_values,
         )?;
         let context = ValidationContext::new(Rc::new(model), data_graph_iri);
-        Ok(Validator { context })
+        validate::check_options(&context, &validation_options)?;
+        if let Some(path) = validation_options.audit_log() {
+            audit::AuditLog::check(path)?;
+        }
+        if let Some(path) = validation_options.persistent_cache() {
+            persistent_cache::PersistentConformanceCache::check(path)?;
+        }
+        Ok(Validator {
+            context,
+            options: validation_options,
+            shares_store,
+        })
     }
 
     fn default_config() -> Result<Config, Box<dyn Error>> {
@@ -205,22 +801,331 @@ impl ValidatorBuilder {
             })
     }
 
+    /// Loads every source into its own graph, then merges the graphs into the first one.
+    /// Returns the graph of each source, the merged graph first.
+    fn add_sources(
+        env: &mut OntoEnv,
+        sources: &[Source],
+        label: &str,
+        fetch_policy: Option<&FetchPolicy>,
+    ) -> Result<Vec<NamedNode>, Box<dyn Error>> {
+        let mut graphs: Vec<NamedNode> = Vec::with_capacity(sources.len());
+        for (index, source) in sources.iter().enumerate() {
+            if sources[..index]
+                .iter()
+                .any(|earlier| earlier.same_location(source))
+            {
+                continue;
+            }
+            let graph = Self::add_source(env, source, label, fetch_policy)?;
+            if let Some(position) = graphs.iter().position(|earlier| *earlier == graph) {
+                return Err(format!(
+                    "The {} sources {} and {} both resolve to graph {}, so the second replaced \
+                     the first. Give the files distinct owl:Ontology IRIs.",
+                    label, sources[position], source, graph
+                )
+                .into());
+            }
+            graphs.push(graph);
+        }
+
+        let store = env.io().store().clone();
+        for graph in &graphs[1..] {
+            Self::merge_graph(&store, graph, &graphs[0])?;
+        }
+        Ok(graphs)
+    }
+
+    /// Moves the triples of graph `from` into graph `into`, giving the blank nodes of `from`
+    /// fresh identifiers so they cannot coincide with blank nodes already in `into`.
+    fn merge_graph(
+        store: &Store,
+        from: &NamedNode,
+        into: &NamedNode,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut transaction = store.start_transaction()?;
+        let quads: Vec<Quad> = transaction
+            .quads_for_pattern(
+                None,
+                None,
+                None,
+                Some(GraphNameRef::NamedNode(from.as_ref())),
+            )
+            .collect::<Result<_, _>>()?;
+        let mut renamed: HashMap<BlankNode, BlankNode> = HashMap::new();
+        let mut rename = |node: &BlankNode| renamed.entry(node.clone()).or_default().clone();
+        for quad in quads {
+            let subject: NamedOrBlankNode = match quad.subject {
+                NamedOrBlankNode::BlankNode(node) => rename(&node).into(),
+                other => other,
+            };
+            let object: Term = match quad.object {
+                Term::BlankNode(node) => rename(&node).into(),
+                other => other,
+            };
+            transaction.insert(QuadRef::new(
+                subject.as_ref(),
+                quad.predicate.as_ref(),
+                object.as_ref(),
+                GraphNameRef::NamedNode(into.as_ref()),
+            ));
+        }
+        transaction.clear_graph(GraphNameRef::NamedNode(from.as_ref()))?;
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Returns the graphs of the `owl:imports` closure of `shapes_graph`, loading the imports
+    /// that are not in the store yet. The shapes and data graphs themselves are left out.
+    fn load_shapes_imports(
+        env: &mut OntoEnv,
+        shapes_graph: &NamedNode,
+        data_graph: &NamedNode,
+        fetch_policy: Option<&FetchPolicy>,
+    ) -> Result<Vec<NamedNode>, Box<dyn Error>> {
+        let owl = OWL::get();
+        let mut graphs: Vec<NamedNode> = vec![shapes_graph.clone()];
+        let mut seen: HashSet<NamedNode> = HashSet::new();
+        let mut next = 0;
+        while next < graphs.len() {
+            let store = env.io().store().clone();
+            let imports: Vec<NamedNode> = store
+                .quads_for_pattern(
+                    None,
+                    Some(owl.imports),
+                    None,
+                    Some(GraphNameRef::NamedNode(graphs[next].as_ref())),
+                )
+                .filter_map(|quad| match quad.ok()?.object {
+                    Term::NamedNode(import) => Some(import),
+                    _ => None,
+                })
+                .collect();
+            next += 1;
+            for import in imports {
+                if !seen.insert(import.clone()) {
+                    continue;
+                }
+                let resolved = env
+                    .resolve(ResolveTarget::Graph(import.clone()))
+                    .and_then(|id| env.get_ontology(&id).ok().map(|o| o.name().clone()));
+                let graph = if store.contains_named_graph(&import)? {
+                    import
+                } else if let Some(name) = resolved {
+                    name
+                } else {
+                    let source = Self::source_for_graph(&import);
+                    Self::add_source(env, &source, "imported shapes", fetch_policy).map_err(
+                        |e| {
+                            format!(
+                                "Failed to load {} imported by the shapes graph: {}",
+                                import, e
+                            )
+                        },
+                    )?
+                };
+                if !graphs.contains(&graph) {
+                    graphs.push(graph);
+                }
+            }
+        }
+        graphs.retain(|graph| graph != shapes_graph && graph != data_graph);
+        Ok(graphs)
+    }
+
+    /// Returns the source that loads graph `graph`: the file of a `file://` IRI, otherwise
+    /// the graph itself.
+    fn source_for_graph(graph: &NamedNode) -> Source {
+        match url::Url::parse(graph.as_str()) {
+            Ok(url) if url.scheme() == "file" => url
+                .to_file_path()
+                .map(Source::File)
+                .unwrap_or_else(|_| Source::Graph(graph.as_str().to_string())),
+            _ => Source::Graph(graph.as_str().to_string()),
+        }
+    }
+
+    /// Returns the sources of the shapes graphs that `data_graph` declares with
+    /// `sh:shapesGraph`, ordered by IRI. A data graph that declares itself is its own
+    /// shapes graph and is loaded again from `data_sources`.
+    fn declared_shapes_sources(
+        store: &Store,
+        data_graph: &NamedNode,
+        data_sources: &[Source],
+    ) -> Result<Vec<Source>, Box<dyn Error>> {
+        let mut declared: Vec<NamedNode> = store
+            .quads_for_pattern(
+                None,
+                Some(SHACL::get().shapes_graph),
+                None,
+                Some(GraphNameRef::NamedNode(data_graph.as_ref())),
+            )
+            .filter_map(|quad| match quad.ok()?.object {
+                Term::NamedNode(graph) => Some(graph),
+                _ => None,
+            })
+            .collect();
+        declared.sort();
+        declared.dedup();
+
+        if declared.is_empty() {
+            return Err(format!(
+                "Data graph {} declares no shapes graph with sh:shapesGraph",
+                data_graph
+            )
+            .into());
+        }
+        if declared.contains(data_graph) {
+            if declared.len() > 1 {
+                return Err(format!(
+                    "Data graph {} declares itself and other graphs as shapes graphs; \
+                     load the shapes explicitly instead",
+                    data_graph
+                )
+                .into());
+            }
+            return Ok(data_sources.to_vec());
+        }
+        for graph in &declared {
+            info!("Data graph {} declares shapes graph {}", data_graph, graph);
+        }
+        Ok(declared.iter().map(Self::source_for_graph).collect())
+    }
+
+    /// Evaluates the `CONSTRUCT` or `DESCRIBE` `query` over `data_graph` in `from` and makes
+    /// its result the contents of `data_graph` in `into`.
+    fn subset_data_graph(
+        from: &Store,
+        into: &Store,
+        data_graph: &NamedNode,
+        query: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut prepared = SparqlEvaluator::new()
+            .parse_query(query)
+            .map_err(|e| format!("Failed to parse the data subset query: {}", e))?;
+        prepared
+            .dataset_mut()
+            .set_default_graph(vec![GraphName::NamedNode(data_graph.clone())]);
+        let triples: Vec<Triple> = match prepared.on_store(from).execute()? {
+            QueryResults::Graph(triples) => triples.collect::<Result<_, _>>()?,
+            _ => return Err("The data subset query must be a CONSTRUCT or DESCRIBE query".into()),
+        };
+        info!(
+            "Data subset query kept {} triples of data graph <{}>",
+            triples.len(),
+            data_graph
+        );
+
+        let graph = GraphNameRef::NamedNode(data_graph.as_ref());
+        let mut transaction = into.start_transaction()?;
+        transaction.clear_graph(graph)?;
+        for triple in &triples {
+            transaction.insert(triple.as_ref().in_graph(graph));
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Copies graph `graph` of `from` into the same graph of `into`.
+    fn copy_graph(from: &Store, into: &Store, graph: &NamedNode) -> Result<(), Box<dyn Error>> {
+        let quads: Vec<Quad> = from
+            .quads_for_pattern(
+                None,
+                None,
+                None,
+                Some(GraphNameRef::NamedNode(graph.as_ref())),
+            )
+            .collect::<Result<_, _>>()?;
+        let mut transaction = into.start_transaction()?;
+        for quad in &quads {
+            transaction.insert(quad.as_ref());
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
     fn add_source(
         env: &mut OntoEnv,
         source: &Source,
         label: &str,
+        fetch_policy: Option<&FetchPolicy>,
     ) -> Result<NamedNode, Box<dyn Error>> {
-        let graph_id = match source {
-            Source::Graph(uri) => env.add(
-                OntologyLocation::Url(uri.clone()),
+        let fetched = match (source, fetch_policy) {
+            (Source::Graph(uri), Some(policy)) if fetch::is_remote(uri) => {
+                Some(fetch::fetch_graph(uri, policy)?)
+            }
+            _ => None,
+        };
+        let parsed = match source {
+            Source::Bytes { data, format } => Some((
+                load::parse_graph(data, *format, label, Path::new("<bytes>"))?,
+                content_graph_name(data),
+            )),
+            Source::InlineTurtle(turtle) => Some((
+                load::parse_graph(
+                    turtle.as_bytes(),
+                    RdfFormat::Turtle,
+                    label,
+                    Path::new("<inline Turtle>"),
+                )?,
+                content_graph_name(turtle.as_bytes()),
+            )),
+            Source::File(path) => match load::parsed_file_format(path) {
+                Some(format) => Some((
+                    load::parse_graph(&std::fs::read(path)?, format, label, path)?,
+                    synthetic_graph_name(path),
+                )),
+                None => None,
+            },
+            Source::FileGraph { path, graph_iri } => {
+                let format = load::dataset_file_format(path).ok_or_else(|| {
+                    format!(
+                        "Cannot select graph <{}> from {}: not a TriG, N-Quads or JSON-LD file",
+                        graph_iri,
+                        path.display()
+                    )
+                })?;
+                let graph = NamedNode::new(graph_iri.as_str())?;
+                let triples = load::parse_named_graph(
+                    &std::fs::read(path)?,
+                    format,
+                    graph.as_ref(),
+                    label,
+                    path,
+                )?
+                .ok_or_else(|| format!("{} has no named graph <{}>", path.display(), graph_iri))?;
+                Some((triples, graph))
+            }
+            Source::Graph(_) => None,
+        };
+        if let Some((triples, fallback_name)) = parsed {
+            return Self::insert_parsed_graph(env, source, label, &triples, fallback_name);
+        }
+
+        let graph_id = match (source, &fetched) {
+            (Source::Graph(_), Some(fetched)) => env.add(
+                OntologyLocation::File(fetched.path().to_path_buf()),
                 Overwrite::Allow,
                 RefreshStrategy::Force,
             )?,
-            Source::File(path) => env.add(
-                OntologyLocation::File(path.clone()),
+            (Source::Graph(uri), None) => env.add(
+                OntologyLocation::Url(uri.clone()),
                 Overwrite::Allow,
                 RefreshStrategy::Force,
             )?,
+            (Source::File(path), _) => env
+                .add(
+                    OntologyLocation::File(path.clone()),
+                    Overwrite::Allow,
+                    RefreshStrategy::Force,
+                )
+                .map_err(|e| match load::diagnose_file(path, label) {
+                    Some(load_error) => Box::new(load_error) as Box<dyn Error>,
+                    None => e.into(),
+                })?,
+            (Source::Bytes { .. } | Source::InlineTurtle(_) | Source::FileGraph { .. }, _) => {
+                unreachable!("in-memory sources and file graphs are parsed above")
+            }
         };
 
         let ontology = env
@@ -232,7 +1137,25 @@ impl ValidatorBuilder {
                 ))) as Box<dyn Error>
             })?
             .clone();
-        let graph_iri = ontology.name().clone();
+        let mut graph_iri = ontology.name().clone();
+        // Without an owl:Ontology declaration OntoEnv names the graph after the first
+        // sh:declare subject, if any, so unrelated files can end up sharing a name. Such
+        // files get a name derived from their path instead, and fetched graphs their URL.
+        let fallback_name = match source {
+            Source::File(path) => Some(synthetic_graph_name(path)),
+            Source::Graph(uri) if fetched.is_some() => Some(NamedNode::new(uri.as_str())?),
+            Source::Graph(_)
+            | Source::Bytes { .. }
+            | Source::InlineTurtle(_)
+            | Source::FileGraph { .. } => None,
+        };
+        if let Some(fallback_name) = fallback_name {
+            let store = env.io().store().clone();
+            if !Self::declares_ontology(&store, &graph_iri) && fallback_name != graph_iri {
+                Self::rename_graph(&store, &graph_iri, &fallback_name)?;
+                graph_iri = fallback_name;
+            }
+        }
         let location = ontology
             .location()
             .map(|loc| loc.as_str().to_string())
@@ -246,26 +1169,90 @@ impl ValidatorBuilder {
         Ok(graph_iri)
     }
 
-    fn skolem_base(iri: &NamedNode) -> String {
-        format!("{}/.well-known/skolem/", iri.as_str().trim_end_matches('/'))
+    /// Inserts a graph parsed by [`load::parse_graph`] into OntoEnv's store, named after
+    /// its `owl:Ontology` declaration or `fallback_name`. An earlier graph of that name is
+    /// replaced, as OntoEnv does when a file is added again.
+    fn insert_parsed_graph(
+        env: &OntoEnv,
+        source: &Source,
+        label: &str,
+        triples: &[Triple],
+        fallback_name: NamedNode,
+    ) -> Result<NamedNode, Box<dyn Error>> {
+        let ontology = OWL::get().ontology;
+        let graph_iri = triples
+            .iter()
+            .filter(|triple| {
+                triple.predicate == rdf::TYPE
+                    && matches!(&triple.object, Term::NamedNode(class) if class.as_ref() == ontology)
+            })
+            .find_map(|triple| match &triple.subject {
+                NamedOrBlankNode::NamedNode(node) => Some(node.clone()),
+                NamedOrBlankNode::BlankNode(_) => None,
+            })
+            .unwrap_or(fallback_name);
+
+        let store = env.io().store().clone();
+        let graph = GraphNameRef::NamedNode(graph_iri.as_ref());
+        let mut transaction = store.start_transaction()?;
+        transaction.clear_graph(graph)?;
+        for triple in triples {
+            transaction.insert(triple.as_ref().in_graph(graph));
+        }
+        transaction.commit()?;
+
+        eprintln!("Loaded {} graph {} (location {})", label, graph_iri, source);
+        info!("Added {} graph: {}", label, graph_iri);
+        Ok(graph_iri)
+    }
+
+    fn declares_ontology(store: &Store, graph_iri: &NamedNode) -> bool {
+        store
+            .quads_for_pattern(
+                Some(graph_iri.as_ref().into()),
+                Some(rdf::TYPE),
+                Some(OWL::get().ontology.into()),
+                Some(GraphNameRef::NamedNode(graph_iri.as_ref())),
+            )
+            .next()
+            .is_some()
+    }
+
+    fn rename_graph(store: &Store, from: &NamedNode, to: &NamedNode) -> Result<(), Box<dyn Error>> {
+        let mut transaction = store.start_transaction()?;
+        let quads: Vec<Quad> = transaction
+            .quads_for_pattern(
+                None,
+                None,
+                None,
+                Some(GraphNameRef::NamedNode(from.as_ref())),
+            )
+            .collect::<Result<_, _>>()?;
+        transaction.clear_graph(GraphNameRef::NamedNode(to.as_ref()))?;
+        for quad in quads {
+            transaction.insert(QuadRef::new(
+                quad.subject.as_ref(),
+                quad.predicate.as_ref(),
+                quad.object.as_ref(),
+                GraphNameRef::NamedNode(to.as_ref()),
+            ));
+        }
+        transaction.clear_graph(GraphNameRef::NamedNode(from.as_ref()))?;
+        transaction.commit()?;
+        Ok(())
     }
 
-    fn maybe_skolemize_graph(
+    fn skolemize_graph(
         graph_label: &str,
         store: &Store,
         graph_iri: &NamedNode,
-        should_skolemize: bool,
+        base: &str,
     ) -> Result<(), Box<dyn Error>> {
-        if !should_skolemize {
-            return Ok(());
-        }
-
-        let base = Self::skolem_base(graph_iri);
         info!(
             "Skolemizing {} graph <{}> with base IRI <{}>",
             graph_label, graph_iri, base
         );
-        skolemize(store, GraphNameRef::NamedNode(graph_iri.as_ref()), &base)?;
+        skolemize(store, GraphNameRef::NamedNode(graph_iri.as_ref()), base)?;
         Ok(())
     }
 
@@ -291,6 +1278,11 @@ impl ValidatorBuilder {
         optimizer.optimize()?;
         let final_ctx = optimizer.finish();
 
+        let prefixes = PrefixMap::for_shapes(
+            &final_ctx.store,
+            &final_ctx.env,
+            final_ctx.shape_graph_iri_ref(),
+        );
         Ok(ShapesModel {
             nodeshape_id_lookup: final_ctx.nodeshape_id_lookup,
             propshape_id_lookup: final_ctx.propshape_id_lookup,
@@ -311,6 +1303,9 @@ impl ValidatorBuilder {
             sparql: final_ctx.sparql.clone(),
             features: final_ctx.features.clone(),
             original_values,
+            prefixes,
+            unsupported_features: final_ctx.unsupported_features,
+            custom_components: final_ctx.custom_components,
         })
     }
 }
@@ -329,8 +1324,27 @@ impl Default for ValidatorBuilder {
 ///
 /// For more advanced control, such as inspecting the parsed shapes or performing
 /// optimizations, use `ValidationContext` directly.
+///
+/// # Data graph isolation
+///
+/// Validation never modifies the data graph: `validate`, `validate_with_options`,
+/// `validate_shape`, `validate_node(s)`, `validate_property_shape` and the reporting
+/// methods only read it, so a store shared through [`ValidatorBuilder::with_store`] can be
+/// reused afterwards. The data graph changes only when a caller asks for it through
+/// [`replace_data_graph`](Self::replace_data_graph),
+/// [`extend_data_graph`](Self::extend_data_graph),
+/// [`clear_data_graph`](Self::clear_data_graph), [`revalidate`](Self::revalidate) or
+/// inference ([`run_inference`](Self::run_inference) and
+/// [`validate_with_inference`](Self::validate_with_inference)), which adds the inferred
+/// triples to it. Skolemization at build time never rewrites an application's store, and
+/// `validate_each` and `validate_new_graphs` load their payloads into a separate scratch
+/// graph that is removed again when they return.
 pub struct Validator {
     context: ValidationContext,
+    options: ValidationOptions,
+    /// Whether the store belongs to the application (see [`Validator::from_store`]), so
+    /// the validator must not add graphs of its own to it.
+    shares_store: bool,
 }
 
 impl Validator {
@@ -357,6 +1371,17 @@ impl Validator {
             .build()
     }
 
+    /// Creates a Validator for `data_source` whose shapes come from the graphs the data
+    /// graph declares with `sh:shapesGraph`, loaded through OntoEnv. The data is validated
+    /// against the union of the declared graphs; see
+    /// [`ValidatorBuilder::with_declared_shapes_graphs`].
+    pub fn from_data_only(data_source: Source) -> Result<Self, Box<dyn Error>> {
+        ValidatorBuilder::new()
+            .with_data_source(data_source)
+            .with_declared_shapes_graphs(true)
+            .build()
+    }
+
     /// Creates a new Validator from the given shapes and data sources.
     ///
     /// This method initializes the underlying `ValidationContext`, loading data from files
@@ -376,17 +1401,344 @@ impl Validator {
             .build()
     }
 
+    /// Creates a new Validator over graphs of an existing store, without copying them.
+    ///
+    /// Blank nodes are not skolemized, so the store is left unmodified. Use
+    /// [`ValidatorBuilder::with_store`] for the other options.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store holding both graphs.
+    /// * `shapes_graph` - The named graph containing the SHACL shapes.
+    /// * `data_graph` - The named graph containing the data to be validated.
+    pub fn from_store(
+        store: Store,
+        shapes_graph: GraphNameRef<'_>,
+        data_graph: GraphNameRef<'_>,
+    ) -> Result<Self, Box<dyn Error>> {
+        ValidatorBuilder::new()
+            .with_store(store, shapes_graph, data_graph)?
+            .with_skolemization(false, false)
+            .build()
+    }
+
+    /// Returns the name of the graph the shapes were loaded into.
+    pub fn shapes_graph_iri(&self) -> &NamedNode {
+        &self.context.model.shape_graph_iri
+    }
+
+    /// Returns the prefixes declared for the shapes, which reports, Graphviz labels and
+    /// message templates use to shorten IRIs.
+    pub fn prefix_map(&self) -> &PrefixMap {
+        &self.context.model.prefixes
+    }
+
+    /// Returns the constructs of the shapes graph that the engine does not implement,
+    /// such as `sh:js` constraints or unrecognized `sh:` predicates. They were ignored
+    /// during validation and are listed on the report node.
+    pub fn unsupported_features(&self) -> &[UnsupportedFeature] {
+        &self.context.model.unsupported_features
+    }
+
+    /// Returns the custom constraint components declared in the shapes graph, sorted by
+    /// IRI, with their parameters and validators. Components are listed even when no
+    /// shape uses them; `node_shape_validator` and `property_shape_validator` tell which
+    /// validator each kind of shape runs.
+    pub fn custom_components(&self) -> &[CustomConstraintComponentDefinition] {
+        &self.context.model.custom_components
+    }
+
+    /// Returns the `owl:versionInfo` / `owl:versionIRI` declared by the shapes graph.
+    pub fn shapes_version(&self) -> ShapesVersion {
+        version::read_shapes_version(
+            self.context.model.store(),
+            &self.context.model.shape_graph_iri,
+        )
+    }
+
+    /// Returns the name of the graph the data was loaded into. Files without an
+    /// `owl:Ontology` declaration are named by [`synthetic_graph_name`].
+    pub fn data_graph_iri(&self) -> &NamedNode {
+        &self.context.data_graph_iri
+    }
+
+    /// Returns read-only views of every parsed node and property shape, ordered by
+    /// shape term. Each view exposes the shape's targets, path and constraint descriptors.
+    pub fn shapes(&self) -> Vec<ShapeView<'_>> {
+        ShapeView::all(&self.context.model)
+    }
+
+    /// Looks up a parsed shape by the IRI or blank node naming it in the shapes graph.
+    /// A term declared as both a node and a property shape resolves to the property shape.
+    pub fn shape(&self, term: &Term) -> Option<ShapeView<'_>> {
+        let model = &self.context.model;
+        let prop_id = model.propshape_id_lookup().borrow().get(term);
+        if let Some(view) = prop_id.and_then(|id| ShapeView::property(model, &id)) {
+            return Some(view);
+        }
+        let node_id = model.nodeshape_id_lookup().borrow().get(term);
+        node_id.and_then(|id| ShapeView::node(model, &id))
+    }
+
+    /// Reports, for every shape in [`Validator::shapes`] order, whether a targeted shape
+    /// reaches it through shape references, along with the shortest reference chain.
+    /// Unreachable shapes never run and are usually authoring errors.
+    pub fn shape_reachability(&self) -> Vec<ShapeReachability> {
+        reachability::shape_reachability(&self.shapes())
+    }
+
     /// Validates the data graph against the shapes graph.
     ///
     /// This method executes the core validation logic and returns a `ValidationReport`.
     /// The report contains the outcome of the validation (conformity) and detailed
     /// results for any failures. The returned report is tied to the lifetime of the Validator.
+    ///
+    /// The audit log and persistent cache configured in the validator's
+    /// [`ValidationOptions`] are checked when the validator is built; if one still cannot be
+    /// read or written during the run, the error is logged and the report is returned
+    /// anyway. Use [`Validator::validate_with_options`] to have
+    /// such runs fail instead.
     pub fn validate(&self) -> ValidationReport<'_> {
-        let report_builder = validate::validate(&self.context);
+        let report_builder = validate::validate_logging_output_errors(&self.context, &self.options);
         // The report needs the context to be able to serialize itself later.
         ValidationReport::new(report_builder.unwrap(), &self.context)
     }
 
+    /// Validates the data graph with `options` instead of the validator's own
+    /// [`ValidationOptions`].
+    ///
+    /// Fails if `options` names a shape that is not in the shapes graph. Check
+    /// [`ValidationReport::stop_reason`] to tell whether the run stopped early.
+    pub fn validate_with_options(
+        &self,
+        options: &ValidationOptions,
+    ) -> Result<ValidationReport<'_>, String> {
+        let report_builder = validate::validate(&self.context, options)?;
+        Ok(ValidationReport::new(report_builder, &self.context))
+    }
+
+    /// Validates the targets of the single shape `shape` and nothing else, e.g. to check one
+    /// profile of a large shapes library.
+    ///
+    /// Shapes that `shape` references (through `sh:node`, `sh:property`, ...) are evaluated
+    /// as part of it, but their own targets are not validated. The validator's other
+    /// [`ValidationOptions`] still apply. Fails if `shape` is not in the shapes graph.
+    pub fn validate_shape(&self, shape: &NamedNode) -> Result<ValidationReport<'_>, String> {
+        let options = self
+            .options
+            .clone()
+            .with_shapes([Term::NamedNode(shape.clone())]);
+        self.validate_with_options(&options)
+    }
+
+    /// Validates only `node`, against every shape that targets it.
+    ///
+    /// Each target is checked for `node` alone rather than resolved over the whole data
+    /// graph (except advanced targets, which have no such check), so this is cheap enough to
+    /// run per request, e.g. when an API server receives a single resource. Shapes that do
+    /// not target `node` are not run, and the report only contains results about it.
+    pub fn validate_node(&self, node: &Term) -> Result<ValidationReport<'_>, String> {
+        self.validate_nodes(std::slice::from_ref(node))
+    }
+
+    /// Validates only the focus nodes in `nodes`, as [`Validator::validate_node`] does for
+    /// a single node.
+    pub fn validate_nodes(&self, nodes: &[Term]) -> Result<ValidationReport<'_>, String> {
+        let focus_nodes: HashSet<Term> = nodes.iter().cloned().collect();
+        let report_builder =
+            validate::validate_focus_nodes(&self.context, &self.options, &focus_nodes)?;
+        Ok(ValidationReport::new(report_builder, &self.context))
+    }
+
+    /// Validates `focus_nodes` against the single property shape `shape`, resolving their
+    /// value nodes through the shape's path.
+    ///
+    /// The shape's own targets are ignored and no other shape is run, which makes this a
+    /// cheap way to spot-check one property of a set of nodes.
+    pub fn validate_property_shape(
+        &self,
+        shape: NamedNodeRef<'_>,
+        focus_nodes: &[Term],
+    ) -> Result<ValidationReport<'_>, String> {
+        let report_builder = validate::validate_property_shape(
+            &self.context,
+            &shape.into_owned().into(),
+            focus_nodes,
+        )?;
+        Ok(ValidationReport::new(report_builder, &self.context))
+    }
+
+    /// Reports, for every property shape with `sh:datatype` or `sh:class`, how the values
+    /// found along its path are distributed over datatypes and classes.
+    ///
+    /// Paths are resolved over the whole data graph, not only from targeted focus nodes,
+    /// so the numbers describe the data as a whole.
+    pub fn path_type_statistics(&self) -> Result<Vec<PathTypeStatistics>, String> {
+        stats::path_type_statistics(&self.context)
+    }
+
+    /// Validates each graph in `graphs` against the already compiled shapes, passing every
+    /// report to `on_report` together with the index of the graph it belongs to.
+    ///
+    /// This is meant for streams of small payloads: the shapes are parsed once and every
+    /// graph is loaded into a reusable scratch graph of the validator's store instead of a
+    /// fresh `OntoEnv` and store per run. SPARQL-based constraints query the scratch graph,
+    /// unless `QueryDataset::Union` was chosen, in which case they can also observe the
+    /// validator's original data graph.
+    ///
+    /// Fails for validators working directly on an application's store (see
+    /// [`Validator::from_store`]), whose store must not gain a scratch graph.
+    pub fn validate_each<I, F>(&self, graphs: I, mut on_report: F) -> Result<(), String>
+    where
+        I: IntoIterator<Item = Graph>,
+        F: FnMut(usize, ValidationReport<'_>),
+    {
+        let context = self.scratch_context()?;
+
+        for (index, graph) in graphs.into_iter().enumerate() {
+            context
+                .replace_data_graph(&graph)
+                .map_err(|e| format!("Failed to load graph {}: {}", index, e))?;
+            let report_builder = validate::validate_scratch(&context, &self.options)?;
+            on_report(index, ValidationReport::new(report_builder, &context));
+        }
+
+        context.remove_data_graph()
+    }
+
+    /// Validates the named graphs of `store` that are new or changed since they were last
+    /// recorded in `tracker`, and passes one combined report to `on_report`.
+    ///
+    /// The changed graphs are merged into a scratch data graph of the validator's store (as
+    /// in [`Validator::validate_each`]) and validated together, so the report covers every
+    /// new batch at once. Their digests are recorded in `tracker` once `on_report` returns;
+    /// graphs that disappeared from `store` are forgotten. Returns the names of the graphs
+    /// that were validated, ordered by name; when nothing changed, `on_report` is not called.
+    /// Fails for validators working directly on an application's store, as
+    /// [`Validator::validate_each`] does.
+    pub fn validate_new_graphs<F>(
+        &self,
+        store: &Store,
+        tracker: &mut GraphTracker,
+        on_report: F,
+    ) -> Result<Vec<NamedNode>, String>
+    where
+        F: FnOnce(ValidationReport<'_>),
+    {
+        let stored = graph_tracker::read_named_graphs(store)?;
+        let present: Vec<NamedNode> = stored.iter().map(|g| g.name.clone()).collect();
+        tracker.retain_graphs(&present);
+
+        let changed: Vec<_> = stored
+            .into_iter()
+            .filter(|g| !tracker.is_current(&g.name, g.digest))
+            .collect();
+        if changed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut combined = Graph::new();
+        for stored_graph in &changed {
+            combined.extend(stored_graph.graph.iter());
+        }
+
+        let context = self.scratch_context()?;
+        context.replace_data_graph(&combined)?;
+        let report_builder = validate::validate_scratch(&context, &self.options)?;
+        on_report(ValidationReport::new(report_builder, &context));
+        context.remove_data_graph()?;
+
+        Ok(changed
+            .into_iter()
+            .map(|stored_graph| {
+                tracker.record(stored_graph.name.clone(), stored_graph.digest);
+                stored_graph.name
+            })
+            .collect())
+    }
+
+    /// Returns a context over the scratch data graph, unless the store is the application's.
+    fn scratch_context(&self) -> Result<ValidationContext, String> {
+        if self.shares_store {
+            return Err(format!(
+                "Batch validation needs a scratch graph <{}>, which is never added to an \
+                 application's store; build the validator with skolemization enabled so it \
+                 works on a private copy",
+                SCRATCH_DATA_GRAPH
+            ));
+        }
+        let scratch_graph = NamedNode::new_unchecked(SCRATCH_DATA_GRAPH);
+        Ok(ValidationContext::new(
+            Rc::clone(&self.context.model),
+            scratch_graph,
+        ))
+    }
+
+    /// Applies `changes` to the data graph and re-validates only the focus nodes whose
+    /// results they can affect.
+    ///
+    /// The returned partial report covers the focus nodes listed by
+    /// [`ValidationReport::revalidated_focus_nodes`]; fold it into the previous full report
+    /// with [`ValidationReport::merge_revalidation`]. When the shapes use SPARQL-based
+    /// constraints, custom components or advanced targets, which may read any part of the
+    /// data, every focus node is re-validated and the report covers them all.
+    pub fn revalidate(&self, changes: &[QuadChange]) -> Result<ValidationReport<'_>, String> {
+        let changed = self.context.apply_data_changes(changes)?;
+        let report_builder = match incremental::affected_focus_nodes(&self.context, &changed) {
+            Some(focus_nodes) => {
+                validate::validate_focus_nodes(&self.context, &self.options, &focus_nodes)?
+                    .with_revalidated(focus_nodes)
+            }
+            None => validate::validate(&self.context, &self.options)?,
+        };
+        Ok(ValidationReport::new(report_builder, &self.context))
+    }
+
+    /// Re-validates the focus nodes affected by changes already made to the data graph and
+    /// returns `previous` with their results replaced.
+    ///
+    /// `changed_subjects` names the subjects of every triple added or removed since
+    /// `previous` was produced, e.g. through [`Validator::replace_data_graph`]; when the
+    /// shapes use `sh:inversePath`, the objects of those triples must be included as well.
+    /// Blank nodes are accepted with the labels of the loaded data. Results for focus
+    /// nodes that cannot reach a changed subject are kept from `previous`, which must come
+    /// from this validator. Unlike [`Validator::revalidate`], the returned report is a full
+    /// report rather than a partial one.
+    pub fn revalidate_subjects<'a>(
+        &'a self,
+        mut previous: ValidationReport<'a>,
+        changed_subjects: &[Term],
+    ) -> Result<ValidationReport<'a>, String> {
+        let subjects = changed_subjects
+            .iter()
+            .map(|subject| self.context.skolemize_data_term(subject));
+        let report_builder = match incremental::focus_nodes_reaching(&self.context, subjects) {
+            Some(focus_nodes) => {
+                validate::validate_focus_nodes(&self.context, &self.options, &focus_nodes)?
+                    .with_revalidated(focus_nodes)
+            }
+            None => validate::validate(&self.context, &self.options)?,
+        };
+        previous.merge_revalidation(ValidationReport::new(report_builder, &self.context));
+        Ok(previous)
+    }
+
+    /// Atomically replaces the data graph with `graph`, so a long-lived validator can be
+    /// pointed at new data without rebuilding its store or re-parsing the shapes.
+    pub fn replace_data_graph(&self, graph: &Graph) -> Result<(), String> {
+        self.context.replace_data_graph(graph)
+    }
+
+    /// Adds the triples of `graph` to the data graph.
+    pub fn extend_data_graph(&self, graph: &Graph) -> Result<(), String> {
+        self.context.extend_data_graph(graph)
+    }
+
+    /// Removes every triple from the data graph.
+    pub fn clear_data_graph(&self) -> Result<(), String> {
+        self.context.clear_data_graph()
+    }
+
     /// Executes inference with a custom configuration and returns the outcome.
     pub fn run_inference_with_config(
         &self,
@@ -400,6 +1752,12 @@ impl Validator {
         self.run_inference_with_config(InferenceConfig::default())
     }
 
+    /// Executes the shapes graph's `sh:TripleRule` and `sh:SPARQLRule`s and materializes
+    /// the inferred triples into the data graph; the same as [`Validator::run_inference`].
+    pub fn infer(&self) -> Result<InferenceOutcome, InferenceError> {
+        self.run_inference()
+    }
+
     /// Returns all quads currently stored in the validator's data graph.
     pub fn data_graph_quads(&self) -> Result<Vec<Quad>, String> {
         let graph = GraphName::NamedNode(self.context.data_graph_iri.clone());
@@ -434,6 +1792,32 @@ impl Validator {
         render_shapes_graphviz(self.context.model.as_ref())
     }
 
+    /// Generates a Graphviz DOT string for one shape: the shape named `shape`, its
+    /// constraints, its property shapes and the shapes it references, following
+    /// references (`sh:node`, `sh:and`, ...) at most `max_depth` levels deep. Referenced
+    /// shapes beyond that depth are drawn dashed without their constraints.
+    ///
+    /// Unlike [`Validator::to_graphviz`], the output stays small enough to render for a
+    /// single shape of a large shapes graph.
+    pub fn to_graphviz_for_shape(&self, shape: &Term, max_depth: usize) -> Result<String, String> {
+        let root = self
+            .shape(shape)
+            .ok_or_else(|| format!("No shape named {} in the shapes graph", shape))?;
+        render_shape_graphviz(self.context.model.as_ref(), root, max_depth)
+    }
+
+    /// Renders documentation of the shapes as Markdown: a section for each node shape named
+    /// by an IRI with its `sh:name`, `sh:description` and targets, and a table of its
+    /// property shapes giving each path's cardinality, value type and other constraints.
+    pub fn to_markdown(&self) -> String {
+        render_shapes_markdown(self.context.model.as_ref())
+    }
+
+    /// Renders the documentation of [`Validator::to_markdown`] as a standalone HTML page.
+    pub fn to_html(&self) -> String {
+        render_shapes_html(self.context.model.as_ref())
+    }
+
     /// Generates a Graphviz DOT string representation of the shapes, with nodes colored by execution frequency.
     ///
     /// This can be used to visualize which parts of the shapes graph were most active during validation.
@@ -455,7 +1839,7 @@ mod tests {
     use crate::runtime::Component;
     use crate::sparql::validate_prebound_variable_usage;
     use oxigraph::model::vocab::rdf;
-    use oxigraph::model::{NamedNode, NamedOrBlankNode, Term, TermRef};
+    use oxigraph::model::{Literal, LiteralRef, NamedNode, NamedOrBlankNode, Term, TermRef};
     use std::error::Error;
     use std::fs;
     use std::io::Write;
@@ -543,7 +1927,7 @@ ex:Alice a ex:Person ;
         assert!(!report.conforms(), "Expected validation to fail");
 
         let graph = report.to_graph();
-        let sh = SHACL::new();
+        let sh = SHACL::get();
 
         let mut result_nodes: Vec<NamedOrBlankNode> = Vec::new();
         for triple in graph.iter() {
@@ -716,6 +2100,83 @@ ex:ThingB a ex:Thing ;
         Ok(())
     }
 
+    #[test]
+    fn conformance_checks_stop_at_first_failure() -> Result<(), Box<dyn Error>> {
+        let _guard = validator_lock().lock().unwrap();
+        let temp_dir = unique_temp_dir("shacl_conformance_mode_test")?;
+
+        let shapes_ttl = r#"@prefix sh: <http://www.w3.org/ns/shacl#> .
+@prefix ex: <http://example.com/ns#> .
+
+ex:Outer a sh:NodeShape ;
+    sh:targetNode ex:root ;
+    sh:node ex:Inner .
+
+ex:Inner a sh:NodeShape ;
+    sh:property [
+        sh:path ex:child ;
+        sh:node ex:Leaf ;
+    ] .
+
+ex:Leaf a sh:NodeShape ;
+    sh:property [
+        sh:path ex:name ;
+        sh:minCount 1 ;
+    ] .
+"#;
+        let data_ttl = r#"@prefix ex: <http://example.com/ns#> .
+
+ex:root ex:child ex:a, ex:b, ex:c .
+"#;
+
+        let shapes_path = temp_dir.join("shapes.ttl");
+        let data_path = temp_dir.join("data.ttl");
+        fs::write(&shapes_path, shapes_ttl)?;
+        fs::write(&data_path, data_ttl)?;
+
+        let validator =
+            Validator::from_files(&shapes_path.to_string_lossy(), &data_path.to_string_lossy())?;
+        let report = validator.validate();
+        assert!(!report.conforms(), "Expected ex:root to violate ex:Outer");
+
+        let context = validator.context();
+        let child = NamedNode::new_unchecked("http://example.com/ns#child");
+        let child_shape = context
+            .model
+            .prop_shapes
+            .values()
+            .find(|shape| shape.path_term() == &Term::NamedNode(child.clone()))
+            .expect("ex:child property shape should exist");
+
+        let root = Term::NamedNode(NamedNode::new_unchecked("http://example.com/ns#root"));
+        let count_failures = |mode: runtime::ConformanceMode| -> Result<usize, String> {
+            let mut focus = context::Context::new(
+                root.clone(),
+                None,
+                None,
+                context::SourceShape::PropertyShape(*child_shape.identifier()),
+                0,
+            );
+            focus.set_conformance_mode(mode);
+            let mut trace = Vec::new();
+            let results = child_shape.validate(&mut focus, context, &mut trace)?;
+            Ok(results
+                .iter()
+                .filter(|r| matches!(r, runtime::ComponentValidationResult::Fail(_, _)))
+                .count())
+        };
+
+        assert_eq!(count_failures(runtime::ConformanceMode::Report)?, 3);
+        assert_eq!(
+            count_failures(runtime::ConformanceMode::FirstFailure)?,
+            1,
+            "Conformance checks should stop after the first non-conforming value node"
+        );
+
+        fs::remove_dir_all(&temp_dir)?;
+        Ok(())
+    }
+
     #[test]
     fn sparql_constraint_allows_missing_path_but_requires_this() {
         let _guard = validator_lock().lock().unwrap();
@@ -781,4 +2242,55 @@ ex:Alice a ex:Person ;
         )
         .is_ok());
     }
+
+    #[test]
+    fn report_metadata_is_attached_to_report_node() -> Result<(), Box<dyn Error>> {
+        let _guard = validator_lock().lock().unwrap();
+        let temp_dir = unique_temp_dir("shacl_report_metadata_test")?;
+
+        let shapes_ttl = r#"@prefix sh: <http://www.w3.org/ns/shacl#> .
+@prefix ex: <http://example.com/ns#> .
+
+ex:ThingShape a sh:NodeShape ;
+    sh:targetClass ex:Thing ;
+    sh:property [
+        sh:path ex:name ;
+        sh:minCount 1 ;
+    ] .
+"#;
+        let data_ttl = r#"@prefix ex: <http://example.com/ns#> .
+
+ex:a a ex:Thing .
+"#;
+
+        let shapes_path = temp_dir.join("shapes.ttl");
+        let data_path = temp_dir.join("data.ttl");
+        fs::write(&shapes_path, shapes_ttl)?;
+        fs::write(&data_path, data_ttl)?;
+
+        let validator =
+            Validator::from_files(&shapes_path.to_string_lossy(), &data_path.to_string_lossy())?;
+        let request_id = NamedNode::new("http://purl.org/dc/terms/identifier")?;
+        let report = validator
+            .validate()
+            .with_metadata(request_id.clone(), Literal::new_simple_literal("req-42"));
+        assert_eq!(report.metadata().len(), 1);
+
+        let graph = report.to_graph();
+        let sh = SHACL::get();
+        let report_node = graph
+            .subjects_for_predicate_object(rdf::TYPE, sh.validation_report)
+            .next()
+            .expect("report node missing");
+        let value = graph
+            .object_for_subject_predicate(report_node, &request_id)
+            .expect("metadata should be attached to the report node");
+        assert_eq!(
+            value,
+            TermRef::Literal(LiteralRef::new_simple_literal("req-42"))
+        );
+
+        fs::remove_dir_all(&temp_dir)?;
+        Ok(())
+    }
 }
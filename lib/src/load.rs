@@ -0,0 +1,208 @@
+//! Loading of shapes and data graphs that OntoEnv cannot read by itself, and structured
+//! errors for graphs that fail to load.
+//!
+//! `ValidatorBuilder::build` returns a `GraphLoadError` (boxed, so callers can
+//! `downcast_ref` it) when a `Source::File` or `Source::Bytes` is not valid RDF. This
+//! separates "the input is not RDF" from "the input violates the shapes", which a
+//! validation report covers.
+//!
+//! OntoEnv picks a parser from the file extension and does not read JSON-LD, so JSON-LD
+//! files, single named graphs of dataset files and in-memory sources are parsed here and
+//! inserted into OntoEnv's store directly, without touching the filesystem.
+
+use oxigraph::io::{RdfFormat, RdfParseError, RdfParser};
+use oxigraph::model::{GraphNameRef, NamedNodeRef, Quad, Triple};
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+/// Upper bound on the number of syntax issues collected from one file.
+const MAX_SYNTAX_ISSUES: usize = 50;
+
+/// A single syntax error found while parsing a graph file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxIssue {
+    /// 1-based line of the error, when the parser reports one.
+    pub line: Option<u64>,
+    /// 1-based column of the error, when the parser reports one.
+    pub column: Option<u64>,
+    /// The parser's description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for SyntaxIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{}:{}: {}", line, column, self.message),
+            (Some(line), None) => write!(f, "{}: {}", line, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// A shapes or data file that could not be parsed as RDF.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphLoadError {
+    /// Which input failed: `"shapes"` or `"data"`.
+    pub role: String,
+    /// The file that failed to parse.
+    pub path: PathBuf,
+    /// Syntax issues in file order. Never empty.
+    pub issues: Vec<SyntaxIssue>,
+}
+
+impl fmt::Display for GraphLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} graph {} is not valid RDF ({} syntax error{})",
+            self.role,
+            self.path.display(),
+            self.issues.len(),
+            if self.issues.len() == 1 { "" } else { "s" }
+        )?;
+        for issue in &self.issues {
+            write!(f, "\n  {}:{}", self.path.display(), issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for GraphLoadError {}
+
+/// Re-parses `path` to collect its syntax errors.
+///
+/// Returns `None` when the format cannot be inferred from the extension, the
+/// file cannot be read, or the file parses cleanly; the caller should then
+/// surface its original error.
+pub(crate) fn diagnose_file(path: &Path, role: &str) -> Option<GraphLoadError> {
+    let format = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(RdfFormat::from_extension)?;
+    let file = File::open(path).ok()?;
+
+    let issues = match parse_quads(RdfParser::from_format(format).for_reader(BufReader::new(file)))
+    {
+        Ok(_) => return None,
+        Err(issues) => issues,
+    };
+    Some(GraphLoadError {
+        role: role.to_string(),
+        path: path.to_path_buf(),
+        issues,
+    })
+}
+
+/// Collects the quads of a parse, or its syntax issues when there are any.
+fn parse_quads(
+    results: impl IntoIterator<Item = Result<Quad, RdfParseError>>,
+) -> Result<Vec<Quad>, Vec<SyntaxIssue>> {
+    let mut quads = Vec::new();
+    let mut issues = Vec::new();
+    for result in results {
+        match result {
+            Ok(quad) => quads.push(quad),
+            Err(RdfParseError::Syntax(e)) => {
+                let position = e.location().map(|range| range.start);
+                issues.push(SyntaxIssue {
+                    line: position.as_ref().map(|p| p.line + 1),
+                    column: position.as_ref().map(|p| p.column + 1),
+                    message: strip_location_prefix(&e.to_string()),
+                });
+                if issues.len() >= MAX_SYNTAX_ISSUES {
+                    break;
+                }
+            }
+            Err(RdfParseError::Io(e)) => {
+                issues.push(SyntaxIssue {
+                    line: None,
+                    column: None,
+                    message: e.to_string(),
+                });
+                break;
+            }
+        }
+    }
+    if issues.is_empty() {
+        Ok(quads)
+    } else {
+        Err(issues)
+    }
+}
+
+/// Returns the format of `path` when OntoEnv cannot load it by extension, which is the
+/// case for JSON-LD (`.jsonld`).
+pub(crate) fn parsed_file_format(path: &Path) -> Option<RdfFormat> {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .and_then(|ext| RdfFormat::from_extension(&ext))
+        .filter(|format| matches!(format, RdfFormat::JsonLd { .. }))
+}
+
+/// Returns the format of `path` if it is a dataset format (TriG, N-Quads or JSON-LD),
+/// judging by its extension.
+pub(crate) fn dataset_file_format(path: &Path) -> Option<RdfFormat> {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .and_then(|ext| RdfFormat::from_extension(&ext))
+        .filter(|format| format.supports_datasets())
+}
+
+/// Parses `data` as `format`. Triples in named graphs of dataset formats (TriG, N-Quads,
+/// JSON-LD) are merged into the one graph. `origin` names the input in a
+/// `GraphLoadError`.
+pub(crate) fn parse_graph(
+    data: &[u8],
+    format: RdfFormat,
+    role: &str,
+    origin: &Path,
+) -> Result<Vec<Triple>, GraphLoadError> {
+    let quads = parse_dataset(data, format, role, origin)?;
+    Ok(quads.into_iter().map(Triple::from).collect())
+}
+
+/// Parses the dataset `data` as `format` and returns the triples of its named graph
+/// `graph`, or `None` when the dataset has no triples in that graph.
+pub(crate) fn parse_named_graph(
+    data: &[u8],
+    format: RdfFormat,
+    graph: NamedNodeRef<'_>,
+    role: &str,
+    origin: &Path,
+) -> Result<Option<Vec<Triple>>, GraphLoadError> {
+    let triples: Vec<Triple> = parse_dataset(data, format, role, origin)?
+        .into_iter()
+        .filter(|quad| quad.graph_name.as_ref() == GraphNameRef::NamedNode(graph))
+        .map(Triple::from)
+        .collect();
+    Ok(Some(triples).filter(|triples| !triples.is_empty()))
+}
+
+fn parse_dataset(
+    data: &[u8],
+    format: RdfFormat,
+    role: &str,
+    origin: &Path,
+) -> Result<Vec<Quad>, GraphLoadError> {
+    parse_quads(RdfParser::from_format(format).for_slice(data)).map_err(|issues| GraphLoadError {
+        role: role.to_string(),
+        path: origin.to_path_buf(),
+        issues,
+    })
+}
+
+/// Drops the "Parser error at line X column Y: " prefix the Turtle-family
+/// parsers add, since the position is reported separately.
+fn strip_location_prefix(message: &str) -> String {
+    if message.starts_with("Parser error") {
+        if let Some((_, rest)) = message.split_once(": ") {
+            return rest.to_string();
+        }
+    }
+    message.to_string()
+}
@@ -0,0 +1,358 @@
+//! Catalog of the messages the engine writes for validation results.
+//!
+//! Core constraint components describe each failure in a sentence such as "Value count (0)
+//! does not meet minimum requirement: 1". That sentence is the result's `engine_message`,
+//! is printed by `dump`, and explains failures nested inside `sh:node` and logical
+//! constraints. It is looked up by key in a [`MessageCatalog`], so a deployment can ship
+//! translated diagnostics as a catalog file instead of patching component code.
+//!
+//! Catalog files hold one `key = template` entry per line; blank lines and lines starting
+//! with `#` are ignored. Templates refer to the values of a message with `{name}`
+//! placeholders, as in `min_count = Anzahl der Werte ({count}) unter dem Minimum {min}`.
+//! Keys a file does not translate keep their English text. [`MessageCatalog::english_source`]
+//! returns every key with its English template, as a starting point for a translation.
+//!
+//! Messages of shapes (`sh:message`) are unaffected; they are part of the shapes graph.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File extension of the catalog files [`MessageCatalog::for_locale`] looks for.
+pub const CATALOG_EXTENSION: &str = "messages";
+
+/// Every message key with its English template.
+const ENGLISH: &[(&str, &str)] = &[
+    (
+        "min_count",
+        "Value count ({count}) does not meet minimum requirement: {min}",
+    ),
+    (
+        "max_count",
+        "Value count ({count}) exceeds maximum requirement: {max}",
+    ),
+    (
+        "length.blank_node",
+        "Blank node {value} found where string length constraints apply ({constraint}).",
+    ),
+    (
+        "min_length",
+        "Value {value} has length {length} which is less than minLength {min}.",
+    ),
+    (
+        "max_length",
+        "Value {value} has length {length} which is greater than maxLength {max}.",
+    ),
+    (
+        "pattern.blank_node",
+        "Blank node {value} cannot be matched against a pattern.",
+    ),
+    (
+        "pattern.mismatch",
+        "Value {value} does not match pattern '{pattern}'.",
+    ),
+    (
+        "pattern.mismatch_with_flags",
+        "Value {value} does not match pattern '{pattern}' with flags '{flags}'.",
+    ),
+    (
+        "language_in.empty_list",
+        "Value {value} fails sh:languageIn constraint because the list of allowed languages is empty.",
+    ),
+    (
+        "language_in.mismatch",
+        "Language tag '{tag}' of value {value} is not in the allowed list {allowed}.",
+    ),
+    (
+        "language_in.not_literal",
+        "Value {value} is not a literal, but sh:languageIn applies to literals.",
+    ),
+    (
+        "unique_lang",
+        "Language tag '{tag}' is used by more than one value node, but sh:uniqueLang is true.",
+    ),
+    (
+        "class",
+        "Value {value} does not conform to class constraint: {class}",
+    ),
+    (
+        "datatype.lang_string_without_tag",
+        "Value {value} is not a language-tagged string for datatype rdf:langString",
+    ),
+    (
+        "datatype.lang_string_not_literal",
+        "Value {value} is not a literal for datatype rdf:langString",
+    ),
+    (
+        "datatype.ill_formed",
+        "Value {value} has an invalid lexical form for datatype {datatype}",
+    ),
+    (
+        "datatype.mismatch",
+        "Value {value} does not have datatype {datatype}",
+    ),
+    (
+        "datatype.not_literal",
+        "Value {value} is not a literal, expected datatype {datatype}",
+    ),
+    (
+        "datatype_in",
+        "Value {value} is not a valid literal of any of the datatypes {datatypes}",
+    ),
+    (
+        "node_kind",
+        "Value {value} is {actual}, but sh:nodeKind {node_kind} requires {expected}",
+    ),
+    (
+        "in.empty_list",
+        "sh:in constraint has an empty list, but value nodes {values} exist.",
+    ),
+    ("in.mismatch", "Value {value} is not in the allowed list {allowed}."),
+    (
+        "closed",
+        "Node {node} has a value for property {property}, which the closed shape does not allow",
+    ),
+    (
+        "has_value.missing",
+        "None of the value nodes {values} are equal to the required value {required}",
+    ),
+    (
+        "has_value.no_values",
+        "No value nodes found to check against required value {required}",
+    ),
+    (
+        "equals.missing_value",
+        "Value node {value} not found in values of property <{property}>",
+    ),
+    (
+        "equals.extra_value",
+        "Value {value} of property <{property}> not found in value nodes",
+    ),
+    (
+        "disjoint",
+        "Value {value} is not disjoint with values of property <{property}>",
+    ),
+    (
+        "less_than",
+        "Value {value} is not less than {other} from property <{property}>",
+    ),
+    (
+        "less_than_or_equals",
+        "Value {value} is not less than or equal to {other} from property <{property}>",
+    ),
+    ("nested.on_path", "on path {path}"),
+    ("nested.for_value", "for value {value}"),
+];
+
+/// Engine messages for one locale, with English for every key it does not translate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageCatalog {
+    locale: String,
+    templates: HashMap<String, String>,
+}
+
+impl Default for MessageCatalog {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+impl MessageCatalog {
+    /// The built-in English messages.
+    pub fn english() -> Self {
+        Self {
+            locale: "en".to_string(),
+            templates: ENGLISH
+                .iter()
+                .map(|(key, template)| (key.to_string(), template.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Parses catalog entries for `locale`. Fails on malformed lines, unknown keys and
+    /// placeholders the English message does not have, which are usually typos.
+    pub fn parse(locale: &str, text: &str) -> Result<Self, String> {
+        let mut catalog = Self::english();
+        catalog.locale = locale.to_string();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, template) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected `key = template`", index + 1))?;
+            let (key, template) = (key.trim(), template.trim());
+            let english = ENGLISH
+                .iter()
+                .find(|(known, _)| *known == key)
+                .map(|(_, english)| *english)
+                .ok_or_else(|| format!("line {}: unknown message key '{}'", index + 1, key))?;
+            let allowed = placeholders(english);
+            if let Some(unknown) = placeholders(template)
+                .into_iter()
+                .find(|name| !allowed.contains(name))
+            {
+                return Err(format!(
+                    "line {}: message '{}' has no placeholder {{{}}}",
+                    index + 1,
+                    key,
+                    unknown
+                ));
+            }
+            catalog
+                .templates
+                .insert(key.to_string(), template.to_string());
+        }
+        Ok(catalog)
+    }
+
+    /// Reads a catalog file. The locale is the file name without its extension, so
+    /// `messages/de.messages` holds the `de` catalog.
+    pub fn from_file(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read message catalog {}: {}", path.display(), e))?;
+        let locale = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self::parse(&locale, &text)
+            .map_err(|e| format!("Invalid message catalog {}: {}", path.display(), e).into())
+    }
+
+    /// Selects the catalog for `locale` among the `<locale>.messages` files in `dir`,
+    /// trying ever shorter language ranges: `de-CH` uses `de-CH.messages`, else
+    /// `de.messages`. English locales need no file; for others `dir` must contain one.
+    pub fn for_locale(dir: &Path, locale: &str) -> Result<Self, Box<dyn Error>> {
+        let mut range = locale;
+        loop {
+            let path: PathBuf = dir.join(format!("{}.{}", range, CATALOG_EXTENSION));
+            if path.is_file() {
+                return Self::from_file(&path);
+            }
+            if range.eq_ignore_ascii_case("en") {
+                return Ok(Self::english());
+            }
+            match range.rsplit_once('-') {
+                Some((shorter, _)) => range = shorter,
+                None => {
+                    return Err(format!(
+                        "No message catalog for locale {} in {}",
+                        locale,
+                        dir.display()
+                    )
+                    .into())
+                }
+            }
+        }
+    }
+
+    /// The locale the catalog translates to, e.g. `en` or `de`.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Every message key with its English template, in catalog file syntax.
+    pub fn english_source() -> String {
+        ENGLISH
+            .iter()
+            .map(|(key, template)| format!("{} = {}\n", key, template))
+            .collect()
+    }
+
+    /// Renders message `key`, replacing each `{name}` placeholder with its value in
+    /// `args`.
+    pub(crate) fn format(&self, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let Some(template) = self.templates.get(key) else {
+            return key.to_string();
+        };
+        let mut message = String::with_capacity(template.len());
+        let mut rest = template.as_str();
+        while let Some(start) = rest.find('{') {
+            message.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let value = after.find('}').and_then(|end| {
+                let name = &after[..end];
+                args.iter()
+                    .find(|(arg, _)| *arg == name)
+                    .map(|(_, value)| (end, value.to_string()))
+            });
+            match value {
+                Some((end, value)) => {
+                    message.push_str(&value);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    message.push('{');
+                    rest = after;
+                }
+            }
+        }
+        message.push_str(rest);
+        message
+    }
+}
+
+/// The `{name}` placeholders of a template.
+fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) => {
+                names.push(&after[..end]);
+                rest = &after[end + 1..];
+            }
+            None => break,
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_messages_fill_their_placeholders() {
+        let catalog = MessageCatalog::english();
+        assert_eq!(
+            catalog.format("min_count", &[("count", &0), ("min", &1)]),
+            "Value count (0) does not meet minimum requirement: 1"
+        );
+    }
+
+    #[test]
+    fn translations_override_english_per_key() {
+        let catalog = MessageCatalog::parse(
+            "de",
+            "# Kardinalität\nmin_count = Anzahl der Werte ({count}) unter dem Minimum {min}\n",
+        )
+        .unwrap();
+        assert_eq!(catalog.locale(), "de");
+        assert_eq!(
+            catalog.format("min_count", &[("count", &0), ("min", &1)]),
+            "Anzahl der Werte (0) unter dem Minimum 1"
+        );
+        assert_eq!(
+            catalog.format("max_count", &[("count", &3), ("max", &2)]),
+            "Value count (3) exceeds maximum requirement: 2"
+        );
+    }
+
+    #[test]
+    fn unknown_keys_and_placeholders_are_rejected() {
+        assert!(MessageCatalog::parse("de", "min_cuont = x").is_err());
+        assert!(MessageCatalog::parse("de", "min_count = {minimum}").is_err());
+        assert!(MessageCatalog::parse("de", "min_count").is_err());
+    }
+
+    #[test]
+    fn english_source_round_trips() {
+        let catalog = MessageCatalog::parse("en", &MessageCatalog::english_source()).unwrap();
+        assert_eq!(catalog, MessageCatalog::english());
+    }
+}
@@ -30,6 +30,10 @@ pub enum ComponentDescriptor {
     Datatype {
         datatype: Term,
     },
+    /// `shrs:datatypeIn`: the value must be a well-formed literal of one of `datatypes`.
+    DatatypeIn {
+        datatypes: Vec<Term>,
+    },
     NodeKind {
         node_kind: Term,
     },
@@ -1,4 +1,5 @@
 use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 
 use oxigraph::model::{NamedNode, Term};
 
@@ -45,3 +46,126 @@ pub struct CustomConstraintComponentDefinition {
 
 /// Concrete values bound to a parameterized SPARQL validator during parsing time.
 pub type ParameterBindings = HashMap<NamedNode, Vec<Term>>;
+
+impl CustomConstraintComponentDefinition {
+    /// Messages declared on the component, falling back to those of its template.
+    pub fn effective_messages(&self) -> &[Term] {
+        match &self.template {
+            Some(template) if self.messages.is_empty() => &template.messages,
+            _ => &self.messages,
+        }
+    }
+
+    /// Returns the `sh:defaultValue`s of the parameter bound to `path`, consulting the
+    /// template declaration when the component itself does not provide any.
+    pub fn parameter_default_values(&self, path: &NamedNode) -> &[Term] {
+        let own = self
+            .parameters
+            .iter()
+            .find(|param| &param.path == path)
+            .map(|param| param.default_values.as_slice())
+            .unwrap_or_default();
+        if !own.is_empty() {
+            return own;
+        }
+        self.template
+            .as_ref()
+            .and_then(|template| template.parameter_by_path(path))
+            .map(|param| param.default_values.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the `sh:varName` override for the parameter bound to `path`, consulting the
+    /// template declaration when the component itself does not provide one.
+    pub fn parameter_var_name(&self, path: &NamedNode) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|param| &param.path == path)
+            .and_then(|param| param.var_name.as_deref())
+            .or_else(|| {
+                self.template
+                    .as_ref()
+                    .and_then(|template| template.parameter_by_path(path))
+                    .and_then(|param| param.var_name.as_deref())
+            })
+    }
+
+    /// Severity declared on the component, falling back to that of its template.
+    pub fn effective_severity(&self) -> Option<&Severity> {
+        self.severity.as_ref().or_else(|| {
+            self.template
+                .as_ref()
+                .and_then(|template| template.severity.as_ref())
+        })
+    }
+
+    /// The validator run when the component is used on a node shape: its
+    /// `sh:nodeValidator`, falling back to `sh:validator`.
+    pub fn node_shape_validator(&self) -> Option<&SPARQLValidator> {
+        self.node_validator.as_ref().or(self.validator.as_ref())
+    }
+
+    /// The validator run when the component is used on a property shape: its
+    /// `sh:propertyValidator`, falling back to `sh:validator`.
+    pub fn property_shape_validator(&self) -> Option<&SPARQLValidator> {
+        self.property_validator.as_ref().or(self.validator.as_ref())
+    }
+}
+
+/// Names the validator chosen for one kind of shape, e.g. `sh:nodeValidator (SELECT)`.
+fn describe_validator(
+    specific: Option<&SPARQLValidator>,
+    specific_name: &str,
+    general: Option<&SPARQLValidator>,
+) -> String {
+    let (name, validator) = match (specific, general) {
+        (Some(validator), _) => (specific_name, validator),
+        (None, Some(validator)) => ("sh:validator", validator),
+        (None, None) => return "none, the component is ignored".to_string(),
+    };
+    let kind = if validator.is_ask { "ASK" } else { "SELECT" };
+    format!("{} ({})", name, kind)
+}
+
+/// Lists the component's parameters and the validator used for node and property shapes,
+/// one per line.
+impl fmt::Display for CustomConstraintComponentDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.iri)?;
+        for parameter in &self.parameters {
+            let optionality = if parameter.optional {
+                "optional"
+            } else {
+                "required"
+            };
+            write!(f, "  parameter {} ({}", parameter.path, optionality)?;
+            if !parameter.default_values.is_empty() {
+                let defaults: Vec<String> = parameter
+                    .default_values
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect();
+                write!(f, ", default {}", defaults.join(" "))?;
+            }
+            writeln!(f, ")")?;
+        }
+        writeln!(
+            f,
+            "  node shapes: {}",
+            describe_validator(
+                self.node_validator.as_ref(),
+                "sh:nodeValidator",
+                self.validator.as_ref()
+            )
+        )?;
+        write!(
+            f,
+            "  property shapes: {}",
+            describe_validator(
+                self.property_validator.as_ref(),
+                "sh:propertyValidator",
+                self.validator.as_ref()
+            )
+        )
+    }
+}
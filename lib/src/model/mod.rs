@@ -1,4 +1,5 @@
 pub mod components;
+pub(crate) mod normalize;
 pub mod rules;
 pub mod shapes;
 pub mod templates;
@@ -0,0 +1,134 @@
+//! Canonical forms for literals that appear as constraint parameters.
+//!
+//! The parser normalizes every object of a shape's constraint parameters, and validators that
+//! compare terms (`sh:hasValue`, `sh:in`) normalize value nodes the same way, so literals that
+//! denote the same value in different lexical forms compare equal. Normalized parameters also
+//! give identical descriptor keys, which lets the optimizer deduplicate more components.
+
+use oxigraph::model::vocab::xsd;
+use oxigraph::model::{Literal, NamedNodeRef, Term};
+use oxsdatatypes::{Boolean, Decimal, Double, Float, Integer};
+use std::str::FromStr;
+
+/// Returns the canonical form of `term`.
+///
+/// * Language tags are lowercased: they are case-insensitive (BCP 47), so `"chat"@EN` and
+///   `"chat"@en` are the same term.
+/// * Well-formed `xsd:boolean`, `xsd:decimal`, `xsd:double`, `xsd:float`, `xsd:integer` and
+///   integer-derived literals get their canonical lexical form (`"01"` becomes `"1"`, `"1.50"`
+///   becomes `"1.5"`), keeping their datatype.
+///
+/// Ill-typed literals and other terms are returned unchanged.
+pub(crate) fn normalize_term(term: Term) -> Term {
+    let Term::Literal(literal) = term else {
+        return term;
+    };
+    if let Some(language) = literal.language() {
+        if language.bytes().any(|b| b.is_ascii_uppercase()) {
+            return Literal::new_language_tagged_literal_unchecked(
+                literal.value(),
+                language.to_ascii_lowercase(),
+            )
+            .into();
+        }
+        return literal.into();
+    }
+    match canonical_lexical_form(literal.value(), literal.datatype()) {
+        Some(canonical) if canonical != literal.value() => {
+            Literal::new_typed_literal(canonical, literal.datatype()).into()
+        }
+        _ => literal.into(),
+    }
+}
+
+fn canonical_lexical_form(value: &str, datatype: NamedNodeRef<'_>) -> Option<String> {
+    fn canonical<T: FromStr + ToString>(value: &str) -> Option<String> {
+        T::from_str(value).ok().map(|parsed| parsed.to_string())
+    }
+
+    if datatype == xsd::BOOLEAN {
+        canonical::<Boolean>(value)
+    } else if datatype == xsd::DECIMAL {
+        canonical::<Decimal>(value)
+    } else if datatype == xsd::DOUBLE {
+        canonical::<Double>(value)
+    } else if datatype == xsd::FLOAT {
+        canonical::<Float>(value)
+    } else if is_integer_datatype(datatype) {
+        canonical::<Integer>(value)
+    } else {
+        None
+    }
+}
+
+fn is_integer_datatype(datatype: NamedNodeRef<'_>) -> bool {
+    [
+        xsd::INTEGER,
+        xsd::LONG,
+        xsd::INT,
+        xsd::SHORT,
+        xsd::BYTE,
+        xsd::NON_NEGATIVE_INTEGER,
+        xsd::POSITIVE_INTEGER,
+        xsd::NON_POSITIVE_INTEGER,
+        xsd::NEGATIVE_INTEGER,
+        xsd::UNSIGNED_LONG,
+        xsd::UNSIGNED_INT,
+        xsd::UNSIGNED_SHORT,
+        xsd::UNSIGNED_BYTE,
+    ]
+    .contains(&datatype)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxigraph::model::NamedNode;
+
+    fn typed(value: &str, datatype: NamedNodeRef<'_>) -> Term {
+        Literal::new_typed_literal(value, datatype).into()
+    }
+
+    #[test]
+    fn language_tags_are_lowercased() {
+        let upper: Term = Literal::new_language_tagged_literal_unchecked("chat", "EN-GB").into();
+        let lower: Term = Literal::new_language_tagged_literal("chat", "en-gb")
+            .unwrap()
+            .into();
+        assert_eq!(normalize_term(upper), lower);
+    }
+
+    #[test]
+    fn numeric_and_boolean_literals_get_canonical_forms() {
+        assert_eq!(
+            normalize_term(typed("+007", xsd::INTEGER)),
+            typed("7", xsd::INTEGER)
+        );
+        assert_eq!(
+            normalize_term(typed("010", xsd::UNSIGNED_BYTE)),
+            typed("10", xsd::UNSIGNED_BYTE)
+        );
+        assert_eq!(
+            normalize_term(typed("1.50", xsd::DECIMAL)),
+            typed("1.5", xsd::DECIMAL)
+        );
+        assert_eq!(
+            normalize_term(typed("1.0E1", xsd::DOUBLE)),
+            normalize_term(typed("10", xsd::DOUBLE))
+        );
+        assert_eq!(
+            normalize_term(typed("1", xsd::BOOLEAN)),
+            typed("true", xsd::BOOLEAN)
+        );
+    }
+
+    #[test]
+    fn ill_typed_and_other_terms_are_unchanged() {
+        let ill_typed = typed("twelve", xsd::INTEGER);
+        let plain: Term = Literal::new_simple_literal("EN").into();
+        let iri: Term = NamedNode::new_unchecked("http://example.org/EN").into();
+        assert_eq!(normalize_term(ill_typed.clone()), ill_typed);
+        assert_eq!(normalize_term(plain.clone()), plain);
+        assert_eq!(normalize_term(iri.clone()), iri);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::types::{ComponentID, Path, PropShapeID, Severity, Target, ID};
 use oxigraph::model::Term;
 
+static DEFAULT_SEVERITY: Severity = Severity::Violation;
+
 /// Immutable description of a SHACL node shape.
 #[derive(Debug)]
 pub struct NodeShape {
@@ -8,7 +10,7 @@ pub struct NodeShape {
     /// Target selectors identifying candidate focus nodes.
     pub targets: Vec<Target>,
     constraints: Vec<ComponentID>,
-    severity: Severity,
+    severity: Option<Severity>,
     deactivated: bool,
 }
 
@@ -24,7 +26,7 @@ impl NodeShape {
             identifier,
             targets,
             constraints,
-            severity: severity.unwrap_or_default(),
+            severity,
             deactivated,
         }
     }
@@ -37,8 +39,17 @@ impl NodeShape {
         &self.constraints
     }
 
+    pub(crate) fn constraints_mut(&mut self) -> &mut Vec<ComponentID> {
+        &mut self.constraints
+    }
+
     pub fn severity(&self) -> &Severity {
-        &self.severity
+        self.severity.as_ref().unwrap_or(&DEFAULT_SEVERITY)
+    }
+
+    /// Returns the severity only when the shape declares `sh:severity` explicitly.
+    pub fn declared_severity(&self) -> Option<&Severity> {
+        self.severity.as_ref()
     }
 
     pub fn is_deactivated(&self) -> bool {
@@ -55,8 +66,9 @@ pub struct PropertyShape {
     path: Path,
     path_term: Term,
     constraints: Vec<ComponentID>,
-    severity: Severity,
+    severity: Option<Severity>,
     deactivated: bool,
+    order: Option<f64>,
 }
 
 impl PropertyShape {
@@ -75,11 +87,18 @@ impl PropertyShape {
             path,
             path_term,
             constraints,
-            severity: severity.unwrap_or_default(),
+            severity,
             deactivated,
+            order: None,
         }
     }
 
+    /// Sets the shape's `sh:order`.
+    pub fn with_order(mut self, order: Option<f64>) -> Self {
+        self.order = order;
+        self
+    }
+
     pub fn identifier(&self) -> &PropShapeID {
         &self.identifier
     }
@@ -100,11 +119,26 @@ impl PropertyShape {
         &self.constraints
     }
 
+    pub(crate) fn constraints_mut(&mut self) -> &mut Vec<ComponentID> {
+        &mut self.constraints
+    }
+
     pub fn severity(&self) -> &Severity {
-        &self.severity
+        self.severity.as_ref().unwrap_or(&DEFAULT_SEVERITY)
+    }
+
+    /// Returns the severity only when the shape declares `sh:severity` explicitly.
+    pub fn declared_severity(&self) -> Option<&Severity> {
+        self.severity.as_ref()
     }
 
     pub fn is_deactivated(&self) -> bool {
         self.deactivated
     }
+
+    /// The shape's `sh:order`, which fixes its position among the `sh:property` shapes
+    /// of a referencing shape.
+    pub fn order(&self) -> Option<f64> {
+        self.order
+    }
 }
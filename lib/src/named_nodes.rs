@@ -118,155 +118,183 @@ pub(crate) struct SHACL {
     pub(crate) source_constraint: NamedNodeRef<'static>,
     pub(crate) source_constraint_component: NamedNodeRef<'static>,
     pub(crate) result_message: NamedNodeRef<'static>,
+    pub(crate) detail: NamedNodeRef<'static>,
     pub(crate) result_severity: NamedNodeRef<'static>,
+    pub(crate) shapes_graph: NamedNodeRef<'static>,
+    pub(crate) constraint_component: NamedNodeRef<'static>,
 }
 
+static SHACL_TERMS: SHACL = SHACL::new();
+
 impl SHACL {
+    /// Returns the shared, statically initialized `SHACL` vocabulary.
+    pub(crate) fn get() -> &'static Self {
+        &SHACL_TERMS
+    }
+
     /// Creates a new `SHACL` instance with all terms initialized.
-    pub(crate) fn new() -> Self {
+    const fn new() -> Self {
         SHACL {
-            class: NamedNodeRef::new("http://www.w3.org/ns/shacl#class").unwrap(),
-            node: NamedNodeRef::new("http://www.w3.org/ns/shacl#node").unwrap(),
-            property: NamedNodeRef::new("http://www.w3.org/ns/shacl#property").unwrap(),
-            qualified_value_shape: NamedNodeRef::new(
+            class: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#class"),
+            node: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#node"),
+            property: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#property"),
+            qualified_value_shape: NamedNodeRef::new_unchecked(
                 "http://www.w3.org/ns/shacl#qualifiedValueShape",
-            )
-            .unwrap(),
-            qualified_min_count: NamedNodeRef::new("http://www.w3.org/ns/shacl#qualifiedMinCount")
-                .unwrap(),
-            qualified_max_count: NamedNodeRef::new("http://www.w3.org/ns/shacl#qualifiedMaxCount")
-                .unwrap(),
-            min_count: NamedNodeRef::new("http://www.w3.org/ns/shacl#minCount").unwrap(),
-            max_count: NamedNodeRef::new("http://www.w3.org/ns/shacl#maxCount").unwrap(),
-            qualified_value_shapes_disjoint: NamedNodeRef::new(
+            ),
+            qualified_min_count: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#qualifiedMinCount",
+            ),
+            qualified_max_count: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#qualifiedMaxCount",
+            ),
+            min_count: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#minCount"),
+            max_count: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#maxCount"),
+            qualified_value_shapes_disjoint: NamedNodeRef::new_unchecked(
                 "http://www.w3.org/ns/shacl#qualifiedValueShapesDisjoint",
-            )
-            .unwrap(),
-            not: NamedNodeRef::new("http://www.w3.org/ns/shacl#not").unwrap(),
-            node_kind: NamedNodeRef::new("http://www.w3.org/ns/shacl#nodeKind").unwrap(),
-            datatype: NamedNodeRef::new("http://www.w3.org/ns/shacl#datatype").unwrap(),
-            min_exclusive: NamedNodeRef::new("http://www.w3.org/ns/shacl#minExclusive").unwrap(),
-            min_inclusive: NamedNodeRef::new("http://www.w3.org/ns/shacl#minInclusive").unwrap(),
-            max_exclusive: NamedNodeRef::new("http://www.w3.org/ns/shacl#maxExclusive").unwrap(),
-            max_inclusive: NamedNodeRef::new("http://www.w3.org/ns/shacl#maxInclusive").unwrap(),
-            min_length: NamedNodeRef::new("http://www.w3.org/ns/shacl#minLength").unwrap(),
-            max_length: NamedNodeRef::new("http://www.w3.org/ns/shacl#maxLength").unwrap(),
-            pattern: NamedNodeRef::new("http://www.w3.org/ns/shacl#pattern").unwrap(),
-            flags: NamedNodeRef::new("http://www.w3.org/ns/shacl#flags").unwrap(),
-            language_in: NamedNodeRef::new("http://www.w3.org/ns/shacl#languageIn").unwrap(),
-            unique_lang: NamedNodeRef::new("http://www.w3.org/ns/shacl#uniqueLang").unwrap(),
-            node_shape: NamedNodeRef::new("http://www.w3.org/ns/shacl#NodeShape").unwrap(),
-            property_shape: NamedNodeRef::new("http://www.w3.org/ns/shacl#PropertyShape").unwrap(),
-            and_: NamedNodeRef::new("http://www.w3.org/ns/shacl#and").unwrap(),
-            or_: NamedNodeRef::new("http://www.w3.org/ns/shacl#or").unwrap(),
-            xone: NamedNodeRef::new("http://www.w3.org/ns/shacl#xone").unwrap(),
-            path: NamedNodeRef::new("http://www.w3.org/ns/shacl#path").unwrap(),
-            inverse_path: NamedNodeRef::new("http://www.w3.org/ns/shacl#inversePath").unwrap(),
-            alternative_path: NamedNodeRef::new("http://www.w3.org/ns/shacl#alternativePath")
-                .unwrap(),
-            sequence_path: NamedNodeRef::new("http://www.w3.org/ns/shacl#sequencePath").unwrap(),
-            zero_or_more_path: NamedNodeRef::new("http://www.w3.org/ns/shacl#zeroOrMorePath")
-                .unwrap(),
-            one_or_more_path: NamedNodeRef::new("http://www.w3.org/ns/shacl#oneOrMorePath")
-                .unwrap(),
-            zero_or_one_path: NamedNodeRef::new("http://www.w3.org/ns/shacl#zeroOrOnePath")
-                .unwrap(),
-
-            target_class: NamedNodeRef::new("http://www.w3.org/ns/shacl#targetClass").unwrap(),
-            target_node: NamedNodeRef::new("http://www.w3.org/ns/shacl#targetNode").unwrap(),
-            target_objects_of: NamedNodeRef::new("http://www.w3.org/ns/shacl#targetObjectsOf")
-                .unwrap(),
-            target_subjects_of: NamedNodeRef::new("http://www.w3.org/ns/shacl#targetSubjectsOf")
-                .unwrap(),
-            target: NamedNodeRef::new("http://www.w3.org/ns/shacl#target").unwrap(),
-            target_validator: NamedNodeRef::new("http://www.w3.org/ns/shacl#targetValidator")
-                .unwrap(),
-            target_shape: NamedNodeRef::new("http://www.w3.org/ns/shacl#targetShape").unwrap(),
-            filter_shape: NamedNodeRef::new("http://www.w3.org/ns/shacl#filterShape").unwrap(),
-
-            equals: NamedNodeRef::new("http://www.w3.org/ns/shacl#equals").unwrap(),
-            disjoint: NamedNodeRef::new("http://www.w3.org/ns/shacl#disjoint").unwrap(),
-            less_than: NamedNodeRef::new("http://www.w3.org/ns/shacl#lessThan").unwrap(),
-            less_than_or_equals: NamedNodeRef::new("http://www.w3.org/ns/shacl#lessThanOrEquals")
-                .unwrap(),
-
-            closed: NamedNodeRef::new("http://www.w3.org/ns/shacl#closed").unwrap(),
-            ignored_properties: NamedNodeRef::new("http://www.w3.org/ns/shacl#ignoredProperties")
-                .unwrap(),
-            has_value: NamedNodeRef::new("http://www.w3.org/ns/shacl#hasValue").unwrap(),
-            in_: NamedNodeRef::new("http://www.w3.org/ns/shacl#in").unwrap(),
+            ),
+            not: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#not"),
+            node_kind: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#nodeKind"),
+            datatype: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#datatype"),
+            min_exclusive: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#minExclusive"),
+            min_inclusive: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#minInclusive"),
+            max_exclusive: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#maxExclusive"),
+            max_inclusive: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#maxInclusive"),
+            min_length: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#minLength"),
+            max_length: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#maxLength"),
+            pattern: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#pattern"),
+            flags: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#flags"),
+            language_in: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#languageIn"),
+            unique_lang: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#uniqueLang"),
+            node_shape: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#NodeShape"),
+            property_shape: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#PropertyShape"),
+            and_: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#and"),
+            or_: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#or"),
+            xone: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#xone"),
+            path: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#path"),
+            inverse_path: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#inversePath"),
+            alternative_path: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#alternativePath",
+            ),
+            sequence_path: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#sequencePath"),
+            zero_or_more_path: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#zeroOrMorePath",
+            ),
+            one_or_more_path: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#oneOrMorePath",
+            ),
+            zero_or_one_path: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#zeroOrOnePath",
+            ),
+
+            target_class: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#targetClass"),
+            target_node: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#targetNode"),
+            target_objects_of: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#targetObjectsOf",
+            ),
+            target_subjects_of: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#targetSubjectsOf",
+            ),
+            target: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#target"),
+            target_validator: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#targetValidator",
+            ),
+            target_shape: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#targetShape"),
+            filter_shape: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#filterShape"),
+
+            equals: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#equals"),
+            disjoint: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#disjoint"),
+            less_than: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#lessThan"),
+            less_than_or_equals: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#lessThanOrEquals",
+            ),
+
+            closed: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#closed"),
+            ignored_properties: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#ignoredProperties",
+            ),
+            has_value: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#hasValue"),
+            in_: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#in"),
 
             // NodeKind instances
-            iri: NamedNodeRef::new("http://www.w3.org/ns/shacl#IRI").unwrap(),
-            literal: NamedNodeRef::new("http://www.w3.org/ns/shacl#Literal").unwrap(),
-            blank_node: NamedNodeRef::new("http://www.w3.org/ns/shacl#BlankNode").unwrap(),
-            blank_node_or_iri: NamedNodeRef::new("http://www.w3.org/ns/shacl#BlankNodeOrIRI")
-                .unwrap(),
-            blank_node_or_literal: NamedNodeRef::new(
+            iri: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#IRI"),
+            literal: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#Literal"),
+            blank_node: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#BlankNode"),
+            blank_node_or_iri: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#BlankNodeOrIRI",
+            ),
+            blank_node_or_literal: NamedNodeRef::new_unchecked(
                 "http://www.w3.org/ns/shacl#BlankNodeOrLiteral",
-            )
-            .unwrap(),
-            iri_or_literal: NamedNodeRef::new("http://www.w3.org/ns/shacl#IRIOrLiteral").unwrap(),
+            ),
+            iri_or_literal: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#IRIOrLiteral"),
 
             // Severities
-            severity: NamedNodeRef::new("http://www.w3.org/ns/shacl#severity").unwrap(),
-            info: NamedNodeRef::new("http://www.w3.org/ns/shacl#Info").unwrap(),
-            warning: NamedNodeRef::new("http://www.w3.org/ns/shacl#Warning").unwrap(),
-            violation: NamedNodeRef::new("http://www.w3.org/ns/shacl#Violation").unwrap(),
+            severity: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#severity"),
+            info: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#Info"),
+            warning: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#Warning"),
+            violation: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#Violation"),
 
             // SPARQL
-            select: NamedNodeRef::new("http://www.w3.org/ns/shacl#select").unwrap(),
-            ask: NamedNodeRef::new("http://www.w3.org/ns/shacl#ask").unwrap(),
-            deactivated: NamedNodeRef::new("http://www.w3.org/ns/shacl#deactivated").unwrap(),
-            message: NamedNodeRef::new("http://www.w3.org/ns/shacl#message").unwrap(),
-            sparql: NamedNodeRef::new("http://www.w3.org/ns/shacl#sparql").unwrap(),
-            prefixes: NamedNodeRef::new("http://www.w3.org/ns/shacl#prefixes").unwrap(),
-            declare: NamedNodeRef::new("http://www.w3.org/ns/shacl#declare").unwrap(),
-            prefix: NamedNodeRef::new("http://www.w3.org/ns/shacl#prefix").unwrap(),
-            namespace: NamedNodeRef::new("http://www.w3.org/ns/shacl#namespace").unwrap(),
-            default_value: NamedNodeRef::new("http://www.w3.org/ns/shacl#defaultValue").unwrap(),
-            name: NamedNodeRef::new("http://www.w3.org/ns/shacl#name").unwrap(),
-            description: NamedNodeRef::new("http://www.w3.org/ns/shacl#description").unwrap(),
-            parameter: NamedNodeRef::new("http://www.w3.org/ns/shacl#parameter").unwrap(),
-            optional: NamedNodeRef::new("http://www.w3.org/ns/shacl#optional").unwrap(),
-            var_name: NamedNodeRef::new("http://www.w3.org/ns/shacl#varName").unwrap(),
-            validator: NamedNodeRef::new("http://www.w3.org/ns/shacl#validator").unwrap(),
-            node_validator: NamedNodeRef::new("http://www.w3.org/ns/shacl#nodeValidator").unwrap(),
-            property_validator: NamedNodeRef::new("http://www.w3.org/ns/shacl#propertyValidator")
-                .unwrap(),
-            shape_class: NamedNodeRef::new("http://www.w3.org/ns/shacl#Shape").unwrap(),
-            shape_prop: NamedNodeRef::new("http://www.w3.org/ns/shacl#shape").unwrap(),
-            rule: NamedNodeRef::new("http://www.w3.org/ns/shacl#rule").unwrap(),
-            triple_rule: NamedNodeRef::new("http://www.w3.org/ns/shacl#TripleRule").unwrap(),
-            sparql_rule: NamedNodeRef::new("http://www.w3.org/ns/shacl#SPARQLRule").unwrap(),
-            construct: NamedNodeRef::new("http://www.w3.org/ns/shacl#construct").unwrap(),
-            condition: NamedNodeRef::new("http://www.w3.org/ns/shacl#condition").unwrap(),
-            rule_subject: NamedNodeRef::new("http://www.w3.org/ns/shacl#subject").unwrap(),
-            rule_predicate: NamedNodeRef::new("http://www.w3.org/ns/shacl#predicate").unwrap(),
-            rule_object: NamedNodeRef::new("http://www.w3.org/ns/shacl#object").unwrap(),
-            order: NamedNodeRef::new("http://www.w3.org/ns/shacl#order").unwrap(),
-            this: NamedNodeRef::new("http://www.w3.org/ns/shacl#this").unwrap(),
+            select: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#select"),
+            ask: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#ask"),
+            deactivated: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#deactivated"),
+            message: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#message"),
+            sparql: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#sparql"),
+            prefixes: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#prefixes"),
+            declare: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#declare"),
+            prefix: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#prefix"),
+            namespace: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#namespace"),
+            default_value: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#defaultValue"),
+            name: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#name"),
+            description: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#description"),
+            parameter: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#parameter"),
+            optional: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#optional"),
+            var_name: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#varName"),
+            validator: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#validator"),
+            node_validator: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#nodeValidator"),
+            property_validator: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#propertyValidator",
+            ),
+            shape_class: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#Shape"),
+            shape_prop: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#shape"),
+            rule: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#rule"),
+            triple_rule: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#TripleRule"),
+            sparql_rule: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#SPARQLRule"),
+            construct: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#construct"),
+            condition: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#condition"),
+            rule_subject: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#subject"),
+            rule_predicate: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#predicate"),
+            rule_object: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#object"),
+            order: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#order"),
+            this: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#this"),
 
             // Validation Report
-            validation_report: NamedNodeRef::new("http://www.w3.org/ns/shacl#ValidationReport")
-                .unwrap(),
-            conforms: NamedNodeRef::new("http://www.w3.org/ns/shacl#conforms").unwrap(),
-            result: NamedNodeRef::new("http://www.w3.org/ns/shacl#result").unwrap(),
-            validation_result: NamedNodeRef::new("http://www.w3.org/ns/shacl#ValidationResult")
-                .unwrap(),
-            focus_node: NamedNodeRef::new("http://www.w3.org/ns/shacl#focusNode").unwrap(),
-            value: NamedNodeRef::new("http://www.w3.org/ns/shacl#value").unwrap(),
-            result_path: NamedNodeRef::new("http://www.w3.org/ns/shacl#resultPath").unwrap(),
-            source_shape: NamedNodeRef::new("http://www.w3.org/ns/shacl#sourceShape").unwrap(),
-            source_constraint: NamedNodeRef::new("http://www.w3.org/ns/shacl#sourceConstraint")
-                .unwrap(),
-            source_constraint_component: NamedNodeRef::new(
+            validation_report: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#ValidationReport",
+            ),
+            conforms: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#conforms"),
+            result: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#result"),
+            validation_result: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#ValidationResult",
+            ),
+            focus_node: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#focusNode"),
+            value: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#value"),
+            result_path: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#resultPath"),
+            source_shape: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#sourceShape"),
+            source_constraint: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#sourceConstraint",
+            ),
+            source_constraint_component: NamedNodeRef::new_unchecked(
                 "http://www.w3.org/ns/shacl#sourceConstraintComponent",
-            )
-            .unwrap(),
-            result_message: NamedNodeRef::new("http://www.w3.org/ns/shacl#resultMessage").unwrap(),
-            result_severity: NamedNodeRef::new("http://www.w3.org/ns/shacl#resultSeverity")
-                .unwrap(),
+            ),
+            result_message: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#resultMessage"),
+            detail: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#detail"),
+            result_severity: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#resultSeverity",
+            ),
+            shapes_graph: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#shapesGraph"),
+            constraint_component: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl#ConstraintComponent",
+            ),
         }
     }
 }
@@ -283,19 +311,30 @@ pub(crate) struct RDF {
     pub(crate) nil: NamedNodeRef<'static>,
 }
 
+static RDF_TERMS: RDF = RDF::new();
+
 impl RDF {
+    /// Returns the shared, statically initialized `RDF` vocabulary.
+    pub(crate) fn get() -> &'static Self {
+        &RDF_TERMS
+    }
+
     /// Creates a new `RDF` instance.
-    pub(crate) fn new() -> Self {
+    const fn new() -> Self {
         RDF {
-            type_: NamedNodeRef::new("http://www.w3.org/1999/02/22-rdf-syntax-ns#type").unwrap(),
-            subject: NamedNodeRef::new("http://www.w3.org/1999/02/22-rdf-syntax-ns#subject")
-                .unwrap(),
-            predicate: NamedNodeRef::new("http://www.w3.org/1999/02/22-rdf-syntax-ns#predicate")
-                .unwrap(),
-            object: NamedNodeRef::new("http://www.w3.org/1999/02/22-rdf-syntax-ns#object").unwrap(),
-            first: NamedNodeRef::new("http://www.w3.org/1999/02/22-rdf-syntax-ns#first").unwrap(),
-            rest: NamedNodeRef::new("http://www.w3.org/1999/02/22-rdf-syntax-ns#rest").unwrap(),
-            nil: NamedNodeRef::new("http://www.w3.org/1999/02/22-rdf-syntax-ns#nil").unwrap(),
+            type_: NamedNodeRef::new_unchecked("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"),
+            subject: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#subject",
+            ),
+            predicate: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#predicate",
+            ),
+            object: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#object",
+            ),
+            first: NamedNodeRef::new_unchecked("http://www.w3.org/1999/02/22-rdf-syntax-ns#first"),
+            rest: NamedNodeRef::new_unchecked("http://www.w3.org/1999/02/22-rdf-syntax-ns#rest"),
+            nil: NamedNodeRef::new_unchecked("http://www.w3.org/1999/02/22-rdf-syntax-ns#nil"),
         }
     }
 }
@@ -309,15 +348,23 @@ pub(crate) struct RDFS {
     pub(crate) class: NamedNodeRef<'static>,
 }
 
+static RDFS_TERMS: RDFS = RDFS::new();
+
 impl RDFS {
+    /// Returns the shared, statically initialized `RDFS` vocabulary.
+    pub(crate) fn get() -> &'static Self {
+        &RDFS_TERMS
+    }
+
     /// Creates a new `RDFS` instance.
-    pub(crate) fn new() -> Self {
+    const fn new() -> Self {
         RDFS {
-            sub_class_of: NamedNodeRef::new("http://www.w3.org/2000/01/rdf-schema#subClassOf")
-                .unwrap(),
-            label: NamedNodeRef::new("http://www.w3.org/2000/01/rdf-schema#label").unwrap(),
-            comment: NamedNodeRef::new("http://www.w3.org/2000/01/rdf-schema#comment").unwrap(),
-            class: NamedNodeRef::new("http://www.w3.org/2000/01/rdf-schema#Class").unwrap(),
+            sub_class_of: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/2000/01/rdf-schema#subClassOf",
+            ),
+            label: NamedNodeRef::new_unchecked("http://www.w3.org/2000/01/rdf-schema#label"),
+            comment: NamedNodeRef::new_unchecked("http://www.w3.org/2000/01/rdf-schema#comment"),
+            class: NamedNodeRef::new_unchecked("http://www.w3.org/2000/01/rdf-schema#Class"),
         }
     }
 }
@@ -325,13 +372,54 @@ impl RDFS {
 /// A struct holding static `NamedNodeRef`s for OWL vocabulary terms.
 pub(crate) struct OWL {
     pub(crate) class: NamedNodeRef<'static>,
+    pub(crate) imports: NamedNodeRef<'static>,
+    pub(crate) ontology: NamedNodeRef<'static>,
+    pub(crate) version_info: NamedNodeRef<'static>,
+    pub(crate) version_iri: NamedNodeRef<'static>,
 }
 
+static OWL_TERMS: OWL = OWL::new();
+
 impl OWL {
+    /// Returns the shared, statically initialized `OWL` vocabulary.
+    pub(crate) fn get() -> &'static Self {
+        &OWL_TERMS
+    }
+
     /// Creates a new `OWL` instance.
-    pub(crate) fn new() -> Self {
+    const fn new() -> Self {
         OWL {
-            class: NamedNodeRef::new("http://www.w3.org/2002/07/owl#Class").unwrap(),
+            class: NamedNodeRef::new_unchecked("http://www.w3.org/2002/07/owl#Class"),
+            imports: NamedNodeRef::new_unchecked("http://www.w3.org/2002/07/owl#imports"),
+            ontology: NamedNodeRef::new_unchecked("http://www.w3.org/2002/07/owl#Ontology"),
+            version_info: NamedNodeRef::new_unchecked("http://www.w3.org/2002/07/owl#versionInfo"),
+            version_iri: NamedNodeRef::new_unchecked("http://www.w3.org/2002/07/owl#versionIRI"),
+        }
+    }
+}
+
+/// A struct holding static `NamedNodeRef`s for the shacl-rs extension vocabulary
+/// (native constraint components outside of SHACL and SHACL-AF).
+pub(crate) struct SHRS {
+    pub(crate) datatype_in: NamedNodeRef<'static>,
+    pub(crate) datatype_in_constraint_component: NamedNodeRef<'static>,
+}
+
+static SHRS_TERMS: SHRS = SHRS::new();
+
+impl SHRS {
+    /// Returns the shared, statically initialized `SHRS` vocabulary.
+    pub(crate) fn get() -> &'static Self {
+        &SHRS_TERMS
+    }
+
+    /// Creates a new `SHRS` instance.
+    const fn new() -> Self {
+        SHRS {
+            datatype_in: NamedNodeRef::new_unchecked("urn:shacl-rs:datatypeIn"),
+            datatype_in_constraint_component: NamedNodeRef::new_unchecked(
+                "urn:shacl-rs:DatatypeInConstraintComponent",
+            ),
         }
     }
 }
@@ -347,34 +435,35 @@ pub(crate) struct MF {
     pub(crate) include: NamedNodeRef<'static>,
 }
 
+static MF_TERMS: MF = MF::new();
+
 impl MF {
+    /// Returns the shared, statically initialized `MF` vocabulary.
+    pub(crate) fn get() -> &'static Self {
+        &MF_TERMS
+    }
+
     /// Creates a new `MF` instance.
-    pub(crate) fn new() -> Self {
+    const fn new() -> Self {
         MF {
-            manifest: NamedNodeRef::new(
+            manifest: NamedNodeRef::new_unchecked(
                 "http://www.w3.org/2001/sw/DataAccess/tests/test-manifest#Manifest",
-            )
-            .unwrap(),
-            entries: NamedNodeRef::new(
+            ),
+            entries: NamedNodeRef::new_unchecked(
                 "http://www.w3.org/2001/sw/DataAccess/tests/test-manifest#entries",
-            )
-            .unwrap(),
-            action: NamedNodeRef::new(
+            ),
+            action: NamedNodeRef::new_unchecked(
                 "http://www.w3.org/2001/sw/DataAccess/tests/test-manifest#action",
-            )
-            .unwrap(),
-            result: NamedNodeRef::new(
+            ),
+            result: NamedNodeRef::new_unchecked(
                 "http://www.w3.org/2001/sw/DataAccess/tests/test-manifest#result",
-            )
-            .unwrap(),
-            status: NamedNodeRef::new(
+            ),
+            status: NamedNodeRef::new_unchecked(
                 "http://www.w3.org/2001/sw/DataAccess/tests/test-manifest#status",
-            )
-            .unwrap(),
-            include: NamedNodeRef::new(
+            ),
+            include: NamedNodeRef::new_unchecked(
                 "http://www.w3.org/2001/sw/DataAccess/tests/test-manifest#include",
-            )
-            .unwrap(),
+            ),
         }
     }
 }
@@ -386,13 +475,22 @@ pub(crate) struct SHT {
     pub(crate) shapes_graph: NamedNodeRef<'static>,
 }
 
+static SHT_TERMS: SHT = SHT::new();
+
 impl SHT {
+    /// Returns the shared, statically initialized `SHT` vocabulary.
+    pub(crate) fn get() -> &'static Self {
+        &SHT_TERMS
+    }
+
     /// Creates a new `SHT` instance.
-    pub(crate) fn new() -> Self {
+    const fn new() -> Self {
         SHT {
-            validate: NamedNodeRef::new("http://www.w3.org/ns/shacl-test#Validate").unwrap(),
-            data_graph: NamedNodeRef::new("http://www.w3.org/ns/shacl-test#dataGraph").unwrap(),
-            shapes_graph: NamedNodeRef::new("http://www.w3.org/ns/shacl-test#shapesGraph").unwrap(),
+            validate: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl-test#Validate"),
+            data_graph: NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl-test#dataGraph"),
+            shapes_graph: NamedNodeRef::new_unchecked(
+                "http://www.w3.org/ns/shacl-test#shapesGraph",
+            ),
         }
     }
 }
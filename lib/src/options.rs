@@ -0,0 +1,205 @@
+//! Per-run settings for a validation pass.
+//!
+//! [`ValidationOptions`] bounds how much work one call to `Validator::validate` does and which
+//! results it keeps. A validator built with `ValidatorBuilder::with_validation_options` uses
+//! the options for every run; `Validator::validate_with_options` overrides them for one run.
+
+use crate::types::Severity;
+use oxigraph::model::Term;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Settings for one validation run. The defaults validate every shape, keep every result and
+/// never stop early.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationOptions {
+    max_errors: Option<usize>,
+    fail_fast: bool,
+    ignored_severities: HashSet<Severity>,
+    shapes: Option<HashSet<Term>>,
+    sparql_constraints: bool,
+    timeout: Option<Duration>,
+    result_details: bool,
+    audit_log: Option<PathBuf>,
+    persistent_cache: Option<PathBuf>,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            max_errors: None,
+            fail_fast: false,
+            ignored_severities: HashSet::new(),
+            shapes: None,
+            sparql_constraints: true,
+            timeout: None,
+            result_details: false,
+            audit_log: None,
+            persistent_cache: None,
+        }
+    }
+}
+
+impl ValidationOptions {
+    /// Creates options with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops validating once the report holds `limit` results.
+    pub fn with_max_errors(mut self, limit: usize) -> Self {
+        self.max_errors = Some(limit);
+        self
+    }
+
+    /// Stops validating at the first result; the same as a limit of one result.
+    pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    /// Drops results of `severity` from the report. Dropped results do not count towards
+    /// the result limit and do not make the report non-conforming.
+    pub fn with_ignored_severity(mut self, severity: Severity) -> Self {
+        self.ignored_severities.insert(severity);
+        self
+    }
+
+    /// Validates only the targets of the given shapes; results of shapes they reference
+    /// (through `sh:node`, `sh:property`, ...) are still produced.
+    pub fn with_shapes(mut self, shapes: impl IntoIterator<Item = Term>) -> Self {
+        self.shapes = Some(shapes.into_iter().collect());
+        self
+    }
+
+    /// Enables or disables `sh:sparql` constraints and SPARQL-based constraint components.
+    /// Disabled constraints are skipped as if they held.
+    pub fn with_sparql_constraints(mut self, enabled: bool) -> Self {
+        self.sparql_constraints = enabled;
+        self
+    }
+
+    /// Stops validating once `timeout` has elapsed. The deadline is checked between focus
+    /// nodes, so a run can overshoot it by the time one focus node takes.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Explains failures of `sh:node`, `sh:and`, `sh:or` and `sh:xone` constraints with
+    /// nested `sh:detail` results: the first failure of each nested shape the value node
+    /// does not conform to. Off by default, as the nested results can be numerous.
+    pub fn with_result_details(mut self, enabled: bool) -> Self {
+        self.result_details = enabled;
+        self
+    }
+
+    /// Appends a JSON line to the file at `path` for every constraint the run evaluates:
+    /// the shape, constraint component, focus node, path and value nodes, and whether it
+    /// passed, failed or could not be evaluated. The run fails if the log cannot be written,
+    /// except for `Validator::validate`, which logs the error and returns the report; a
+    /// validator built with these options checks that the log can be opened.
+    pub fn with_audit_log(mut self, path: impl Into<PathBuf>) -> Self {
+        self.audit_log = Some(path.into());
+        self
+    }
+
+    /// Keeps a cache of the focus nodes that conformed to each shape in the file at `path`,
+    /// and skips validating them in later runs while the shapes graph and the focus node's
+    /// own triples are unchanged. Only shapes whose outcome depends on nothing else are
+    /// cached: IRI-named shapes with predicate paths and constraints such as `sh:datatype`,
+    /// `sh:minCount` or `sh:pattern`, but not `sh:class`, `sh:node`, logical or SPARQL-based
+    /// ones. The file is rewritten after every run; failures to read or write it are handled
+    /// like those of [`ValidationOptions::with_audit_log`].
+    pub fn with_persistent_cache(mut self, path: impl Into<PathBuf>) -> Self {
+        self.persistent_cache = Some(path.into());
+        self
+    }
+
+    /// Returns the number of results after which validation stops, taking fail-fast into
+    /// account.
+    pub fn max_errors(&self) -> Option<usize> {
+        if self.fail_fast {
+            Some(1)
+        } else {
+            self.max_errors
+        }
+    }
+
+    /// Returns `true` if results of `severity` are dropped.
+    pub fn ignores_severity(&self, severity: &Severity) -> bool {
+        self.ignored_severities.contains(severity)
+    }
+
+    /// Returns the shapes whose targets are validated, or `None` for every shape.
+    pub fn shapes(&self) -> Option<&HashSet<Term>> {
+        self.shapes.as_ref()
+    }
+
+    /// Returns `true` if SPARQL-based constraints are evaluated.
+    pub fn sparql_constraints(&self) -> bool {
+        self.sparql_constraints
+    }
+
+    /// Returns `true` if results carry nested `sh:detail` results.
+    pub fn result_details(&self) -> bool {
+        self.result_details
+    }
+
+    /// Returns the file focus nodes that conformed are cached in.
+    pub fn persistent_cache(&self) -> Option<&Path> {
+        self.persistent_cache.as_deref()
+    }
+
+    /// Returns the file constraint evaluations are logged to.
+    pub fn audit_log(&self) -> Option<&Path> {
+        self.audit_log.as_deref()
+    }
+
+    /// Returns the time after which validation stops.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+/// Why a validation run stopped before validating every focus node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// The report reached the result limit (or fail-fast was set).
+    MaxErrors,
+    /// The run exceeded its timeout.
+    Timeout,
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StopReason::MaxErrors => write!(f, "result limit reached"),
+            StopReason::Timeout => write!(f, "timeout exceeded"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fail_fast_overrides_the_result_limit() {
+        let options = ValidationOptions::new().with_max_errors(10);
+        assert_eq!(options.max_errors(), Some(10));
+        assert_eq!(options.with_fail_fast(true).max_errors(), Some(1));
+        assert_eq!(ValidationOptions::new().max_errors(), None);
+    }
+
+    #[test]
+    fn defaults_keep_everything() {
+        let options = ValidationOptions::default();
+        assert!(options.sparql_constraints());
+        assert!(options.shapes().is_none());
+        assert!(!options.ignores_severity(&Severity::Info));
+        assert!(options.timeout().is_none());
+    }
+}
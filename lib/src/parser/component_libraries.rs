@@ -0,0 +1,105 @@
+//! Custom constraint components kept in library graphs next to the shapes graph.
+//!
+//! Organizations ship reusable SHACL-SPARQL constraint components as a graph of their own.
+//! The parser only discovers components in the shapes graph, so the definition of every
+//! `sh:ConstraintComponent` of a library graph is copied into it before parsing. The rest
+//! of the library, such as shapes it uses for its own tests, stays in the library graph
+//! and is not validated.
+
+use crate::named_nodes::{RDF, SHACL};
+use oxigraph::model::{
+    BlankNode, GraphNameRef, NamedNode, NamedOrBlankNode, Quad, QuadRef, Term, TermRef,
+};
+use oxigraph::store::Store;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+/// Copies the components declared in `library` into `shapes_graph`, with everything their
+/// definitions reach in the library graph: parameters, validators, message and prefix
+/// declarations. Returns the IRIs of the copied components.
+///
+/// Blank nodes get fresh identifiers, as when merging shapes sources. Fails when the
+/// library declares no component, which usually means the wrong graph was given.
+pub(crate) fn merge_component_library(
+    store: &Store,
+    shapes_graph: &NamedNode,
+    library: &NamedNode,
+) -> Result<Vec<NamedNode>, Box<dyn Error>> {
+    let rdf = RDF::get();
+    let source = GraphNameRef::NamedNode(library.as_ref());
+    let mut components: Vec<NamedNode> = store
+        .quads_for_pattern(
+            None,
+            Some(rdf.type_),
+            Some(SHACL::get().constraint_component.into()),
+            Some(source),
+        )
+        .filter_map(|quad| match quad.ok()?.subject {
+            NamedOrBlankNode::NamedNode(component) => Some(component),
+            NamedOrBlankNode::BlankNode(_) => None,
+        })
+        .collect();
+    if components.is_empty() {
+        return Err(format!(
+            "Component library {} declares no sh:ConstraintComponent",
+            library
+        )
+        .into());
+    }
+    components.sort();
+    components.dedup();
+
+    // Everything the definitions reach, except the classes they are typed with.
+    let mut reached: HashSet<NamedOrBlankNode> = components
+        .iter()
+        .cloned()
+        .map(NamedOrBlankNode::NamedNode)
+        .collect();
+    let mut pending: Vec<NamedOrBlankNode> = reached.iter().cloned().collect();
+    let mut quads: Vec<Quad> = Vec::new();
+    while let Some(subject) = pending.pop() {
+        for quad in store.quads_for_pattern(Some(subject.as_ref()), None, None, Some(source)) {
+            let quad = quad?;
+            if quad.predicate != rdf.type_ {
+                let next = match quad.object.as_ref() {
+                    TermRef::BlankNode(node) => Some(NamedOrBlankNode::from(node.into_owned())),
+                    TermRef::NamedNode(node) => store
+                        .quads_for_pattern(Some(node.into()), None, None, Some(source))
+                        .next()
+                        .is_some()
+                        .then(|| NamedOrBlankNode::from(node.into_owned())),
+                    _ => None,
+                };
+                if let Some(next) = next {
+                    if reached.insert(next.clone()) {
+                        pending.push(next);
+                    }
+                }
+            }
+            quads.push(quad);
+        }
+    }
+
+    let target = GraphNameRef::NamedNode(shapes_graph.as_ref());
+    let mut renamed: HashMap<BlankNode, BlankNode> = HashMap::new();
+    let mut rename = |node: &BlankNode| renamed.entry(node.clone()).or_default().clone();
+    let mut transaction = store.start_transaction()?;
+    for quad in &quads {
+        let subject: NamedOrBlankNode = match &quad.subject {
+            NamedOrBlankNode::BlankNode(node) => rename(node).into(),
+            other => other.clone(),
+        };
+        let object: Term = match &quad.object {
+            Term::BlankNode(node) => rename(node).into(),
+            other => other.clone(),
+        };
+        transaction.insert(QuadRef::new(
+            subject.as_ref(),
+            quad.predicate.as_ref(),
+            object.as_ref(),
+            target,
+        ));
+    }
+    transaction.commit()?;
+    Ok(components)
+}
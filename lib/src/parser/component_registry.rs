@@ -1,11 +1,9 @@
 #![allow(clippy::too_many_arguments)]
 
-use super::{
-    components::ensure_node_shape, components::resolve_shape_reference, parse_rdf_list,
-    ParsingContext,
-};
+use super::{components::resolve_shape_reference, parse_rdf_list, ParsingContext};
 use crate::model::components::ComponentDescriptor;
-use crate::named_nodes::SHACL;
+use crate::model::normalize::normalize_term;
+use crate::named_nodes::{SHACL, SHRS};
 use crate::types::{ComponentID, ID};
 use oxigraph::model::{Literal, NamedNode, NamedNodeRef, Term};
 use std::collections::{HashMap, HashSet};
@@ -32,6 +30,9 @@ pub(crate) static COMPONENT_REGISTRY: &[ComponentRegistryEntry] = &[
     ComponentRegistryEntry {
         apply: handle_datatype_constraint,
     },
+    ComponentRegistryEntry {
+        apply: handle_datatype_in_constraint,
+    },
     ComponentRegistryEntry {
         apply: handle_node_kind_constraint,
     },
@@ -188,6 +189,40 @@ fn handle_datatype_constraint(
     Ok(())
 }
 
+fn handle_datatype_in_constraint(
+    _shacl: &SHACL,
+    shape_term: &Term,
+    context: &mut ParsingContext,
+    _unique_lang: &HashMap<Term, String>,
+    pred_obj_pairs: &HashMap<NamedNode, Vec<Term>>,
+    processed: &mut HashSet<NamedNode>,
+    descriptors: &mut HashMap<ComponentID, ComponentDescriptor>,
+    _is_property_shape: bool,
+) -> Result<(), String> {
+    let predicate = owned_predicate(SHRS::get().datatype_in);
+    if let Some(terms) = pred_obj_pairs.get(&predicate) {
+        processed.insert(predicate.clone());
+        for list_head in terms {
+            let datatypes = parse_rdf_list(context, list_head.clone());
+            if datatypes.is_empty() {
+                return Err(format!(
+                    "Shape {} has an empty {} list",
+                    shape_term, predicate
+                ));
+            }
+            if let Some(bad) = datatypes.iter().find(|t| !matches!(t, Term::NamedNode(_))) {
+                return Err(format!(
+                    "Shape {} lists {} in {}, but datatypes must be IRIs",
+                    shape_term, bad, predicate
+                ));
+            }
+            let component_id = context.get_or_create_component_id(list_head.clone());
+            descriptors.insert(component_id, ComponentDescriptor::DatatypeIn { datatypes });
+        }
+    }
+    Ok(())
+}
+
 fn handle_node_kind_constraint(
     shacl: &SHACL,
     _shape_term: &Term,
@@ -233,10 +268,12 @@ fn handle_node_constraint(
     if let Some(terms) = pred_obj_pairs.get(&predicate) {
         processed.insert(predicate.clone());
         for term in terms {
-            let target_shape_id = ensure_node_shape(context, term.clone(), unique_lang)?;
+            let target_shape_id = resolve_shape_reference(context, term, shape_term, unique_lang)?;
+            // Template references resolve to a distinct shape per owner, so key on the
+            // resolved shape as well as the referenced term.
             let key = Term::Literal(Literal::new_simple_literal(format!(
-                "NodeConstraint:{}",
-                term
+                "NodeConstraint:{}@{}",
+                term, target_shape_id
             )));
             insert_descriptor(
                 context,
@@ -255,8 +292,8 @@ fn handle_node_constraint(
         for term in terms {
             let target_shape_id = resolve_shape_reference(context, term, shape_term, unique_lang)?;
             let key = Term::Literal(Literal::new_simple_literal(format!(
-                "ShapeConstraint:{}",
-                term
+                "ShapeConstraint:{}@{}",
+                term, target_shape_id
             )));
             insert_descriptor(
                 context,
@@ -1083,7 +1120,10 @@ fn handle_in_constraint(
     if let Some(terms) = pred_obj_pairs.get(&predicate) {
         processed.insert(predicate.clone());
         if let Some(list_head) = terms.first() {
-            let values = parse_rdf_list(context, list_head.clone());
+            let values = parse_rdf_list(context, list_head.clone())
+                .into_iter()
+                .map(normalize_term)
+                .collect();
             let component_id = context.get_or_create_component_id(list_head.clone());
             descriptors.insert(component_id, ComponentDescriptor::In { values });
         }
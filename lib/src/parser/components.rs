@@ -10,12 +10,15 @@ use super::{
 };
 use crate::model::components::sparql::CustomConstraintComponentDefinition;
 use crate::model::components::ComponentDescriptor;
+use crate::model::normalize::normalize_term;
 use crate::model::templates::{
     ComponentTemplateDefinition, PrefixDeclaration, ShapeTemplateDefinition, TemplateParameter,
     TemplateValidators,
 };
 use crate::named_nodes::{RDF, RDFS, SHACL};
 use crate::types::{ComponentID, ID};
+use crate::unsupported;
+use log::warn;
 
 type CustomComponentMaps = (
     HashMap<NamedNode, CustomConstraintComponentDefinition>,
@@ -32,7 +35,7 @@ pub(crate) fn parse_components(
     is_property_shape: bool,
 ) -> Result<HashMap<ComponentID, ComponentDescriptor>, String> {
     let mut descriptors = HashMap::new();
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     let shape_ref = shape_term.as_ref();
 
     let pred_obj_pairs: HashMap<NamedNode, Vec<Term>> = context
@@ -45,7 +48,9 @@ pub(crate) fn parse_components(
         )
         .filter_map(Result::ok)
         .fold(HashMap::new(), |mut acc, quad| {
-            acc.entry(quad.predicate).or_default().push(quad.object);
+            acc.entry(quad.predicate)
+                .or_default()
+                .push(normalize_term(quad.object));
             acc
         });
 
@@ -53,7 +58,7 @@ pub(crate) fn parse_components(
 
     for entry in COMPONENT_REGISTRY {
         (entry.apply)(
-            &shacl,
+            shacl,
             shape_term,
             context,
             unique_lang_lexicals,
@@ -78,12 +83,24 @@ pub(crate) fn parse_components(
         }
     }
 
-    if context.features.enable_af {
-        register_shape_templates(context)?;
+    for feature in unsupported::detect(shape_term, pred_obj_pairs.keys(), &processed_predicates) {
+        if !context.unsupported_features.contains(&feature) {
+            warn!("{}", feature);
+            context.unsupported_features.push(feature);
+        }
+    }
 
-        let (custom_component_defs, param_to_component) =
+    if context.features.enable_af {
+        let (mut custom_component_defs, param_to_component) =
             parse_custom_constraint_components(context)?;
         register_component_templates(context, &custom_component_defs)?;
+        // Templates are registered from the definitions themselves, so link them back once
+        // registration is complete to keep the first parsed shape consistent with later ones.
+        for (cc_iri, definition) in custom_component_defs.iter_mut() {
+            if definition.template.is_none() {
+                definition.template = context.component_templates.get(cc_iri).cloned();
+            }
+        }
 
         let mut shape_predicates: HashSet<NamedNode> = pred_obj_pairs.keys().cloned().collect();
         for p in processed_predicates {
@@ -103,10 +120,11 @@ pub(crate) fn parse_components(
                 let mut parameter_values = HashMap::new();
 
                 for param in &cc_def.parameters {
+                    let default_values = cc_def.parameter_default_values(&param.path);
                     if let Some(values) = pred_obj_pairs.get(&param.path) {
                         parameter_values.insert(param.path.clone(), values.clone());
-                    } else if !param.default_values.is_empty() {
-                        parameter_values.insert(param.path.clone(), param.default_values.clone());
+                    } else if !default_values.is_empty() {
+                        parameter_values.insert(param.path.clone(), default_values.to_vec());
                     } else if !param.optional {
                         has_all_mandatory = false;
                         break;
@@ -114,34 +132,38 @@ pub(crate) fn parse_components(
                 }
 
                 if has_all_mandatory {
-                    let mut param_entries: Vec<String> = cc_def
-                        .parameters
-                        .iter()
-                        .map(|param| {
-                            let mut values: Vec<String> = parameter_values
-                                .get(&param.path)
-                                .map(|vals| vals.iter().map(|v| v.to_string()).collect())
-                                .unwrap_or_else(|| vec!["<none>".to_string()]);
-                            values.sort();
-                            format!("{}={}", param.path.as_str(), values.join(","))
-                        })
-                        .collect();
-                    param_entries.sort();
-                    let component_key = format!(
-                        "CustomConstraint:{}|{}",
-                        cc_iri.as_str(),
-                        param_entries.join("|")
-                    );
-                    let component_id = context.get_or_create_component_id(Term::Literal(
-                        Literal::new_simple_literal(component_key),
-                    ));
-                    descriptors.insert(
-                        component_id,
-                        ComponentDescriptor::Custom {
-                            definition: Box::new(cc_def.clone()),
-                            parameter_values,
-                        },
-                    );
+                    // Each combination of parameter values is a separate constraint, so a
+                    // parameter given several times yields one component per value.
+                    for combination in parameter_value_combinations(cc_def, &parameter_values) {
+                        let mut param_entries: Vec<String> = cc_def
+                            .parameters
+                            .iter()
+                            .map(|param| {
+                                let mut values: Vec<String> = combination
+                                    .get(&param.path)
+                                    .map(|vals| vals.iter().map(|v| v.to_string()).collect())
+                                    .unwrap_or_else(|| vec!["<none>".to_string()]);
+                                values.sort();
+                                format!("{}={}", param.path.as_str(), values.join(","))
+                            })
+                            .collect();
+                        param_entries.sort();
+                        let component_key = format!(
+                            "CustomConstraint:{}|{}",
+                            cc_iri.as_str(),
+                            param_entries.join("|")
+                        );
+                        let component_id = context.get_or_create_component_id(Term::Literal(
+                            Literal::new_simple_literal(component_key),
+                        ));
+                        descriptors.insert(
+                            component_id,
+                            ComponentDescriptor::Custom {
+                                definition: Box::new(cc_def.clone()),
+                                parameter_values: combination,
+                            },
+                        );
+                    }
                 }
             }
         }
@@ -150,12 +172,40 @@ pub(crate) fn parse_components(
     Ok(descriptors)
 }
 
+/// Expands multi-valued parameters into the cross product of single-valued bindings,
+/// following the declaration order of the component's parameters.
+fn parameter_value_combinations(
+    definition: &CustomConstraintComponentDefinition,
+    parameter_values: &HashMap<NamedNode, Vec<Term>>,
+) -> Vec<HashMap<NamedNode, Vec<Term>>> {
+    let mut combinations = vec![HashMap::new()];
+    for param in &definition.parameters {
+        let Some(values) = parameter_values.get(&param.path) else {
+            continue;
+        };
+        let mut sorted_values = values.clone();
+        sorted_values.sort_by_key(|value| value.to_string());
+        sorted_values.dedup();
+        combinations = combinations
+            .into_iter()
+            .flat_map(|combination| {
+                sorted_values.iter().map(move |value| {
+                    let mut next = combination.clone();
+                    next.insert(param.path.clone(), vec![value.clone()]);
+                    next
+                })
+            })
+            .collect();
+    }
+    combinations
+}
+
 fn validate_sparql_constraint_node(
     context: &ParsingContext,
     constraint_term: &Term,
     is_property_shape: bool,
 ) -> Result<(), String> {
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     let subject_node = constraint_term
         .as_ref()
         .try_to_subject_ref()
@@ -254,12 +304,31 @@ fn parse_custom_constraint_components(
         .map_err(|e| format!("Error parsing custom constraint components: {}", e))
 }
 
+/// Parses every custom constraint component declared in the shapes graph, whether or not
+/// a shape uses it, sorted by IRI.
+pub(crate) fn parse_declared_custom_components(
+    context: &ParsingContext,
+) -> Result<Vec<CustomConstraintComponentDefinition>, String> {
+    let (definitions, _) = parse_custom_constraint_components(context)?;
+    let mut definitions: Vec<CustomConstraintComponentDefinition> = definitions
+        .into_values()
+        .map(|mut definition| {
+            if definition.template.is_none() {
+                definition.template = context.component_templates.get(&definition.iri).cloned();
+            }
+            definition
+        })
+        .collect();
+    definitions.sort_by(|a, b| a.iri.cmp(&b.iri));
+    Ok(definitions)
+}
+
 fn register_component_templates(
     context: &mut ParsingContext,
     definitions: &HashMap<NamedNode, CustomConstraintComponentDefinition>,
 ) -> Result<(), String> {
-    let shacl = SHACL::new();
-    let rdfs = RDFS::new();
+    let shacl = SHACL::get();
+    let rdfs = RDFS::get();
 
     for (template_iri, definition) in definitions {
         if context.component_templates.contains_key(template_iri) {
@@ -269,7 +338,7 @@ fn register_component_templates(
         let template_term = Term::NamedNode(template_iri.clone());
         let label = literal_for_predicate(context, &template_term, rdfs.label);
         let comment = literal_for_predicate(context, &template_term, rdfs.comment);
-        let prefix_declarations = collect_prefix_declarations(context, &template_term, &shacl);
+        let prefix_declarations = collect_prefix_declarations(context, &template_term, shacl);
 
         let mut ignored_predicates = vec![
             shacl.parameter.into_owned(),
@@ -327,10 +396,10 @@ fn register_component_templates(
     Ok(())
 }
 
-fn register_shape_templates(context: &mut ParsingContext) -> Result<(), String> {
-    let shacl = SHACL::new();
-    let rdfs = RDFS::new();
-    let rdf = RDF::new();
+pub(crate) fn register_shape_templates(context: &mut ParsingContext) -> Result<(), String> {
+    let shacl = SHACL::get();
+    let rdfs = RDFS::get();
+    let rdf = RDF::get();
 
     let mut seen = HashSet::new();
 
@@ -361,8 +430,8 @@ fn register_shape_templates(context: &mut ParsingContext) -> Result<(), String>
 
         let label = literal_for_predicate(context, &template_term, rdfs.label);
         let comment = literal_for_predicate(context, &template_term, rdfs.comment);
-        let prefix_declarations = collect_prefix_declarations(context, &template_term, &shacl);
-        let parameters = collect_template_parameters(context, &template_term, &shacl)?;
+        let prefix_declarations = collect_prefix_declarations(context, &template_term, shacl);
+        let parameters = collect_template_parameters(context, &template_term, shacl)?;
         let body = literal_or_term(context, &template_term, shacl.shape_prop)
             .unwrap_or_else(|| template_term.clone());
         let mut ignored = vec![
@@ -625,8 +694,18 @@ fn clone_template_body(
     queue.push_back(root.clone());
 
     let graph_name = GraphName::NamedNode(context.shape_graph_iri.clone());
-    let shacl = SHACL::new();
-    let rdf = RDF::new();
+    let shacl = SHACL::get();
+    let rdf = RDF::get();
+    // Blank nodes in the shapes graph are skolemized on load, so nested body nodes may
+    // appear as skolem IRIs and must be copied just like blank nodes.
+    let skolem_base = context
+        .features
+        .shape_skolem_base_for(&context.shape_graph_iri);
+    let is_body_node = |term: &Term| match term {
+        Term::BlankNode(_) => true,
+        Term::NamedNode(nn) => nn.as_str().starts_with(&skolem_base),
+        _ => false,
+    };
 
     while let Some(current) = queue.pop_front() {
         let subject_ref = to_subject_ref(current.as_ref())?;
@@ -647,19 +726,24 @@ fn clone_template_body(
             .filter_map(Result::ok)
         {
             let predicate = quad.predicate;
-            let mut object = quad.object;
+            // A template used as its own body keeps its parameter declarations out of
+            // the instantiated shape.
+            if current == *root && predicate == shacl.parameter {
+                continue;
+            }
 
-            if let Term::NamedNode(nn) = &object {
-                if let Some(values) = bindings.get(nn) {
-                    if predicate == shacl.path && !values.is_empty() {
-                        object = values[0].clone();
+            // Parameters are referenced in the body by their path IRI. `sh:path` takes a
+            // single value; any other position receives every bound value.
+            let new_objects: Vec<Term> = match quad.object {
+                Term::NamedNode(nn) if bindings.contains_key(&nn) => {
+                    let values = &bindings[&nn];
+                    if predicate == shacl.path {
+                        values.iter().take(1).cloned().collect()
+                    } else {
+                        values.clone()
                     }
                 }
-            }
-
-            let new_object = match object.clone() {
-                Term::BlankNode(bn) => {
-                    let original = Term::BlankNode(bn);
+                original if is_body_node(&original) => {
                     let is_new = !map.contains_key(&original);
                     let entry = map
                         .entry(original.clone())
@@ -668,38 +752,29 @@ fn clone_template_body(
                     if is_new {
                         queue.push_back(original);
                     }
-                    entry
-                }
-                Term::NamedNode(nn) => {
-                    if let Some(values) = bindings.get(&nn) {
-                        if predicate == shacl.path && !values.is_empty() {
-                            values[0].clone()
-                        } else {
-                            Term::NamedNode(nn)
-                        }
-                    } else {
-                        Term::NamedNode(nn)
-                    }
+                    vec![entry]
                 }
-                other => other,
+                other => vec![other],
             };
 
-            if predicate.as_str() == rdf.type_.as_str()
-                && matches!(new_object, Term::NamedNode(ref nn)
-                    if nn.as_str() == shacl.shape_class.as_str())
-            {
-                continue;
-            }
+            for new_object in new_objects {
+                if predicate.as_str() == rdf.type_.as_str()
+                    && matches!(new_object, Term::NamedNode(ref nn)
+                        if nn.as_str() == shacl.shape_class.as_str())
+                {
+                    continue;
+                }
 
-            context
-                .store
-                .insert(&Quad::new(
-                    new_subject.clone(),
-                    predicate.clone(),
-                    new_object.clone(),
-                    graph_name.clone(),
-                ))
-                .map_err(|e| e.to_string())?;
+                context
+                    .store
+                    .insert(&Quad::new(
+                        new_subject.clone(),
+                        predicate.clone(),
+                        new_object,
+                        graph_name.clone(),
+                    ))
+                    .map_err(|e| e.to_string())?;
+            }
         }
     }
 
@@ -751,7 +826,7 @@ fn collect_component_extras(
         .map(|pred| pred.as_str().to_string())
         .collect();
 
-    let rdf = RDF::new();
+    let rdf = RDF::get();
 
     for quad in context
         .store
@@ -789,26 +864,50 @@ fn collect_prefix_declarations(
         Err(_) => return declarations,
     };
 
-    for quad in context
-        .store
-        .quads_for_pattern(
-            Some(subject_ref),
-            Some(shacl.declare),
-            None,
-            Some(context.shape_graph_iri_ref()),
-        )
-        .filter_map(Result::ok)
-    {
-        let decl_subject_term = match quad.object {
-            Term::NamedNode(_) | Term::BlankNode(_) => quad.object,
-            _ => continue,
+    // Declarations may be attached directly (`sh:declare`) or through the nodes
+    // referenced by `sh:prefixes`.
+    let mut declaring_subjects = vec![subject.clone()];
+    declaring_subjects.extend(
+        context
+            .store
+            .quads_for_pattern(
+                Some(subject_ref),
+                Some(shacl.prefixes),
+                None,
+                Some(context.shape_graph_iri_ref()),
+            )
+            .filter_map(Result::ok)
+            .map(|quad| quad.object),
+    );
+
+    for declaring_subject in &declaring_subjects {
+        let Ok(declaring_ref) = to_subject_ref(declaring_subject.as_ref()) else {
+            continue;
         };
+        for quad in context
+            .store
+            .quads_for_pattern(
+                Some(declaring_ref),
+                Some(shacl.declare),
+                None,
+                Some(context.shape_graph_iri_ref()),
+            )
+            .filter_map(Result::ok)
+        {
+            let decl_subject_term = match quad.object {
+                Term::NamedNode(_) | Term::BlankNode(_) => quad.object,
+                _ => continue,
+            };
 
-        let prefix = literal_for_predicate(context, &decl_subject_term, shacl.prefix);
-        let namespace = literal_for_predicate(context, &decl_subject_term, shacl.namespace);
+            let prefix = literal_for_predicate(context, &decl_subject_term, shacl.prefix);
+            let namespace = literal_for_predicate(context, &decl_subject_term, shacl.namespace);
 
-        if let (Some(prefix), Some(namespace)) = (prefix, namespace) {
-            declarations.push(PrefixDeclaration { prefix, namespace });
+            if let (Some(prefix), Some(namespace)) = (prefix, namespace) {
+                let declaration = PrefixDeclaration { prefix, namespace };
+                if !declarations.contains(&declaration) {
+                    declarations.push(declaration);
+                }
+            }
         }
     }
 
@@ -0,0 +1,189 @@
+//! Detection of shapes that the shapes graph and its imports define differently.
+//!
+//! Unless imports are merged into the shapes graph (see [`super::imports`]), only the shapes
+//! graph itself is parsed, so when a module in its `owl:imports` closure defines the same
+//! shape IRI with other constraints, that definition is silently ignored. Comparing the
+//! definitions surfaces such collisions between layered modules; with merged imports they
+//! are reported because the combined definition is rarely what either module intended.
+
+use crate::context::ParsingContext;
+use crate::named_nodes::OWL;
+use crate::types::DuplicateShapePolicy;
+use log::warn;
+use oxigraph::model::{GraphNameRef, NamedNode, NamedOrBlankNodeRef, Term, TermRef};
+use oxigraph::store::Store;
+use std::collections::{HashSet, VecDeque};
+
+/// Nesting depth up to which blank-node structures are compared.
+const MAX_DESCRIPTION_DEPTH: usize = 16;
+
+/// Compares every parsed IRI shape with its definitions in imported graphs and applies
+/// the configured policy to the conflicts.
+pub(crate) fn check_duplicate_shapes(context: &ParsingContext) -> Result<(), String> {
+    let imported = imported_graphs(context);
+    if imported.is_empty() {
+        return Ok(());
+    }
+
+    let skolem_base = context
+        .features
+        .shape_skolem_base_for(&context.shape_graph_iri);
+    let shape_terms: Vec<Term> = {
+        let node_lookup = context.nodeshape_id_lookup.borrow();
+        let prop_lookup = context.propshape_id_lookup.borrow();
+        let mut terms: Vec<Term> = context
+            .node_shapes
+            .keys()
+            .filter_map(|id| node_lookup.get_term(*id).cloned())
+            .chain(
+                context
+                    .prop_shapes
+                    .keys()
+                    .filter_map(|id| prop_lookup.get_term(*id).cloned()),
+            )
+            .filter(|term| matches!(term, Term::NamedNode(_)) && !is_skolem(term, &skolem_base))
+            .collect();
+        terms.sort_by_key(|term| term.to_string());
+        terms.dedup();
+        terms
+    };
+
+    let shapes_graph = context.shape_graph_iri_ref();
+    let mut conflicts = Vec::new();
+    for term in &shape_terms {
+        let Term::NamedNode(shape) = term else {
+            continue;
+        };
+        let subject = NamedOrBlankNodeRef::NamedNode(shape.as_ref());
+        let local = describe_subject(&context.store, subject, shapes_graph, &skolem_base, 0);
+        for graph in &imported {
+            let other = describe_subject(
+                &context.store,
+                subject,
+                GraphNameRef::NamedNode(graph.as_ref()),
+                &skolem_base,
+                0,
+            );
+            if other.is_empty() || other == local {
+                continue;
+            }
+            conflicts.push(if context.features.shapes_imports {
+                format!(
+                    "Shape {} is defined differently in shapes graph {} and imported graph {}; \
+                     the definitions are combined",
+                    shape, context.shape_graph_iri, graph
+                )
+            } else {
+                format!(
+                    "Shape {} is defined differently in shapes graph {} and imported graph {}; \
+                     only the definition in {} is used",
+                    shape, context.shape_graph_iri, graph, context.shape_graph_iri
+                )
+            });
+        }
+    }
+
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+    match context.features.duplicate_shape_policy {
+        DuplicateShapePolicy::Warn => {
+            for conflict in &conflicts {
+                warn!("{}", conflict);
+            }
+            Ok(())
+        }
+        DuplicateShapePolicy::Error => Err(conflicts.join("\n")),
+    }
+}
+
+/// Returns the graphs reachable from the shapes graph through `owl:imports`, excluding
+/// the shapes and data graphs themselves. Imports that were not loaded have no triples
+/// and therefore never conflict.
+fn imported_graphs(context: &ParsingContext) -> Vec<NamedNode> {
+    let owl = OWL::get();
+    let mut seen: HashSet<NamedNode> = HashSet::new();
+    let mut queue: VecDeque<NamedNode> = VecDeque::new();
+    seen.insert(context.shape_graph_iri.clone());
+    queue.push_back(context.shape_graph_iri.clone());
+    while let Some(graph) = queue.pop_front() {
+        for quad in context
+            .store
+            .quads_for_pattern(
+                None,
+                Some(owl.imports),
+                None,
+                Some(GraphNameRef::NamedNode(graph.as_ref())),
+            )
+            .filter_map(Result::ok)
+        {
+            if let Term::NamedNode(import) = quad.object {
+                if seen.insert(import.clone()) {
+                    queue.push_back(import);
+                }
+            }
+        }
+    }
+    seen.remove(&context.shape_graph_iri);
+    seen.remove(&context.data_graph_iri);
+    let mut graphs: Vec<NamedNode> = seen.into_iter().collect();
+    graphs.sort();
+    graphs
+}
+
+/// Describes the triples about `subject` in `graph` as a sorted list of
+/// `predicate object` strings. Blank nodes (and IRIs under `skolem_base` standing in for
+/// them) are described by their own triples, so structurally equal definitions compare equal
+/// regardless of node labels or skolemization.
+pub(super) fn describe_subject(
+    store: &Store,
+    subject: NamedOrBlankNodeRef<'_>,
+    graph: GraphNameRef<'_>,
+    skolem_base: &str,
+    depth: usize,
+) -> Vec<String> {
+    let mut entries: Vec<String> = store
+        .quads_for_pattern(Some(subject), None, None, Some(graph))
+        .filter_map(Result::ok)
+        .map(|quad| {
+            format!(
+                "{} {}",
+                quad.predicate,
+                describe_object(store, quad.object.as_ref(), graph, skolem_base, depth + 1)
+            )
+        })
+        .collect();
+    entries.sort();
+    entries
+}
+
+fn describe_object(
+    store: &Store,
+    object: TermRef<'_>,
+    graph: GraphNameRef<'_>,
+    skolem_base: &str,
+    depth: usize,
+) -> String {
+    let owned = object.into_owned();
+    let nested = match object {
+        TermRef::BlankNode(node) => Some(NamedOrBlankNodeRef::BlankNode(node)),
+        TermRef::NamedNode(node) if is_skolem(&owned, skolem_base) => {
+            Some(NamedOrBlankNodeRef::NamedNode(node))
+        }
+        _ => None,
+    };
+    match nested {
+        Some(subject) if depth < MAX_DESCRIPTION_DEPTH => {
+            format!(
+                "[{}]",
+                describe_subject(store, subject, graph, skolem_base, depth).join("; ")
+            )
+        }
+        Some(_) => "[...]".to_string(),
+        None => owned.to_string(),
+    }
+}
+
+fn is_skolem(term: &Term, skolem_base: &str) -> bool {
+    matches!(term, Term::NamedNode(node) if node.as_str().starts_with(skolem_base))
+}
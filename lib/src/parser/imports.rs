@@ -0,0 +1,83 @@
+//! Merging the `owl:imports` closure of the shapes graph into the shapes graph.
+//!
+//! SHACL processors may treat the graphs imported by the shapes graph as part of it, which
+//! modular shape libraries rely on. The parser only reads the shapes graph, so the imported
+//! triples are copied into it before parsing.
+
+use super::duplicates::describe_subject;
+use oxigraph::model::{
+    BlankNode, GraphNameRef, NamedNode, NamedOrBlankNode, NamedOrBlankNodeRef, Quad, QuadRef, Term,
+};
+use oxigraph::store::Store;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+/// Copies the triples of every graph in `imported` into `shapes_graph`.
+///
+/// Blank nodes get fresh identifiers, as when merging shapes sources. An IRI subject that
+/// an imported graph describes exactly as the shapes graph already does is not copied
+/// again, so a shape that several modules repeat verbatim is parsed once instead of
+/// contributing duplicate property shapes. IRIs under `skolem_base` are compared like the
+/// blank nodes they stand for.
+pub(crate) fn merge_imports(
+    store: &Store,
+    shapes_graph: &NamedNode,
+    imported: &[NamedNode],
+    skolem_base: &str,
+) -> Result<(), Box<dyn Error>> {
+    let target = GraphNameRef::NamedNode(shapes_graph.as_ref());
+    for graph in imported {
+        let source = GraphNameRef::NamedNode(graph.as_ref());
+        let quads: Vec<Quad> = store
+            .quads_for_pattern(None, None, None, Some(source))
+            .collect::<Result<_, _>>()?;
+
+        let subjects: HashSet<&NamedOrBlankNode> = quads.iter().map(|q| &q.subject).collect();
+        let mut skipped: HashSet<NamedOrBlankNode> = subjects
+            .into_iter()
+            .filter(|subject| match subject {
+                NamedOrBlankNode::NamedNode(node) => {
+                    let subject = NamedOrBlankNodeRef::NamedNode(node.as_ref());
+                    describe_subject(store, subject, source, skolem_base, 0)
+                        == describe_subject(store, subject, target, skolem_base, 0)
+                }
+                NamedOrBlankNode::BlankNode(_) => false,
+            })
+            .cloned()
+            .collect();
+        // Blank nodes only reachable through a skipped subject belong to its definition.
+        let mut pending: Vec<NamedOrBlankNode> = skipped.iter().cloned().collect();
+        while let Some(subject) = pending.pop() {
+            for quad in quads.iter().filter(|q| q.subject == subject) {
+                if let Term::BlankNode(node) = &quad.object {
+                    let node = NamedOrBlankNode::BlankNode(node.clone());
+                    if skipped.insert(node.clone()) {
+                        pending.push(node);
+                    }
+                }
+            }
+        }
+
+        let mut renamed: HashMap<BlankNode, BlankNode> = HashMap::new();
+        let mut rename = |node: &BlankNode| renamed.entry(node.clone()).or_default().clone();
+        let mut transaction = store.start_transaction()?;
+        for quad in quads.iter().filter(|q| !skipped.contains(&q.subject)) {
+            let subject: NamedOrBlankNode = match &quad.subject {
+                NamedOrBlankNode::BlankNode(node) => rename(node).into(),
+                other => other.clone(),
+            };
+            let object: Term = match &quad.object {
+                Term::BlankNode(node) => rename(node).into(),
+                other => other.clone(),
+            };
+            transaction.insert(QuadRef::new(
+                subject.as_ref(),
+                quad.predicate.as_ref(),
+                object.as_ref(),
+                target,
+            ));
+        }
+        transaction.commit()?;
+    }
+    Ok(())
+}
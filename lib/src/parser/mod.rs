@@ -1,12 +1,16 @@
+pub(crate) mod component_libraries;
 mod component_registry;
 mod components;
+mod duplicates;
+pub(crate) mod imports;
 mod rules;
 
 use crate::context::ParsingContext;
+use crate::model::components::ComponentDescriptor;
 use crate::named_nodes::{OWL, RDF, RDFS, SHACL};
 use crate::shape::{NodeShape, PropertyShape};
 use crate::types::{ComponentID, Path as PShapePath, PropShapeID, Severity, ID};
-use components::parse_components;
+use components::{parse_components, parse_declared_custom_components, register_shape_templates};
 use log::{debug, warn};
 use ontoenv::ontology::OntologyLocation;
 use oxigraph::io::{RdfFormat, RdfParser};
@@ -46,7 +50,7 @@ impl ToSubjectRef for TermRef<'_> {
 
 fn load_unique_lang_lexicals(context: &ParsingContext) -> HashMap<Term, String> {
     let mut map = HashMap::new();
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
 
     let mut candidate_paths: Vec<std::path::PathBuf> = Vec::new();
 
@@ -102,8 +106,8 @@ fn load_unique_lang_lexicals(context: &ParsingContext) -> HashMap<Term, String>
 pub(crate) fn run_parser(context: &mut ParsingContext) -> Result<(), String> {
     // parses the shape graph to get all of the shapes and components defined within
     let unique_lang_lexicals = load_unique_lang_lexicals(context);
-    let rdf = RDF::new();
-    let sh = SHACL::new();
+    let rdf = RDF::get();
+    let sh = SHACL::get();
     let shape_graph_name_ref = context.shape_graph_iri_ref();
     let property_shape_count = context
         .store
@@ -124,6 +128,10 @@ pub(crate) fn run_parser(context: &mut ParsingContext) -> Result<(), String> {
         "run_parser shape graph {} has {} property shape type triples (total across graphs {})",
         context.shape_graph_iri, property_shape_count, property_shape_total
     );
+    // Shape templates must be known before any shape that references them is parsed.
+    if context.features.enable_af {
+        register_shape_templates(context)?;
+    }
     let shapes = get_node_shapes(context);
     let skip_invalid = context.features.skip_invalid_rules;
 
@@ -158,6 +166,11 @@ pub(crate) fn run_parser(context: &mut ParsingContext) -> Result<(), String> {
             }
         }
     }
+    if context.features.enable_af {
+        context.custom_components = parse_declared_custom_components(context)?;
+    }
+    order_property_constraints(context);
+    duplicates::check_duplicate_shapes(context)?;
     eprintln!(
         "run_parser parsed node_shapes={} prop_shapes={}",
         context.node_shapes.len(),
@@ -167,8 +180,8 @@ pub(crate) fn run_parser(context: &mut ParsingContext) -> Result<(), String> {
 }
 
 fn get_property_shapes(context: &ParsingContext) -> Vec<Term> {
-    let rdf = RDF::new();
-    let sh = SHACL::new();
+    let rdf = RDF::get();
+    let sh = SHACL::get();
     let mut prop_shapes = HashSet::new();
     let shape_graph_name_ref = GraphNameRef::NamedNode(context.shape_graph_iri.as_ref());
 
@@ -195,6 +208,41 @@ fn get_property_shapes(context: &ParsingContext) -> Vec<Term> {
         prop_shapes.insert(quad.object); // quad.object is Term
     }
 
+    // - <pshape> sh:path ?, with its own target declaration. Such shapes are
+    //   validated standalone even when no node shape references them.
+    for target_predicate in [
+        sh.target,
+        sh.target_node,
+        sh.target_class,
+        sh.target_subjects_of,
+        sh.target_objects_of,
+    ] {
+        for quad in context
+            .store
+            .quads_for_pattern(
+                None,
+                Some(target_predicate),
+                None,
+                Some(shape_graph_name_ref),
+            )
+            .flatten()
+        {
+            let has_path = context
+                .store
+                .quads_for_pattern(
+                    Some(quad.subject.as_ref()),
+                    Some(sh.path),
+                    None,
+                    Some(shape_graph_name_ref),
+                )
+                .next()
+                .is_some();
+            if has_path {
+                prop_shapes.insert(quad.subject.into());
+            }
+        }
+    }
+
     prop_shapes.into_iter().collect()
 }
 
@@ -207,8 +255,8 @@ fn get_node_shapes(context: &ParsingContext) -> Vec<Term> {
     // - ? sh:or (list of <shape>)
     // - ? sh:and (list of <shape>)
     // - ? sh:xone (list of <shape>)
-    let rdf = RDF::new();
-    let shacl = SHACL::new();
+    let rdf = RDF::get();
+    let shacl = SHACL::get();
     let shape_graph_name_ref = GraphNameRef::NamedNode(context.shape_graph_iri.as_ref());
 
     // parse these out of the shape graph and return a vector of IDs
@@ -335,7 +383,14 @@ fn get_node_shapes(context: &ParsingContext) -> Vec<Term> {
         node_shapes.insert(quad.subject.into());
     }
 
-    node_shapes.into_iter().collect()
+    // Shape templates are only instantiated through references; never parse them directly.
+    node_shapes
+        .into_iter()
+        .filter(|term| match term {
+            Term::NamedNode(nn) => !context.shape_templates.contains_key(nn),
+            _ => true,
+        })
+        .collect()
 }
 
 pub(crate) fn parse_node_shape(
@@ -346,7 +401,7 @@ pub(crate) fn parse_node_shape(
     // Parses a shape from the shape graph and returns its ID.
     // Adds the shape to the node_shapes map.
     let id = context.get_or_create_node_id(shape_term.clone());
-    let sh = SHACL::new();
+    let sh = SHACL::get();
     let shape_ref = shape_term.as_ref();
 
     let subject: SubjectRef = shape_ref.to_subject_ref();
@@ -368,9 +423,9 @@ pub(crate) fn parse_node_shape(
     // check for implicit classes. If 'shape' is also a class (rdfs:Class or owl:Class)
     // then add a Target::Class for it.
     // use store.contains(quad) to check
-    let rdf = RDF::new();
-    let rdfs = RDFS::new();
-    let owl = OWL::new();
+    let rdf = RDF::get();
+    let rdfs = RDFS::get();
+    let owl = OWL::get();
     let is_rdfs_class = context
         .store
         .contains(QuadRef::new(
@@ -448,7 +503,7 @@ fn parse_property_shape(
 ) -> Result<PropShapeID, String> {
     debug!("parse_property_shape: {}", shape_term);
     let id = context.get_or_create_prop_id(shape_term.clone());
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     let shape_ref = shape_term.as_ref();
     let subject: SubjectRef = shape_ref.to_subject_ref();
     let ps_shape_graph_name = GraphName::NamedNode(context.shape_graph_iri.clone());
@@ -511,6 +566,7 @@ fn parse_property_shape(
     let severity = severity_term_opt.as_ref().and_then(Severity::from_term);
 
     let deactivated = shape_is_deactivated(context, subject, ps_shape_graph_name.as_ref());
+    let order = parse_shape_order(context, subject, ps_shape_graph_name.as_ref())?;
 
     let prop_shape = PropertyShape::new(
         id,
@@ -520,7 +576,8 @@ fn parse_property_shape(
         component_ids,
         severity,
         deactivated,
-    );
+    )
+    .with_order(order);
     let rule_ids = parse_rules_for_shape(context, &shape_term, unique_lang_lexicals)?;
     if !rule_ids.is_empty() {
         context.prop_shape_rules.insert(id, rule_ids);
@@ -529,12 +586,110 @@ fn parse_property_shape(
     Ok(id)
 }
 
+/// Reads a shape's `sh:order`, which must be a numeric literal.
+fn parse_shape_order(
+    context: &ParsingContext,
+    subject: SubjectRef,
+    graph: GraphNameRef,
+) -> Result<Option<f64>, String> {
+    let shacl = SHACL::get();
+    let order_term = context
+        .store
+        .quads_for_pattern(Some(subject), Some(shacl.order), None, Some(graph))
+        .filter_map(Result::ok)
+        .map(|quad| quad.object)
+        .next();
+    match order_term {
+        None => Ok(None),
+        Some(Term::Literal(lit)) => lit.value().parse::<f64>().map(Some).map_err(|e| {
+            format!(
+                "Failed to parse sh:order {} of shape {}: {}",
+                lit.value(),
+                subject,
+                e
+            )
+        }),
+        Some(other) => Err(format!(
+            "Shape {} has non-literal sh:order {}",
+            subject, other
+        )),
+    }
+}
+
+/// Reorders the `sh:property` constraints of every shape by the referenced property
+/// shapes' `sh:order` (unordered shapes last, then by shape term), so evaluation and the
+/// results it produces follow the declared order. Other constraints keep their positions.
+fn order_property_constraints(context: &mut ParsingContext) {
+    let sort_key = |id: &PropShapeID| {
+        let order = context.prop_shapes.get(id).and_then(|shape| shape.order());
+        let term = context
+            .propshape_id_lookup
+            .borrow()
+            .get_term(*id)
+            .map(|term| term.to_string())
+            .unwrap_or_default();
+        (order, term)
+    };
+    let reorder = |constraints: &[ComponentID]| {
+        let slots: Vec<(usize, PropShapeID)> = constraints
+            .iter()
+            .enumerate()
+            .filter_map(|(index, id)| match context.component_descriptors.get(id) {
+                Some(ComponentDescriptor::Property { shape }) => Some((index, *shape)),
+                _ => None,
+            })
+            .collect();
+        if slots.len() < 2 {
+            return None;
+        }
+        let mut ordered: Vec<(ComponentID, (Option<f64>, String))> = slots
+            .iter()
+            .map(|(index, shape)| (constraints[*index], sort_key(shape)))
+            .collect();
+        ordered.sort_by(|(_, (a_order, a_term)), (_, (b_order, b_term))| {
+            match (a_order, b_order) {
+                (Some(a), Some(b)) => a.total_cmp(b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }
+            .then_with(|| a_term.cmp(b_term))
+        });
+        let mut reordered = constraints.to_vec();
+        for ((index, _), (component_id, _)) in slots.iter().zip(ordered) {
+            reordered[*index] = component_id;
+        }
+        Some(reordered)
+    };
+
+    let node_updates: Vec<(ID, Vec<ComponentID>)> = context
+        .node_shapes
+        .iter()
+        .filter_map(|(id, shape)| reorder(shape.constraints()).map(|ordered| (*id, ordered)))
+        .collect();
+    let prop_updates: Vec<(PropShapeID, Vec<ComponentID>)> = context
+        .prop_shapes
+        .iter()
+        .filter_map(|(id, shape)| reorder(shape.constraints()).map(|ordered| (*id, ordered)))
+        .collect();
+    for (id, ordered) in node_updates {
+        if let Some(shape) = context.node_shapes.get_mut(&id) {
+            *shape.constraints_mut() = ordered;
+        }
+    }
+    for (id, ordered) in prop_updates {
+        if let Some(shape) = context.prop_shapes.get_mut(&id) {
+            *shape.constraints_mut() = ordered;
+        }
+    }
+}
+
 fn shape_is_deactivated(
     context: &ParsingContext,
     subject: SubjectRef,
     graph_name: GraphNameRef<'_>,
 ) -> bool {
-    let sh = SHACL::new();
+    let sh = SHACL::get();
     context
         .store
         .quads_for_pattern(Some(subject), Some(sh.deactivated), None, Some(graph_name))
@@ -563,8 +718,8 @@ pub(super) fn parse_shacl_path_recursive(
     context: &ParsingContext,
     path_term_ref: TermRef,
 ) -> Result<PShapePath, String> {
-    let shacl = SHACL::new();
-    let _rdf = RDF::new();
+    let shacl = SHACL::get();
+    let _rdf = RDF::get();
     let shape_graph_name_ref = context.shape_graph_iri_ref();
 
     // Check if this term directly encodes an RDF list for a sequence path.
@@ -679,7 +834,7 @@ pub(super) fn parse_shacl_path_recursive(
 /// Parses an RDF list starting from list_head_term (owned Term) and returns a Vec of owned Terms.
 pub(crate) fn parse_rdf_list(context: &ParsingContext, list_head_term: Term) -> Vec<Term> {
     let mut items: Vec<Term> = Vec::new();
-    let rdf = RDF::new();
+    let rdf = RDF::get();
     let mut current_term = list_head_term;
     let nil_term: Term = rdf.nil.into_owned().into(); // Convert NamedNodeRef to Term
     let shape_graph_name_ref = GraphNameRef::NamedNode(context.shape_graph_iri.as_ref());
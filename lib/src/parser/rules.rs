@@ -16,7 +16,7 @@ pub(crate) fn parse_rules_for_shape(
     owner_shape: &Term,
     unique_lang: &HashMap<Term, String>,
 ) -> Result<Vec<RuleID>, String> {
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     let shape_graph = GraphName::NamedNode(context.shape_graph_iri.clone());
     let mut rule_ids = Vec::new();
     let owner_ref = owner_shape.as_ref();
@@ -73,8 +73,8 @@ fn parse_rule(
         return Ok(rule_id);
     }
 
-    let shacl = SHACL::new();
-    let rdf = RDF::new();
+    let shacl = SHACL::get();
+    let rdf = RDF::get();
     let rule_term_ref = rule_term.as_ref();
     let subject_ref = rule_term_ref.to_subject_ref();
 
@@ -135,7 +135,7 @@ fn parse_triple_rule(
     owner_shape: &Term,
     unique_lang: &HashMap<Term, String>,
 ) -> Result<TripleRule, String> {
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     let rule_term_ref = rule_term.as_ref();
     let subject_ref = rule_term_ref.to_subject_ref();
 
@@ -197,7 +197,7 @@ fn parse_sparql_rule(
     owner_shape: &Term,
     unique_lang: &HashMap<Term, String>,
 ) -> Result<SparqlRule, String> {
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     let rule_term_ref = rule_term.as_ref();
     let subject_ref = rule_term_ref.to_subject_ref();
 
@@ -258,7 +258,7 @@ fn parse_triple_pattern_term(
     context: &mut ParsingContext,
     template: Term,
 ) -> Result<TriplePatternTerm, String> {
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     if let Term::NamedNode(nn) = template.clone() {
         if nn.as_str() == shacl.this.as_str() {
             return Ok(TriplePatternTerm::This);
@@ -282,7 +282,7 @@ fn parse_triple_pattern_term(
 }
 
 fn parse_blank_node_path(context: &ParsingContext, term_ref: TermRef) -> Result<Path, String> {
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     if let Some(quad) = context
         .store
         .quads_for_pattern(
@@ -309,7 +309,7 @@ fn collect_conditions(
     owner_shape: &Term,
     unique_lang: &HashMap<Term, String>,
 ) -> Result<Vec<RuleCondition>, String> {
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     let graph = context.shape_graph_iri_ref();
     let mut conditions = Vec::new();
     for quad in context
@@ -328,7 +328,7 @@ fn is_deactivated(
     rule_subject: NamedOrBlankNodeRef<'_>,
     graph: GraphNameRef<'_>,
 ) -> bool {
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     context
         .store
         .quads_for_pattern(
@@ -346,7 +346,7 @@ fn parse_order(
     rule_subject: NamedOrBlankNodeRef<'_>,
     graph: GraphNameRef<'_>,
 ) -> Result<Option<RuleOrder>, String> {
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     let order_term = context
         .store
         .quads_for_pattern(Some(rule_subject), Some(shacl.order), None, Some(graph))
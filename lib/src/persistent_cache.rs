@@ -0,0 +1,294 @@
+//! On-disk cache of focus nodes that conformed to a shape in an earlier run.
+//!
+//! A run with [`ValidationOptions::with_persistent_cache`](crate::ValidationOptions::with_persistent_cache)
+//! reads the cache file, skips every focus node an earlier run found conforming to a shape
+//! when nothing the outcome depends on has changed, and writes the entries of this run
+//! back. Nodes that did not conform are always validated again, since their results are
+//! needed for the report. Only a run that validated every shape over the whole data graph
+//! and completed drops the entries it did not revisit; partial runs, such as
+//! `Validator::validate_node`, `Validator::revalidate` or runs stopped early, keep them.
+//!
+//! An entry is the SHA-256 of the shapes graph (as canonical N-Quads), the shape, the focus
+//! node and the focus node's outgoing triples in the data graph. So that those triples are
+//! everything the outcome depends on, only shapes that look no further are cached: IRI-named
+//! shapes whose property shapes have predicate paths and whose constraints only inspect
+//! the focus node and its values (no `sh:class`, `sh:node`, logical, qualified, SPARQL or
+//! custom constraints). Blank focus nodes, and nodes with blank values, are relabelled on
+//! every load and never hit.
+
+use crate::canonicalization::{deskolemize_graph, to_canonical_nquads};
+use crate::context::{SourceShape, ValidationContext};
+use crate::model::components::ComponentDescriptor;
+use crate::shape::ShapeView;
+use crate::types::Path as PShapePath;
+use oxigraph::model::{Graph, GraphNameRef, NamedOrBlankNodeRef, Term, TripleRef};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+
+pub(crate) struct PersistentConformanceCache {
+    path: PathBuf,
+    shapes_hash: String,
+    /// Entries read from the file.
+    known: HashSet<String>,
+    /// Entries confirmed or added by this run; the file is rewritten with these.
+    current: HashSet<String>,
+    /// Whether each shape can be cached, decided on first use.
+    cacheable: HashMap<SourceShape, bool>,
+    pub(crate) hits: usize,
+}
+
+impl PersistentConformanceCache {
+    /// Reads the cache at `path`; a missing file is an empty cache.
+    pub(crate) fn open(path: &Path, context: &ValidationContext) -> Result<Self, String> {
+        let known = match fs::read_to_string(path) {
+            Ok(contents) => contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashSet::new(),
+            Err(e) => {
+                return Err(format!(
+                    "Failed to read conformance cache {}: {}",
+                    path.display(),
+                    e
+                ))
+            }
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            shapes_hash: shapes_graph_hash(context)?,
+            known,
+            current: HashSet::new(),
+            cacheable: HashMap::new(),
+            hits: 0,
+        })
+    }
+
+    /// Returns the entry for `focus_node` and the shape `source`, or `None` when the pair
+    /// cannot be cached.
+    pub(crate) fn key(
+        &mut self,
+        context: &ValidationContext,
+        source: &SourceShape,
+        focus_node: &Term,
+    ) -> Result<Option<String>, String> {
+        let cacheable = match self.cacheable.get(source) {
+            Some(cacheable) => *cacheable,
+            None => {
+                let cacheable = is_cacheable(context, source);
+                self.cacheable.insert(source.clone(), cacheable);
+                cacheable
+            }
+        };
+        let subject = match focus_node {
+            Term::NamedNode(node) if !context.is_data_skolem_iri(node.as_ref()) => {
+                NamedOrBlankNodeRef::NamedNode(node.as_ref())
+            }
+            _ => return Ok(None),
+        };
+        let Some(shape) = source.get_term(context).filter(|_| cacheable) else {
+            return Ok(None);
+        };
+
+        let mut hasher = Sha256::new();
+        for part in [
+            &self.shapes_hash,
+            &shape.to_string(),
+            &focus_node.to_string(),
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update(b"\n");
+        }
+        let mut triples = Vec::new();
+        for quad in context.model.store().quads_for_pattern(
+            Some(subject),
+            None,
+            None,
+            Some(context.data_graph_iri_ref()),
+        ) {
+            let quad = quad.map_err(|e| e.to_string())?;
+            if let Term::NamedNode(object) = &quad.object {
+                if context.is_data_skolem_iri(object.as_ref()) {
+                    return Ok(None);
+                }
+            }
+            triples.push(format!("{} {} .\n", quad.predicate, quad.object));
+        }
+        triples.sort();
+        for triple in triples {
+            hasher.update(triple.as_bytes());
+        }
+        Ok(Some(format!("{:x}", hasher.finalize())))
+    }
+
+    /// Returns whether an earlier run found the pair behind `key` conforming, keeping the
+    /// entry if so.
+    pub(crate) fn conforms(&mut self, key: &str) -> bool {
+        if self.known.contains(key) {
+            self.current.insert(key.to_string());
+            self.hits += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Records that the pair behind `key` conforms.
+    pub(crate) fn insert(&mut self, key: String) {
+        self.current.insert(key);
+    }
+
+    /// Rewrites the cache file with this run's entries. With `prune`, entries this run did
+    /// not revisit are dropped; otherwise they are kept as well.
+    pub(crate) fn finish(self, prune: bool) -> Result<(), String> {
+        let mut entries: Vec<String> = if prune {
+            self.current.into_iter().collect()
+        } else {
+            self.known.union(&self.current).cloned().collect()
+        };
+        entries.sort();
+        let mut contents = entries.join("\n");
+        contents.push('\n');
+        // Concurrent runs each write their own temporary file; the last rename wins.
+        let error = |e: std::io::Error| {
+            format!(
+                "Failed to write conformance cache {}: {}",
+                self.path.display(),
+                e
+            )
+        };
+        let mut file = NamedTempFile::new_in(parent_dir(&self.path)).map_err(error)?;
+        file.write_all(contents.as_bytes()).map_err(error)?;
+        file.persist(&self.path).map_err(|e| error(e.error))?;
+        Ok(())
+    }
+
+    /// Checks that the cache at `path` can be read and rewritten, without parsing the
+    /// shapes graph as [`Self::open`] does.
+    pub(crate) fn check(path: &Path) -> Result<(), String> {
+        match fs::read_to_string(path) {
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(format!(
+                    "Failed to read conformance cache {}: {}",
+                    path.display(),
+                    e
+                ))
+            }
+        }
+        NamedTempFile::new_in(parent_dir(path))
+            .map(|_| ())
+            .map_err(|e| {
+                format!(
+                    "Failed to write conformance cache {}: {}",
+                    path.display(),
+                    e
+                )
+            })
+    }
+}
+
+/// The directory holding `path`; a bare file name is in the working directory.
+fn parent_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+/// Hashes the shapes graph with its blank nodes restored, so reloading the same file gives
+/// the same hash.
+fn shapes_graph_hash(context: &ValidationContext) -> Result<String, String> {
+    let shapes_graph_iri = &context.model.shape_graph_iri;
+    let mut graph = Graph::new();
+    for quad in context.model.store().quads_for_pattern(
+        None,
+        None,
+        None,
+        Some(GraphNameRef::NamedNode(shapes_graph_iri.as_ref())),
+    ) {
+        let quad = quad.map_err(|e| e.to_string())?;
+        graph.insert(TripleRef::new(&quad.subject, &quad.predicate, &quad.object));
+    }
+    let graph = deskolemize_graph(
+        &graph,
+        &context
+            .model
+            .features
+            .shape_skolem_base_for(shapes_graph_iri),
+    );
+    let mut hasher = Sha256::new();
+    hasher.update(to_canonical_nquads(&graph).as_bytes());
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Whether a shape's outcome for a focus node depends only on the node's outgoing triples.
+fn is_cacheable(context: &ValidationContext, source: &SourceShape) -> bool {
+    let view = match source {
+        SourceShape::NodeShape(id) => ShapeView::node(&context.model, id),
+        SourceShape::PropertyShape(id) => ShapeView::property(&context.model, id),
+    };
+    let Some(view) = view else {
+        return false;
+    };
+    let named = match view.term() {
+        Term::NamedNode(node) => !context.is_shape_skolem_iri(node.as_ref()),
+        _ => false,
+    };
+    if !named {
+        return false;
+    }
+    match source {
+        SourceShape::NodeShape(_) => {
+            view.constraints().all(|(_, descriptor)| {
+                matches!(descriptor, ComponentDescriptor::Property { .. })
+                    || is_local_constraint(descriptor)
+            }) && view.property_shapes().iter().all(is_local_property_shape)
+        }
+        SourceShape::PropertyShape(_) => is_local_property_shape(&view),
+    }
+}
+
+/// Whether a property shape has a predicate path and only local constraints.
+fn is_local_property_shape(shape: &ShapeView<'_>) -> bool {
+    matches!(shape.path(), Some(PShapePath::Simple(Term::NamedNode(_))))
+        && shape
+            .constraints()
+            .all(|(_, descriptor)| is_local_constraint(descriptor))
+}
+
+/// Constraints that only inspect the value nodes themselves or other values of the focus
+/// node.
+fn is_local_constraint(descriptor: &ComponentDescriptor) -> bool {
+    matches!(
+        descriptor,
+        ComponentDescriptor::Datatype { .. }
+            | ComponentDescriptor::DatatypeIn { .. }
+            | ComponentDescriptor::NodeKind { .. }
+            | ComponentDescriptor::MinCount { .. }
+            | ComponentDescriptor::MaxCount { .. }
+            | ComponentDescriptor::MinExclusive { .. }
+            | ComponentDescriptor::MinInclusive { .. }
+            | ComponentDescriptor::MaxExclusive { .. }
+            | ComponentDescriptor::MaxInclusive { .. }
+            | ComponentDescriptor::MinLength { .. }
+            | ComponentDescriptor::MaxLength { .. }
+            | ComponentDescriptor::Pattern { .. }
+            | ComponentDescriptor::LanguageIn { .. }
+            | ComponentDescriptor::UniqueLang { .. }
+            | ComponentDescriptor::Equals { .. }
+            | ComponentDescriptor::Disjoint { .. }
+            | ComponentDescriptor::LessThan { .. }
+            | ComponentDescriptor::LessThanOrEquals { .. }
+            | ComponentDescriptor::Closed { .. }
+            | ComponentDescriptor::HasValue { .. }
+            | ComponentDescriptor::In { .. }
+    )
+}
@@ -0,0 +1,232 @@
+//! Prefix-to-namespace bindings used to shorten IRIs for display.
+//!
+//! A validator collects one [`PrefixMap`] from its shapes: the well-known SHACL, RDF, RDFS,
+//! XSD and OWL namespaces, the namespace maps OntoEnv gathered for each loaded ontology, and
+//! every `sh:declare` in the shapes graph. Graphviz shape labels, execution trace labels,
+//! `ValidationReport::dump` and the placeholders of `sh:message` templates all render IRIs
+//! through it, so the same IRI reads the same everywhere.
+
+use crate::context::format_term_for_label;
+use crate::named_nodes::SHACL;
+use ontoenv::api::OntoEnv;
+use oxigraph::model::{GraphNameRef, NamedNode, NamedOrBlankNodeRef, Term};
+use oxigraph::store::Store;
+use std::collections::BTreeMap;
+
+const WELL_KNOWN_PREFIXES: [(&str, &str); 5] = [
+    ("owl", "http://www.w3.org/2002/07/owl#"),
+    ("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+    ("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+    ("sh", "http://www.w3.org/ns/shacl#"),
+    ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+];
+
+/// An ordered set of `prefix -> namespace` bindings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrefixMap {
+    namespaces: BTreeMap<String, String>,
+}
+
+impl PrefixMap {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a map with the `owl`, `rdf`, `rdfs`, `sh` and `xsd` prefixes.
+    pub fn with_well_known() -> Self {
+        let mut map = Self::new();
+        for (prefix, namespace) in WELL_KNOWN_PREFIXES {
+            map.insert(prefix, namespace);
+        }
+        map
+    }
+
+    /// Binds `prefix` to `namespace`, returning the namespace it was bound to before.
+    pub fn insert(
+        &mut self,
+        prefix: impl Into<String>,
+        namespace: impl Into<String>,
+    ) -> Option<String> {
+        self.namespaces.insert(prefix.into(), namespace.into())
+    }
+
+    /// Returns the namespace bound to `prefix`.
+    pub fn namespace(&self, prefix: &str) -> Option<&str> {
+        self.namespaces.get(prefix).map(String::as_str)
+    }
+
+    /// Iterates over the bindings in prefix order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.namespaces
+            .iter()
+            .map(|(prefix, namespace)| (prefix.as_str(), namespace.as_str()))
+    }
+
+    /// Returns the number of bindings.
+    pub fn len(&self) -> usize {
+        self.namespaces.len()
+    }
+
+    /// Returns `true` if there are no bindings.
+    pub fn is_empty(&self) -> bool {
+        self.namespaces.is_empty()
+    }
+
+    /// Shortens `iri` to a prefixed name (`ex:Person`) using the longest matching
+    /// namespace. Returns `None` when no namespace matches or the remainder is not a plain
+    /// local name (it contains `/`, `#`, `?`, or ends with `.`).
+    pub fn shorten(&self, iri: &str) -> Option<String> {
+        self.namespaces
+            .iter()
+            .filter_map(|(prefix, namespace)| {
+                let local = iri.strip_prefix(namespace.as_str())?;
+                is_local_name(local).then_some((prefix, namespace.len(), local))
+            })
+            // Longest namespace wins; among equal namespaces, the first prefix.
+            .min_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)))
+            .map(|(prefix, _, local)| format!("{}:{}", prefix, local))
+    }
+
+    /// Expands a prefixed name (`ex:Person`) to an IRI.
+    pub fn expand(&self, prefixed_name: &str) -> Option<NamedNode> {
+        let (prefix, local) = prefixed_name.split_once(':')?;
+        let namespace = self.namespace(prefix)?;
+        NamedNode::new(format!("{}{}", namespace, local)).ok()
+    }
+
+    /// Renders `term` compactly: IRIs as prefixed names when possible and `<iri>` otherwise;
+    /// literals and blank nodes in N-Triples syntax.
+    pub fn compact(&self, term: &Term) -> String {
+        match term {
+            Term::NamedNode(node) => self
+                .shorten(node.as_str())
+                .unwrap_or_else(|| node.to_string()),
+            other => other.to_string(),
+        }
+    }
+
+    /// Short human-readable label for `term`: a prefixed name when possible, otherwise the
+    /// local name used by Graphviz output; literals render as their lexical form.
+    pub(crate) fn label(&self, term: &Term) -> String {
+        match term {
+            Term::NamedNode(node) => self
+                .shorten(node.as_str())
+                .unwrap_or_else(|| format_term_for_label(term)),
+            _ => format_term_for_label(term),
+        }
+    }
+
+    /// Collects the prefixes declared for a shapes graph: the well-known prefixes, the
+    /// namespace maps of every ontology in `env`, then each `sh:declare` in the shapes graph.
+    /// Later sources override earlier bindings of the same prefix.
+    pub(crate) fn for_shapes(store: &Store, env: &OntoEnv, shapes_graph: GraphNameRef<'_>) -> Self {
+        let mut map = Self::with_well_known();
+        let mut ontologies: Vec<_> = env.ontologies().values().collect();
+        ontologies.sort_by(|a, b| a.name().as_str().cmp(b.name().as_str()));
+        for ontology in ontologies {
+            for (prefix, namespace) in ontology.namespace_map() {
+                map.insert(prefix.clone(), namespace.clone());
+            }
+        }
+
+        let shacl = SHACL::get();
+        let object = |subject: NamedOrBlankNodeRef<'_>, predicate| {
+            store
+                .quads_for_pattern(Some(subject), Some(predicate), None, Some(shapes_graph))
+                .filter_map(Result::ok)
+                .find_map(|quad| match quad.object {
+                    Term::Literal(literal) => Some(literal.value().to_string()),
+                    Term::NamedNode(node) => Some(node.into_string()),
+                    Term::BlankNode(_) => None,
+                })
+        };
+        let mut declared: Vec<(String, String)> = store
+            .quads_for_pattern(None, Some(shacl.declare), None, Some(shapes_graph))
+            .filter_map(Result::ok)
+            .filter_map(|quad| {
+                let declaration: NamedOrBlankNodeRef<'_> = match &quad.object {
+                    Term::NamedNode(node) => node.as_ref().into(),
+                    Term::BlankNode(node) => node.as_ref().into(),
+                    Term::Literal(_) => return None,
+                };
+                Some((
+                    object(declaration, shacl.prefix)?,
+                    object(declaration, shacl.namespace)?,
+                ))
+            })
+            .collect();
+        declared.sort();
+        for (prefix, namespace) in declared {
+            map.insert(prefix, namespace);
+        }
+        map
+    }
+}
+
+fn is_local_name(local: &str) -> bool {
+    !local.ends_with('.')
+        && local
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxigraph::model::Literal;
+
+    fn example_map() -> PrefixMap {
+        let mut map = PrefixMap::with_well_known();
+        map.insert("ex", "http://example.org/");
+        map.insert("exb", "http://example.org/building/");
+        map
+    }
+
+    #[test]
+    fn shortens_with_the_longest_namespace() {
+        let map = example_map();
+        assert_eq!(
+            map.shorten("http://example.org/building/Room").as_deref(),
+            Some("exb:Room")
+        );
+        assert_eq!(
+            map.shorten("http://example.org/Room").as_deref(),
+            Some("ex:Room")
+        );
+        assert_eq!(
+            map.shorten("http://www.w3.org/ns/shacl#minCount")
+                .as_deref(),
+            Some("sh:minCount")
+        );
+    }
+
+    #[test]
+    fn leaves_iris_without_a_plain_local_name() {
+        let map = example_map();
+        assert_eq!(map.shorten("http://example.org/a/b/c"), None);
+        assert_eq!(map.shorten("http://example.org/x?y=1"), None);
+        assert_eq!(map.shorten("http://other.org/Room"), None);
+        let term: Term = NamedNode::new_unchecked("http://other.org/Room").into();
+        assert_eq!(map.compact(&term), "<http://other.org/Room>");
+        assert_eq!(map.label(&term), "Room");
+    }
+
+    #[test]
+    fn expands_prefixed_names() {
+        let map = example_map();
+        assert_eq!(
+            map.expand("exb:Room"),
+            Some(NamedNode::new_unchecked("http://example.org/building/Room"))
+        );
+        assert_eq!(map.expand("nope:Room"), None);
+    }
+
+    #[test]
+    fn literals_keep_their_syntax_when_compacted() {
+        let map = example_map();
+        let literal: Term = Literal::new_simple_literal("42").into();
+        assert_eq!(map.compact(&literal), "\"42\"");
+        assert_eq!(map.label(&literal), "42");
+    }
+}
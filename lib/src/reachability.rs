@@ -0,0 +1,154 @@
+//! Which shapes can be reached from a targeted shape.
+//!
+//! Validation only starts at shapes with targets; every other shape runs because a
+//! targeted shape references it through `sh:property`, `sh:node`, `sh:qualifiedValueShape`
+//! or the logical constraints (`sh:not`, `sh:and`, `sh:or`, `sh:xone`). A shape that no
+//! such chain reaches never runs, which usually points at a missing target or a typo in
+//! a reference.
+
+use crate::shape::ShapeView;
+use oxigraph::model::Term;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+/// Reachability of one node or property shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeReachability {
+    /// The shape's IRI or blank node.
+    pub shape: Term,
+    pub is_property_shape: bool,
+    /// Whether the shape declares targets of its own.
+    pub targeted: bool,
+    /// A shortest reference chain from a targeted shape to this shape, both inclusive.
+    /// `None` when the shape is unreachable; a single element for targeted shapes.
+    pub chain: Option<Vec<Term>>,
+}
+
+impl ShapeReachability {
+    pub fn is_reachable(&self) -> bool {
+        self.chain.is_some()
+    }
+}
+
+impl fmt::Display for ShapeReachability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.is_property_shape {
+            "property shape"
+        } else {
+            "node shape"
+        };
+        match &self.chain {
+            Some(_) if self.targeted => write!(f, "{} {}: targeted", kind, self.shape),
+            Some(chain) => {
+                let chain: Vec<String> = chain.iter().map(|term| term.to_string()).collect();
+                write!(
+                    f,
+                    "{} {}: reachable via {}",
+                    kind,
+                    self.shape,
+                    chain.join(" -> ")
+                )
+            }
+            None => write!(
+                f,
+                "{} {}: unreachable from any targeted shape",
+                kind, self.shape
+            ),
+        }
+    }
+}
+
+/// Computes reachability for every shape, in the order of `shapes`.
+///
+/// Deactivated shapes neither start a chain nor pass one on, since their references
+/// are never evaluated.
+pub(crate) fn shape_reachability(shapes: &[ShapeView<'_>]) -> Vec<ShapeReachability> {
+    let mut edges: HashMap<Term, Vec<Term>> = HashMap::new();
+    for shape in shapes {
+        if shape.is_deactivated() {
+            continue;
+        }
+        let referenced = edges.entry(shape.term()).or_default();
+        for (_, descriptor) in shape.constraints() {
+            referenced.extend(
+                shape
+                    .referenced_shapes(descriptor)
+                    .iter()
+                    .map(|target| target.term()),
+            );
+        }
+    }
+
+    // Breadth-first from all targeted shapes at once yields the shortest chain.
+    let mut parents: HashMap<Term, Option<Term>> = HashMap::new();
+    let mut queue: VecDeque<Term> = VecDeque::new();
+    for shape in shapes {
+        if !shape.is_deactivated() && !shape.targets().is_empty() {
+            parents.insert(shape.term(), None);
+            queue.push_back(shape.term());
+        }
+    }
+    while let Some(current) = queue.pop_front() {
+        for next in edges.get(&current).into_iter().flatten() {
+            if !edges.contains_key(next) || parents.contains_key(next) {
+                continue;
+            }
+            parents.insert(next.clone(), Some(current.clone()));
+            queue.push_back(next.clone());
+        }
+    }
+
+    shapes
+        .iter()
+        .map(|shape| {
+            let term = shape.term();
+            let chain = parents.contains_key(&term).then(|| {
+                let mut chain = vec![term.clone()];
+                while let Some(Some(parent)) = parents.get(chain.last().unwrap()) {
+                    chain.push(parent.clone());
+                }
+                chain.reverse();
+                chain
+            });
+            ShapeReachability {
+                targeted: !shape.targets().is_empty(),
+                is_property_shape: shape.is_property_shape(),
+                shape: term,
+                chain,
+            }
+        })
+        .collect()
+}
+
+/// Orders `shapes` so that every shape comes after the shapes it references, whether
+/// directly or through its property shapes and logical constraints. Validating in this
+/// order settles whether a focus node conforms to a referenced node shape before a
+/// referencing shape asks, so the answer can come from the conformance cache.
+///
+/// Shapes on a reference cycle cannot all come after each other; the cycle is broken at
+/// the reference leading back to a shape whose dependencies are still being ordered.
+/// Shapes without dependencies between them keep their order in `shapes`.
+pub(crate) fn dependency_order<'a>(shapes: &[ShapeView<'a>]) -> Vec<ShapeView<'a>> {
+    fn visit<'a>(
+        shape: ShapeView<'a>,
+        visited: &mut HashSet<Term>,
+        ordered: &mut Vec<ShapeView<'a>>,
+    ) {
+        if !visited.insert(shape.term()) {
+            return;
+        }
+        for (_, descriptor) in shape.constraints() {
+            for referenced in shape.referenced_shapes(descriptor) {
+                visit(referenced, visited, ordered);
+            }
+        }
+        ordered.push(shape);
+    }
+
+    let mut visited = HashSet::new();
+    let mut ordered = Vec::with_capacity(shapes.len());
+    for shape in shapes {
+        visit(*shape, &mut visited, &mut ordered);
+    }
+    ordered
+}
@@ -0,0 +1,292 @@
+//! Redaction of data values in validation reports.
+//!
+//! Results copy focus nodes and value nodes from the data graph verbatim, and messages
+//! often repeat them, so a report of personal data is personal data itself. A report built
+//! with [`ValidationReport::with_redaction`](crate::ValidationReport::with_redaction)
+//! replaces those values in every rendering: the report graph, typed results and `dump`.
+//! Messages are rewritten by replacing the focus node and value where they repeat them;
+//! any other data a message mentions, such as a value a SPARQL constraint binds into its
+//! message, is not known to be data and is kept.
+//! Shapes, paths, constraint components and severities are kept, so the report still says
+//! which constraint failed where and how often.
+//!
+//! Literals keep their datatype and language tag; only their lexical form is replaced.
+//! Blank nodes are kept, their labels carry no data. IRIs are kept unless
+//! [`Redaction::with_iris`] is set.
+
+use crate::named_nodes::SHACL;
+use crate::prefix_map::PrefixMap;
+use crate::report::{FocusNodeContext, ValidationResult};
+use oxigraph::model::{Graph, Literal, NamedNode, NamedOrBlankNode, Term, Triple};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Namespace of the IRIs that stand in for redacted IRIs.
+pub const REDACTED_IRI_NAMESPACE: &str = "urn:shacl-rs:redacted:";
+
+/// Lexical form of masked literals.
+const MASK: &str = "[redacted]";
+
+/// How the data values of a report are redacted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redaction {
+    hash: bool,
+    iris: bool,
+    salt: String,
+}
+
+impl Redaction {
+    /// Replaces the lexical form of every literal with `[redacted]`.
+    pub fn mask() -> Self {
+        Self {
+            hash: false,
+            iris: false,
+            salt: String::new(),
+        }
+    }
+
+    /// Replaces the lexical form of every literal with `sha256:<hex>`, the SHA-256 digest
+    /// of the salt followed by the lexical form. Equal values get equal hashes, so results
+    /// about the same value can still be grouped, and a data owner can find the value a
+    /// hash stands for.
+    pub fn hash() -> Self {
+        Self {
+            hash: true,
+            ..Self::mask()
+        }
+    }
+
+    /// Also replaces IRIs with `urn:shacl-rs:redacted:<hex>`, the digest of the salt
+    /// followed by the IRI, in both modes, so distinct focus nodes stay distinct.
+    pub fn with_iris(mut self, iris: bool) -> Self {
+        self.iris = iris;
+        self
+    }
+
+    /// Sets the salt hashed in front of each value. Without one, short or guessable values
+    /// can be recovered by hashing candidates.
+    pub fn with_salt(mut self, salt: impl Into<String>) -> Self {
+        self.salt = salt.into();
+        self
+    }
+
+    /// Returns the term that stands in for the data-graph term `term`.
+    pub fn term(&self, term: &Term) -> Term {
+        match term {
+            Term::Literal(literal) => {
+                let lexical = if self.hash {
+                    format!("sha256:{}", self.digest(literal.value()))
+                } else {
+                    MASK.to_string()
+                };
+                match literal.language() {
+                    Some(language) => {
+                        Literal::new_language_tagged_literal_unchecked(lexical, language).into()
+                    }
+                    None => Literal::new_typed_literal(lexical, literal.datatype()).into(),
+                }
+            }
+            Term::NamedNode(node) if self.iris => NamedNode::new_unchecked(format!(
+                "{}{}",
+                REDACTED_IRI_NAMESPACE,
+                self.digest(node.as_str())
+            ))
+            .into(),
+            other => other.clone(),
+        }
+    }
+
+    /// Replaces every occurrence of `terms` in `text`: their N-Triples form, lexical form
+    /// or IRI, and prefixed name. Occurrences are replaced wherever they appear, which can
+    /// hide more than the value itself. Only `terms` are replaced: other data a message
+    /// repeats, such as values a SPARQL constraint binds or the value of another property,
+    /// is left as it is.
+    pub(crate) fn text(&self, text: &str, terms: &[&Term], prefixes: &PrefixMap) -> String {
+        let mut replacements: Vec<(String, String)> = Vec::new();
+        for term in terms {
+            let redacted = self.term(term);
+            if redacted == **term {
+                continue;
+            }
+            replacements.push((term.to_string(), redacted.to_string()));
+            match (term, &redacted) {
+                (Term::Literal(literal), Term::Literal(replacement)) => {
+                    replacements.push((literal.value().to_string(), replacement.value().into()));
+                }
+                (Term::NamedNode(node), Term::NamedNode(replacement)) => {
+                    replacements.push((prefixes.compact(term), redacted.to_string()));
+                    replacements.push((prefixes.label(term), redacted.to_string()));
+                    replacements.push((node.as_str().to_string(), replacement.as_str().into()));
+                }
+                _ => {}
+            }
+        }
+        replacements.retain(|(from, _)| !from.is_empty());
+        if replacements.is_empty() {
+            return text.to_string();
+        }
+        // Longest first, so a term's N-Triples form wins over its lexical form.
+        replacements.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+
+        let mut redacted = String::with_capacity(text.len());
+        let mut rest = text;
+        'scan: while let Some(c) = rest.chars().next() {
+            for (from, to) in &replacements {
+                if let Some(after) = rest.strip_prefix(from.as_str()) {
+                    redacted.push_str(to);
+                    rest = after;
+                    continue 'scan;
+                }
+            }
+            redacted.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+        redacted
+    }
+
+    /// Redacts the focus node, value and messages of a typed result and its details.
+    pub(crate) fn result(
+        &self,
+        result: ValidationResult,
+        prefixes: &PrefixMap,
+    ) -> ValidationResult {
+        let terms: Vec<&Term> = std::iter::once(&result.focus_node)
+            .chain(result.value.as_ref())
+            .collect();
+        let messages = result
+            .messages
+            .iter()
+            .map(|message| self.message(message, &terms, prefixes))
+            .collect();
+        let engine_message = self.text(&result.engine_message, &terms, prefixes);
+        ValidationResult {
+            focus_node: self.term(&result.focus_node),
+            value: result.value.as_ref().map(|value| self.term(value)),
+            messages,
+            engine_message,
+            details: result
+                .details
+                .into_iter()
+                .map(|detail| self.result(detail, prefixes))
+                .collect(),
+            ..result
+        }
+    }
+
+    /// Redacts the types and path values of a focus node.
+    pub(crate) fn focus_node_context(&self, focus_context: FocusNodeContext) -> FocusNodeContext {
+        FocusNodeContext {
+            types: focus_context.types.iter().map(|t| self.term(t)).collect(),
+            values: focus_context.values.iter().map(|t| self.term(t)).collect(),
+            truncated: focus_context.truncated,
+        }
+    }
+
+    /// Redacts the `sh:focusNode` and `sh:value` of every result in a report graph, and
+    /// their occurrences in the result's `sh:resultMessage`s.
+    pub(crate) fn graph(&self, graph: &Graph, prefixes: &PrefixMap) -> Graph {
+        let sh = SHACL::get();
+        let mut values: HashMap<NamedOrBlankNode, Vec<Term>> = HashMap::new();
+        for triple in graph.iter() {
+            if triple.predicate == sh.focus_node || triple.predicate == sh.value {
+                values
+                    .entry(triple.subject.into_owned())
+                    .or_default()
+                    .push(triple.object.into_owned());
+            }
+        }
+
+        let mut redacted = Graph::new();
+        for triple in graph.iter() {
+            let object = if triple.predicate == sh.focus_node || triple.predicate == sh.value {
+                self.term(&triple.object.into_owned())
+            } else if triple.predicate == sh.result_message {
+                let terms: Vec<&Term> = values
+                    .get(&triple.subject.into_owned())
+                    .map(|terms| terms.iter().collect())
+                    .unwrap_or_default();
+                self.message(&triple.object.into_owned(), &terms, prefixes)
+            } else {
+                triple.object.into_owned()
+            };
+            redacted.insert(&Triple::new(
+                triple.subject.into_owned(),
+                triple.predicate.into_owned(),
+                object,
+            ));
+        }
+        redacted
+    }
+
+    /// Redacts `terms` in a message literal, keeping its language tag or datatype.
+    fn message(&self, message: &Term, terms: &[&Term], prefixes: &PrefixMap) -> Term {
+        let Term::Literal(literal) = message else {
+            return message.clone();
+        };
+        let text = self.text(literal.value(), terms, prefixes);
+        match literal.language() {
+            Some(language) => Literal::new_language_tagged_literal_unchecked(text, language),
+            None => Literal::new_typed_literal(text, literal.datatype()),
+        }
+        .into()
+    }
+
+    fn digest(&self, value: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.salt.as_bytes());
+        hasher.update(value.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxigraph::model::vocab::xsd;
+
+    #[test]
+    fn literals_keep_datatype_and_language() {
+        let redaction = Redaction::mask();
+        let age: Term = Literal::new_typed_literal("42", xsd::INTEGER).into();
+        assert_eq!(
+            redaction.term(&age),
+            Term::from(Literal::new_typed_literal(MASK, xsd::INTEGER))
+        );
+        let name: Term = Literal::new_language_tagged_literal_unchecked("Alice", "en").into();
+        assert_eq!(
+            redaction.term(&name),
+            Term::from(Literal::new_language_tagged_literal_unchecked(MASK, "en"))
+        );
+    }
+
+    #[test]
+    fn iris_are_hashed_only_when_asked() {
+        let alice: Term = NamedNode::new_unchecked("http://example.org/alice").into();
+        assert_eq!(Redaction::hash().term(&alice), alice);
+        let redacted = Redaction::hash().with_iris(true).term(&alice);
+        assert!(redacted.to_string().starts_with("<urn:shacl-rs:redacted:"));
+        assert_ne!(
+            redacted,
+            Redaction::hash()
+                .with_iris(true)
+                .with_salt("s")
+                .term(&alice)
+        );
+    }
+
+    #[test]
+    fn text_replaces_every_rendering_of_a_value() {
+        let redaction = Redaction::mask().with_iris(true);
+        let alice: Term = NamedNode::new_unchecked("http://example.org/alice").into();
+        let email: Term = Literal::new_simple_literal("alice@example.org").into();
+        let mut prefixes = PrefixMap::new();
+        prefixes.insert("ex", "http://example.org/");
+        let text = redaction.text(
+            "ex:alice has email alice@example.org",
+            &[&alice, &email],
+            &prefixes,
+        );
+        assert!(!text.contains("alice"), "{}", text);
+        assert!(text.ends_with("has email [redacted]"), "{}", text);
+    }
+}
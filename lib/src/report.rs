@@ -1,15 +1,192 @@
 use crate::context::{Context, SourceShape, ValidationContext};
+use crate::error_code::{ErrorCode, ResultCode};
 use crate::named_nodes::SHACL;
-use crate::runtime::ValidationFailure;
-use crate::types::{Path, Severity};
+use crate::options::StopReason;
+use crate::prefix_map::PrefixMap;
+use crate::redaction::Redaction;
+use crate::runtime::{value_node_kind, Component, ValidationFailure};
+use crate::sparql::SparqlExecutor;
+use crate::types::{CustomSeverityConformance, NodeKind, Path, Severity, TraceItem};
+use crate::unsupported::UNSUPPORTED_FEATURE;
 use oxigraph::io::{RdfFormat, RdfSerializer};
-use oxigraph::model::vocab::rdf;
+use oxigraph::model::vocab::{rdf, xsd};
 use oxigraph::model::{
-    BlankNode, Graph, Literal, NamedOrBlankNode, NamedOrBlankNode as Subject,
-    NamedOrBlankNodeRef as SubjectRef, Term, Triple,
+    BlankNode, Graph, Literal, NamedNode, NamedOrBlankNode, NamedOrBlankNode as Subject,
+    NamedOrBlankNodeRef as SubjectRef, Quad, Term, TermRef, Triple, TripleRef,
 };
-use std::collections::HashMap; // For using Term as a HashMap key
+use oxigraph::sparql::QueryResults;
+use oxigraph::store::Store;
+use oxsdatatypes::DateTime;
+use std::collections::{HashMap, HashSet, VecDeque}; // For using Term as a HashMap key
 use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+use xxhash_rust::xxh3::xxh3_64_with_seed;
+
+/// Named graph that dataset serializations place the report in unless
+/// [`ValidationReport::with_report_graph`] chooses another one.
+pub const DEFAULT_REPORT_GRAPH: &str = "urn:shacl-rs:validation-report";
+
+const PROV_GENERATED_AT_TIME: &str = "http://www.w3.org/ns/prov#generatedAtTime";
+
+/// `sh:sourceConstraintComponent` of a result that stands in for several aggregated results.
+pub const AGGREGATED_RESULTS_COMPONENT: &str = "urn:shacl-rs:AggregatedResults";
+/// Number of results an aggregated result stands in for (`xsd:integer`).
+pub const RESULT_COUNT: &str = "urn:shacl-rs:resultCount";
+/// A constraint component violated by one of the results an aggregated result stands in for.
+pub const VIOLATED_COMPONENT: &str = "urn:shacl-rs:violatedComponent";
+
+/// `sh:sourceConstraintComponent` of a result reporting that a path has more value nodes
+/// than `ValidatorBuilder::with_max_value_nodes` allows.
+pub const VALUE_NODE_LIMIT_COMPONENT: &str = "urn:shacl-rs:ValueNodeLimit";
+
+/// The kind of shapes-graph element a [`HeatEntry`] describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeatEntryKind {
+    NodeShape,
+    PropertyShape,
+    Component,
+}
+
+/// Execution statistics for one node shape, property shape or constraint component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatEntry {
+    /// Stable identifier of the element within this validator (e.g. `Component(c12)`).
+    pub id: String,
+    /// The shape's IRI, or the constraint component type for components.
+    pub iri: Term,
+    /// Human-readable label: the shape name, property path or component label.
+    pub label: String,
+    pub kind: HeatEntryKind,
+    /// Number of times the element was evaluated.
+    pub invocations: usize,
+    /// Total wall-clock time spent in the element, including nested shapes.
+    pub duration: Duration,
+}
+
+/// How often one node shape, property shape or constraint component appears in the
+/// execution traces of validation failures.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentFrequency {
+    /// The element, keyed by its ID in the shapes model.
+    pub item: TraceItem,
+    /// The shape's IRI, or the constraint component type for components.
+    pub iri: Term,
+    /// Human-readable label: the shape name, property path or component label.
+    pub label: String,
+    pub kind: HeatEntryKind,
+    /// Number of failure traces the element appears in (once per visit).
+    pub count: usize,
+}
+
+/// Wall-clock time spent in one node shape, property shape or constraint component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentTiming {
+    /// The element, keyed by its ID in the shapes model.
+    pub item: TraceItem,
+    /// The shape's IRI, or the constraint component type for components.
+    pub iri: Term,
+    /// Human-readable label: the shape name, property path or component label.
+    pub label: String,
+    pub kind: HeatEntryKind,
+    /// Number of times the element was evaluated.
+    pub invocations: usize,
+    /// Total wall-clock time spent in the element, including nested shapes.
+    pub total: Duration,
+}
+
+impl ComponentTiming {
+    /// Average time per evaluation.
+    pub fn mean(&self) -> Duration {
+        match self.invocations {
+            0 => Duration::ZERO,
+            invocations => self.total.div_f64(invocations as f64),
+        }
+    }
+}
+
+impl fmt::Display for ComponentTiming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?}\t{}\t{}\t{:.3}ms\t{:.3}ms",
+            self.kind,
+            self.label,
+            self.invocations,
+            self.total.as_secs_f64() * 1000.0,
+            self.mean().as_secs_f64() * 1000.0,
+        )
+    }
+}
+
+/// How often a check whether a node conforms to a shape, made for `sh:node`, `sh:not`,
+/// `sh:and`, `sh:or`, `sh:xone` or `sh:qualifiedValueShape`, was answered from the
+/// validation run's cache instead of being evaluated again.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConformanceCacheStats {
+    /// Checks answered from the cache.
+    pub hits: usize,
+    /// Checks that evaluated the shape and stored the outcome.
+    pub misses: usize,
+}
+
+impl ConformanceCacheStats {
+    /// Share of checks answered from the cache, from 0 to 1.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+/// One validation result, as it appears in the report graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationResult {
+    /// `sh:focusNode`.
+    pub focus_node: Term,
+    /// `sh:resultPath`, for results produced by property shapes or path overrides.
+    pub path: Option<Path>,
+    /// `sh:value`, when a specific value node failed.
+    pub value: Option<Term>,
+    /// `sh:resultSeverity`.
+    pub severity: Severity,
+    /// `sh:resultMessage` values: the shape's `sh:message`, or the constraint's messages.
+    pub messages: Vec<Term>,
+    /// The engine's own explanation of the failure, also printed by `dump`.
+    pub engine_message: String,
+    /// `sh:sourceShape`.
+    pub source_shape: Option<Term>,
+    /// `sh:sourceConstraintComponent`.
+    pub source_constraint_component: Option<NamedNode>,
+    /// `sh:sourceConstraint`, for `sh:sparql` constraints.
+    pub source_constraint: Option<Term>,
+    /// Stable code derived from `source_constraint_component`.
+    pub code: Option<ErrorCode>,
+    /// For `sh:nodeKind` results, the node kind the constraint requires; `None` for other
+    /// results, or if the `sh:nodeKind` value is not a SHACL node kind.
+    pub expected_node_kind: Option<NodeKind>,
+    /// For `sh:nodeKind` results, the kind of the offending value: `BlankNode`, `Iri` or
+    /// `Literal`.
+    pub actual_node_kind: Option<NodeKind>,
+    /// `sh:detail`: nested results explaining a failed `sh:node`, `sh:and`, `sh:or` or
+    /// `sh:xone` constraint. Empty unless the run enabled
+    /// [`ValidationOptions::with_result_details`](crate::ValidationOptions::with_result_details).
+    pub details: Vec<ValidationResult>,
+}
+
+/// Data-graph values around a result's focus node, so a result can be understood without
+/// opening the data. `dump` prints them under each result when
+/// [`ValidationReport::with_focus_node_context`] is set.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FocusNodeContext {
+    /// The focus node's `rdf:type` values.
+    pub types: Vec<Term>,
+    /// The focus node's values along the result path; empty for results without a path.
+    pub values: Vec<Term>,
+    /// Whether `types` or `values` were cut off at the limit.
+    pub truncated: bool,
+}
 
 /// Represents the result of a SHACL validation.
 ///
@@ -24,15 +201,280 @@ pub struct ValidationReport<'a> {
 impl<'a> ValidationReport<'a> {
     /// Creates a new ValidationReport.
     /// This is intended for internal use by the library.
-    pub(crate) fn new(builder: ValidationReportBuilder, context: &'a ValidationContext) -> Self {
+    pub(crate) fn new(
+        mut builder: ValidationReportBuilder,
+        context: &'a ValidationContext,
+    ) -> Self {
+        builder.canonicalize(context);
         ValidationReport { builder, context }
     }
 
+    /// Attaches caller-supplied metadata (a request id, dataset version, tenant, ...) to
+    /// the report. Each entry is emitted as a `predicate value` pair on the
+    /// `sh:ValidationReport` node of every serialization.
+    pub fn with_metadata(mut self, predicate: NamedNode, value: impl Into<Term>) -> Self {
+        self.builder.metadata.push((predicate, value.into()));
+        self
+    }
+
+    /// Iterates over the individual results of the report as typed [`ValidationResult`]s,
+    /// in report order. Results are built lazily and are not affected by
+    /// [`ValidationReport::with_focus_node_aggregation`].
+    ///
+    /// Report order is canonical: results are sorted by focus node, result path and
+    /// constraint component (then source shape, value and messages), and results that
+    /// would appear identically in the report graph are listed once, so two runs over the
+    /// same graphs produce the same report.
+    ///
+    /// With [`ValidationReport::with_redaction`], focus nodes, values and messages are
+    /// redacted.
+    pub fn results(&self) -> impl Iterator<Item = ValidationResult> + '_ {
+        self.builder.results.iter().map(|(context, failure)| {
+            let mut result = self.builder.typed_result(context, failure, self.context);
+            if !self.builder.keep_skolem_iris {
+                result = deskolemize_result(result, self.context);
+            }
+            match &self.builder.redaction {
+                Some(redaction) => redaction.result(result, &self.context.model.prefixes),
+                None => result,
+            }
+        })
+    }
+
+    /// Returns the stable [`ErrorCode`] of every result, in report order.
+    pub fn result_codes(&self) -> Vec<ResultCode> {
+        self.builder.result_codes(self.context)
+    }
+
+    /// Returns the metadata attached with [`ValidationReport::with_metadata`].
+    pub fn metadata(&self) -> &[(NamedNode, Term)] {
+        &self.builder.metadata
+    }
+
+    /// Sets the named graph that dataset formats (TriG, N-Quads) place the report in,
+    /// e.g. a graph named after the data graph it describes. Defaults to
+    /// [`DEFAULT_REPORT_GRAPH`].
+    pub fn with_report_graph(mut self, graph: NamedNode) -> Self {
+        self.builder.report_graph = Some(graph);
+        self
+    }
+
+    /// Derives the report's blank node labels from `seed`, e.g. `_:b3f0c2...` instead of
+    /// `_:report` and `_:r0`. Labels are stable without a seed too; seeding gives reports
+    /// that are serialized into one document disjoint labels, and makes
+    /// [`ValidationReport::insert_into`] keep them instead of drawing fresh ones.
+    pub fn with_blank_node_seed(mut self, seed: u64) -> Self {
+        self.builder.blank_node_seed = Some(seed);
+        self
+    }
+
+    /// Keeps the skolem IRIs that stand in for blank nodes of the data graph in
+    /// `sh:focusNode` and `sh:value`, as the validator saw them. By default they are turned
+    /// back into blank nodes labelled as in the loaded data graph, so results point at the
+    /// original nodes; the IRIs help when debugging which node a result is about.
+    pub fn with_skolem_iris(mut self, keep: bool) -> Self {
+        self.builder.keep_skolem_iris = keep;
+        self
+    }
+
+    /// Collapses the results of every focus node that has more than `threshold` results
+    /// into a single aggregated result.
+    ///
+    /// The aggregated result keeps the focus node and the most severe severity, uses
+    /// [`AGGREGATED_RESULTS_COMPONENT`] as its `sh:sourceConstraintComponent`, and records
+    /// the number of results ([`RESULT_COUNT`]) and each distinct violated component
+    /// ([`VIOLATED_COMPONENT`]). This only affects how the report is rendered;
+    /// conformance is still computed from every result.
+    pub fn with_focus_node_aggregation(mut self, threshold: usize) -> Self {
+        self.builder.aggregation_threshold = Some(threshold);
+        self
+    }
+
+    /// Makes `dump` print, under each result, the focus node's types and its values along
+    /// the result path, at most `limit` of each, read from the data graph.
+    pub fn with_focus_node_context(mut self, limit: usize) -> Self {
+        self.builder.focus_context_limit = Some(limit);
+        self
+    }
+
+    /// Reads the types of `result`'s focus node and its values along the result path from
+    /// the data graph, at most `limit` of each, sorted. Blank focus nodes have no context,
+    /// unless they stand for skolemized blank nodes of the data graph.
+    pub fn focus_node_context(
+        &self,
+        result: &ValidationResult,
+        limit: usize,
+    ) -> Result<FocusNodeContext, String> {
+        focus_node_context(
+            self.context,
+            &self.context.skolemize_data_term(&result.focus_node),
+            result.path.as_ref(),
+            limit,
+        )
+        .map(|mut focus_context| {
+            if !self.builder.keep_skolem_iris {
+                for term in focus_context
+                    .types
+                    .iter_mut()
+                    .chain(focus_context.values.iter_mut())
+                {
+                    *term = self.context.deskolemize_data_term(term);
+                }
+            }
+            match &self.builder.redaction {
+                Some(redaction) => redaction.focus_node_context(focus_context),
+                None => focus_context,
+            }
+        })
+    }
+
+    /// Redacts the data values of every rendering of the report: focus nodes, values and
+    /// the messages that repeat them, in the report graph, typed results, result codes
+    /// and `dump`. Conformance is unaffected. See [`Redaction`] for what is replaced.
+    pub fn with_redaction(mut self, redaction: Redaction) -> Self {
+        self.builder.redaction = Some(redaction);
+        self
+    }
+
+    /// Returns the focus nodes this report covers if it is a partial report returned by
+    /// `Validator::revalidate`, ordered by term; `None` for a report covering every focus node.
+    pub fn revalidated_focus_nodes(&self) -> Option<Vec<Term>> {
+        self.builder.revalidated.as_ref().map(|focus_nodes| {
+            let mut focus_nodes: Vec<Term> = focus_nodes.iter().cloned().collect();
+            focus_nodes.sort_by_key(|term| term.to_string());
+            focus_nodes
+        })
+    }
+
+    /// Folds a report returned by `Validator::revalidate` into this one: results for the
+    /// focus nodes it covers are replaced by its results, all other results are kept.
+    pub fn merge_revalidation(&mut self, partial: ValidationReport<'a>) {
+        self.builder.merge_revalidation(partial.builder);
+        self.builder.canonicalize(self.context);
+    }
+
+    /// Returns the named graph used by dataset serializations.
+    pub fn report_graph(&self) -> NamedNode {
+        self.builder.report_graph()
+    }
+
+    /// Returns the report as quads in [`ValidationReport::report_graph`].
+    pub fn to_quads(&self) -> Vec<Quad> {
+        self.builder.to_quads(self.context)
+    }
+
+    /// Inserts the report into the validator's own store, see [`ValidationReport::insert_into`].
+    pub fn insert_into_store(&self) -> Result<NamedNode, String> {
+        self.insert_into(self.context.model.store())
+    }
+
+    /// Inserts the report into `store` under a fresh named graph
+    /// `{report_graph}/{timestamp}` and returns that graph's name.
+    ///
+    /// The report node is stamped with `prov:generatedAtTime` so that historical reports
+    /// kept in the same store can be ordered and queried with SPARQL.
+    pub fn insert_into(&self, store: &Store) -> Result<NamedNode, String> {
+        let generated_at = DateTime::now();
+        let base = format!("{}/{}", self.report_graph().as_str(), generated_at);
+        let mut graph_name = NamedNode::new(base.clone())
+            .map_err(|e| format!("Invalid report graph name: {}", e))?;
+        // Reports inserted within the same clock tick get a numeric suffix rather than merging.
+        let mut suffix = 1;
+        while store
+            .contains_named_graph(graph_name.as_ref())
+            .map_err(|e| format!("Failed to read store: {}", e))?
+        {
+            graph_name = NamedNode::new_unchecked(format!("{}-{}", base, suffix));
+            suffix += 1;
+        }
+        // The report's blank node labels are stable across runs; unless they were seeded, give
+        // this copy fresh ones so reports kept in the same store never share nodes.
+        let mut graph = match self.builder.blank_node_seed {
+            Some(_) => self.to_graph(),
+            None => relabel_blank_nodes(&self.to_graph(), |_| BlankNode::default()),
+        };
+        let sh = SHACL::get();
+        let report_nodes: Vec<Subject> = graph
+            .subjects_for_predicate_object(rdf::TYPE, sh.validation_report)
+            .map(|subject| subject.into_owned())
+            .collect();
+        let timestamp = Literal::new_typed_literal(generated_at.to_string(), xsd::DATE_TIME);
+        for report_node in report_nodes {
+            graph.insert(&Triple::new(
+                report_node,
+                NamedNode::new_unchecked(PROV_GENERATED_AT_TIME),
+                timestamp.clone(),
+            ));
+        }
+        let mut transaction = store
+            .start_transaction()
+            .map_err(|e| format!("Failed to start report transaction: {}", e))?;
+        for triple in graph.iter() {
+            transaction.insert(triple.in_graph(graph_name.as_ref()));
+        }
+        transaction
+            .commit()
+            .map_err(|e| format!("Failed to insert report into store: {}", e))?;
+        Ok(graph_name)
+    }
+
     /// Checks if the validation conformed.
     ///
-    /// Returns `true` if there were no validation failures, `false` otherwise.
+    /// Returns `true` if there were no validation failures, `false` otherwise. Results with
+    /// a custom severity are disregarded when the validator was built with
+    /// `CustomSeverityConformance::Ignored`.
     pub fn conforms(&self) -> bool {
-        self.builder.results.is_empty()
+        self.builder.conforms(self.context)
+    }
+
+    /// Checks conformance counting only results whose severity is at least `threshold`,
+    /// so `conforms_with(&Severity::Violation)` is `true` when the report holds only
+    /// `sh:Info` and `sh:Warning` results. Custom severities count as violations (unless
+    /// the validator ignores them, as for [`conforms`](Self::conforms)), and
+    /// `conforms_with(&Severity::Info)` equals `conforms()`. `sh:conforms` in the
+    /// serialized report is not affected.
+    pub fn conforms_with(&self, threshold: &Severity) -> bool {
+        self.builder.conforms_with(self.context, threshold)
+    }
+
+    /// Returns why validation stopped before every focus node was validated, or `None` if
+    /// the run was complete. A report that stopped early only holds the results found up to
+    /// that point, so `conforms()` can be `true` although unvalidated nodes violate shapes.
+    pub fn stop_reason(&self) -> Option<StopReason> {
+        self.builder.stop_reason
+    }
+
+    /// Returns the shapes that produced no results: every node and property shape of the
+    /// shapes graph that is not deactivated and is not the `sh:sourceShape` of any result.
+    ///
+    /// Together with [`ValidationReport::violating_shapes`] this gives a per-shape pass/fail
+    /// view of the run. A shape whose nested shapes failed is still conforming here unless it
+    /// produced a result itself (for example through `sh:node`).
+    pub fn conforming_shapes(&self) -> HashSet<Term> {
+        let violating = self.violating_shapes();
+        let model = &self.context.model;
+        let node_shapes = model
+            .node_shapes
+            .values()
+            .filter(|shape| !shape.is_deactivated())
+            .filter_map(|shape| SourceShape::NodeShape(*shape.identifier()).get_term(self.context));
+        let prop_shapes = model
+            .prop_shapes
+            .values()
+            .filter(|shape| !shape.is_deactivated())
+            .filter_map(|shape| {
+                SourceShape::PropertyShape(*shape.identifier()).get_term(self.context)
+            });
+        node_shapes
+            .chain(prop_shapes)
+            .filter(|term| !violating.contains(term))
+            .collect()
+    }
+
+    /// Returns the shapes that are the `sh:sourceShape` of at least one result, whatever
+    /// its severity.
+    pub fn violating_shapes(&self) -> HashSet<Term> {
+        self.builder.violating_shapes(self.context)
     }
 
     /// Returns the validation report as an `oxigraph::model::Graph`.
@@ -40,7 +482,18 @@ impl<'a> ValidationReport<'a> {
         self.builder.to_graph(self.context)
     }
 
+    /// Checks this report against the bundled SHACL validation-report shapes and returns
+    /// any structural problems. Use a [`crate::ReportChecker`] directly to check many
+    /// reports without recompiling the report shapes each time.
+    pub fn self_check(&self) -> Result<Vec<crate::ReportIssue>, Box<dyn Error>> {
+        let checker = crate::ReportChecker::new()?;
+        Ok(checker.check(&self.to_graph())?)
+    }
+
     /// Serializes the validation report to a string in the specified RDF format.
+    ///
+    /// Dataset formats such as TriG and N-Quads place the report in
+    /// [`ValidationReport::report_graph`].
     pub fn to_rdf(&self, format: RdfFormat) -> Result<String, Box<dyn Error>> {
         self.builder.to_rdf(self.context, format)
     }
@@ -83,6 +536,51 @@ impl<'a> ValidationReport<'a> {
         self.builder.print_traces(self.context);
     }
 
+    /// Returns how often, and for how long, every node shape, property shape and component
+    /// was evaluated during validation, most invoked first.
+    ///
+    /// This is the data behind `Validator::to_graphviz_heatmap`, for callers that want to
+    /// render it themselves. Elements that never ran are omitted.
+    pub fn heatmap(&self) -> Vec<HeatEntry> {
+        heatmap_entries(self.context)
+    }
+
+    /// Returns the wall-clock time spent in every node shape, property shape and
+    /// component that ran, slowest first (ties broken by ID).
+    ///
+    /// Shape times include the shapes and components evaluated beneath them, so the
+    /// slowest components usually point at the cost a slow shape is made of.
+    pub fn get_component_timings(&self) -> Vec<ComponentTiming> {
+        let durations = self.context.execution_durations.borrow();
+        let mut timings: Vec<ComponentTiming> = invocation_counts(self.context)
+            .into_iter()
+            .filter_map(|(item, invocations)| {
+                let (kind, iri) = resolve_trace_item(self.context, &item)?;
+                let (label, _) = self.context.get_trace_item_label_and_type(&item);
+                Some(ComponentTiming {
+                    total: durations.get(&item).copied().unwrap_or_default(),
+                    item,
+                    iri,
+                    label,
+                    kind,
+                    invocations,
+                })
+            })
+            .collect();
+        timings.sort_by(|a, b| {
+            b.total
+                .cmp(&a.total)
+                .then_with(|| a.item.to_string().cmp(&b.item.to_string()))
+        });
+        timings
+    }
+
+    /// Returns how often checks of a node against a shape were answered from the
+    /// conformance cache during this run.
+    pub fn conformance_cache_stats(&self) -> ConformanceCacheStats {
+        self.context.conformance_cache_stats.get()
+    }
+
     /// Calculates the frequency of each component, node shape, and property shape invocation
     /// across all validation failures.
     ///
@@ -90,14 +588,158 @@ impl<'a> ValidationReport<'a> {
     pub fn get_component_frequencies(&self) -> HashMap<(String, String, String), usize> {
         self.builder.get_component_frequencies(self.context)
     }
+
+    /// Like [`ValidationReport::get_component_frequencies`], but keyed by the typed
+    /// [`TraceItem`] (carrying the `ID`, `PropShapeID` or `ComponentID`) so callers can
+    /// relate each count back to the shapes model, with the IRI already resolved.
+    pub fn get_component_frequencies_by_id(&self) -> HashMap<TraceItem, ComponentFrequency> {
+        self.builder.get_component_frequencies_by_id(self.context)
+    }
+}
+
+/// Counts how often each trace item was visited across all execution traces.
+fn invocation_counts(validation_context: &ValidationContext) -> HashMap<TraceItem, usize> {
+    let mut invocations: HashMap<TraceItem, usize> = HashMap::new();
+    for trace in validation_context.execution_traces.borrow().iter() {
+        for item in trace {
+            *invocations.entry(item.clone()).or_insert(0) += 1;
+        }
+    }
+    invocations
+}
+
+fn heatmap_entries(validation_context: &ValidationContext) -> Vec<HeatEntry> {
+    let durations = validation_context.execution_durations.borrow();
+
+    let mut entries: Vec<HeatEntry> = invocation_counts(validation_context)
+        .into_iter()
+        .filter_map(|(item, count)| {
+            let (kind, iri) = resolve_trace_item(validation_context, &item)?;
+            let (label, _) = validation_context.get_trace_item_label_and_type(&item);
+            Some(HeatEntry {
+                id: item.to_string(),
+                iri,
+                label,
+                kind,
+                invocations: count,
+                duration: durations.get(&item).copied().unwrap_or_default(),
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        b.invocations
+            .cmp(&a.invocations)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    entries
+}
+
+/// Resolves a trace item to its kind and IRI: the shape's IRI, or the constraint
+/// component type for components.
+fn resolve_trace_item(
+    validation_context: &ValidationContext,
+    item: &TraceItem,
+) -> Option<(HeatEntryKind, Term)> {
+    match item {
+        TraceItem::NodeShape(id) => Some((
+            HeatEntryKind::NodeShape,
+            validation_context
+                .model
+                .nodeshape_id_lookup()
+                .borrow()
+                .get_term(*id)
+                .cloned()?,
+        )),
+        TraceItem::PropertyShape(id) => Some((
+            HeatEntryKind::PropertyShape,
+            validation_context
+                .model
+                .propshape_id_lookup()
+                .borrow()
+                .get_term(*id)
+                .cloned()?,
+        )),
+        TraceItem::Component(id) => Some((
+            HeatEntryKind::Component,
+            validation_context
+                .get_component(id)?
+                .component_type()
+                .into(),
+        )),
+    }
+}
+
+/// Orders [`ReportShard`]s when they are merged: by shape term, with a node shape before
+/// a property shape that has the same term.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct ShardKey {
+    shape: String,
+    is_property_shape: bool,
+}
+
+impl ShardKey {
+    /// Key for the results of every focus node of one shape.
+    pub(crate) fn shape(shape: String, is_property_shape: bool) -> Self {
+        ShardKey {
+            shape,
+            is_property_shape,
+        }
+    }
+}
+
+/// Results collected by one unit of validation work, currently one shape.
+///
+/// Shards are `Send`, so workers can each fill their own shard without locking and hand
+/// it back; [`ValidationReportBuilder::from_shards`] orders them by [`ShardKey`], which makes
+/// the final report independent of the order in which workers finished. Within a shard,
+/// results keep the order they were added in.
+#[derive(Debug)]
+pub(crate) struct ReportShard {
+    key: ShardKey,
+    results: Vec<(Context, ValidationFailure)>,
+}
+
+impl ReportShard {
+    pub(crate) fn new(key: ShardKey) -> Self {
+        ReportShard {
+            key,
+            results: Vec::new(),
+        }
+    }
+
+    /// Adds a validation failure to the shard.
+    pub(crate) fn add_failure(&mut self, context: &Context, failure: ValidationFailure) {
+        self.results.push((context.clone(), failure));
+    }
 }
 
+// Shards cross thread boundaries; keep that a compile-time guarantee.
+const _: fn() = || {
+    fn assert_send<T: Send>() {}
+    assert_send::<ReportShard>();
+};
+
 /// A builder for creating a `ValidationReport`.
 ///
 /// It collects validation results and can then be used to generate
 /// the final report in various formats.
 pub struct ValidationReportBuilder {
     results: Vec<(Context, ValidationFailure)>,
+    metadata: Vec<(NamedNode, Term)>,
+    report_graph: Option<NamedNode>,
+    aggregation_threshold: Option<usize>,
+    /// How many types and path values of each focus node `dump` prints, if any.
+    focus_context_limit: Option<usize>,
+    /// Focus nodes covered by a partial report from `Validator::revalidate`.
+    revalidated: Option<HashSet<Term>>,
+    /// Set when the run stopped before validating every focus node.
+    stop_reason: Option<StopReason>,
+    /// Replaces data values in every rendering of the report.
+    redaction: Option<Redaction>,
+    /// Seed the report's blank node labels are derived from.
+    blank_node_seed: Option<u64>,
+    /// Keeps the skolem IRIs of blank focus nodes and values instead of blank nodes.
+    keep_skolem_iris: bool,
 }
 
 impl ValidationReportBuilder {
@@ -105,9 +747,30 @@ impl ValidationReportBuilder {
     pub fn new() -> Self {
         ValidationReportBuilder {
             results: Vec::new(),
+            metadata: Vec::new(),
+            report_graph: None,
+            aggregation_threshold: None,
+            focus_context_limit: None,
+            revalidated: None,
+            stop_reason: None,
+            redaction: None,
+            blank_node_seed: None,
+            keep_skolem_iris: false,
         }
     }
 
+    /// Builds a report from shards filled independently (possibly on different threads).
+    ///
+    /// Shards are concatenated in [`ShardKey`] order; shards with equal keys keep the order
+    /// they were passed in.
+    pub(crate) fn from_shards(shards: impl IntoIterator<Item = ReportShard>) -> Self {
+        let mut shards: Vec<ReportShard> = shards.into_iter().collect();
+        shards.sort_by(|a, b| a.key.cmp(&b.key));
+        let mut builder = ValidationReportBuilder::new();
+        builder.results = shards.into_iter().flat_map(|shard| shard.results).collect();
+        builder
+    }
+
     /// Adds a validation failure to the report.
     ///
     /// # Arguments
@@ -125,6 +788,27 @@ impl ValidationReportBuilder {
         &self.results
     }
 
+    /// Sorts the results into canonical report order and drops results that are identical
+    /// to an earlier one: same focus node, path, value, severity, messages, source shape,
+    /// component, source constraint and details. The engine's own message and the trace are
+    /// not compared, as neither appears in the report graph.
+    ///
+    /// Keys are built from the raw results; rendered messages follow from the source shape,
+    /// source constraint and the constraint's message terms, so they are never rendered here.
+    pub(crate) fn canonicalize(&mut self, validation_context: &ValidationContext) {
+        let results = std::mem::take(&mut self.results);
+        let mut keyed: Vec<(Vec<String>, (Context, ValidationFailure))> = results
+            .into_iter()
+            .map(|(context, failure)| {
+                let key = result_sort_key(&context, &failure, validation_context);
+                (key, (context, failure))
+            })
+            .collect();
+        keyed.sort_by(|a, b| a.0.cmp(&b.0));
+        keyed.dedup_by(|a, b| a.0 == b.0);
+        self.results = keyed.into_iter().map(|(_, result)| result).collect();
+    }
+
     /// Calculates the frequency of each component, node shape, and property shape invocation
     /// across all validation failures.
     ///
@@ -155,37 +839,280 @@ impl ValidationReportBuilder {
         frequencies
     }
 
+    pub(crate) fn get_component_frequencies_by_id(
+        &self,
+        validation_context: &ValidationContext,
+    ) -> HashMap<TraceItem, ComponentFrequency> {
+        let mut frequencies: HashMap<TraceItem, ComponentFrequency> = HashMap::new();
+        let traces = validation_context.execution_traces.borrow();
+        for (context, _) in &self.results {
+            let Some(trace) = traces.get(context.trace_index()) else {
+                continue;
+            };
+            for item in trace {
+                if let Some(entry) = frequencies.get_mut(item) {
+                    entry.count += 1;
+                    continue;
+                }
+                let Some((kind, iri)) = resolve_trace_item(validation_context, item) else {
+                    continue;
+                };
+                let (label, _) = validation_context.get_trace_item_label_and_type(item);
+                frequencies.insert(
+                    item.clone(),
+                    ComponentFrequency {
+                        item: item.clone(),
+                        iri,
+                        label,
+                        kind,
+                        count: 1,
+                    },
+                );
+            }
+        }
+        frequencies
+    }
+
+    pub(crate) fn violating_shapes(&self, vc: &ValidationContext) -> HashSet<Term> {
+        self.results
+            .iter()
+            .filter_map(|(context, _)| context.source_shape().get_term(vc))
+            .collect()
+    }
+
+    /// Whether the results make the data non-conforming under the validator's
+    /// custom-severity policy.
+    pub(crate) fn conforms(&self, vc: &ValidationContext) -> bool {
+        self.conforms_with(vc, &Severity::Info)
+    }
+
+    /// Whether no result has a severity at or above `threshold`, disregarding custom
+    /// severities when the validator ignores them.
+    pub(crate) fn conforms_with(&self, vc: &ValidationContext, threshold: &Severity) -> bool {
+        let ignore_custom =
+            vc.model.features.custom_severity_conformance == CustomSeverityConformance::Ignored;
+        self.results.iter().all(|(context, failure)| {
+            match Self::severity_for_result(context, failure, vc).unwrap_or(&Severity::Violation) {
+                Severity::Custom(_) if ignore_custom => true,
+                severity => !severity.is_at_least(threshold),
+            }
+        })
+    }
+
     fn severity_term_for_result(
         context: &Context,
         failure: &ValidationFailure,
         vc: &ValidationContext,
     ) -> Term {
-        let sh = SHACL::new();
-        let default_violation = Term::from(sh.violation);
+        let sh = SHACL::get();
+        match Self::severity_for_result(context, failure, vc) {
+            Some(severity) => severity_to_term(severity, sh),
+            None => Term::from(sh.violation),
+        }
+    }
+
+    /// Returns the `sh:resultMessage` values of a result.
+    fn result_messages(
+        context: &Context,
+        failure: &ValidationFailure,
+        validation_context: &ValidationContext,
+    ) -> Vec<Term> {
+        let mut message_terms = Vec::new();
+
+        if let Some(shape_term) = context.source_shape().get_term(validation_context) {
+            message_terms.extend(fetch_shape_messages(validation_context, &shape_term));
+        }
+
+        // Messages declared on the shape take precedence over those produced by the
+        // constraint (instantiated validator/component/template messages).
+        if message_terms.is_empty() {
+            message_terms.extend(failure.message_terms.iter().cloned());
+        }
+
+        if message_terms.is_empty() {
+            if let Some(constraint_term) = &failure.source_constraint {
+                message_terms.extend(fetch_shape_messages(validation_context, constraint_term));
+            }
+        }
+        message_terms
+    }
 
-        if let Some(severity) = &failure.severity {
-            return severity_to_term(severity, &sh);
+    fn typed_result(
+        &self,
+        context: &Context,
+        failure: &ValidationFailure,
+        validation_context: &ValidationContext,
+    ) -> ValidationResult {
+        let path = result_path(context, failure, validation_context);
+        let component = validation_context.get_component(&failure.component_id);
+        let source_constraint_component = validation_context.component_type(&failure.component_id);
+        let (expected_node_kind, actual_node_kind) = match component {
+            Some(Component::NodeKindConstraint(node_kind)) => (
+                node_kind.expected(),
+                failure
+                    .failed_value_node
+                    .as_ref()
+                    .map(|value| value_node_kind(value, validation_context)),
+            ),
+            _ => (None, None),
+        };
+        ValidationResult {
+            focus_node: context.focus_node().clone(),
+            path,
+            value: failure.failed_value_node.clone(),
+            severity: Self::severity_for_result(context, failure, validation_context)
+                .cloned()
+                .unwrap_or(Severity::Violation),
+            messages: Self::result_messages(context, failure, validation_context),
+            engine_message: failure.message.clone(),
+            source_shape: context.source_shape().get_term(validation_context),
+            code: source_constraint_component
+                .as_ref()
+                .map(|component| ErrorCode::for_component(component.as_ref())),
+            source_constraint_component,
+            source_constraint: failure.source_constraint.clone(),
+            expected_node_kind,
+            actual_node_kind,
+            details: failure
+                .details
+                .iter()
+                .map(|(detail_context, detail_failure)| {
+                    self.typed_result(detail_context, detail_failure, validation_context)
+                })
+                .collect(),
         }
+    }
 
-        match context.source_shape() {
+    /// Groups the results of focus nodes that exceed the aggregation threshold, in the order
+    /// the focus nodes were first reported.
+    fn aggregated_groups(&self) -> Vec<(&Term, Vec<&(Context, ValidationFailure)>)> {
+        let Some(threshold) = self.aggregation_threshold else {
+            return Vec::new();
+        };
+        let mut groups: Vec<(&Term, Vec<&(Context, ValidationFailure)>)> = Vec::new();
+        let mut group_index: HashMap<&Term, usize> = HashMap::new();
+        for result in &self.results {
+            let focus_node = result.0.focus_node();
+            let index = *group_index.entry(focus_node).or_insert_with(|| {
+                groups.push((focus_node, Vec::new()));
+                groups.len() - 1
+            });
+            groups[index].1.push(result);
+        }
+        groups.retain(|(_, results)| results.len() > threshold);
+        groups
+    }
+
+    /// Inserts one result standing in for all of `results`, which share `focus_node`.
+    fn insert_aggregated_result(
+        graph: &mut Graph,
+        report_node: &Subject,
+        result_node: Subject,
+        focus_node: &Term,
+        results: &[&(Context, ValidationFailure)],
+        validation_context: &ValidationContext,
+    ) {
+        let sh = SHACL::get();
+        graph.insert(&Triple::new(
+            report_node.clone(),
+            sh.result,
+            Term::from(result_node.clone()),
+        ));
+        graph.insert(&Triple::new(
+            result_node.clone(),
+            rdf::TYPE,
+            Term::from(sh.validation_result),
+        ));
+        graph.insert(&Triple::new(
+            result_node.clone(),
+            sh.focus_node,
+            focus_node.clone(),
+        ));
+
+        let severity = results
+            .iter()
+            .map(|(context, failure)| {
+                Self::severity_for_result(context, failure, validation_context)
+                    .unwrap_or(&Severity::Violation)
+            })
+            .max_by_key(|severity| match severity {
+                Severity::Violation => 3,
+                Severity::Warning => 2,
+                Severity::Info => 1,
+                Severity::Custom(_) => 0,
+            })
+            .unwrap_or(&Severity::Violation);
+        graph.insert(&Triple::new(
+            result_node.clone(),
+            sh.result_severity,
+            severity_to_term(severity, sh),
+        ));
+        graph.insert(&Triple::new(
+            result_node.clone(),
+            sh.source_constraint_component,
+            NamedNode::new_unchecked(AGGREGATED_RESULTS_COMPONENT),
+        ));
+        graph.insert(&Triple::new(
+            result_node.clone(),
+            NamedNode::new_unchecked(RESULT_COUNT),
+            Literal::from(results.len() as i64),
+        ));
+
+        let mut components: Vec<NamedNode> = Vec::new();
+        for (_, failure) in results {
+            if let Some(component) = validation_context.component_type(&failure.component_id) {
+                if !components.contains(&component) {
+                    components.push(component);
+                }
+            }
+        }
+        for component in &components {
+            graph.insert(&Triple::new(
+                result_node.clone(),
+                NamedNode::new_unchecked(VIOLATED_COMPONENT),
+                component.clone(),
+            ));
+        }
+
+        let labels: Vec<String> = components.iter().map(|c| c.to_string()).collect();
+        graph.insert(&Triple::new(
+            result_node,
+            sh.result_message,
+            Literal::new_simple_literal(format!(
+                "{} results aggregated for this focus node; violated components: {}",
+                results.len(),
+                labels.join(", ")
+            )),
+        ));
+    }
+
+    /// Returns the severity a result is reported with; `None` means sh:Violation.
+    pub(crate) fn severity_for_result<'v>(
+        context: &Context,
+        failure: &'v ValidationFailure,
+        vc: &'v ValidationContext,
+    ) -> Option<&'v Severity> {
+        // Precedence: severity declared on the shape, then the override carried by the
+        // failure (validator, component, template), then sh:Violation.
+        let declared = match context.source_shape() {
             SourceShape::PropertyShape(prop_id) => vc
                 .model
                 .get_prop_shape_by_id(&prop_id)
-                .map(|ps| severity_to_term(ps.severity(), &sh))
-                .unwrap_or(default_violation),
+                .and_then(|ps| ps.declared_severity()),
             SourceShape::NodeShape(node_id) => vc
                 .model
                 .get_node_shape_by_id(&node_id)
-                .map(|ns| severity_to_term(ns.severity(), &sh))
-                .unwrap_or(default_violation),
-        }
+                .and_then(|ns| ns.declared_severity()),
+        };
+
+        declared.or(failure.severity.as_ref())
     }
 
     /// Constructs an `oxigraph::model::Graph` representing the validation report.
     pub fn to_graph(&self, validation_context: &ValidationContext) -> Graph {
         let mut graph = Graph::new();
-        let report_node: Subject = BlankNode::default().into();
-        let sh = SHACL::new();
+        let report_node: Subject = BlankNode::new_unchecked("report").into();
+        let sh = SHACL::get();
 
         graph.insert(&Triple::new(
             report_node.clone(),
@@ -193,162 +1120,231 @@ impl ValidationReportBuilder {
             Term::from(sh.validation_report),
         ));
 
-        let conforms = self.results.is_empty();
+        let conforms = self.conforms(validation_context);
         graph.insert(&Triple::new(
             report_node.clone(),
             sh.conforms,
             Term::from(Literal::from(conforms)),
         ));
 
-        if !conforms {
-            for (context, failure) in &self.results {
-                let result_node: Subject = BlankNode::default().into();
-                graph.insert(&Triple::new(
-                    report_node.clone(),
-                    sh.result,
-                    Term::from(result_node.clone()),
-                ));
-
-                graph.insert(&Triple::new(
-                    result_node.clone(),
-                    rdf::TYPE,
-                    Term::from(sh.validation_result),
-                ));
-
-                // sh:focusNode
-                graph.insert(&Triple::new(
-                    result_node.clone(),
-                    sh.focus_node,
-                    context.focus_node().clone(),
-                ));
-
-                // sh:resultMessage
-                let mut message_terms = Vec::new();
-
-                if let Some(shape_term) = context.source_shape().get_term(validation_context) {
-                    message_terms.extend(fetch_shape_messages(validation_context, &shape_term));
-                }
+        for (predicate, value) in &self.metadata {
+            graph.insert(&Triple::new(
+                report_node.clone(),
+                predicate.clone(),
+                value.clone(),
+            ));
+        }
 
-                if message_terms.is_empty() {
-                    if let Some(constraint_term) = &failure.source_constraint {
-                        message_terms
-                            .extend(fetch_shape_messages(validation_context, constraint_term));
-                    }
-                }
+        let unsupported_feature = NamedNode::new_unchecked(UNSUPPORTED_FEATURE);
+        for feature in &validation_context.model.unsupported_features {
+            graph.insert(&Triple::new(
+                report_node.clone(),
+                unsupported_feature.clone(),
+                Term::from(Literal::new_simple_literal(feature.to_string())),
+            ));
+        }
 
-                for term in &failure.message_terms {
-                    if !message_terms.contains(term) {
-                        message_terms.push(term.clone());
-                    }
-                }
+        let aggregated = self.aggregated_groups();
+        let mut blank_nodes = BlankNodeGenerator::default();
+        let mut roots: Vec<Subject> = vec![report_node.clone()];
+        for (context, failure) in &self.results {
+            if aggregated
+                .iter()
+                .any(|(focus_node, _)| *focus_node == context.focus_node())
+            {
+                continue;
+            }
+            let result_node = BlankNode::new_unchecked(format!("r{}", roots.len() - 1));
+            roots.push(result_node.clone().into());
+            graph.insert(&Triple::new(
+                report_node.clone(),
+                sh.result,
+                Term::from(result_node.clone()),
+            ));
+
+            Self::insert_result(
+                &mut graph,
+                &result_node,
+                context,
+                failure,
+                validation_context,
+                &mut blank_nodes,
+            );
+        }
 
-                if !message_terms.is_empty() {
-                    for message_term in message_terms {
-                        graph.insert(&Triple::new(
-                            result_node.clone(),
-                            sh.result_message,
-                            message_term,
-                        ));
-                    }
-                }
+        for (focus_node, results) in &aggregated {
+            let result_node: Subject =
+                BlankNode::new_unchecked(format!("r{}", roots.len() - 1)).into();
+            roots.push(result_node.clone());
+            Self::insert_aggregated_result(
+                &mut graph,
+                &report_node,
+                result_node,
+                focus_node,
+                results,
+                validation_context,
+            );
+        }
 
-                // sh:resultPath
-                let result_path_term = if let Some(path_override) = &failure.result_path {
-                    // If the override is a blank node head from the shapes graph, deep-clone its subgraph.
-                    // Otherwise, build structurally.
-                    Some(match path_override {
-                        Path::Simple(t) if matches!(t, Term::BlankNode(_)) => {
-                            clone_path_term_from_shapes_graph(t, validation_context, &mut graph)
-                        }
-                        _ => path_to_rdf(path_override, &mut graph),
-                    })
-                } else if let Some(_p) = context.result_path() {
-                    context.result_path().map(|p| match p {
-                        Path::Simple(t) if matches!(t, Term::BlankNode(_)) => {
-                            clone_path_term_from_shapes_graph(t, validation_context, &mut graph)
-                        }
-                        _ => path_to_rdf(p, &mut graph),
-                    })
-                    // Prefer the original shapes-graph term when the source is a PropertyShape.
-                    //match context.source_shape() {
-                    //    SourceShape::PropertyShape(prop_id) => validation_context
-                    //        .model
-                    //        .get_prop_shape_by_id(&prop_id)
-                    //        .map(|ps| clone_path_term_from_shapes_graph(ps.path_term(), validation_context, &mut graph)),
-                    //    // For NodeShape-derived paths (rare), if it's a blank node head from the shapes graph, clone it.
-                    //    // Otherwise, fall back to structural build.
-                    //    _ => context.result_path().map(|p| match p {
-                    //        Path::Simple(t) if matches!(t, Term::BlankNode(_)) => {
-                    //            clone_path_term_from_shapes_graph(t, validation_context, &mut graph)
-                    //        }
-                    //        _ => path_to_rdf(p, &mut graph),
-                    //    }),
-                    //}
-                } else {
-                    // No runtime path set; if the source is a PropertyShape, clone from shapes graph.
-                    match context.source_shape() {
-                        SourceShape::PropertyShape(prop_id) => validation_context
-                            .model
-                            .get_prop_shape_by_id(&prop_id)
-                            .map(|ps| {
-                                clone_path_term_from_shapes_graph(
-                                    ps.path_term(),
-                                    validation_context,
-                                    &mut graph,
-                                )
-                            }),
-                        _ => None,
-                    }
-                };
+        let mut graph = stabilize_blank_nodes(&graph, &roots);
+        if let Some(seed) = self.blank_node_seed {
+            graph = relabel_blank_nodes(&graph, |node| {
+                BlankNode::new_unchecked(format!(
+                    "b{:016x}",
+                    xxh3_64_with_seed(node.as_str().as_bytes(), seed)
+                ))
+            });
+        }
+        if !self.keep_skolem_iris {
+            graph = deskolemize_results(&graph, validation_context);
+        }
+        match &self.redaction {
+            Some(redaction) => redaction.graph(&graph, &validation_context.model.prefixes),
+            None => graph,
+        }
+    }
+
+    /// Writes the triples of one result to `graph`, including its nested `sh:detail`
+    /// results.
+    fn insert_result(
+        graph: &mut Graph,
+        result_node: &BlankNode,
+        context: &Context,
+        failure: &ValidationFailure,
+        validation_context: &ValidationContext,
+        blank_nodes: &mut BlankNodeGenerator,
+    ) {
+        let sh = SHACL::get();
+        graph.insert(&Triple::new(
+            result_node.clone(),
+            rdf::TYPE,
+            Term::from(sh.validation_result),
+        ));
 
-                let source_shape_term = context.source_shape().get_term(validation_context);
+        // sh:focusNode
+        graph.insert(&Triple::new(
+            result_node.clone(),
+            sh.focus_node,
+            context.focus_node().clone(),
+        ));
 
-                let source_constraint_component_term = validation_context
-                    .get_component(&failure.component_id)
-                    .map(|component| component.component_type());
+        // sh:resultMessage
+        for message_term in Self::result_messages(context, failure, validation_context) {
+            graph.insert(&Triple::new(
+                result_node.clone(),
+                sh.result_message,
+                message_term,
+            ));
+        }
 
-                if let Some(v) = &failure.failed_value_node {
-                    graph.insert(&Triple::new(result_node.clone(), sh.value, v.clone()));
+        // sh:resultPath
+        let result_path_term = if let Some(path_override) = &failure.result_path {
+            // If the override is a blank node head from the shapes graph, deep-clone its subgraph.
+            // Otherwise, build structurally.
+            Some(match path_override {
+                Path::Simple(t) if matches!(t, Term::BlankNode(_)) => {
+                    clone_path_term_from_shapes_graph(t, validation_context, graph, blank_nodes)
                 }
-
-                if let Some(term) = source_shape_term {
-                    graph.insert(&Triple::new(result_node.clone(), sh.source_shape, term));
+                _ => path_to_rdf(path_override, graph, blank_nodes),
+            })
+        } else if let Some(_p) = context.result_path() {
+            context.result_path().map(|p| match p {
+                Path::Simple(t) if matches!(t, Term::BlankNode(_)) => {
+                    clone_path_term_from_shapes_graph(t, validation_context, graph, blank_nodes)
                 }
+                _ => path_to_rdf(p, graph, blank_nodes),
+            })
+            // Prefer the original shapes-graph term when the source is a PropertyShape.
+            //match context.source_shape() {
+            //    SourceShape::PropertyShape(prop_id) => validation_context
+            //        .model
+            //        .get_prop_shape_by_id(&prop_id)
+            //        .map(|ps| clone_path_term_from_shapes_graph(ps.path_term(), validation_context, &mut graph)),
+            //    // For NodeShape-derived paths (rare), if it's a blank node head from the shapes graph, clone it.
+            //    // Otherwise, fall back to structural build.
+            //    _ => context.result_path().map(|p| match p {
+            //        Path::Simple(t) if matches!(t, Term::BlankNode(_)) => {
+            //            clone_path_term_from_shapes_graph(t, validation_context, &mut graph)
+            //        }
+            //        _ => path_to_rdf(p, &mut graph),
+            //    }),
+            //}
+        } else {
+            // No runtime path set; if the source is a PropertyShape, clone from shapes graph.
+            match context.source_shape() {
+                SourceShape::PropertyShape(prop_id) => validation_context
+                    .model
+                    .get_prop_shape_by_id(&prop_id)
+                    .map(|ps| {
+                        clone_path_term_from_shapes_graph(
+                            ps.path_term(),
+                            validation_context,
+                            graph,
+                            blank_nodes,
+                        )
+                    }),
+                _ => None,
+            }
+        };
 
-                if let Some(term) = result_path_term {
-                    graph.insert(&Triple::new(result_node.clone(), sh.result_path, term));
-                }
+        let source_shape_term = context.source_shape().get_term(validation_context);
 
-                let severity_term = ValidationReportBuilder::severity_term_for_result(
-                    context,
-                    failure,
-                    validation_context,
-                );
-                graph.insert(&Triple::new(
-                    result_node.clone(),
-                    sh.result_severity,
-                    severity_term,
-                ));
-
-                if let Some(term) = source_constraint_component_term {
-                    graph.insert(&Triple::new(
-                        result_node.clone(),
-                        sh.source_constraint_component,
-                        term,
-                    ));
-                }
+        let source_constraint_component_term =
+            validation_context.component_type(&failure.component_id);
 
-                if let Some(term) = &failure.source_constraint {
-                    graph.insert(&Triple::new(
-                        result_node.clone(),
-                        sh.source_constraint,
-                        term.clone(),
-                    ));
-                }
-            }
+        if let Some(v) = &failure.failed_value_node {
+            graph.insert(&Triple::new(result_node.clone(), sh.value, v.clone()));
+        }
+
+        if let Some(term) = source_shape_term {
+            graph.insert(&Triple::new(result_node.clone(), sh.source_shape, term));
         }
 
-        graph
+        if let Some(term) = result_path_term {
+            graph.insert(&Triple::new(result_node.clone(), sh.result_path, term));
+        }
+
+        let severity_term =
+            ValidationReportBuilder::severity_term_for_result(context, failure, validation_context);
+        graph.insert(&Triple::new(
+            result_node.clone(),
+            sh.result_severity,
+            severity_term,
+        ));
+
+        if let Some(term) = source_constraint_component_term {
+            graph.insert(&Triple::new(
+                result_node.clone(),
+                sh.source_constraint_component,
+                term,
+            ));
+        }
+
+        if let Some(term) = &failure.source_constraint {
+            graph.insert(&Triple::new(
+                result_node.clone(),
+                sh.source_constraint,
+                term.clone(),
+            ));
+        }
+
+        for (index, (detail_context, detail_failure)) in failure.details.iter().enumerate() {
+            let detail_node =
+                BlankNode::new_unchecked(format!("{}d{}", result_node.as_str(), index));
+            graph.insert(&Triple::new(
+                result_node.clone(),
+                sh.detail,
+                Term::from(detail_node.clone()),
+            ));
+            Self::insert_result(
+                graph,
+                &detail_node,
+                detail_context,
+                detail_failure,
+                validation_context,
+                blank_nodes,
+            );
+        }
     }
 
     /// Serializes the validation report to a string in the specified RDF format.
@@ -365,13 +1361,34 @@ impl ValidationReportBuilder {
             .with_prefix("rdfs", "http://www.w3.org/2000/01/rdf-schema#")?
             .for_writer(&mut writer);
 
-        for triple in graph.iter() {
-            serializer.serialize_triple(triple)?;
+        if format.supports_datasets() {
+            let report_graph = self.report_graph();
+            for triple in sorted_triples(&graph) {
+                serializer.serialize_quad(triple.in_graph(report_graph.as_ref()))?;
+            }
+        } else {
+            for triple in sorted_triples(&graph) {
+                serializer.serialize_triple(triple)?;
+            }
         }
         serializer.finish()?;
         Ok(String::from_utf8(writer)?)
     }
 
+    pub(crate) fn report_graph(&self) -> NamedNode {
+        self.report_graph
+            .clone()
+            .unwrap_or_else(|| NamedNode::new_unchecked(DEFAULT_REPORT_GRAPH))
+    }
+
+    pub(crate) fn to_quads(&self, validation_context: &ValidationContext) -> Vec<Quad> {
+        let report_graph = self.report_graph();
+        sorted_triples(&self.to_graph(validation_context))
+            .into_iter()
+            .map(|triple| triple.in_graph(report_graph.as_ref()).into_owned())
+            .collect()
+    }
+
     /// Serializes the validation report to a string in Turtle format.
     pub(crate) fn to_turtle(
         &self,
@@ -390,6 +1407,29 @@ impl ValidationReportBuilder {
     /// The execution trace is a sequential log of the validation steps (`NodeShape`,
     /// `PropertyShape`, and `Component` visitations) that occurred before the failure.
     /// This is invaluable for debugging complex shapes.
+    pub(crate) fn result_codes(&self, validation_context: &ValidationContext) -> Vec<ResultCode> {
+        self.results
+            .iter()
+            .filter_map(|(context, failure)| {
+                let component = validation_context.component_type(&failure.component_id)?;
+                let focus_node = if self.keep_skolem_iris {
+                    context.focus_node().clone()
+                } else {
+                    validation_context.deskolemize_data_term(context.focus_node())
+                };
+                let focus_node = match &self.redaction {
+                    Some(redaction) => redaction.term(&focus_node),
+                    None => focus_node,
+                };
+                Some(ResultCode {
+                    focus_node,
+                    code: ErrorCode::for_component(component.as_ref()),
+                    component,
+                })
+            })
+            .collect()
+    }
+
     pub(crate) fn dump(&self, validation_context: &ValidationContext) {
         if self.results.is_empty() {
             println!("Validation report: No errors found.");
@@ -399,27 +1439,102 @@ impl ValidationReportBuilder {
         println!("Validation Report:");
         println!("------------------");
 
-        let mut grouped_errors: HashMap<Term, Vec<(&Context, &ValidationFailure)>> = HashMap::new();
+        // Results are in canonical order, so focus nodes are listed sorted and each focus
+        // node's results are sorted by path and component.
+        let mut grouped_errors: Vec<(Term, Vec<(&Context, &ValidationFailure)>)> = Vec::new();
+        let mut group_index: HashMap<Term, usize> = HashMap::new();
 
         for (context, failure) in &self.results {
-            grouped_errors
+            let index = *group_index
                 .entry(context.focus_node().clone())
-                .or_default()
-                .push((context, failure));
+                .or_insert_with(|| {
+                    grouped_errors.push((context.focus_node().clone(), Vec::new()));
+                    grouped_errors.len() - 1
+                });
+            grouped_errors[index].1.push((context, failure));
         }
 
+        let aggregated = self.aggregated_groups();
+        let prefixes = &validation_context.model.prefixes;
         let traces = validation_context.execution_traces.borrow();
+        let redaction = self.redaction.as_ref();
         for (focus_node, context_failure_pairs) in grouped_errors {
-            println!("\nFocus Node: {}", focus_node);
+            let shown_focus_node = match redaction {
+                Some(redaction) => redaction.term(&focus_node),
+                None => focus_node.clone(),
+            };
+            println!("\nFocus Node: {}", prefixes.compact(&shown_focus_node));
+            if aggregated
+                .iter()
+                .any(|(aggregated_node, _)| **aggregated_node == focus_node)
+            {
+                let mut components: Vec<String> = Vec::new();
+                for (_, failure) in &context_failure_pairs {
+                    if let Some(component) =
+                        validation_context.component_type(&failure.component_id)
+                    {
+                        let code = ErrorCode::for_component(component.as_ref()).to_string();
+                        if !components.contains(&code) {
+                            components.push(code);
+                        }
+                    }
+                }
+                println!(
+                    "  - {} results aggregated: {}",
+                    context_failure_pairs.len(),
+                    components.join(", ")
+                );
+                continue;
+            }
             for (context, failure) in context_failure_pairs {
-                println!("  - Error: {}", failure.message);
+                let message = match redaction {
+                    Some(redaction) => {
+                        let terms: Vec<&Term> = std::iter::once(context.focus_node())
+                            .chain(failure.failed_value_node.as_ref())
+                            .collect();
+                        redaction.text(&failure.message, &terms, prefixes)
+                    }
+                    None => failure.message.clone(),
+                };
+                match validation_context.component_type(&failure.component_id) {
+                    Some(component) => println!(
+                        "  - Error [{}]: {}",
+                        ErrorCode::for_component(component.as_ref()),
+                        message
+                    ),
+                    None => println!("  - Error: {}", message),
+                }
                 if let Some(source_shape_term) = context.source_shape().get_term(validation_context)
                 {
-                    println!("    From shape: {}", source_shape_term);
+                    println!("    From shape: {}", prefixes.compact(&source_shape_term));
                 } else {
                     println!("    From shape: {}", context.source_shape());
                 }
 
+                if let Some(limit) = self.focus_context_limit {
+                    let result = self.typed_result(context, failure, validation_context);
+                    match focus_node_context(
+                        validation_context,
+                        context.focus_node(),
+                        result.path.as_ref(),
+                        limit,
+                    ) {
+                        Ok(focus_context) => {
+                            let focus_context = match redaction {
+                                Some(redaction) => redaction.focus_node_context(focus_context),
+                                None => focus_context,
+                            };
+                            print_focus_node_context(
+                                &shown_focus_node,
+                                &result,
+                                &focus_context,
+                                prefixes,
+                            )
+                        }
+                        Err(e) => println!("    Context unavailable: {}", e),
+                    }
+                }
+
                 println!("    Trace:");
                 if let Some(trace) = traces.get(context.trace_index()) {
                     for item in trace {
@@ -470,6 +1585,35 @@ impl ValidationReportBuilder {
         }
     }
 
+    /// Records why the run stopped early, if it did.
+    pub(crate) fn with_stop_reason(mut self, stop_reason: Option<StopReason>) -> Self {
+        self.stop_reason = stop_reason;
+        self
+    }
+
+    /// Marks this builder as covering only `focus_nodes`.
+    pub(crate) fn with_revalidated(mut self, focus_nodes: HashSet<Term>) -> Self {
+        self.revalidated = Some(focus_nodes);
+        self
+    }
+
+    /// Replaces the results of the focus nodes `partial` covers with its results; a
+    /// report covering every focus node replaces all results.
+    pub(crate) fn merge_revalidation(&mut self, partial: ValidationReportBuilder) {
+        match &partial.revalidated {
+            Some(focus_nodes) => {
+                self.results
+                    .retain(|(context, _)| !focus_nodes.contains(context.focus_node()));
+                self.results.extend(partial.results);
+                self.stop_reason = partial.stop_reason.or(self.stop_reason);
+            }
+            None => {
+                self.results = partial.results;
+                self.stop_reason = partial.stop_reason;
+            }
+        }
+    }
+
     /// Merges results from another `ValidationReportBuilder` into this one.
     #[allow(dead_code)]
     pub(crate) fn merge(&mut self, other: ValidationReportBuilder) {
@@ -477,6 +1621,284 @@ impl ValidationReportBuilder {
     }
 }
 
+/// The `sh:resultPath` of a result: the path a constraint reported, else the path of the
+/// property shape it belongs to.
+fn result_path(
+    context: &Context,
+    failure: &ValidationFailure,
+    validation_context: &ValidationContext,
+) -> Option<Path> {
+    failure
+        .result_path
+        .clone()
+        .or_else(|| context.result_path().cloned())
+        .or_else(|| match context.source_shape() {
+            SourceShape::PropertyShape(prop_id) => validation_context
+                .model
+                .get_prop_shape_by_id(&prop_id)
+                .map(|ps| ps.path().clone()),
+            SourceShape::NodeShape(_) => None,
+        })
+}
+
+/// Orders results by focus node, path and constraint component, with the remaining
+/// fields breaking ties.
+fn result_sort_key(
+    context: &Context,
+    failure: &ValidationFailure,
+    validation_context: &ValidationContext,
+) -> Vec<String> {
+    let path = result_path(context, failure, validation_context).map(|path| {
+        path.to_sparql_path()
+            .unwrap_or_else(|_| format!("{:?}", path))
+    });
+    let message_terms: Vec<String> = failure
+        .message_terms
+        .iter()
+        .map(|m| m.to_string())
+        .collect();
+    let mut key = vec![
+        context.focus_node().to_string(),
+        path.unwrap_or_default(),
+        optional_string(&validation_context.component_type(&failure.component_id)),
+        optional_string(&context.source_shape().get_term(validation_context)),
+        optional_string(&failure.failed_value_node),
+        message_terms.join("\n"),
+        optional_string(&failure.source_constraint),
+        format!("{:?}", failure.severity),
+    ];
+    for (detail_context, detail_failure) in &failure.details {
+        key.push(result_sort_key(detail_context, detail_failure, validation_context).join("\t"));
+    }
+    key
+}
+
+fn optional_string(value: &Option<impl ToString>) -> String {
+    value.as_ref().map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Reads the context of a result from the data graph; see [`FocusNodeContext`].
+fn focus_node_context(
+    validation_context: &ValidationContext,
+    focus_node: &Term,
+    path: Option<&Path>,
+    limit: usize,
+) -> Result<FocusNodeContext, String> {
+    let mut focus_context = FocusNodeContext::default();
+    let subject = match focus_node {
+        Term::NamedNode(node) => SubjectRef::NamedNode(node.as_ref()),
+        _ => return Ok(focus_context),
+    };
+
+    let data_graph = validation_context.data_graph_iri_ref();
+    for quad in validation_context
+        .model
+        .store()
+        .quads_for_pattern(Some(subject), Some(rdf::TYPE), None, Some(data_graph))
+        .take(limit + 1)
+    {
+        focus_context
+            .types
+            .push(quad.map_err(|e| e.to_string())?.object);
+    }
+
+    if let Some(path) = path {
+        let query = format!(
+            "SELECT DISTINCT ?value WHERE {{ GRAPH {} {{ {} {} ?value . }} }} LIMIT {}",
+            validation_context.data_graph_iri,
+            focus_node,
+            path.to_sparql_path()?,
+            limit + 1
+        );
+        let sparql = &validation_context.model.sparql;
+        let prepared = sparql.prepared_query(&query)?;
+        if let QueryResults::Solutions(solutions) = sparql.execute_with_substitutions(
+            &query,
+            &prepared,
+            validation_context.model.store(),
+            &[],
+            false,
+        )? {
+            for solution in solutions {
+                if let Some(value) = solution.map_err(|e| e.to_string())?.get("value") {
+                    focus_context.values.push(value.clone());
+                }
+            }
+        }
+    }
+
+    for terms in [&mut focus_context.types, &mut focus_context.values] {
+        terms.sort_by_key(|term| term.to_string());
+        if terms.len() > limit {
+            terms.truncate(limit);
+            focus_context.truncated = true;
+        }
+    }
+    Ok(focus_context)
+}
+
+/// Prints a result's [`FocusNodeContext`] as Turtle-like triples for `dump`.
+fn print_focus_node_context(
+    focus_node: &Term,
+    result: &ValidationResult,
+    focus_context: &FocusNodeContext,
+    prefixes: &PrefixMap,
+) {
+    println!("    Context:");
+    let subject = prefixes.compact(focus_node);
+    for type_ in &focus_context.types {
+        println!("      {} a {} .", subject, prefixes.compact(type_));
+    }
+    if let Some(path) = &result.path {
+        let predicate = match path {
+            Path::Simple(term) => prefixes.compact(term),
+            other => other.to_sparql_path().unwrap_or_default(),
+        };
+        for value in &focus_context.values {
+            println!(
+                "      {} {} {} .",
+                subject,
+                predicate,
+                prefixes.compact(value)
+            );
+        }
+    }
+    if focus_context.truncated {
+        println!("      ...");
+    }
+}
+
+/// Gives the blank nodes reachable from `roots` (which keep their labels) sequential
+/// labels in traversal order, visiting each node's blank objects by predicate, so that
+/// labels follow the report's structure rather than the order it was built in.
+fn stabilize_blank_nodes(graph: &Graph, roots: &[Subject]) -> Graph {
+    let mut labels: HashMap<BlankNode, BlankNode> = HashMap::new();
+    let mut queue: VecDeque<BlankNode> = VecDeque::new();
+    for root in roots {
+        if let Subject::BlankNode(node) = root {
+            labels.insert(node.clone(), node.clone());
+            queue.push_back(node.clone());
+        }
+    }
+    let mut next = 0;
+    while let Some(node) = queue.pop_front() {
+        let mut objects: Vec<(&str, BlankNode)> = graph
+            .triples_for_subject(node.as_ref())
+            .filter_map(|triple| match triple.object {
+                TermRef::BlankNode(object) => {
+                    Some((triple.predicate.as_str(), object.into_owned()))
+                }
+                _ => None,
+            })
+            .collect();
+        objects.sort_by(|a, b| a.0.cmp(b.0));
+        for (_, object) in objects {
+            if !labels.contains_key(&object) {
+                labels.insert(
+                    object.clone(),
+                    BlankNode::new_unchecked(format!("p{}", next)),
+                );
+                next += 1;
+                queue.push_back(object);
+            }
+        }
+    }
+    relabel_blank_nodes(graph, |node| {
+        labels.get(node).cloned().unwrap_or_else(|| node.clone())
+    })
+}
+
+/// Copies `graph`, replacing each blank node by `label(node)`, called once per node.
+fn relabel_blank_nodes(graph: &Graph, mut label: impl FnMut(&BlankNode) -> BlankNode) -> Graph {
+    let mut mapping: HashMap<BlankNode, BlankNode> = HashMap::new();
+    let mut relabel = |node: BlankNode| {
+        mapping
+            .entry(node)
+            .or_insert_with_key(|node| label(node))
+            .clone()
+    };
+    let mut relabeled = Graph::new();
+    for triple in graph.iter() {
+        let subject: Subject = match triple.subject {
+            SubjectRef::BlankNode(node) => relabel(node.into_owned()).into(),
+            subject => subject.into_owned(),
+        };
+        let object: Term = match triple.object {
+            TermRef::BlankNode(node) => relabel(node.into_owned()).into(),
+            object => object.into_owned(),
+        };
+        relabeled.insert(&Triple::new(subject, triple.predicate.into_owned(), object));
+    }
+    relabeled
+}
+
+/// Turns the data-graph skolem IRIs in `sh:focusNode` and `sh:value` back into blank
+/// nodes. Runs after the report's own blank nodes are labelled, so the data graph's
+/// labels are kept.
+fn deskolemize_results(graph: &Graph, validation_context: &ValidationContext) -> Graph {
+    let sh = SHACL::get();
+    let mut deskolemized = Graph::new();
+    for triple in graph.iter() {
+        let object = triple.object.into_owned();
+        let object = if triple.predicate == sh.focus_node || triple.predicate == sh.value {
+            validation_context.deskolemize_data_term(&object)
+        } else {
+            object
+        };
+        deskolemized.insert(&Triple::new(
+            triple.subject.into_owned(),
+            triple.predicate.into_owned(),
+            object,
+        ));
+    }
+    deskolemized
+}
+
+/// Turns the data-graph skolem IRIs of a typed result and its details back into blank
+/// nodes, as [`deskolemize_results`] does for the report graph.
+fn deskolemize_result(
+    result: ValidationResult,
+    validation_context: &ValidationContext,
+) -> ValidationResult {
+    ValidationResult {
+        focus_node: validation_context.deskolemize_data_term(&result.focus_node),
+        value: result
+            .value
+            .as_ref()
+            .map(|value| validation_context.deskolemize_data_term(value)),
+        details: result
+            .details
+            .into_iter()
+            .map(|detail| deskolemize_result(detail, validation_context))
+            .collect(),
+        ..result
+    }
+}
+
+/// Labels the blank nodes built while writing results (path structures and RDF lists) in
+/// creation order, so the same report always builds the same graph.
+#[derive(Default)]
+struct BlankNodeGenerator {
+    next: usize,
+}
+
+impl BlankNodeGenerator {
+    fn fresh(&mut self) -> BlankNode {
+        let node = BlankNode::new_unchecked(format!("g{}", self.next));
+        self.next += 1;
+        node
+    }
+}
+
+/// The triples of `graph` ordered by their N-Triples form. A `Graph` iterates in an order
+/// that depends on its term interner, which differs between graphs, so serializations
+/// sort first to be identical byte for byte.
+fn sorted_triples(graph: &Graph) -> Vec<TripleRef<'_>> {
+    let mut triples: Vec<TripleRef<'_>> = graph.iter().collect();
+    triples.sort_by_cached_key(|triple| triple.to_string());
+    triples
+}
+
 fn severity_to_term(severity: &Severity, sh: &SHACL) -> Term {
     match severity {
         Severity::Info => Term::from(sh.info),
@@ -487,84 +1909,102 @@ fn severity_to_term(severity: &Severity, sh: &SHACL) -> Term {
 }
 
 #[allow(dead_code)]
-fn result_path_term_for_property_shape(path: &Path, graph: &mut Graph) -> Term {
+fn result_path_term_for_property_shape(
+    path: &Path,
+    graph: &mut Graph,
+    blank_nodes: &mut BlankNodeGenerator,
+) -> Term {
     match path {
-        Path::Sequence(elements) => build_list_minimal(elements, graph),
+        Path::Sequence(elements) => build_list_minimal(elements, graph, blank_nodes),
         Path::Alternative(options) => {
-            let sh = SHACL::new();
-            let head = BlankNode::default();
+            let sh = SHACL::get();
+            let head = blank_nodes.fresh();
             let head_subject: Subject = head.clone().into();
-            let list_head = build_list_minimal(options, graph);
+            let list_head = build_list_minimal(options, graph, blank_nodes);
             graph.insert(&Triple::new(head_subject, sh.alternative_path, list_head));
             head.into()
         }
-        _ => path_to_rdf(path, graph),
+        _ => path_to_rdf(path, graph, blank_nodes),
     }
 }
 
 #[allow(dead_code)]
-fn build_list_minimal(elements: &[Path], graph: &mut Graph) -> Term {
-    let head_bnode = BlankNode::default();
+fn build_list_minimal(
+    elements: &[Path],
+    graph: &mut Graph,
+    blank_nodes: &mut BlankNodeGenerator,
+) -> Term {
+    let head_bnode = blank_nodes.fresh();
     let head_subject: Subject = head_bnode.clone().into();
 
     if let Some(first) = elements.first() {
-        let first_term = path_to_rdf(first, graph);
+        let first_term = path_to_rdf(first, graph, blank_nodes);
         graph.insert(&Triple::new(head_subject.clone(), rdf::FIRST, first_term));
     }
 
     let rest_term: Term = if elements.is_empty() {
         rdf::NIL.into()
     } else {
-        Term::from(BlankNode::default())
+        Term::from(blank_nodes.fresh())
     };
 
     graph.insert(&Triple::new(head_subject, rdf::REST, rest_term.clone()));
     head_bnode.into()
 }
 
-fn path_to_rdf(path: &Path, graph: &mut Graph) -> Term {
-    let sh = SHACL::new();
+fn path_to_rdf(path: &Path, graph: &mut Graph, blank_nodes: &mut BlankNodeGenerator) -> Term {
+    let sh = SHACL::get();
     match path {
         Path::Simple(term) => term.clone(),
         Path::Inverse(inner) => {
-            let bn: Subject = BlankNode::default().into();
-            let inner_term = path_to_rdf(inner, graph);
+            let bn: Subject = blank_nodes.fresh().into();
+            let inner_term = path_to_rdf(inner, graph, blank_nodes);
             graph.insert(&Triple::new(bn.clone(), sh.inverse_path, inner_term));
             bn.into()
         }
         Path::Sequence(paths) => {
-            let items: Vec<Term> = paths.iter().map(|p| path_to_rdf(p, graph)).collect();
-            build_rdf_list(items, graph)
+            let items: Vec<Term> = paths
+                .iter()
+                .map(|p| path_to_rdf(p, graph, blank_nodes))
+                .collect();
+            build_rdf_list(items, graph, blank_nodes)
         }
         Path::Alternative(paths) => {
-            let bn: Subject = BlankNode::default().into();
-            let items: Vec<Term> = paths.iter().map(|p| path_to_rdf(p, graph)).collect();
-            let list_head = build_rdf_list(items, graph);
+            let bn: Subject = blank_nodes.fresh().into();
+            let items: Vec<Term> = paths
+                .iter()
+                .map(|p| path_to_rdf(p, graph, blank_nodes))
+                .collect();
+            let list_head = build_rdf_list(items, graph, blank_nodes);
             graph.insert(&Triple::new(bn.clone(), sh.alternative_path, list_head));
             bn.into()
         }
         Path::ZeroOrMore(inner) => {
-            let bn: Subject = BlankNode::default().into();
-            let inner_term = path_to_rdf(inner, graph);
+            let bn: Subject = blank_nodes.fresh().into();
+            let inner_term = path_to_rdf(inner, graph, blank_nodes);
             graph.insert(&Triple::new(bn.clone(), sh.zero_or_more_path, inner_term));
             bn.into()
         }
         Path::OneOrMore(inner) => {
-            let bn: Subject = BlankNode::default().into();
-            let inner_term = path_to_rdf(inner, graph);
+            let bn: Subject = blank_nodes.fresh().into();
+            let inner_term = path_to_rdf(inner, graph, blank_nodes);
             graph.insert(&Triple::new(bn.clone(), sh.one_or_more_path, inner_term));
             bn.into()
         }
         Path::ZeroOrOne(inner) => {
-            let bn: Subject = BlankNode::default().into();
-            let inner_term = path_to_rdf(inner, graph);
+            let bn: Subject = blank_nodes.fresh().into();
+            let inner_term = path_to_rdf(inner, graph, blank_nodes);
             graph.insert(&Triple::new(bn.clone(), sh.zero_or_one_path, inner_term));
             bn.into()
         }
     }
 }
 
-fn build_rdf_list(items: impl IntoIterator<Item = Term>, graph: &mut Graph) -> Term {
+fn build_rdf_list(
+    items: impl IntoIterator<Item = Term>,
+    graph: &mut Graph,
+    blank_nodes: &mut BlankNodeGenerator,
+) -> Term {
     let head: Subject = rdf::NIL.into();
 
     let items: Vec<Term> = items.into_iter().collect();
@@ -573,7 +2013,7 @@ fn build_rdf_list(items: impl IntoIterator<Item = Term>, graph: &mut Graph) -> T
     }
 
     let bnodes: Vec<NamedOrBlankNode> = (0..items.len())
-        .map(|_| BlankNode::default().into())
+        .map(|_| blank_nodes.fresh().into())
         .collect();
     let head: Subject = bnodes[0].clone();
 
@@ -596,13 +2036,20 @@ fn clone_path_term_from_shapes_graph(
     term: &Term,
     validation_context: &ValidationContext,
     out_graph: &mut Graph,
+    blank_nodes: &mut BlankNodeGenerator,
 ) -> Term {
     let mut memo: HashMap<Term, Term> = HashMap::new();
-    clone_path_term_from_shapes_graph_inner(term, validation_context, out_graph, &mut memo)
+    clone_path_term_from_shapes_graph_inner(
+        term,
+        validation_context,
+        out_graph,
+        &mut memo,
+        blank_nodes,
+    )
 }
 
 fn fetch_shape_messages(validation_context: &ValidationContext, term: &Term) -> Vec<Term> {
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     if let Some(subject_ref) = term_to_subject_ref(term) {
         validation_context
             .model
@@ -634,6 +2081,7 @@ fn clone_path_term_from_shapes_graph_inner(
     validation_context: &ValidationContext,
     out_graph: &mut Graph,
     memo: &mut HashMap<Term, Term>,
+    blank_nodes: &mut BlankNodeGenerator,
 ) -> Term {
     if !matches!(term, Term::BlankNode(_)) {
         return term.clone();
@@ -643,11 +2091,11 @@ fn clone_path_term_from_shapes_graph_inner(
         return mapped.clone();
     }
 
-    let new_bn_term: Term = BlankNode::default().into();
+    let new_bn_term: Term = blank_nodes.fresh().into();
     memo.insert(term.clone(), new_bn_term.clone());
 
     let store = validation_context.model.store();
-    let sh = SHACL::new();
+    let sh = SHACL::get();
 
     let subject_ref = match term {
         Term::BlankNode(b) => SubjectRef::BlankNode(b.as_ref()),
@@ -676,6 +2124,7 @@ fn clone_path_term_from_shapes_graph_inner(
                 validation_context,
                 out_graph,
                 memo,
+                blank_nodes,
             );
 
             let new_subject: Subject = match &new_bn_term {
@@ -690,3 +2139,65 @@ fn clone_path_term_from_shapes_graph_inner(
 
     new_bn_term
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ComponentID, ID};
+    use std::thread;
+
+    fn shard(shape: &str, focus_nodes: &[&str]) -> ReportShard {
+        let mut shard = ReportShard::new(ShardKey::shape(shape.to_string(), false));
+        for focus in focus_nodes {
+            let context = Context::new(
+                NamedNode::new_unchecked(*focus).into(),
+                None,
+                None,
+                SourceShape::NodeShape(ID(0)),
+                0,
+            );
+            let failure =
+                ValidationFailure::new(ComponentID(0), None, focus.to_string(), None, None);
+            shard.add_failure(&context, failure);
+        }
+        shard
+    }
+
+    fn messages(builder: &ValidationReportBuilder) -> Vec<String> {
+        builder
+            .results()
+            .iter()
+            .map(|(_, failure)| failure.message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn shards_merge_in_key_order_regardless_of_completion_order() {
+        let workers: Vec<_> = [
+            ("urn:b", vec!["urn:b2", "urn:b1"]),
+            ("urn:a", vec!["urn:a1"]),
+        ]
+        .into_iter()
+        .map(|(shape, focus_nodes)| thread::spawn(move || shard(shape, &focus_nodes)))
+        .collect();
+        let shards: Vec<ReportShard> = workers
+            .into_iter()
+            .rev()
+            .map(|worker| worker.join().unwrap())
+            .collect();
+
+        let merged = ValidationReportBuilder::from_shards(shards);
+        // Shards sort by key; results inside a shard keep their insertion order.
+        assert_eq!(messages(&merged), vec!["urn:a1", "urn:b2", "urn:b1"]);
+    }
+
+    #[test]
+    fn node_shape_shard_precedes_property_shape_with_same_term() {
+        let mut property = ReportShard::new(ShardKey::shape("urn:s".to_string(), true));
+        property.results = shard("urn:s", &["urn:p"]).results;
+        let node = shard("urn:s", &["urn:n"]);
+
+        let merged = ValidationReportBuilder::from_shards([property, node]);
+        assert_eq!(messages(&merged), vec!["urn:n", "urn:p"]);
+    }
+}
@@ -2,9 +2,9 @@
 use crate::context::{Context, ValidationContext};
 use crate::runtime::validators::{
     AndConstraintComponent, ClassConstraintComponent, ClosedConstraintComponent,
-    CustomConstraintComponent, DatatypeConstraintComponent, DisjointConstraintComponent,
-    EqualsConstraintComponent, HasValueConstraintComponent, InConstraintComponent,
-    LanguageInConstraintComponent, LessThanConstraintComponent,
+    CustomConstraintComponent, DatatypeConstraintComponent, DatatypeInConstraintComponent,
+    DisjointConstraintComponent, EqualsConstraintComponent, HasValueConstraintComponent,
+    InConstraintComponent, LanguageInConstraintComponent, LessThanConstraintComponent,
     LessThanOrEqualsConstraintComponent, MaxCountConstraintComponent,
     MaxExclusiveConstraintComponent, MaxInclusiveConstraintComponent, MaxLengthConstraintComponent,
     MinCountConstraintComponent, MinExclusiveConstraintComponent, MinInclusiveConstraintComponent,
@@ -14,8 +14,9 @@ use crate::runtime::validators::{
     UniqueLangConstraintComponent, XoneConstraintComponent,
 };
 use crate::shape::NodeShape;
-use crate::types::{ComponentID, Path, Severity, TraceItem};
+use crate::types::{ComponentID, Path, Severity, TraceItem, ID};
 use oxigraph::model::{NamedNode, NamedOrBlankNodeRef as SubjectRef, Term, TermRef};
+use std::time::Instant;
 
 /// The result of validating a single value node against a constraint component.
 #[derive(Debug, Clone)]
@@ -32,8 +33,37 @@ pub(crate) enum ComponentValidationResult {
 pub(crate) enum ConformanceReport {
     /// The node conforms to the shape.
     Conforms,
-    /// The node does not conform to the shape, with details of the first failure.
-    NonConforms(ValidationFailure),
+    /// The node does not conform to the shape, with the first failure and its context.
+    NonConforms(Context, ValidationFailure),
+}
+
+/// Controls how much work a validation pass performs once a failure is found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub(crate) enum ConformanceMode {
+    /// Collect every validation result; used when building a validation report.
+    #[default]
+    Report,
+    /// Only the boolean conformance outcome matters (`sh:node`, `sh:or` members, rule
+    /// conditions), so evaluation stops at the first violating component or value node.
+    FirstFailure,
+}
+
+impl ConformanceMode {
+    /// Returns `true` when evaluation should stop after the first failure.
+    pub(crate) fn stops_at_first_failure(self) -> bool {
+        matches!(self, ConformanceMode::FirstFailure)
+    }
+}
+
+/// How a component reads the value nodes of a property shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ValueNodeUse {
+    /// Each value node is checked on its own, so value nodes can be evaluated in chunks.
+    Each,
+    /// Only the number of value nodes matters.
+    Count,
+    /// The value nodes are compared with each other or with other values as a set.
+    All,
 }
 
 /// Details about a single validation failure.
@@ -53,6 +83,9 @@ pub(crate) struct ValidationFailure {
     pub severity: Option<Severity>,
     /// RDF message terms contributed by the constraint or validator.
     pub message_terms: Vec<Term>,
+    /// Failures of nested shapes that explain this one, reported as `sh:detail`. Only
+    /// shape-based constraints fill them in, and only when the run asks for result details.
+    pub details: Vec<(Context, ValidationFailure)>,
 }
 
 impl ValidationFailure {
@@ -72,6 +105,7 @@ impl ValidationFailure {
             source_constraint,
             severity: None,
             message_terms: Vec::new(),
+            details: Vec::new(),
         }
     }
 
@@ -161,6 +195,8 @@ pub(crate) enum Component {
     ClassConstraint(ClassConstraintComponent),
     /// `sh:datatype`
     DatatypeConstraint(DatatypeConstraintComponent),
+    /// `shrs:datatypeIn`
+    DatatypeInConstraint(DatatypeInConstraintComponent),
     /// `sh:nodeKind`
     NodeKindConstraint(NodeKindConstraintComponent),
     /// `sh:minCount`
@@ -222,6 +258,7 @@ impl Component {
             Component::QualifiedValueShape(_) => "QualifiedValueShape".to_string(),
             Component::ClassConstraint(_) => "ClassConstraint".to_string(),
             Component::DatatypeConstraint(_) => "DatatypeConstraint".to_string(),
+            Component::DatatypeInConstraint(_) => "DatatypeInConstraint".to_string(),
             Component::NodeKindConstraint(_) => "NodeKindConstraint".to_string(),
             Component::MinCount(_) => "MinCount".to_string(),
             Component::MaxCount(_) => "MaxCount".to_string(),
@@ -250,6 +287,42 @@ impl Component {
         }
     }
 
+    /// Returns how the component reads the value nodes of a property shape.
+    pub(crate) fn value_node_use(&self) -> ValueNodeUse {
+        match self {
+            Component::MinCount(_) | Component::MaxCount(_) => ValueNodeUse::Count,
+            Component::NodeConstraint(_)
+            | Component::PropertyConstraint(_)
+            | Component::ClassConstraint(_)
+            | Component::DatatypeConstraint(_)
+            | Component::DatatypeInConstraint(_)
+            | Component::NodeKindConstraint(_)
+            | Component::MinExclusiveConstraint(_)
+            | Component::MinInclusiveConstraint(_)
+            | Component::MaxExclusiveConstraint(_)
+            | Component::MaxInclusiveConstraint(_)
+            | Component::MinLengthConstraint(_)
+            | Component::MaxLengthConstraint(_)
+            | Component::PatternConstraint(_)
+            | Component::LanguageInConstraint(_)
+            | Component::NotConstraint(_)
+            | Component::AndConstraint(_)
+            | Component::OrConstraint(_)
+            | Component::XoneConstraint(_)
+            | Component::ClosedConstraint(_)
+            | Component::InConstraint(_) => ValueNodeUse::Each,
+            Component::QualifiedValueShape(_)
+            | Component::UniqueLangConstraint(_)
+            | Component::EqualsConstraint(_)
+            | Component::DisjointConstraint(_)
+            | Component::LessThanConstraint(_)
+            | Component::LessThanOrEqualsConstraint(_)
+            | Component::HasValueConstraint(_)
+            | Component::SPARQLConstraint(_)
+            | Component::CustomConstraint(_) => ValueNodeUse::All,
+        }
+    }
+
     /// Delegates to the inner component to get its SHACL IRI type.
     pub(crate) fn component_type(&self) -> NamedNode {
         match self {
@@ -258,6 +331,7 @@ impl Component {
             Component::QualifiedValueShape(c) => c.component_type(),
             Component::ClassConstraint(c) => c.component_type(),
             Component::DatatypeConstraint(c) => c.component_type(),
+            Component::DatatypeInConstraint(c) => c.component_type(),
             Component::NodeKindConstraint(c) => c.component_type(),
             Component::MinCount(c) => c.component_type(),
             Component::MaxCount(c) => c.component_type(),
@@ -298,6 +372,7 @@ impl Component {
             Component::QualifiedValueShape(c) => c.to_graphviz_string(component_id, context),
             Component::ClassConstraint(c) => c.to_graphviz_string(component_id, context),
             Component::DatatypeConstraint(c) => c.to_graphviz_string(component_id, context),
+            Component::DatatypeInConstraint(c) => c.to_graphviz_string(component_id, context),
             Component::NodeKindConstraint(c) => c.to_graphviz_string(component_id, context),
             Component::MinCount(c) => c.to_graphviz_string(component_id, context),
             Component::MaxCount(c) => c.to_graphviz_string(component_id, context),
@@ -334,13 +409,23 @@ impl Component {
         context: &ValidationContext,
         trace: &mut Vec<TraceItem>,
     ) -> Result<Vec<ComponentValidationResult>, String> {
+        if !context.sparql_constraints_enabled.get()
+            && matches!(
+                self,
+                Component::SPARQLConstraint(_) | Component::CustomConstraint(_)
+            )
+        {
+            return Ok(Vec::new());
+        }
         trace.push(TraceItem::Component(component_id));
-        match self {
+        let started = Instant::now();
+        let result = match self {
             Component::ClassConstraint(comp) => comp.validate(component_id, c, context, trace),
             Component::NodeConstraint(comp) => comp.validate(component_id, c, context, trace),
             Component::PropertyConstraint(comp) => comp.validate(component_id, c, context, trace),
             Component::QualifiedValueShape(comp) => comp.validate(component_id, c, context, trace),
             Component::DatatypeConstraint(comp) => comp.validate(component_id, c, context, trace),
+            Component::DatatypeInConstraint(comp) => comp.validate(component_id, c, context, trace),
             Component::NodeKindConstraint(comp) => comp.validate(component_id, c, context, trace),
             Component::MinCount(comp) => comp.validate(component_id, c, context, trace),
             Component::MaxCount(comp) => comp.validate(component_id, c, context, trace),
@@ -376,7 +461,12 @@ impl Component {
             }
             Component::ClosedConstraint(comp) => comp.validate(component_id, c, context, trace),
             Component::CustomConstraint(comp) => comp.validate(component_id, c, context, trace),
+        };
+        context.record_duration(TraceItem::Component(component_id), started.elapsed());
+        if let Some(audit_log) = context.audit_log.borrow_mut().as_mut() {
+            audit_log.record(self, component_id, c, &result, context);
         }
+        result
     }
 }
 
@@ -391,8 +481,77 @@ pub(crate) fn check_conformance_for_node(
         return Ok(ConformanceReport::Conforms);
     }
 
-    trace.push(TraceItem::NodeShape(*shape_to_check_against.identifier()));
+    let shape_id = *shape_to_check_against.identifier();
+    let focus_node = node_as_context.focus_node().clone();
+    if let Some(cached) = main_validation_context.cached_conformance(&focus_node, shape_id) {
+        return Ok(cached);
+    }
+
+    let shape_item = TraceItem::NodeShape(shape_id);
+    trace.push(shape_item.clone());
+    node_as_context.set_conformance_mode(ConformanceMode::FirstFailure);
 
+    let started = Instant::now();
+    let outcome = first_nonconformance(
+        node_as_context,
+        shape_to_check_against,
+        main_validation_context,
+        trace,
+    );
+    main_validation_context.record_duration(shape_item, started.elapsed());
+    if let Ok(report) = &outcome {
+        main_validation_context.store_conformance(focus_node, shape_id, report.clone());
+    }
+    outcome
+}
+
+/// Caches whether `focus_node` conforms to the node shape `shape_id` after the node was
+/// validated as one of the shape's targets, so later `sh:node` and logical checks of the
+/// pair do not evaluate the shape again. `first_failure` is the shape's first failure in
+/// constraint order, the one [`check_conformance_for_node`] would have stopped at. An
+/// outcome a nested check already cached is kept.
+pub(crate) fn store_target_conformance(
+    main_validation_context: &ValidationContext,
+    focus_node: Term,
+    shape_id: ID,
+    first_failure: Option<(Context, ValidationFailure)>,
+) {
+    if main_validation_context
+        .conformance_cache
+        .borrow()
+        .contains_key(&(focus_node.clone(), shape_id))
+    {
+        return;
+    }
+    let outcome = match first_failure {
+        Some((ctx, mut failure)) => {
+            failure.message = describe_failure(&ctx, &failure, main_validation_context);
+            ConformanceReport::NonConforms(ctx, failure)
+        }
+        None => ConformanceReport::Conforms,
+    };
+    main_validation_context.store_conformance(focus_node, shape_id, outcome);
+}
+
+/// The nested failures to attach to a failure of a shape-based constraint: `nested` when
+/// the run asks for result details, else none.
+pub(crate) fn nested_details(
+    validation_context: &ValidationContext,
+    nested: Vec<(Context, ValidationFailure)>,
+) -> Vec<(Context, ValidationFailure)> {
+    if validation_context.result_details_enabled.get() {
+        nested
+    } else {
+        Vec::new()
+    }
+}
+
+fn first_nonconformance(
+    node_as_context: &mut Context,
+    shape_to_check_against: &NodeShape,
+    main_validation_context: &ValidationContext,
+    trace: &mut Vec<TraceItem>,
+) -> Result<ConformanceReport, String> {
     for constraint_id in shape_to_check_against.constraints() {
         let component = main_validation_context
             .get_component(constraint_id)
@@ -405,11 +564,12 @@ pub(crate) fn check_conformance_for_node(
             trace,
         ) {
             Ok(validation_results) => {
-                if let Some(ComponentValidationResult::Fail(_ctx, failure)) = validation_results
+                if let Some(ComponentValidationResult::Fail(ctx, mut failure)) = validation_results
                     .into_iter()
                     .find(|r| matches!(r, ComponentValidationResult::Fail(_, _)))
                 {
-                    return Ok(ConformanceReport::NonConforms(failure));
+                    failure.message = describe_failure(&ctx, &failure, main_validation_context);
+                    return Ok(ConformanceReport::NonConforms(ctx, failure));
                 }
             }
             Err(e) => {
@@ -419,3 +579,56 @@ pub(crate) fn check_conformance_for_node(
     }
     Ok(ConformanceReport::Conforms)
 }
+
+/// Prefixes a failure message with the constraint that produced it, the path it was
+/// evaluated on and the offending value, e.g.
+/// `sh:minCount on path <http://ex/name>: Value count (0) does not meet minimum requirement: 1`.
+///
+/// Used for failures nested inside shape-based constraints, whose results are otherwise
+/// reduced to "does not conform".
+fn describe_failure(
+    ctx: &Context,
+    failure: &ValidationFailure,
+    validation_context: &ValidationContext,
+) -> String {
+    let messages = validation_context.messages();
+    let mut description = validation_context
+        .component_type(&failure.component_id)
+        .map(|component| constraint_label(&component))
+        .unwrap_or_else(|| "constraint".to_string());
+    if let Some(path) = failure
+        .result_path
+        .as_ref()
+        .or(ctx.result_path())
+        .and_then(|path| path.to_sparql_path().ok())
+    {
+        description.push(' ');
+        description.push_str(&messages.format("nested.on_path", &[("path", &path)]));
+    }
+    if let Some(value) = &failure.failed_value_node {
+        description.push(' ');
+        description.push_str(&messages.format("nested.for_value", &[("value", value)]));
+    }
+    format!("{}: {}", description, failure.message)
+}
+
+/// Renders a constraint component IRI as the SHACL parameter it is declared with
+/// (`sh:MinCountConstraintComponent` becomes `sh:minCount`); other IRIs are kept as-is.
+fn constraint_label(component_type: &NamedNode) -> String {
+    let local = component_type
+        .as_str()
+        .strip_prefix("http://www.w3.org/ns/shacl#")
+        .and_then(|local| local.strip_suffix("ConstraintComponent"));
+    match local {
+        Some(local) if !local.is_empty() => {
+            let mut chars = local.chars();
+            let first = chars.next().map(|c| c.to_ascii_lowercase());
+            format!(
+                "sh:{}{}",
+                first.into_iter().collect::<String>(),
+                chars.as_str()
+            )
+        }
+        _ => component_type.to_string(),
+    }
+}
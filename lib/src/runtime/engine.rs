@@ -42,6 +42,9 @@ pub(crate) fn build_component_from_descriptor(descriptor: &ComponentDescriptor)
         ComponentDescriptor::Datatype { datatype } => Component::DatatypeConstraint(
             validators::DatatypeConstraintComponent::new(datatype.clone()),
         ),
+        ComponentDescriptor::DatatypeIn { datatypes } => Component::DatatypeInConstraint(
+            validators::DatatypeInConstraintComponent::new(datatypes.clone()),
+        ),
         ComponentDescriptor::NodeKind { node_kind } => Component::NodeKindConstraint(
             validators::NodeKindConstraintComponent::new(node_kind.clone()),
         ),
@@ -1,13 +1,16 @@
 //! Shape-level orchestration for runtime validation passes.
 
 use crate::context::ValidationContext;
-use crate::report::ValidationReportBuilder;
+use crate::report::ReportShard;
+use crate::validate::ValidationRun;
 
 /// Coordinates validation of a shape using runtime evaluators.
 pub(crate) trait ValidateShape {
+    /// Validates the shape's focus nodes that `run` selects, until the run stops.
     fn process_targets(
         &self,
         context: &ValidationContext,
-        report_builder: &mut ValidationReportBuilder,
+        run: &ValidationRun<'_>,
+        report_shard: &mut ReportShard,
     ) -> Result<(), String>;
 }
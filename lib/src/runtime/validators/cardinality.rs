@@ -39,24 +39,24 @@ impl ValidateComponent for MinCountConstraintComponent {
         &self,
         component_id: ComponentID,
         c: &mut Context,
-        _context: &ValidationContext,
+        context: &ValidationContext,
         _trace: &mut Vec<TraceItem>,
     ) -> Result<Vec<ComponentValidationResult>, String> {
-        let count = c.value_nodes().map_or(0, |v| v.len());
+        let count = c.value_node_count();
         if count < self.min_count as usize {
             let failure = ValidationFailure {
                 component_id,
                 failed_value_node: None,
-                message: format!(
-                    "Value count ({}) does not meet minimum requirement: {}",
-                    count, self.min_count
-                ),
+                message: context
+                    .messages()
+                    .format("min_count", &[("count", &count), ("min", &self.min_count)]),
                 result_path: None,
                 source_constraint: None,
 
                 severity: None,
 
                 message_terms: Vec::new(),
+                details: Vec::new(),
             };
             Ok(vec![ComponentValidationResult::Fail(c.clone(), failure)])
         } else {
@@ -99,24 +99,24 @@ impl ValidateComponent for MaxCountConstraintComponent {
         &self,
         component_id: ComponentID,
         c: &mut Context,
-        _context: &ValidationContext,
+        context: &ValidationContext,
         _trace: &mut Vec<TraceItem>,
     ) -> Result<Vec<ComponentValidationResult>, String> {
-        let count = c.value_nodes().map_or(0, |v| v.len());
+        let count = c.value_node_count();
         if count > self.max_count as usize {
             let failure = ValidationFailure {
                 component_id,
                 failed_value_node: None,
-                message: format!(
-                    "Value count ({}) exceeds maximum requirement: {}",
-                    count, self.max_count
-                ),
+                message: context
+                    .messages()
+                    .format("max_count", &[("count", &count), ("max", &self.max_count)]),
                 result_path: None,
                 source_constraint: None,
 
                 severity: None,
 
                 message_terms: Vec::new(),
+                details: Vec::new(),
             };
             Ok(vec![ComponentValidationResult::Fail(c.clone(), failure)])
         } else {
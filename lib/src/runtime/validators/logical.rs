@@ -3,8 +3,8 @@ use crate::types::{ComponentID, TraceItem, ID};
 use oxigraph::model::NamedNode;
 
 use crate::runtime::{
-    check_conformance_for_node, ComponentValidationResult, ConformanceReport, GraphvizOutput,
-    ValidateComponent, ValidationFailure,
+    check_conformance_for_node, nested_details, ComponentValidationResult, ConformanceReport,
+    GraphvizOutput, ValidateComponent, ValidationFailure,
 };
 
 // logical constraints
@@ -66,7 +66,12 @@ impl ValidateComponent for NotConstraintComponent {
 
         let mut results = Vec::new();
 
+        let stop_early = c.conformance_mode().stops_at_first_failure();
+
         for value_node_to_check in value_nodes {
+            if stop_early && !results.is_empty() {
+                break;
+            }
             // Create a new context where the current value_node is the focus node.
             let mut value_node_as_context = Context::new(
                 value_node_to_check.clone(),
@@ -105,10 +110,11 @@ impl ValidateComponent for NotConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     };
                     results.push(ComponentValidationResult::Fail(error_context, failure));
                 }
-                ConformanceReport::NonConforms(_) => {
+                ConformanceReport::NonConforms(..) => {
                     // value_node_to_check DOES NOT CONFORM to the negated_node_shape.
                     // This means the sh:not constraint PASSES for this value_node. Continue.
                 }
@@ -170,7 +176,12 @@ impl ValidateComponent for AndConstraintComponent {
         let value_nodes = value_nodes.clone();
         let mut results = Vec::new();
 
+        let stop_early = c.conformance_mode().stops_at_first_failure();
+
         for value_node_to_check in value_nodes {
+            if stop_early && !results.is_empty() {
+                break;
+            }
             // The source_shape for the context used in check_conformance_for_node
             // will be set to the specific conjunct_node_shape's ID.
             'conjunct_loop: for conjunct_shape_id in &self.shapes {
@@ -204,14 +215,14 @@ impl ValidateComponent for AndConstraintComponent {
                     ConformanceReport::Conforms => {
                         // value_node_to_check CONFORMS to this conjunct_node_shape. Continue to next conjunct.
                     }
-                    ConformanceReport::NonConforms(failure) => {
+                    ConformanceReport::NonConforms(inner_context, inner_failure) => {
                         // value_node_to_check DOES NOT CONFORM to this conjunct_node_shape.
                         // For sh:and, all shapes must conform. So, this is a failure for this value_node.
                         let mut error_context = c.clone();
                         error_context.with_value(value_node_to_check.clone());
                         let message = format!(
                             "Value {:?} does not conform to sh:and shape {:?}: {}",
-                            value_node_to_check, conjunct_shape_id, failure.message
+                            value_node_to_check, conjunct_shape_id, inner_failure.message
                         );
                         let failure = ValidationFailure {
                             component_id,
@@ -223,6 +234,10 @@ impl ValidateComponent for AndConstraintComponent {
                             severity: None,
 
                             message_terms: Vec::new(),
+                            details: nested_details(
+                                validation_context,
+                                vec![(inner_context, inner_failure)],
+                            ),
                         };
                         results.push(ComponentValidationResult::Fail(error_context, failure));
                         break 'conjunct_loop; // Fails one, fails all for this value node.
@@ -302,6 +317,7 @@ impl ValidateComponent for OrConstraintComponent {
                     severity: None,
 
                     message_terms: Vec::new(),
+                    details: Vec::new(),
                 };
                 Ok(vec![ComponentValidationResult::Fail(c.clone(), failure)])
             };
@@ -309,8 +325,14 @@ impl ValidateComponent for OrConstraintComponent {
         let value_nodes = value_nodes.clone();
         let mut results = Vec::new();
 
+        let stop_early = c.conformance_mode().stops_at_first_failure();
+
         for value_node_to_check in value_nodes {
+            if stop_early && !results.is_empty() {
+                break;
+            }
             let mut passed_at_least_one_disjunct = false;
+            let mut disjunct_failures = Vec::new();
             // The source_shape for the context used in check_conformance_for_node
             // will be set to the specific disjunct_node_shape's ID.
             for disjunct_shape_id in &self.shapes {
@@ -347,8 +369,9 @@ impl ValidateComponent for OrConstraintComponent {
                         passed_at_least_one_disjunct = true;
                         break; // Move to the next value_node_to_check
                     }
-                    ConformanceReport::NonConforms(_) => {
+                    ConformanceReport::NonConforms(inner_context, inner_failure) => {
                         // value_node_to_check DOES NOT CONFORM. Try next disjunct shape.
+                        disjunct_failures.push((inner_context, inner_failure));
                     }
                 }
             }
@@ -370,6 +393,7 @@ impl ValidateComponent for OrConstraintComponent {
                     severity: None,
 
                     message_terms: Vec::new(),
+                    details: nested_details(validation_context, disjunct_failures),
                 };
                 results.push(ComponentValidationResult::Fail(error_context, failure));
             }
@@ -444,6 +468,7 @@ impl ValidateComponent for XoneConstraintComponent {
                     severity: None,
 
                     message_terms: Vec::new(),
+                    details: Vec::new(),
                 };
                 Ok(vec![ComponentValidationResult::Fail(c.clone(), failure)])
             };
@@ -452,8 +477,14 @@ impl ValidateComponent for XoneConstraintComponent {
         let mut results = Vec::new();
         let value_nodes = value_nodes.clone();
 
+        let stop_early = c.conformance_mode().stops_at_first_failure();
+
         for value_node_to_check in value_nodes {
+            if stop_early && !results.is_empty() {
+                break;
+            }
             let mut conforming_shapes_count = 0;
+            let mut option_failures = Vec::new();
             // The source_shape for the context used in check_conformance_for_node
             // will be set to the specific xone_node_shape's ID.
             for xone_shape_id in &self.shapes {
@@ -487,8 +518,9 @@ impl ValidateComponent for XoneConstraintComponent {
                         // value_node_to_check CONFORMS to this xone_node_shape.
                         conforming_shapes_count += 1;
                     }
-                    ConformanceReport::NonConforms(_) => {
+                    ConformanceReport::NonConforms(inner_context, inner_failure) => {
                         // value_node_to_check DOES NOT CONFORM. Continue.
+                        option_failures.push((inner_context, inner_failure));
                     }
                 }
             }
@@ -501,6 +533,10 @@ impl ValidateComponent for XoneConstraintComponent {
                     "Value {:?} conformed to {} sh:xone shapes, but expected exactly 1.",
                     value_node_to_check, conforming_shapes_count
                 );
+                // Failures of the other shapes only explain the result if none conformed.
+                if conforming_shapes_count > 1 {
+                    option_failures.clear();
+                }
                 let failure = ValidationFailure {
                     component_id,
                     failed_value_node: Some(value_node_to_check.clone()),
@@ -511,6 +547,7 @@ impl ValidateComponent for XoneConstraintComponent {
                     severity: None,
 
                     message_terms: Vec::new(),
+                    details: nested_details(validation_context, option_failures),
                 };
                 results.push(ComponentValidationResult::Fail(error_context, failure));
             }
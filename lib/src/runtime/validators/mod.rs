@@ -5,8 +5,10 @@ mod property_pair;
 mod shape_based;
 mod sparql;
 mod string_based;
+mod temporal;
 mod value_range;
 mod value_type;
+mod xsd_regex;
 
 pub(crate) use cardinality::*;
 pub(crate) use logical::*;
@@ -1,4 +1,5 @@
 use crate::context::{format_term_for_label, Context, ValidationContext};
+use crate::model::normalize::normalize_term;
 use crate::runtime::Component;
 use crate::types::Path;
 use crate::types::{ComponentID, TraceItem};
@@ -16,7 +17,7 @@ impl ValidateComponent for InConstraintComponent {
         &self,
         component_id: ComponentID,
         c: &mut Context,
-        _validation_context: &ValidationContext,
+        validation_context: &ValidationContext,
         _trace: &mut Vec<TraceItem>,
     ) -> Result<Vec<ComponentValidationResult>, String> {
         if self.values.is_empty() {
@@ -30,9 +31,13 @@ impl ValidateComponent for InConstraintComponent {
                 let failure = ValidationFailure {
                     component_id,
                     failed_value_node: None,
-                    message: format!(
-                        "sh:in constraint has an empty list, but value nodes {:?} exist.",
-                        c.value_nodes().unwrap_or(&Vec::new()) // Provide empty vec for formatting if None
+                    message: validation_context.messages().format(
+                        "in.empty_list",
+                        &[(
+                            "values",
+                            // Provide empty vec for formatting if None
+                            &format!("{:?}", c.value_nodes().unwrap_or(&Vec::new())),
+                        )],
                     ),
                     result_path: None,
                     source_constraint: None,
@@ -40,6 +45,7 @@ impl ValidateComponent for InConstraintComponent {
                     severity: None,
 
                     message_terms: Vec::new(),
+                    details: Vec::new(),
                 };
                 Ok(vec![ComponentValidationResult::Fail(c.clone(), failure)])
             };
@@ -48,12 +54,15 @@ impl ValidateComponent for InConstraintComponent {
         let mut results = Vec::new();
         if let Some(value_nodes) = c.value_nodes().cloned() {
             for vn in value_nodes {
-                if !self.values.contains(&vn) {
+                if !self.values.contains(&normalize_term(vn.clone())) {
                     let mut error_context = c.clone();
                     error_context.with_value(vn.clone());
-                    let message = format!(
-                        "Value {:?} is not in the allowed list {:?}.",
-                        vn, self.values
+                    let message = validation_context.messages().format(
+                        "in.mismatch",
+                        &[
+                            ("value", &format!("{:?}", vn)),
+                            ("allowed", &format!("{:?}", self.values)),
+                        ],
                     );
                     let failure = ValidationFailure {
                         component_id,
@@ -65,6 +74,7 @@ impl ValidateComponent for InConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     };
                     results.push(ComponentValidationResult::Fail(error_context, failure));
                 }
@@ -142,24 +152,31 @@ impl ValidateComponent for ClosedConstraintComponent {
             }
         }
 
+        // The predicates enumerated as `sh:path` of the property shapes declared through
+        // `sh:property` at the current shape, which may itself be a node or property shape.
         let source_shape_binding = c.source_shape();
-        let source_shape_id = if let Some(id) = source_shape_binding.as_node_id() {
-            id
+        let shape_constraints = if let Some(id) = source_shape_binding.as_node_id() {
+            validation_context
+                .model
+                .node_shapes
+                .get(id)
+                .map(|shape| shape.constraints())
+        } else if let Some(id) = source_shape_binding.as_prop_id() {
+            validation_context
+                .model
+                .get_prop_shape_by_id(id)
+                .map(|shape| shape.constraints())
         } else {
-            return Err("sh:closed can only be used on a node shape".to_string());
+            return Err("sh:closed is only supported on node and property shapes".to_string());
         };
-
-        if let Some(node_shape) = validation_context.model.node_shapes.get(source_shape_id) {
-            for constraint_com_id in node_shape.constraints() {
-                if let Some(Component::PropertyConstraint(pc)) =
-                    validation_context.get_component(constraint_com_id)
+        for constraint_com_id in shape_constraints.unwrap_or_default() {
+            if let Some(Component::PropertyConstraint(pc)) =
+                validation_context.get_component(constraint_com_id)
+            {
+                if let Some(prop_shape) = validation_context.model.get_prop_shape_by_id(pc.shape())
                 {
-                    if let Some(prop_shape) =
-                        validation_context.model.get_prop_shape_by_id(pc.shape())
-                    {
-                        if let Path::Simple(Term::NamedNode(p)) = prop_shape.path() {
-                            allowed_properties.insert(p.clone());
-                        }
+                    if let Path::Simple(Term::NamedNode(p)) = prop_shape.path() {
+                        allowed_properties.insert(p.clone());
                     }
                 }
             }
@@ -201,10 +218,9 @@ impl ValidateComponent for ClosedConstraintComponent {
                     error_context
                         .with_result_path(oxigraph::model::Term::NamedNode(predicate.clone()));
 
-                    let message = format!(
-                        "Focus node {:?} has value for property {:?} which is not allowed by sh:closed",
-                        vn, predicate
-                    );
+                    let message = validation_context
+                        .messages()
+                        .format("closed", &[("node", &vn), ("property", &predicate)]);
 
                     let failure = ValidationFailure {
                         component_id,
@@ -216,6 +232,7 @@ impl ValidateComponent for ClosedConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     };
                     results.push(ComponentValidationResult::Fail(error_context, failure));
                 }
@@ -260,12 +277,15 @@ impl ValidateComponent for HasValueConstraintComponent {
         &self,
         component_id: ComponentID,
         c: &mut Context,
-        _validation_context: &ValidationContext,
+        validation_context: &ValidationContext,
         _trace: &mut Vec<TraceItem>,
     ) -> Result<Vec<ComponentValidationResult>, String> {
         match c.value_nodes() {
             Some(value_nodes) => {
-                if value_nodes.iter().any(|vn| vn == &self.value) {
+                if value_nodes
+                    .iter()
+                    .any(|vn| normalize_term(vn.clone()) == self.value)
+                {
                     // At least one value node is equal to self.value
                     Ok(vec![])
                 } else {
@@ -273,9 +293,12 @@ impl ValidateComponent for HasValueConstraintComponent {
                     let failure = ValidationFailure {
                         component_id,
                         failed_value_node: None,
-                        message: format!(
-                            "None of the value nodes {:?} are equal to the required value {:?}",
-                            value_nodes, self.value
+                        message: validation_context.messages().format(
+                            "has_value.missing",
+                            &[
+                                ("values", &format!("{:?}", value_nodes)),
+                                ("required", &format!("{:?}", self.value)),
+                            ],
                         ),
                         result_path: None,
                         source_constraint: None,
@@ -283,6 +306,7 @@ impl ValidateComponent for HasValueConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     };
                     Ok(vec![ComponentValidationResult::Fail(c.clone(), failure)])
                 }
@@ -292,9 +316,9 @@ impl ValidateComponent for HasValueConstraintComponent {
                 let failure = ValidationFailure {
                     component_id,
                     failed_value_node: None,
-                    message: format!(
-                        "No value nodes found to check against required value {:?}",
-                        self.value
+                    message: validation_context.messages().format(
+                        "has_value.no_values",
+                        &[("required", &format!("{:?}", self.value))],
                     ),
                     result_path: None,
                     source_constraint: None,
@@ -302,6 +326,7 @@ impl ValidateComponent for HasValueConstraintComponent {
                     severity: None,
 
                     message_terms: Vec::new(),
+                    details: Vec::new(),
                 };
                 Ok(vec![ComponentValidationResult::Fail(c.clone(), failure)])
             }
@@ -1,4 +1,5 @@
 #![allow(deprecated)]
+use super::temporal::explain_incomparable;
 use crate::context::{format_term_for_label, Context, ValidationContext};
 use crate::runtime::{
     ComponentValidationResult, GraphvizOutput, ToSubjectRef, ValidateComponent, ValidationFailure,
@@ -88,10 +89,12 @@ impl ValidateComponent for EqualsConstraintComponent {
                 ValidationFailure {
                     component_id,
                     failed_value_node: Some(value_node.clone()),
-                    message: format!(
-                        "Value node {} not found in values of property <{}>",
-                        format_term_for_label(value_node),
-                        equals_property.as_str()
+                    message: context.messages().format(
+                        "equals.missing_value",
+                        &[
+                            ("value", &format_term_for_label(value_node)),
+                            ("property", &equals_property.as_str()),
+                        ],
                     ),
                     result_path: None,
                     source_constraint: None,
@@ -99,6 +102,7 @@ impl ValidateComponent for EqualsConstraintComponent {
                     severity: None,
 
                     message_terms: Vec::new(),
+                    details: Vec::new(),
                 },
             ));
         }
@@ -112,10 +116,12 @@ impl ValidateComponent for EqualsConstraintComponent {
                 ValidationFailure {
                     component_id,
                     failed_value_node: Some(other_value.clone()),
-                    message: format!(
-                        "Value {} of property <{}> not found in value nodes",
-                        format_term_for_label(other_value),
-                        equals_property.as_str()
+                    message: context.messages().format(
+                        "equals.extra_value",
+                        &[
+                            ("value", &format_term_for_label(other_value)),
+                            ("property", &equals_property.as_str()),
+                        ],
                     ),
                     result_path: None,
                     source_constraint: None,
@@ -123,6 +129,7 @@ impl ValidateComponent for EqualsConstraintComponent {
                     severity: None,
 
                     message_terms: Vec::new(),
+                    details: Vec::new(),
                 },
             ));
         }
@@ -216,10 +223,12 @@ impl ValidateComponent for DisjointConstraintComponent {
                     ValidationFailure {
                         component_id,
                         failed_value_node: Some(value_node.clone()),
-                        message: format!(
-                            "Value {} is not disjoint with values of property <{}>",
-                            format_term_for_label(value_node),
-                            disjoint_property.as_str()
+                        message: context.messages().format(
+                            "disjoint",
+                            &[
+                                ("value", &format_term_for_label(value_node)),
+                                ("property", &disjoint_property.as_str()),
+                            ],
                         ),
                         result_path: None,
                         source_constraint: None,
@@ -227,6 +236,7 @@ impl ValidateComponent for DisjointConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     },
                 ));
             }
@@ -331,11 +341,17 @@ impl ValidateComponent for LessThanConstraintComponent {
                         ValidationFailure {
                             component_id,
                             failed_value_node: Some(value_node.clone()),
-                            message: format!(
-                                "Value {} is not less than {} from property <{}>",
-                                format_term_for_label(value_node),
-                                format_term_for_label(other_value),
-                                less_than_property.as_str()
+                            message: explain_incomparable(
+                                context.messages().format(
+                                    "less_than",
+                                    &[
+                                        ("value", &format_term_for_label(value_node)),
+                                        ("other", &format_term_for_label(other_value)),
+                                        ("property", &less_than_property.as_str()),
+                                    ],
+                                ),
+                                value_node,
+                                other_value,
                             ),
                             result_path: None,
                             source_constraint: None,
@@ -343,6 +359,7 @@ impl ValidateComponent for LessThanConstraintComponent {
                             severity: None,
 
                             message_terms: Vec::new(),
+                            details: Vec::new(),
                         },
                     ));
                 }
@@ -448,11 +465,17 @@ impl ValidateComponent for LessThanOrEqualsConstraintComponent {
                         ValidationFailure {
                             component_id,
                             failed_value_node: Some(value_node.clone()),
-                            message: format!(
-                                "Value {} is not less than or equal to {} from property <{}>",
-                                format_term_for_label(value_node),
-                                format_term_for_label(other_value),
-                                lte_property.as_str()
+                            message: explain_incomparable(
+                                context.messages().format(
+                                    "less_than_or_equals",
+                                    &[
+                                        ("value", &format_term_for_label(value_node)),
+                                        ("other", &format_term_for_label(other_value)),
+                                        ("property", &lte_property.as_str()),
+                                    ],
+                                ),
+                                value_node,
+                                other_value,
                             ),
                             result_path: None,
                             source_constraint: None,
@@ -460,6 +483,7 @@ impl ValidateComponent for LessThanOrEqualsConstraintComponent {
                             severity: None,
 
                             message_terms: Vec::new(),
+                            details: Vec::new(),
                         },
                     ));
                 }
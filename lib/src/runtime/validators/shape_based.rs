@@ -1,13 +1,12 @@
 use crate::context::{format_term_for_label, Context, SourceShape, ValidationContext};
 use crate::shape::NodeShape;
 use crate::types::{ComponentID, PropShapeID, TraceItem, ID};
-use oxigraph::model::NamedNode;
-// Removed: use oxigraph::model::Term;
+use oxigraph::model::{Literal, NamedNode};
 
 use crate::runtime::Component;
 use crate::runtime::{
-    check_conformance_for_node, ComponentValidationResult, ConformanceReport, GraphvizOutput,
-    ValidateComponent, ValidationFailure,
+    check_conformance_for_node, nested_details, ComponentValidationResult, ConformanceReport,
+    GraphvizOutput, ValidateComponent, ValidationFailure,
 };
 
 #[derive(Debug)]
@@ -68,7 +67,12 @@ impl ValidateComponent for NodeConstraintComponent {
 
         let mut results = Vec::new();
 
+        let stop_early = c.conformance_mode().stops_at_first_failure();
+
         for value_node_to_check in value_nodes {
+            if stop_early && !results.is_empty() {
+                break;
+            }
             let mut value_node_as_context = Context::new(
                 value_node_to_check.clone(),
                 None,
@@ -88,20 +92,33 @@ impl ValidateComponent for NodeConstraintComponent {
                 ConformanceReport::Conforms => {
                     // Conforms, so this value node passes. Continue to the next.
                 }
-                ConformanceReport::NonConforms(inner_failure) => {
+                ConformanceReport::NonConforms(inner_context, inner_failure) => {
                     // Does not conform. This is a failure for the NodeConstraintComponent.
                     let mut error_context = c.clone();
                     error_context.with_value(value_node_to_check.clone());
+                    let shape_term = validation_context
+                        .model
+                        .nodeshape_id_lookup()
+                        .borrow()
+                        .get_term(self.shape)
+                        .map_or_else(|| self.shape.to_string(), |term| term.to_string());
+                    let message = format!(
+                        "Value {} does not conform to sh:node shape {}: {}",
+                        value_node_to_check, shape_term, inner_failure.message
+                    );
                     let failure = ValidationFailure {
                         component_id,
                         failed_value_node: Some(value_node_to_check.clone()),
-                        message: inner_failure.message,
+                        message_terms: vec![Literal::new_simple_literal(&message).into()],
+                        details: nested_details(
+                            validation_context,
+                            vec![(inner_context, inner_failure)],
+                        ),
+                        message,
                         result_path: None,
                         source_constraint: None,
 
                         severity: None,
-
-                        message_terms: Vec::new(),
                     };
                     results.push(ComponentValidationResult::Fail(error_context, failure));
                 }
@@ -343,7 +360,7 @@ impl ValidateComponent for QualifiedValueShapeComponent {
 
             let conforms_to_target = match result {
                 ConformanceReport::Conforms => true,
-                ConformanceReport::NonConforms(_) => false,
+                ConformanceReport::NonConforms(..) => false,
             };
 
             if !conforms_to_target {
@@ -377,7 +394,7 @@ impl ValidateComponent for QualifiedValueShapeComponent {
                             conforms_to_sibling = true;
                             break; // Found a conforming sibling, no need to check others for this value_node
                         }
-                        ConformanceReport::NonConforms(_) => {}
+                        ConformanceReport::NonConforms(..) => {}
                     }
                 }
             }
@@ -403,6 +420,7 @@ impl ValidateComponent for QualifiedValueShapeComponent {
                     severity: None,
 
                     message_terms: Vec::new(),
+                    details: Vec::new(),
                 };
                 validation_results.push(ComponentValidationResult::Fail(c.clone(), failure));
             }
@@ -423,6 +441,7 @@ impl ValidateComponent for QualifiedValueShapeComponent {
                     severity: None,
 
                     message_terms: Vec::new(),
+                    details: Vec::new(),
                 };
                 validation_results.push(ComponentValidationResult::Fail(c.clone(), failure));
             }
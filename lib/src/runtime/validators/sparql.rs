@@ -1,7 +1,8 @@
 #![allow(deprecated)]
 use crate::context::{format_term_for_label, Context, ValidationContext};
-use crate::model::components::sparql::CustomConstraintComponentDefinition;
+use crate::model::components::sparql::{CustomConstraintComponentDefinition, SPARQLValidator};
 use crate::named_nodes::SHACL;
+use crate::prefix_map::PrefixMap;
 use crate::runtime::{
     ComponentValidationResult, GraphvizOutput, ToSubjectRef, ValidateComponent, ValidationFailure,
 };
@@ -9,12 +10,28 @@ use crate::sparql::{
     ensure_pre_binding_semantics, validate_prebound_variable_usage, MessageTemplater,
     SparqlExecutor,
 };
-use crate::types::{ComponentID, Path, Severity, TraceItem};
-use oxigraph::model::vocab::xsd;
-use oxigraph::model::{NamedNode, Term, TermRef};
-use oxigraph::sparql::{QueryResults, Variable};
+use crate::types::{ComponentID, Path, QueryDataset, Severity, TraceItem};
+use oxigraph::model::vocab::{rdf, xsd};
+use oxigraph::model::{GraphName, GraphNameRef, NamedNode, NamedNodeRef, Term, TermRef};
+use oxigraph::sparql::{PreparedSparqlQuery, QueryResults, Variable};
+use oxigraph::store::Store;
 use std::collections::{HashMap, HashSet};
 
+/// Restricts the default graph of a prepared constraint query to the data graph unless
+/// the validator was configured with `QueryDataset::Union`. Named graphs, including
+/// `$shapesGraph`, stay reachable through `GRAPH`.
+fn with_constraint_dataset(
+    context: &ValidationContext,
+    mut prepared: PreparedSparqlQuery,
+) -> PreparedSparqlQuery {
+    if context.model.features.query_dataset == QueryDataset::DataGraph {
+        prepared
+            .dataset_mut()
+            .set_default_graph(vec![GraphName::NamedNode(context.data_graph_iri.clone())]);
+    }
+    prepared
+}
+
 fn query_mentions_var(query: &str, var: &str) -> bool {
     fn contains(query: &str, prefix: char, var: &str) -> bool {
         let mut start = 0;
@@ -42,27 +59,38 @@ fn query_mentions_var(query: &str, var: &str) -> bool {
     contains(query, '?', var) || contains(query, '$', var)
 }
 
+/// Message placeholders for the pre-bound variables of a SPARQL query: `$this`,
+/// `$currentShape`, `$shapesGraph`, `$value` and `$PATH`.
 fn gather_default_substitutions(
+    validation_context: &ValidationContext,
     context: &Context,
     current_shape_term: Option<&Term>,
     value_term: Option<&Term>,
     path_override: Option<&String>,
 ) -> Vec<(String, String)> {
+    let prefixes = &validation_context.model.prefixes;
     let mut substitutions = Vec::new();
     substitutions.push((
         "this".to_string(),
-        term_to_message_value(context.focus_node()),
+        term_to_message_value(prefixes, context.focus_node()),
+    ));
+    substitutions.push((
+        "shapesGraph".to_string(),
+        term_to_message_value(
+            prefixes,
+            &validation_context.model.shape_graph_iri.clone().into(),
+        ),
     ));
 
     if let Some(shape_term) = current_shape_term {
         substitutions.push((
             "currentShape".to_string(),
-            term_to_message_value(shape_term),
+            term_to_message_value(prefixes, shape_term),
         ));
     }
 
     if let Some(value) = value_term {
-        substitutions.push(("value".to_string(), term_to_message_value(value)));
+        substitutions.push(("value".to_string(), term_to_message_value(prefixes, value)));
     }
 
     if let Some(path) = path_override {
@@ -72,15 +100,115 @@ fn gather_default_substitutions(
     substitutions
 }
 
-fn term_to_message_value(term: &Term) -> String {
+fn term_to_message_value(prefixes: &PrefixMap, term: &Term) -> String {
     match term {
         Term::Literal(lit) => lit.value().to_string(),
-        _ => format_term_for_label(term),
+        _ => prefixes.label(term),
+    }
+}
+
+fn term_ref_to_message_value(prefixes: &PrefixMap, term: TermRef<'_>) -> String {
+    term_to_message_value(prefixes, &term.into_owned())
+}
+
+/// Nesting depth up to which path structures bound to `?path` are followed.
+const MAX_RESULT_PATH_DEPTH: usize = 32;
+
+/// Resolves the `?path` binding of a SELECT query into the result path. Validators may
+/// return complex paths, such as the `sh:path` value of a property shape: a blank node
+/// (or the skolem IRI standing in for one) is read as a SHACL path from the shapes graph,
+/// or from the data graph if the query built it there. Other IRIs are predicate paths.
+fn result_path_from_binding(context: &ValidationContext, term: &Term) -> Option<Path> {
+    let store = context.model.store();
+    [
+        context.model.shape_graph_iri_ref(),
+        context.data_graph_iri_ref(),
+    ]
+    .into_iter()
+    .find_map(|graph| read_path_structure(store, graph, term, 0))
+    .or_else(|| match term {
+        Term::NamedNode(_) => Some(Path::Simple(term.clone())),
+        _ => None,
+    })
+}
+
+/// Reads the SHACL path whose node is `term` in `graph`. Returns `None` when `term` has
+/// no path structure there, or only part of one.
+fn read_path_structure(
+    store: &Store,
+    graph: GraphNameRef<'_>,
+    term: &Term,
+    depth: usize,
+) -> Option<Path> {
+    if depth > MAX_RESULT_PATH_DEPTH {
+        return None;
+    }
+    let subject = term.try_to_subject_ref().ok()?;
+    let object = |predicate: NamedNodeRef<'_>| {
+        store
+            .quads_for_pattern(Some(subject), Some(predicate), None, Some(graph))
+            .filter_map(Result::ok)
+            .map(|quad| quad.object)
+            .next()
+    };
+    let read_inner = |inner: &Term| {
+        read_path_structure(store, graph, inner, depth + 1).or_else(|| match inner {
+            Term::NamedNode(_) => Some(Path::Simple(inner.clone())),
+            _ => None,
+        })
+    };
+    let read_list = |head: &Term| -> Option<Vec<Path>> {
+        read_rdf_list(store, graph, head)?
+            .iter()
+            .map(read_inner)
+            .collect()
+    };
+
+    let shacl = SHACL::get();
+    if object(rdf::FIRST).is_some() {
+        return read_list(term).map(Path::Sequence);
+    }
+    if let Some(inner) = object(shacl.inverse_path) {
+        return read_inner(&inner).map(|path| Path::Inverse(Box::new(path)));
+    }
+    if let Some(head) = object(shacl.alternative_path) {
+        return read_list(&head).map(Path::Alternative);
+    }
+    if let Some(inner) = object(shacl.zero_or_more_path) {
+        return read_inner(&inner).map(|path| Path::ZeroOrMore(Box::new(path)));
     }
+    if let Some(inner) = object(shacl.one_or_more_path) {
+        return read_inner(&inner).map(|path| Path::OneOrMore(Box::new(path)));
+    }
+    if let Some(inner) = object(shacl.zero_or_one_path) {
+        return read_inner(&inner).map(|path| Path::ZeroOrOne(Box::new(path)));
+    }
+    None
 }
 
-fn term_ref_to_message_value(term: TermRef<'_>) -> String {
-    term_to_message_value(&term.into_owned())
+/// Returns the members of the RDF list starting at `head` in `graph`, or `None` if the
+/// list is malformed or cyclic.
+fn read_rdf_list(store: &Store, graph: GraphNameRef<'_>, head: &Term) -> Option<Vec<Term>> {
+    let nil = Term::from(rdf::NIL.into_owned());
+    let mut items = Vec::new();
+    let mut visited = HashSet::new();
+    let mut current = head.clone();
+    while current != nil {
+        if !visited.insert(current.clone()) {
+            return None;
+        }
+        let subject = current.try_to_subject_ref().ok()?;
+        let object = |predicate: NamedNodeRef<'_>| {
+            store
+                .quads_for_pattern(Some(subject), Some(predicate), None, Some(graph))
+                .filter_map(Result::ok)
+                .map(|quad| quad.object)
+                .next()
+        };
+        items.push(object(rdf::FIRST)?);
+        current = object(rdf::REST)?;
+    }
+    Some(items)
 }
 
 #[derive(Debug, Clone)]
@@ -100,7 +228,7 @@ impl GraphvizOutput for SPARQLConstraintComponent {
     }
 
     fn to_graphviz_string(&self, component_id: ComponentID, context: &ValidationContext) -> String {
-        let shacl = SHACL::new();
+        let shacl = SHACL::get();
         let subject = self.constraint_node.to_subject_ref();
         let select_query_opt = context
             .model
@@ -142,7 +270,7 @@ impl ValidateComponent for SPARQLConstraintComponent {
         context: &ValidationContext,
         _trace: &mut Vec<TraceItem>,
     ) -> Result<Vec<ComponentValidationResult>, String> {
-        let shacl = SHACL::new();
+        let shacl = SHACL::get();
         let sparql_services = context.model.sparql.as_ref();
         let constraint_subject = self.constraint_node.to_subject_ref();
 
@@ -254,6 +382,7 @@ impl ValidateComponent for SPARQLConstraintComponent {
 
         let prepared_query = sparql_services
             .prepared_query(&full_query_str)
+            .map(|prepared| with_constraint_dataset(context, prepared))
             .map_err(|e| format!("Failed to prepare SPARQL constraint query: {}", e))?;
 
         // Prepare pre-bound variables
@@ -353,6 +482,7 @@ impl ValidateComponent for SPARQLConstraintComponent {
                     }
 
                     let mut substitutions_for_messages = gather_default_substitutions(
+                        context,
                         c,
                         current_shape_term.as_ref(),
                         failed_value_node.as_ref(),
@@ -362,7 +492,7 @@ impl ValidateComponent for SPARQLConstraintComponent {
                         if let Some(term) = solution.get(var) {
                             substitutions_for_messages.push((
                                 var.as_str().to_string(),
-                                term_ref_to_message_value(term.into()),
+                                term_ref_to_message_value(&context.model.prefixes, term.into()),
                             ));
                         }
                     }
@@ -375,12 +505,9 @@ impl ValidateComponent for SPARQLConstraintComponent {
 
                     // The path for the validation result is taken from the ?path variable if bound,
                     // otherwise it's taken from the context `c`.
-                    let result_path_override =
-                        if let Some(Term::NamedNode(path_iri)) = solution.get("path") {
-                            Some(Path::Simple(Term::NamedNode(path_iri.clone())))
-                        } else {
-                            None
-                        };
+                    let result_path_override = solution
+                        .get("path")
+                        .and_then(|term| result_path_from_binding(context, term));
 
                     let failure = ValidationFailure::new(
                         component_id,
@@ -421,6 +548,109 @@ impl CustomConstraintComponent {
     pub(crate) fn local_name(&self) -> String {
         local_name(&self.definition.iri)
     }
+
+    /// Query variable bound to a parameter: its `sh:varName`, or the local name of its path.
+    fn parameter_var_name(&self, path: &NamedNode) -> String {
+        self.definition
+            .parameter_var_name(path)
+            .map(str::to_string)
+            .unwrap_or_else(|| local_name(path))
+    }
+
+    /// Parameter values supplied by the shape, with `sh:defaultValue`s filled in for
+    /// parameters the shape leaves out.
+    fn bound_parameter_values(&self) -> HashMap<NamedNode, Vec<Term>> {
+        let mut bound = self.parameter_values.clone();
+        for param in &self.definition.parameters {
+            let defaults = self.definition.parameter_default_values(&param.path);
+            if defaults.is_empty() {
+                continue;
+            }
+            let values = bound.entry(param.path.clone()).or_default();
+            if values.is_empty() {
+                *values = defaults.to_vec();
+            }
+        }
+        bound
+    }
+
+    /// Identifies one evaluation of `validator` for the invocation cache: the component, its
+    /// parameter values, the focus and value nodes, the path, and the current shape when
+    /// the query or one of the messages refers to it. Shapes that share all of these get
+    /// the same results, whatever their component IDs.
+    fn invocation_key(
+        &self,
+        validator: &SPARQLValidator,
+        c: &Context,
+        query_body: &str,
+        path: Option<&String>,
+        current_shape: Option<&Term>,
+    ) -> String {
+        let mut parameters: Vec<String> = self
+            .bound_parameter_values()
+            .iter()
+            .map(|(param, values)| {
+                let mut values: Vec<String> = values.iter().map(Term::to_string).collect();
+                values.sort();
+                format!("{}={}", param, values.join(","))
+            })
+            .collect();
+        parameters.sort();
+
+        let mentions_current_shape = query_mentions_var(query_body, "currentShape")
+            || validator
+                .messages
+                .iter()
+                .chain(self.definition.effective_messages())
+                .any(|message| match message {
+                    Term::Literal(literal) => query_mentions_var(literal.value(), "currentShape"),
+                    _ => false,
+                });
+        let shape = match current_shape {
+            Some(shape) if mentions_current_shape => shape.to_string(),
+            _ => String::new(),
+        };
+        let value_nodes = c
+            .value_nodes()
+            .map(|nodes| {
+                nodes
+                    .iter()
+                    .map(Term::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .unwrap_or_default();
+
+        format!(
+            "{}|{}|{}|{}|{}|{}|{}",
+            self.definition.iri,
+            parameters.join("|"),
+            validator.query,
+            c.focus_node(),
+            value_nodes,
+            path.map(String::as_str).unwrap_or_default(),
+            shape
+        )
+    }
+
+    /// Message placeholders for the bound parameter values, keyed by query variable name
+    /// (and by path local name when `sh:varName` differs from it).
+    fn parameter_message_substitutions(&self, prefixes: &PrefixMap) -> Vec<(String, String)> {
+        let mut substitutions = Vec::new();
+        for (param_path, values) in &self.bound_parameter_values() {
+            let Some(value) = values.first() else {
+                continue;
+            };
+            let rendered = term_to_message_value(prefixes, value);
+            let var_name = self.parameter_var_name(param_path);
+            let path_name = local_name(param_path);
+            if path_name != var_name {
+                substitutions.push((path_name, rendered.clone()));
+            }
+            substitutions.push((var_name, rendered));
+        }
+        substitutions
+    }
 }
 
 fn local_name(iri: &NamedNode) -> String {
@@ -489,22 +719,10 @@ impl ValidateComponent for CustomConstraintComponent {
         let sparql_services = context.model.sparql.as_ref();
         let is_prop_shape = c.source_shape().as_prop_id().is_some();
 
-        enum ValidatorScope {
-            Node,
-            Property,
-            General,
-        }
-
-        let (validator, _scope) = if is_prop_shape {
-            if let Some(v) = self.definition.property_validator.as_ref() {
-                (Some(v), ValidatorScope::Property)
-            } else {
-                (self.definition.validator.as_ref(), ValidatorScope::General)
-            }
-        } else if let Some(v) = self.definition.node_validator.as_ref() {
-            (Some(v), ValidatorScope::Node)
+        let validator = if is_prop_shape {
+            self.definition.property_shape_validator()
         } else {
-            (self.definition.validator.as_ref(), ValidatorScope::General)
+            self.definition.node_shape_validator()
         };
 
         let validator = match validator {
@@ -537,6 +755,23 @@ impl ValidateComponent for CustomConstraintComponent {
 
         let current_shape_term = c.source_shape().get_term(context);
 
+        let invocation_key = self.invocation_key(
+            validator,
+            c,
+            &query_body,
+            path_substitution_value.as_ref(),
+            current_shape_term.as_ref(),
+        );
+        if let Some(failures) = context.cached_invocation(&invocation_key) {
+            return Ok(failures
+                .into_iter()
+                .map(|mut failure| {
+                    failure.component_id = component_id;
+                    ComponentValidationResult::Fail(c.clone(), failure)
+                })
+                .collect());
+        }
+
         let mut substitutions: Vec<(Variable, Term)> = Vec::new();
         let mut prebound_vars: HashSet<Variable> = HashSet::new();
         let mut optional_prebound_vars: HashSet<Variable> = HashSet::new();
@@ -566,16 +801,14 @@ impl ValidateComponent for CustomConstraintComponent {
             prebound_vars.insert(var);
         }
 
-        for (param_path, values) in &self.parameter_values {
+        let parameter_values = self.bound_parameter_values();
+        for (param_path, values) in &parameter_values {
             let param_meta = self
                 .definition
                 .parameters
                 .iter()
                 .find(|p| p.path == *param_path);
-            let var_name = match param_meta.and_then(|p| p.var_name.clone()) {
-                Some(name) => name,
-                None => local_name(param_path),
-            };
+            let var_name = self.parameter_var_name(param_path);
 
             if !query_mentions_var(&query_body, &var_name) {
                 // Skip optional parameters that are unused in the query.
@@ -608,14 +841,11 @@ impl ValidateComponent for CustomConstraintComponent {
         }
 
         for param in &self.definition.parameters {
-            let var_name = param
-                .var_name
-                .clone()
-                .unwrap_or_else(|| local_name(&param.path));
+            let var_name = self.parameter_var_name(&param.path);
             if !query_mentions_var(&query_body, &var_name) {
                 continue;
             }
-            if self.parameter_values.contains_key(&param.path) {
+            if parameter_values.contains_key(&param.path) {
                 continue;
             }
             let var = Variable::new_unchecked(&var_name);
@@ -663,78 +893,78 @@ impl ValidateComponent for CustomConstraintComponent {
 
         let prepared_query = sparql_services
             .prepared_query(&query_with_prefixes)
+            .map(|prepared| with_constraint_dataset(context, prepared))
             .map_err(|e| format!("Failed to prepare SPARQL validator query: {}", e))?;
 
         let mut results = Vec::new();
 
         if validator.is_ask {
-            if let Some(value_nodes) = c.value_nodes() {
-                for value_node in value_nodes {
-                    let mut ask_substitutions = substitutions.clone();
-                    if include_value {
-                        ask_substitutions
-                            .push((Variable::new_unchecked("value"), value_node.clone()));
-                    }
+            // ASK validators are evaluated once per value node. For node shapes the
+            // focus node is the only value node, so `$value` is bound to it.
+            let ask_value_nodes: Vec<Term> = match c.value_nodes() {
+                Some(nodes) if is_prop_shape => nodes.clone(),
+                _ => vec![c.focus_node().clone()],
+            };
+            for value_node in &ask_value_nodes {
+                let mut ask_substitutions = substitutions.clone();
+                if include_value {
+                    ask_substitutions.push((Variable::new_unchecked("value"), value_node.clone()));
+                }
 
-                    match sparql_services.execute_with_substitutions(
-                        &query_with_prefixes,
-                        &prepared_query,
-                        context.model.store(),
-                        &ask_substitutions,
-                        true,
-                    ) {
-                        Ok(QueryResults::Boolean(conforms)) => {
-                            if !conforms {
-                                let message_templates = if !validator.messages.is_empty() {
-                                    validator.messages.clone()
-                                } else {
-                                    self.definition.messages.clone()
-                                };
-                                let mut substitutions_for_messages = gather_default_substitutions(
-                                    c,
-                                    current_shape_term.as_ref(),
-                                    Some(value_node),
-                                    path_substitution_value.as_ref(),
+                match sparql_services.execute_with_substitutions(
+                    &query_with_prefixes,
+                    &prepared_query,
+                    context.model.store(),
+                    &ask_substitutions,
+                    true,
+                ) {
+                    Ok(QueryResults::Boolean(conforms)) => {
+                        if !conforms {
+                            let message_templates = if !validator.messages.is_empty() {
+                                validator.messages.clone()
+                            } else {
+                                self.definition.effective_messages().to_vec()
+                            };
+                            let mut substitutions_for_messages = gather_default_substitutions(
+                                context,
+                                c,
+                                current_shape_term.as_ref(),
+                                Some(value_node),
+                                path_substitution_value.as_ref(),
+                            );
+                            substitutions_for_messages.extend(
+                                self.parameter_message_substitutions(&context.model.prefixes),
+                            );
+                            let (message_opt, message_terms) = sparql_services
+                                .instantiate_messages(
+                                    &message_templates,
+                                    &substitutions_for_messages,
                                 );
-                                for (param_path, values) in &self.parameter_values {
-                                    if let Some(val) = values.first() {
-                                        substitutions_for_messages.push((
-                                            local_name(param_path),
-                                            term_to_message_value(val),
-                                        ));
-                                    }
-                                }
-                                let (message_opt, message_terms) = sparql_services
-                                    .instantiate_messages(
-                                        &message_templates,
-                                        &substitutions_for_messages,
-                                    );
-                                let message = message_opt.unwrap_or_else(|| {
-                                    format!(
-                                        "Value does not conform to custom constraint {}",
-                                        self.definition.iri
-                                    )
-                                });
-                                let severity_override = validator
-                                    .severity
-                                    .clone()
-                                    .or_else(|| self.definition.severity.clone());
-                                let failure = ValidationFailure::new(
-                                    component_id,
-                                    Some(value_node.clone()),
-                                    message,
-                                    None,
-                                    None,
+                            let message = message_opt.unwrap_or_else(|| {
+                                format!(
+                                    "Value does not conform to custom constraint {}",
+                                    self.definition.iri
                                 )
-                                .with_severity(severity_override)
-                                .with_message_terms(message_terms);
+                            });
+                            let severity_override = validator
+                                .severity
+                                .clone()
+                                .or_else(|| self.definition.effective_severity().cloned());
+                            let failure = ValidationFailure::new(
+                                component_id,
+                                Some(value_node.clone()),
+                                message,
+                                None,
+                                None,
+                            )
+                            .with_severity(severity_override)
+                            .with_message_terms(message_terms);
 
-                                results.push(ComponentValidationResult::Fail(c.clone(), failure));
-                            }
+                            results.push(ComponentValidationResult::Fail(c.clone(), failure));
                         }
-                        Ok(_) => {}
-                        Err(e) => return Err(format!("SPARQL query failed: {}", e)),
                     }
+                    Ok(_) => {}
+                    Err(e) => return Err(format!("SPARQL query failed: {}", e)),
                 }
             }
         } else {
@@ -775,29 +1005,25 @@ impl ValidateComponent for CustomConstraintComponent {
                         if message_templates.is_empty() && !validator.messages.is_empty() {
                             message_templates.extend(validator.messages.clone());
                         }
-                        if message_templates.is_empty() && !self.definition.messages.is_empty() {
-                            message_templates.extend(self.definition.messages.clone());
+                        if message_templates.is_empty() {
+                            message_templates
+                                .extend_from_slice(self.definition.effective_messages());
                         }
 
                         let mut substitutions_for_messages = gather_default_substitutions(
+                            context,
                             c,
                             current_shape_term.as_ref(),
                             failed_value_node.as_ref(),
                             path_substitution_value.as_ref(),
                         );
-                        for (param_path, values) in &self.parameter_values {
-                            if let Some(val) = values.first() {
-                                substitutions_for_messages.push((
-                                    local_name(param_path),
-                                    term_ref_to_message_value(val.as_ref()),
-                                ));
-                            }
-                        }
+                        substitutions_for_messages
+                            .extend(self.parameter_message_substitutions(&context.model.prefixes));
                         for var in solution.variables() {
                             if let Some(term) = solution.get(var) {
                                 substitutions_for_messages.push((
                                     var.as_str().to_string(),
-                                    term_ref_to_message_value(term.into()),
+                                    term_ref_to_message_value(&context.model.prefixes, term.into()),
                                 ));
                             }
                         }
@@ -811,15 +1037,20 @@ impl ValidateComponent for CustomConstraintComponent {
                             )
                         });
 
+                        // A bound ?path overrides the path of the focus node's property shape.
+                        let result_path_override = solution
+                            .get("path")
+                            .and_then(|term| result_path_from_binding(context, term));
+
                         let severity_override = validator
                             .severity
                             .clone()
-                            .or_else(|| self.definition.severity.clone());
+                            .or_else(|| self.definition.effective_severity().cloned());
                         let failure = ValidationFailure::new(
                             component_id,
                             failed_value_node.clone(),
                             message,
-                            None,
+                            result_path_override,
                             None,
                         )
                         .with_severity(severity_override)
@@ -833,6 +1064,14 @@ impl ValidateComponent for CustomConstraintComponent {
             }
         }
 
+        let failures = results
+            .iter()
+            .filter_map(|result| match result {
+                ComponentValidationResult::Fail(_, failure) => Some(failure.clone()),
+                ComponentValidationResult::Pass(_) => None,
+            })
+            .collect();
+        context.store_invocation(invocation_key, failures);
         Ok(results)
     }
 }
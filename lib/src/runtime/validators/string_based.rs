@@ -1,9 +1,10 @@
 use crate::context::{sanitize_graphviz_string, Context, ValidationContext};
 use crate::types::{ComponentID, TraceItem};
 use oxigraph::model::{NamedNode, TermRef};
-// Removed: use regex::Regex;
+use regex::Regex;
 use std::collections::HashSet;
 
+use super::xsd_regex;
 use crate::runtime::{
     ComponentValidationResult, GraphvizOutput, ValidateComponent, ValidationFailure,
 };
@@ -53,9 +54,12 @@ impl ValidateComponent for MinLengthConstraintComponent {
                     TermRef::BlankNode(_) => {
                         let mut error_context = c.clone();
                         error_context.with_value(vn.clone());
-                        let message = format!(
-                            "Blank node {:?} found where string length constraints apply (minLength).",
-                            vn
+                        let message = validation_context.messages().format(
+                            "length.blank_node",
+                            &[
+                                ("value", &format!("{:?}", vn)),
+                                ("constraint", &"minLength"),
+                            ],
                         );
                         let failure = ValidationFailure {
                             component_id,
@@ -67,6 +71,7 @@ impl ValidateComponent for MinLengthConstraintComponent {
                             severity: None,
 
                             message_terms: Vec::new(),
+                            details: Vec::new(),
                         };
                         results.push(ComponentValidationResult::Fail(error_context, failure));
                         continue;
@@ -75,9 +80,12 @@ impl ValidateComponent for MinLengthConstraintComponent {
                         if validation_context.is_data_skolem_iri(nn) {
                             let mut error_context = c.clone();
                             error_context.with_value(vn.clone());
-                            let message = format!(
-                                "Blank node {:?} found where string length constraints apply (minLength).",
-                                vn
+                            let message = validation_context.messages().format(
+                                "length.blank_node",
+                                &[
+                                    ("value", &format!("{:?}", vn)),
+                                    ("constraint", &"minLength"),
+                                ],
                             );
                             let failure = ValidationFailure {
                                 component_id,
@@ -89,6 +97,7 @@ impl ValidateComponent for MinLengthConstraintComponent {
                                 severity: None,
 
                                 message_terms: Vec::new(),
+                                details: Vec::new(),
                             };
                             results.push(ComponentValidationResult::Fail(error_context, failure));
                             continue;
@@ -100,9 +109,13 @@ impl ValidateComponent for MinLengthConstraintComponent {
                 if len < self.min_length as usize {
                     let mut error_context = c.clone();
                     error_context.with_value(vn.clone());
-                    let message = format!(
-                        "Value {:?} has length {} which is less than minLength {}.",
-                        vn, len, self.min_length
+                    let message = validation_context.messages().format(
+                        "min_length",
+                        &[
+                            ("value", &format!("{:?}", vn)),
+                            ("length", &len),
+                            ("min", &self.min_length),
+                        ],
                     );
                     let failure = ValidationFailure {
                         component_id,
@@ -114,6 +127,7 @@ impl ValidateComponent for MinLengthConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     };
                     results.push(ComponentValidationResult::Fail(error_context, failure));
                 }
@@ -168,9 +182,12 @@ impl ValidateComponent for MaxLengthConstraintComponent {
                     TermRef::BlankNode(_) => {
                         let mut error_context = c.clone();
                         error_context.with_value(vn.clone());
-                        let message = format!(
-                            "Blank node {:?} found where string length constraints apply (maxLength).",
-                            vn
+                        let message = validation_context.messages().format(
+                            "length.blank_node",
+                            &[
+                                ("value", &format!("{:?}", vn)),
+                                ("constraint", &"maxLength"),
+                            ],
                         );
                         let failure = ValidationFailure {
                             component_id,
@@ -182,6 +199,7 @@ impl ValidateComponent for MaxLengthConstraintComponent {
                             severity: None,
 
                             message_terms: Vec::new(),
+                            details: Vec::new(),
                         };
                         results.push(ComponentValidationResult::Fail(error_context, failure));
                         continue;
@@ -190,9 +208,12 @@ impl ValidateComponent for MaxLengthConstraintComponent {
                         if validation_context.is_data_skolem_iri(nn) {
                             let mut error_context = c.clone();
                             error_context.with_value(vn.clone());
-                            let message = format!(
-                                "Blank node {:?} found where string length constraints apply (maxLength).",
-                                vn
+                            let message = validation_context.messages().format(
+                                "length.blank_node",
+                                &[
+                                    ("value", &format!("{:?}", vn)),
+                                    ("constraint", &"maxLength"),
+                                ],
                             );
                             let failure = ValidationFailure {
                                 component_id,
@@ -204,6 +225,7 @@ impl ValidateComponent for MaxLengthConstraintComponent {
                                 severity: None,
 
                                 message_terms: Vec::new(),
+                                details: Vec::new(),
                             };
                             results.push(ComponentValidationResult::Fail(error_context, failure));
                             continue;
@@ -215,9 +237,13 @@ impl ValidateComponent for MaxLengthConstraintComponent {
                 if len > self.max_length as usize {
                     let mut error_context = c.clone();
                     error_context.with_value(vn.clone());
-                    let message = format!(
-                        "Value {:?} has length {} which is greater than maxLength {}.",
-                        vn, len, self.max_length
+                    let message = validation_context.messages().format(
+                        "max_length",
+                        &[
+                            ("value", &format!("{:?}", vn)),
+                            ("length", &len),
+                            ("max", &self.max_length),
+                        ],
                     );
                     let failure = ValidationFailure {
                         component_id,
@@ -229,6 +255,7 @@ impl ValidateComponent for MaxLengthConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     };
                     results.push(ComponentValidationResult::Fail(error_context, failure));
                 }
@@ -243,11 +270,18 @@ impl ValidateComponent for MaxLengthConstraintComponent {
 pub struct PatternConstraintComponent {
     pattern: String,
     flags: Option<String>,
+    /// The pattern compiled with XPath semantics, or why it could not be.
+    regex: Result<Regex, String>,
 }
 
 impl PatternConstraintComponent {
     pub fn new(pattern: String, flags: Option<String>) -> Self {
-        PatternConstraintComponent { pattern, flags }
+        let regex = xsd_regex::compile(&pattern, flags.as_deref());
+        PatternConstraintComponent {
+            pattern,
+            flags,
+            regex,
+        }
     }
 }
 
@@ -279,22 +313,7 @@ impl ValidateComponent for PatternConstraintComponent {
         validation_context: &ValidationContext,
         _trace: &mut Vec<TraceItem>,
     ) -> Result<Vec<ComponentValidationResult>, String> {
-        let mut pattern_builder = regex::RegexBuilder::new(&self.pattern);
-        if let Some(flags) = &self.flags {
-            // Note: SHACL flags are not identical to Rust regex crate flags.
-            // 'i' for case-insensitive is common.
-            // 'm' (multiline), 's' (dot matches newline), 'x' (ignore whitespace)
-            // 'U' (ungreedy) are other SPARQL flags.
-            // Rust regex crate uses (?i), (?m), (?s), (?x) within the pattern.
-            // We'll only handle 'i' for simplicity here.
-            // A full implementation would parse SPARQL flags and convert them.
-            if flags.contains('i') {
-                pattern_builder.case_insensitive(true);
-            }
-            // Other flags would need more complex handling or might not be directly supported.
-        }
-
-        let re = match pattern_builder.build() {
+        let re = match &self.regex {
             Ok(r) => r,
             Err(e) => return Err(format!("Invalid regex pattern '{}': {}", self.pattern, e)),
         };
@@ -306,8 +325,9 @@ impl ValidateComponent for PatternConstraintComponent {
                     TermRef::BlankNode(_) => {
                         let mut error_context = c.clone();
                         error_context.with_value(vn.clone());
-                        let message =
-                            format!("Blank node {:?} cannot be matched against a pattern.", vn);
+                        let message = validation_context
+                            .messages()
+                            .format("pattern.blank_node", &[("value", &format!("{:?}", vn))]);
                         let failure = ValidationFailure {
                             component_id,
                             failed_value_node: Some(vn.clone()),
@@ -318,6 +338,7 @@ impl ValidateComponent for PatternConstraintComponent {
                             severity: None,
 
                             message_terms: Vec::new(),
+                            details: Vec::new(),
                         };
                         results.push(ComponentValidationResult::Fail(error_context, failure));
                         continue;
@@ -326,8 +347,9 @@ impl ValidateComponent for PatternConstraintComponent {
                         if validation_context.is_data_skolem_iri(nn) {
                             let mut error_context = c.clone();
                             error_context.with_value(vn.clone());
-                            let message =
-                                format!("Blank node {:?} cannot be matched against a pattern.", vn);
+                            let message = validation_context
+                                .messages()
+                                .format("pattern.blank_node", &[("value", &format!("{:?}", vn))]);
                             let failure = ValidationFailure {
                                 component_id,
                                 failed_value_node: Some(vn.clone()),
@@ -338,6 +360,7 @@ impl ValidateComponent for PatternConstraintComponent {
                                 severity: None,
 
                                 message_terms: Vec::new(),
+                                details: Vec::new(),
                             };
                             results.push(ComponentValidationResult::Fail(error_context, failure));
                             continue;
@@ -350,14 +373,21 @@ impl ValidateComponent for PatternConstraintComponent {
                 if !re.is_match(&value_str) {
                     let mut error_context = c.clone();
                     error_context.with_value(vn.clone());
-                    let message = format!(
-                        "Value {:?} does not match pattern '{}'{}.",
-                        vn,
-                        self.pattern,
-                        self.flags
-                            .as_ref()
-                            .map_or("".to_string(), |f| format!(" with flags '{}'", f))
-                    );
+                    let value = format!("{:?}", vn);
+                    let message = match &self.flags {
+                        Some(flags) => validation_context.messages().format(
+                            "pattern.mismatch_with_flags",
+                            &[
+                                ("value", &value),
+                                ("pattern", &self.pattern),
+                                ("flags", flags),
+                            ],
+                        ),
+                        None => validation_context.messages().format(
+                            "pattern.mismatch",
+                            &[("value", &value), ("pattern", &self.pattern)],
+                        ),
+                    };
                     let failure = ValidationFailure {
                         component_id,
                         failed_value_node: Some(vn.clone()),
@@ -368,6 +398,7 @@ impl ValidateComponent for PatternConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     };
                     results.push(ComponentValidationResult::Fail(error_context, failure));
                 }
@@ -440,7 +471,7 @@ impl ValidateComponent for LanguageInConstraintComponent {
         &self,
         component_id: ComponentID,
         c: &mut Context,
-        _validation_context: &ValidationContext,
+        validation_context: &ValidationContext,
         _trace: &mut Vec<TraceItem>,
     ) -> Result<Vec<ComponentValidationResult>, String> {
         let mut results = Vec::new();
@@ -454,9 +485,9 @@ impl ValidateComponent for LanguageInConstraintComponent {
                         let lit_lang = lit.language().unwrap_or("");
                         if self.languages.is_empty() {
                             fail = true;
-                            message = format!(
-                                "Value {:?} fails sh:languageIn constraint because the list of allowed languages is empty.",
-                                vn
+                            message = validation_context.messages().format(
+                                "language_in.empty_list",
+                                &[("value", &format!("{:?}", vn))],
                             );
                         } else {
                             let matched = self
@@ -465,9 +496,13 @@ impl ValidateComponent for LanguageInConstraintComponent {
                                 .any(|allowed_lang| lang_matches(lit_lang, allowed_lang));
                             if !matched {
                                 fail = true;
-                                message = format!(
-                                    "Language tag '{}' of value {:?} is not in the allowed list {:?}.",
-                                    lit_lang, vn, self.languages
+                                message = validation_context.messages().format(
+                                    "language_in.mismatch",
+                                    &[
+                                        ("tag", &lit_lang),
+                                        ("value", &format!("{:?}", vn)),
+                                        ("allowed", &format!("{:?}", self.languages)),
+                                    ],
                                 );
                             }
                         }
@@ -475,9 +510,9 @@ impl ValidateComponent for LanguageInConstraintComponent {
                     _ => {
                         // Not a literal, so it cannot conform to a languageIn constraint.
                         fail = true;
-                        message = format!(
-                            "Value {:?} is not a literal, but sh:languageIn applies to literals.",
-                            vn
+                        message = validation_context.messages().format(
+                            "language_in.not_literal",
+                            &[("value", &format!("{:?}", vn))],
                         );
                     }
                 }
@@ -495,6 +530,7 @@ impl ValidateComponent for LanguageInConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     };
                     results.push(ComponentValidationResult::Fail(error_context, failure));
                 }
@@ -539,7 +575,7 @@ impl ValidateComponent for UniqueLangConstraintComponent {
         &self,
         component_id: ComponentID,
         c: &mut Context,
-        _validation_context: &ValidationContext,
+        validation_context: &ValidationContext,
         _trace: &mut Vec<TraceItem>,
     ) -> Result<Vec<ComponentValidationResult>, String> {
         if !self.unique_lang {
@@ -570,16 +606,16 @@ impl ValidateComponent for UniqueLangConstraintComponent {
                 let failure = ValidationFailure {
                     component_id,
                     failed_value_node: None, // sh:uniqueLang does not produce a sh:value
-                    message: format!(
-                        "Language tag '{}' is used by more than one value node, but sh:uniqueLang is true.",
-                        duplicated_tag
-                    ),
+                    message: validation_context
+                        .messages()
+                        .format("unique_lang", &[("tag", &duplicated_tag)]),
                     result_path: None,
                     source_constraint: None,
 
                     severity: None,
 
                     message_terms: Vec::new(),
+                    details: Vec::new(),
                 };
                 results.push(ComponentValidationResult::Fail(c.clone(), failure));
             }
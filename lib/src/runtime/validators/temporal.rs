@@ -0,0 +1,131 @@
+//! Explanations for date/time comparisons that have no defined order.
+//!
+//! Range and property-pair components compare values with SPARQL operators, which follow
+//! the XSD partial order: a value without a time zone is compared against a zoned value
+//! under both the +14:00 and -14:00 extremes, and the pair is incomparable when those
+//! disagree. Such pairs fail the constraint; this module explains why.
+
+use oxigraph::model::vocab::xsd;
+use oxigraph::model::Term;
+use oxsdatatypes::{Date, DateTime, Time};
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+/// A parsed `xsd:dateTime`, `xsd:date` or `xsd:time` literal.
+#[derive(Clone, Copy)]
+enum Temporal {
+    DateTime(DateTime),
+    Date(Date),
+    Time(Time),
+}
+
+impl Temporal {
+    fn parse(term: &Term) -> Option<Self> {
+        let Term::Literal(literal) = term else {
+            return None;
+        };
+        let datatype = literal.datatype();
+        if datatype == xsd::DATE_TIME {
+            DateTime::from_str(literal.value())
+                .ok()
+                .map(Temporal::DateTime)
+        } else if datatype == xsd::DATE {
+            Date::from_str(literal.value()).ok().map(Temporal::Date)
+        } else if datatype == xsd::TIME {
+            Time::from_str(literal.value()).ok().map(Temporal::Time)
+        } else {
+            None
+        }
+    }
+
+    fn has_timezone(self) -> bool {
+        match self {
+            Temporal::DateTime(value) => value.timezone_offset().is_some(),
+            Temporal::Date(value) => value.timezone_offset().is_some(),
+            Temporal::Time(value) => value.timezone_offset().is_some(),
+        }
+    }
+
+    fn partial_cmp(self, other: Self) -> Option<Option<Ordering>> {
+        match (self, other) {
+            (Temporal::DateTime(a), Temporal::DateTime(b)) => Some(a.partial_cmp(&b)),
+            (Temporal::Date(a), Temporal::Date(b)) => Some(a.partial_cmp(&b)),
+            (Temporal::Time(a), Temporal::Time(b)) => Some(a.partial_cmp(&b)),
+            // Different datatypes are not comparable at all; that is not a time zone issue.
+            _ => None,
+        }
+    }
+}
+
+/// Returns why `a` and `b` cannot be ordered, if they are date/time values of the same
+/// datatype whose comparison is indeterminate because only one of them has a time zone.
+pub(crate) fn incomparable_reason(a: &Term, b: &Term) -> Option<String> {
+    let left = Temporal::parse(a)?;
+    let right = Temporal::parse(b)?;
+    if left.partial_cmp(right)?.is_some() || left.has_timezone() == right.has_timezone() {
+        return None;
+    }
+    let (zoned, unzoned) = if left.has_timezone() { (a, b) } else { (b, a) };
+    Some(format!(
+        "{} has no time zone and is within 14 hours of {}, so their order is indeterminate",
+        literal_value(unzoned),
+        literal_value(zoned)
+    ))
+}
+
+/// Appends the explanation from [`incomparable_reason`] to `message`, if there is one.
+pub(crate) fn explain_incomparable(message: String, a: &Term, b: &Term) -> String {
+    match incomparable_reason(a, b) {
+        Some(reason) => format!("{} ({})", message, reason),
+        None => message,
+    }
+}
+
+fn literal_value(term: &Term) -> &str {
+    match term {
+        Term::Literal(literal) => literal.value(),
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxigraph::model::Literal;
+
+    fn date_time(value: &str) -> Term {
+        Literal::new_typed_literal(value, xsd::DATE_TIME).into()
+    }
+
+    #[test]
+    fn unzoned_value_near_zoned_value_is_incomparable() {
+        let reason = incomparable_reason(
+            &date_time("2024-01-01T10:00:00"),
+            &date_time("2024-01-01T12:00:00Z"),
+        )
+        .expect("pair should be incomparable");
+        assert!(reason.contains("2024-01-01T10:00:00 has no time zone"));
+    }
+
+    #[test]
+    fn ordered_pairs_have_no_explanation() {
+        // More than 14 hours apart: ordered despite the missing time zone.
+        assert!(incomparable_reason(
+            &date_time("2024-01-02T10:00:00"),
+            &date_time("2024-01-05T12:00:00Z")
+        )
+        .is_none());
+        // Both zoned: compared on the timeline.
+        assert!(incomparable_reason(
+            &date_time("2024-01-01T01:00:00+02:00"),
+            &date_time("2024-01-01T00:00:00Z")
+        )
+        .is_none());
+        // Different datatypes are not a time zone problem.
+        assert!(incomparable_reason(
+            &date_time("2024-01-01T10:00:00"),
+            &Literal::new_typed_literal("2024-01-01Z", xsd::DATE).into()
+        )
+        .is_none());
+    }
+}
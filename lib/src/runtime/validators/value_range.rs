@@ -1,4 +1,5 @@
 #![allow(deprecated)]
+use super::temporal::explain_incomparable;
 use crate::context::{format_term_for_label, Context, ValidationContext};
 use crate::runtime::{
     ComponentValidationResult, GraphvizOutput, ValidateComponent, ValidationFailure,
@@ -149,10 +150,14 @@ impl ValidateComponent for MinExclusiveConstraintComponent {
                     ValidationFailure {
                         component_id,
                         failed_value_node: Some(reported_term),
-                        message: format!(
-                            "Value {} is not exclusively greater than {}",
-                            format_term_for_label(value_node),
-                            format_term_for_label(&self.min_exclusive),
+                        message: explain_incomparable(
+                            format!(
+                                "Value {} is not exclusively greater than {}",
+                                format_term_for_label(value_node),
+                                format_term_for_label(&self.min_exclusive),
+                            ),
+                            value_node,
+                            &self.min_exclusive,
                         ),
                         result_path: None,
                         source_constraint: None,
@@ -160,6 +165,7 @@ impl ValidateComponent for MinExclusiveConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     },
                 ));
             }
@@ -250,10 +256,14 @@ impl ValidateComponent for MinInclusiveConstraintComponent {
                     ValidationFailure {
                         component_id,
                         failed_value_node: Some(reported_term),
-                        message: format!(
-                            "Value {} is not inclusively greater than or equal to {}",
-                            format_term_for_label(value_node),
-                            format_term_for_label(&self.min_inclusive),
+                        message: explain_incomparable(
+                            format!(
+                                "Value {} is not inclusively greater than or equal to {}",
+                                format_term_for_label(value_node),
+                                format_term_for_label(&self.min_inclusive),
+                            ),
+                            value_node,
+                            &self.min_inclusive,
                         ),
                         result_path: None,
                         source_constraint: None,
@@ -261,6 +271,7 @@ impl ValidateComponent for MinInclusiveConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     },
                 ));
             }
@@ -352,10 +363,14 @@ impl ValidateComponent for MaxExclusiveConstraintComponent {
                     ValidationFailure {
                         component_id,
                         failed_value_node: Some(reported_term),
-                        message: format!(
-                            "Value {} is not exclusively less than {}",
-                            format_term_for_label(value_node),
-                            format_term_for_label(&self.max_exclusive),
+                        message: explain_incomparable(
+                            format!(
+                                "Value {} is not exclusively less than {}",
+                                format_term_for_label(value_node),
+                                format_term_for_label(&self.max_exclusive),
+                            ),
+                            value_node,
+                            &self.max_exclusive,
                         ),
                         result_path: None,
                         source_constraint: None,
@@ -363,6 +378,7 @@ impl ValidateComponent for MaxExclusiveConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     },
                 ));
             }
@@ -453,10 +469,14 @@ impl ValidateComponent for MaxInclusiveConstraintComponent {
                     ValidationFailure {
                         component_id,
                         failed_value_node: Some(reported_term),
-                        message: format!(
-                            "Value {} is not inclusively less than or equal to {}",
-                            format_term_for_label(value_node),
-                            format_term_for_label(&self.max_inclusive),
+                        message: explain_incomparable(
+                            format!(
+                                "Value {} is not inclusively less than or equal to {}",
+                                format_term_for_label(value_node),
+                                format_term_for_label(&self.max_inclusive),
+                            ),
+                            value_node,
+                            &self.max_inclusive,
                         ),
                         result_path: None,
                         source_constraint: None,
@@ -464,6 +484,7 @@ impl ValidateComponent for MaxInclusiveConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     },
                 ));
             }
@@ -1,10 +1,11 @@
 use crate::context::{format_term_for_label, Context, ValidationContext};
-use crate::named_nodes::SHACL;
+use crate::messages::MessageCatalog;
+use crate::named_nodes::SHRS;
 use crate::runtime::ToSubjectRef;
 use crate::sparql::SparqlExecutor;
-use crate::types::{ComponentID, TraceItem};
+use crate::types::{ComponentID, NodeKind, TraceItem};
 use oxigraph::model::vocab::{rdf, xsd};
-use oxigraph::model::{NamedNode, Term, TermRef};
+use oxigraph::model::{NamedNode, NamedNodeRef, Term, TermRef};
 use oxigraph::sparql::{QueryResults, Variable};
 use oxsdatatypes::*;
 use std::str::FromStr;
@@ -89,9 +90,9 @@ impl ValidateComponent for ClassConstraintComponent {
                     if !result {
                         let mut error_context = c.clone();
                         error_context.with_value(vn.clone());
-                        let message = format!(
-                            "Value {:?} does not conform to class constraint: {}",
-                            vn, self.class
+                        let message = context.messages().format(
+                            "class",
+                            &[("value", &format!("{:?}", vn)), ("class", &self.class)],
                         );
                         let failure = ValidationFailure {
                             component_id,
@@ -103,6 +104,7 @@ impl ValidateComponent for ClassConstraintComponent {
                             severity: None,
 
                             message_terms: Vec::new(),
+                            details: Vec::new(),
                         };
                         results.push(ComponentValidationResult::Fail(error_context, failure));
                     }
@@ -136,7 +138,7 @@ impl ValidateComponent for DatatypeConstraintComponent {
         &self,
         component_id: ComponentID,
         c: &mut Context,
-        _context: &ValidationContext,
+        context: &ValidationContext,
         _trace: &mut Vec<TraceItem>,
     ) -> Result<Vec<ComponentValidationResult>, String> {
         let target_datatype_iri = match self.datatype.as_ref() {
@@ -148,158 +150,12 @@ impl ValidateComponent for DatatypeConstraintComponent {
 
         if let Some(value_nodes) = c.value_nodes().cloned() {
             for value_node in value_nodes {
-                let mut fail = false;
-                let mut message = String::new();
-
-                if target_datatype_iri == rdf::LANG_STRING {
-                    match value_node.as_ref() {
-                        TermRef::Literal(lit) => {
-                            if lit.language().is_none() {
-                                fail = true;
-                                message = format!(
-                                    "Value {:?} is not a language-tagged string for datatype rdf:langString",
-                                    value_node
-                                );
-                            }
-                        }
-                        _ => {
-                            fail = true;
-                            message = format!(
-                                "Value {:?} is not a literal for datatype rdf:langString",
-                                value_node
-                            );
-                        }
-                    }
-                } else {
-                    match value_node.as_ref() {
-                        TermRef::Literal(lit) => {
-                            let lit_datatype = lit.datatype();
-                            let mut datatype_matches = lit_datatype == target_datatype_iri;
-
-                            // Exception for xsd:integer being valid for xsd:decimal
-                            if !datatype_matches
-                                && target_datatype_iri == xsd::DECIMAL
-                                && lit_datatype == xsd::INTEGER
-                            {
-                                datatype_matches = true;
-                            }
-
-                            if datatype_matches {
-                                let literal_value = lit.value();
-                                let is_valid = if target_datatype_iri == xsd::STRING {
-                                    true
-                                } else if target_datatype_iri == xsd::BOOLEAN {
-                                    Boolean::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::DECIMAL {
-                                    Decimal::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::INTEGER {
-                                    Integer::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::BYTE {
-                                    Integer::from_str(literal_value)
-                                        .map(|v| {
-                                            let value: i64 = v.into();
-                                            value >= i64::from(i8::MIN)
-                                                && value <= i64::from(i8::MAX)
-                                        })
-                                        .unwrap_or(false)
-                                } else if target_datatype_iri == xsd::SHORT {
-                                    Integer::from_str(literal_value)
-                                        .map(|v| {
-                                            let value: i64 = v.into();
-                                            value >= i64::from(i16::MIN)
-                                                && value <= i64::from(i16::MAX)
-                                        })
-                                        .unwrap_or(false)
-                                } else if target_datatype_iri == xsd::INT {
-                                    Integer::from_str(literal_value)
-                                        .map(|v| {
-                                            let value: i64 = v.into();
-                                            value >= i64::from(i32::MIN)
-                                                && value <= i64::from(i32::MAX)
-                                        })
-                                        .unwrap_or(false)
-                                } else if target_datatype_iri == xsd::LONG {
-                                    Integer::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::UNSIGNED_BYTE {
-                                    Integer::from_str(literal_value)
-                                        .map(|v| {
-                                            let value: i64 = v.into();
-                                            value >= 0 && value <= i64::from(u8::MAX)
-                                        })
-                                        .unwrap_or(false)
-                                } else if target_datatype_iri == xsd::UNSIGNED_SHORT {
-                                    Integer::from_str(literal_value)
-                                        .map(|v| {
-                                            let value: i64 = v.into();
-                                            value >= 0 && value <= i64::from(u16::MAX)
-                                        })
-                                        .unwrap_or(false)
-                                } else if target_datatype_iri == xsd::UNSIGNED_INT {
-                                    Integer::from_str(literal_value)
-                                        .map(|v| {
-                                            let value: i64 = v.into();
-                                            value >= 0 && value <= i64::from(u32::MAX)
-                                        })
-                                        .unwrap_or(false)
-                                } else if target_datatype_iri == xsd::DOUBLE {
-                                    Double::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::FLOAT {
-                                    Float::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::DATE {
-                                    Date::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::TIME {
-                                    Time::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::DATE_TIME {
-                                    DateTime::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::G_YEAR {
-                                    GYear::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::G_MONTH {
-                                    GMonth::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::G_DAY {
-                                    GDay::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::G_YEAR_MONTH {
-                                    GYearMonth::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::G_MONTH_DAY {
-                                    GMonthDay::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::DURATION {
-                                    Duration::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::YEAR_MONTH_DURATION {
-                                    YearMonthDuration::from_str(literal_value).is_ok()
-                                } else if target_datatype_iri == xsd::DAY_TIME_DURATION {
-                                    DayTimeDuration::from_str(literal_value).is_ok()
-                                } else {
-                                    // For unknown or unsupported datatypes, we assume the lexical form is valid
-                                    // as we can't check it. This preserves the old behavior of only checking the datatype IRI.
-                                    true
-                                };
-
-                                if !is_valid {
-                                    fail = true;
-                                    message = format!(
-                                        "Value {:?} has an invalid lexical form for datatype {}",
-                                        value_node, self.datatype
-                                    );
-                                }
-                            } else {
-                                fail = true;
-                                message = format!(
-                                    "Value {:?} does not have datatype {}",
-                                    value_node, self.datatype
-                                );
-                            }
-                        }
-                        _ => {
-                            // Not a literal, so it cannot conform to a datatype constraint
-                            fail = true;
-                            message = format!(
-                                "Value {:?} is not a literal, expected datatype {}",
-                                value_node, self.datatype
-                            );
-                        }
-                    }
-                }
-
-                if fail {
+                if let Some(message) = datatype_violation(
+                    context.messages(),
+                    &value_node,
+                    target_datatype_iri,
+                    &self.datatype,
+                ) {
                     let mut error_context = c.clone();
                     error_context.with_value(value_node.clone());
                     let failure = ValidationFailure {
@@ -312,6 +168,7 @@ impl ValidateComponent for DatatypeConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     };
                     results.push(ComponentValidationResult::Fail(error_context, failure));
                 }
@@ -322,6 +179,174 @@ impl ValidateComponent for DatatypeConstraintComponent {
     }
 }
 
+/// Checks `value_node` against a single datatype, returning the failure message if the
+/// value is not a literal of that datatype or has an invalid lexical form for it.
+fn datatype_violation(
+    messages: &MessageCatalog,
+    value_node: &Term,
+    target_datatype_iri: NamedNodeRef<'_>,
+    datatype: &Term,
+) -> Option<String> {
+    let mut fail = false;
+    let mut message = String::new();
+
+    if target_datatype_iri == rdf::LANG_STRING {
+        match value_node.as_ref() {
+            TermRef::Literal(lit) => {
+                if lit.language().is_none() {
+                    fail = true;
+                    message = messages.format(
+                        "datatype.lang_string_without_tag",
+                        &[("value", &format!("{:?}", value_node))],
+                    );
+                }
+            }
+            _ => {
+                fail = true;
+                message = messages.format(
+                    "datatype.lang_string_not_literal",
+                    &[("value", &format!("{:?}", value_node))],
+                );
+            }
+        }
+    } else {
+        match value_node.as_ref() {
+            TermRef::Literal(lit) => {
+                let lit_datatype = lit.datatype();
+                let mut datatype_matches = lit_datatype == target_datatype_iri;
+
+                // Exception for xsd:integer being valid for xsd:decimal
+                if !datatype_matches
+                    && target_datatype_iri == xsd::DECIMAL
+                    && lit_datatype == xsd::INTEGER
+                {
+                    datatype_matches = true;
+                }
+
+                if datatype_matches {
+                    let literal_value = lit.value();
+                    let is_valid = if target_datatype_iri == xsd::STRING {
+                        true
+                    } else if target_datatype_iri == xsd::BOOLEAN {
+                        Boolean::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::DECIMAL {
+                        Decimal::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::INTEGER {
+                        Integer::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::BYTE {
+                        Integer::from_str(literal_value)
+                            .map(|v| {
+                                let value: i64 = v.into();
+                                value >= i64::from(i8::MIN) && value <= i64::from(i8::MAX)
+                            })
+                            .unwrap_or(false)
+                    } else if target_datatype_iri == xsd::SHORT {
+                        Integer::from_str(literal_value)
+                            .map(|v| {
+                                let value: i64 = v.into();
+                                value >= i64::from(i16::MIN) && value <= i64::from(i16::MAX)
+                            })
+                            .unwrap_or(false)
+                    } else if target_datatype_iri == xsd::INT {
+                        Integer::from_str(literal_value)
+                            .map(|v| {
+                                let value: i64 = v.into();
+                                value >= i64::from(i32::MIN) && value <= i64::from(i32::MAX)
+                            })
+                            .unwrap_or(false)
+                    } else if target_datatype_iri == xsd::LONG {
+                        Integer::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::UNSIGNED_BYTE {
+                        Integer::from_str(literal_value)
+                            .map(|v| {
+                                let value: i64 = v.into();
+                                value >= 0 && value <= i64::from(u8::MAX)
+                            })
+                            .unwrap_or(false)
+                    } else if target_datatype_iri == xsd::UNSIGNED_SHORT {
+                        Integer::from_str(literal_value)
+                            .map(|v| {
+                                let value: i64 = v.into();
+                                value >= 0 && value <= i64::from(u16::MAX)
+                            })
+                            .unwrap_or(false)
+                    } else if target_datatype_iri == xsd::UNSIGNED_INT {
+                        Integer::from_str(literal_value)
+                            .map(|v| {
+                                let value: i64 = v.into();
+                                value >= 0 && value <= i64::from(u32::MAX)
+                            })
+                            .unwrap_or(false)
+                    } else if target_datatype_iri == xsd::DOUBLE {
+                        Double::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::FLOAT {
+                        Float::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::DATE {
+                        Date::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::TIME {
+                        Time::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::DATE_TIME {
+                        DateTime::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::G_YEAR {
+                        GYear::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::G_MONTH {
+                        GMonth::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::G_DAY {
+                        GDay::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::G_YEAR_MONTH {
+                        GYearMonth::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::G_MONTH_DAY {
+                        GMonthDay::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::DURATION {
+                        Duration::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::YEAR_MONTH_DURATION {
+                        YearMonthDuration::from_str(literal_value).is_ok()
+                    } else if target_datatype_iri == xsd::DAY_TIME_DURATION {
+                        DayTimeDuration::from_str(literal_value).is_ok()
+                    } else {
+                        // For unknown or unsupported datatypes, we assume the lexical form is valid
+                        // as we can't check it. This preserves the old behavior of only checking the datatype IRI.
+                        true
+                    };
+
+                    if !is_valid {
+                        fail = true;
+                        message = messages.format(
+                            "datatype.ill_formed",
+                            &[
+                                ("value", &format!("{:?}", value_node)),
+                                ("datatype", datatype),
+                            ],
+                        );
+                    }
+                } else {
+                    fail = true;
+                    message = messages.format(
+                        "datatype.mismatch",
+                        &[
+                            ("value", &format!("{:?}", value_node)),
+                            ("datatype", datatype),
+                        ],
+                    );
+                }
+            }
+            _ => {
+                // Not a literal, so it cannot conform to a datatype constraint
+                fail = true;
+                message = messages.format(
+                    "datatype.not_literal",
+                    &[
+                        ("value", &format!("{:?}", value_node)),
+                        ("datatype", datatype),
+                    ],
+                );
+            }
+        }
+    }
+
+    fail.then_some(message)
+}
+
 impl GraphvizOutput for DatatypeConstraintComponent {
     fn component_type(&self) -> NamedNode {
         NamedNode::new_unchecked("http://www.w3.org/ns/shacl#DatatypeConstraintComponent")
@@ -341,6 +366,98 @@ impl GraphvizOutput for DatatypeConstraintComponent {
     }
 }
 
+/// `shrs:datatypeIn`: a closed-world union of datatypes, equivalent to an `sh:or` of
+/// `sh:datatype` constraints but checked natively.
+#[derive(Debug)]
+pub struct DatatypeInConstraintComponent {
+    datatypes: Vec<Term>,
+}
+
+impl DatatypeInConstraintComponent {
+    pub fn new(datatypes: Vec<Term>) -> Self {
+        DatatypeInConstraintComponent { datatypes }
+    }
+}
+
+impl ValidateComponent for DatatypeInConstraintComponent {
+    fn validate(
+        &self,
+        component_id: ComponentID,
+        c: &mut Context,
+        context: &ValidationContext,
+        _trace: &mut Vec<TraceItem>,
+    ) -> Result<Vec<ComponentValidationResult>, String> {
+        let mut datatype_iris = Vec::with_capacity(self.datatypes.len());
+        for datatype in &self.datatypes {
+            match datatype.as_ref() {
+                TermRef::NamedNode(nn) => datatype_iris.push((nn, datatype)),
+                _ => return Err("shrs:datatypeIn members must be IRIs".to_string()),
+            }
+        }
+
+        let mut results = Vec::new();
+
+        if let Some(value_nodes) = c.value_nodes().cloned() {
+            for value_node in value_nodes {
+                let matches_any = datatype_iris.iter().any(|(iri, datatype)| {
+                    datatype_violation(context.messages(), &value_node, *iri, datatype).is_none()
+                });
+                if !matches_any {
+                    let mut error_context = c.clone();
+                    error_context.with_value(value_node.clone());
+                    let failure = ValidationFailure {
+                        component_id,
+                        failed_value_node: Some(value_node.clone()),
+                        message: context.messages().format(
+                            "datatype_in",
+                            &[
+                                ("value", &format!("{:?}", value_node)),
+                                ("datatypes", &format_datatype_list(&self.datatypes)),
+                            ],
+                        ),
+                        result_path: None,
+                        source_constraint: None,
+                        severity: None,
+                        message_terms: Vec::new(),
+                        details: Vec::new(),
+                    };
+                    results.push(ComponentValidationResult::Fail(error_context, failure));
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+fn format_datatype_list(datatypes: &[Term]) -> String {
+    datatypes
+        .iter()
+        .map(|datatype| datatype.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl GraphvizOutput for DatatypeInConstraintComponent {
+    fn component_type(&self) -> NamedNode {
+        SHRS::get().datatype_in_constraint_component.into_owned()
+    }
+
+    fn to_graphviz_string(
+        &self,
+        component_id: ComponentID,
+        _context: &ValidationContext,
+    ) -> String {
+        let datatype_names: Vec<String> =
+            self.datatypes.iter().map(format_term_for_label).collect();
+        format!(
+            "{} [label=\"DatatypeIn: {}\"];",
+            component_id.to_graphviz_id(),
+            datatype_names.join(", ")
+        )
+    }
+}
+
 #[derive(Debug)]
 pub struct NodeKindConstraintComponent {
     node_kind: Term,
@@ -350,6 +467,26 @@ impl NodeKindConstraintComponent {
     pub fn new(node_kind: Term) -> Self {
         NodeKindConstraintComponent { node_kind }
     }
+
+    /// The required node kind, or `None` if `sh:nodeKind` is not a SHACL node kind, which
+    /// no value satisfies.
+    pub(crate) fn expected(&self) -> Option<NodeKind> {
+        NodeKind::from_term(&self.node_kind)
+    }
+}
+
+/// The node kind of `value_node` as seen by `sh:nodeKind`: skolem IRIs stand in for blank
+/// nodes during validation, so they count as blank nodes.
+pub(crate) fn value_node_kind(value_node: &Term, context: &ValidationContext) -> NodeKind {
+    match value_node {
+        Term::NamedNode(nn)
+            if context.is_data_skolem_iri(nn.as_ref())
+                || context.is_shape_skolem_iri(nn.as_ref()) =>
+        {
+            NodeKind::BlankNode
+        }
+        _ => NodeKind::of(value_node),
+    }
 }
 
 impl ValidateComponent for NodeKindConstraintComponent {
@@ -360,58 +497,27 @@ impl ValidateComponent for NodeKindConstraintComponent {
         context: &ValidationContext,
         _trace: &mut Vec<TraceItem>,
     ) -> Result<Vec<ComponentValidationResult>, String> {
-        let sh = SHACL::new();
-        let expected_node_kind_term = self.node_kind.as_ref();
+        let expected = self.expected();
         let mut results = Vec::new();
 
         if let Some(value_nodes) = c.value_nodes().cloned() {
             for value_node in value_nodes {
-                enum ValueCategory {
-                    Named,
-                    Blank,
-                    Literal,
-                    #[allow(dead_code)]
-                    Unsupported,
-                }
-
-                let category = match value_node.as_ref() {
-                    TermRef::NamedNode(nn) => {
-                        // Skolem IRIs stand in for blank nodes during validation; treat them accordingly.
-                        if context.is_data_skolem_iri(nn) || context.is_shape_skolem_iri(nn) {
-                            ValueCategory::Blank
-                        } else {
-                            ValueCategory::Named
-                        }
-                    }
-                    TermRef::BlankNode(_) => ValueCategory::Blank,
-                    TermRef::Literal(_) => ValueCategory::Literal,
-                };
-
-                let matches = match category {
-                    ValueCategory::Named => {
-                        expected_node_kind_term == sh.iri.into()
-                            || expected_node_kind_term == sh.blank_node_or_iri.into()
-                            || expected_node_kind_term == sh.iri_or_literal.into()
-                    }
-                    ValueCategory::Blank => {
-                        expected_node_kind_term == sh.blank_node.into()
-                            || expected_node_kind_term == sh.blank_node_or_iri.into()
-                            || expected_node_kind_term == sh.blank_node_or_literal.into()
-                    }
-                    ValueCategory::Literal => {
-                        expected_node_kind_term == sh.literal.into()
-                            || expected_node_kind_term == sh.blank_node_or_literal.into()
-                            || expected_node_kind_term == sh.iri_or_literal.into()
-                    }
-                    ValueCategory::Unsupported => false,
-                };
-
-                if !matches {
+                let actual = value_node_kind(&value_node, context);
+                if !expected.is_some_and(|expected| expected.allows(actual)) {
                     let mut error_context = c.clone();
                     error_context.with_value(value_node.clone());
-                    let message = format!(
-                        "Value {:?} does not match nodeKind {}",
-                        value_node, self.node_kind
+                    let expected_description = match expected {
+                        Some(expected) => expected.to_string(),
+                        None => format!("the unknown node kind {}", self.node_kind),
+                    };
+                    let message = context.messages().format(
+                        "node_kind",
+                        &[
+                            ("value", &format!("{:?}", value_node)),
+                            ("actual", &actual),
+                            ("node_kind", &self.node_kind),
+                            ("expected", &expected_description),
+                        ],
                     );
                     let failure = ValidationFailure {
                         component_id,
@@ -423,6 +529,7 @@ impl ValidateComponent for NodeKindConstraintComponent {
                         severity: None,
 
                         message_terms: Vec::new(),
+                        details: Vec::new(),
                     };
                     results.push(ComponentValidationResult::Fail(error_context, failure));
                 }
@@ -458,6 +565,8 @@ mod tests {
     use crate::context::model::FeatureToggles;
     use crate::context::{Context, IDLookupTable, ShapesModel, SourceShape, ValidationContext};
     use crate::model::components::ComponentDescriptor;
+    use crate::named_nodes::SHACL;
+    use crate::prefix_map::PrefixMap;
     use crate::sparql::SparqlServices;
     use crate::types::{ComponentID, PropShapeID};
     use ontoenv::api::OntoEnv;
@@ -502,6 +611,9 @@ mod tests {
             sparql: Rc::new(SparqlServices::new()),
             features: FeatureToggles::default(),
             original_values: None,
+            prefixes: PrefixMap::with_well_known(),
+            unsupported_features: Vec::new(),
+            custom_components: Vec::new(),
         };
 
         ValidationContext::new(Rc::new(model), data_graph_iri)
@@ -522,7 +634,7 @@ mod tests {
 
         let validation_context = build_empty_validation_context();
 
-        let iri_or_literal = Term::NamedNode(SHACL::new().iri_or_literal.into_owned());
+        let iri_or_literal = Term::NamedNode(SHACL::get().iri_or_literal.into_owned());
         let component = NodeKindConstraintComponent::new(iri_or_literal);
 
         let mut trace = Vec::new();
@@ -543,6 +655,13 @@ mod tests {
                     .as_ref()
                     .expect("missing failed value");
                 assert_eq!(value, &Term::NamedNode(skolem_value));
+                assert!(
+                    failure
+                        .message
+                        .contains("is a blank node, but sh:nodeKind <http://www.w3.org/ns/shacl#IRIOrLiteral> requires an IRI or a literal"),
+                    "{}",
+                    failure.message
+                );
             }
             other => panic!("expected failure result, got {:?}", other),
         }
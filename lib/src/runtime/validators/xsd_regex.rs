@@ -0,0 +1,316 @@
+//! XPath regular expressions, as used by `sh:pattern`, on top of the `regex` crate.
+//!
+//! `sh:pattern` and `sh:flags` follow the XPath `fn:matches` function, whose syntax is the
+//! XML Schema regular expression language extended with anchors and reluctant quantifiers.
+//! Most of it is valid `regex` syntax, but some constructs are spelled differently or mean
+//! something else, so patterns are translated before they are compiled:
+//!
+//! - `\i`, `\I`, `\c` and `\C` (XML name characters) become explicit character classes;
+//! - `\w`, `\s` and `.` keep their XSD meaning (`\s` is XML whitespace only, and `.`
+//!   matches neither `\n` nor `\r`);
+//! - class subtraction such as `[a-z-[aeiou]]` becomes a `regex` class difference;
+//! - `\p{IsBlock}` Unicode block escapes become code point ranges.
+//!
+//! All XPath flags are honoured: `s`, `m`, `i`, `x` and `q`. Back-references, which `regex`
+//! cannot evaluate, are rejected along with escapes XSD does not define.
+
+use regex::{Regex, RegexBuilder};
+
+/// `NameStartChar` of XML 1.0, the code points `\i` matches.
+const NAME_START_CHARS: &str = ":A-Z_a-z\\x{C0}-\\x{D6}\\x{D8}-\\x{F6}\\x{F8}-\\x{2FF}\
+    \\x{370}-\\x{37D}\\x{37F}-\\x{1FFF}\\x{200C}-\\x{200D}\\x{2070}-\\x{218F}\
+    \\x{2C00}-\\x{2FEF}\\x{3001}-\\x{D7FF}\\x{F900}-\\x{FDCF}\\x{FDF0}-\\x{FFFD}\
+    \\x{10000}-\\x{EFFFF}";
+
+/// The code points `\c` matches in addition to `\i`, completing XML 1.0 `NameChar`.
+const NAME_CHARS: &str = "\\-.0-9\\x{B7}\\x{300}-\\x{36F}\\x{203F}-\\x{2040}";
+
+/// Code points that are not word characters for `\w`: punctuation, separators and other.
+const NON_WORD_CHARS: &str = "\\p{P}\\p{Z}\\p{C}";
+
+/// XML whitespace, which is all `\s` matches.
+const WHITESPACE_CHARS: &str = "\\x20\\t\\n\\r";
+
+/// Unicode blocks that `\p{IsBlock}` may name, with their code point ranges.
+const BLOCKS: &[(&str, &str)] = &[
+    ("BasicLatin", "\\x{0}-\\x{7F}"),
+    ("Latin-1Supplement", "\\x{80}-\\x{FF}"),
+    ("LatinExtended-A", "\\x{100}-\\x{17F}"),
+    ("LatinExtended-B", "\\x{180}-\\x{24F}"),
+    ("IPAExtensions", "\\x{250}-\\x{2AF}"),
+    ("SpacingModifierLetters", "\\x{2B0}-\\x{2FF}"),
+    ("CombiningDiacriticalMarks", "\\x{300}-\\x{36F}"),
+    ("Greek", "\\x{370}-\\x{3FF}"),
+    ("GreekandCoptic", "\\x{370}-\\x{3FF}"),
+    ("Cyrillic", "\\x{400}-\\x{4FF}"),
+    ("Armenian", "\\x{530}-\\x{58F}"),
+    ("Hebrew", "\\x{590}-\\x{5FF}"),
+    ("Arabic", "\\x{600}-\\x{6FF}"),
+    ("Devanagari", "\\x{900}-\\x{97F}"),
+    ("Thai", "\\x{E00}-\\x{E7F}"),
+    ("Georgian", "\\x{10A0}-\\x{10FF}"),
+    ("HangulJamo", "\\x{1100}-\\x{11FF}"),
+    ("LatinExtendedAdditional", "\\x{1E00}-\\x{1EFF}"),
+    ("GreekExtended", "\\x{1F00}-\\x{1FFF}"),
+    ("GeneralPunctuation", "\\x{2000}-\\x{206F}"),
+    ("SuperscriptsandSubscripts", "\\x{2070}-\\x{209F}"),
+    ("CurrencySymbols", "\\x{20A0}-\\x{20CF}"),
+    ("LetterlikeSymbols", "\\x{2100}-\\x{214F}"),
+    ("NumberForms", "\\x{2150}-\\x{218F}"),
+    ("Arrows", "\\x{2190}-\\x{21FF}"),
+    ("MathematicalOperators", "\\x{2200}-\\x{22FF}"),
+    ("BoxDrawing", "\\x{2500}-\\x{257F}"),
+    ("GeometricShapes", "\\x{25A0}-\\x{25FF}"),
+    ("MiscellaneousSymbols", "\\x{2600}-\\x{26FF}"),
+    ("CJKSymbolsandPunctuation", "\\x{3000}-\\x{303F}"),
+    ("Hiragana", "\\x{3040}-\\x{309F}"),
+    ("Katakana", "\\x{30A0}-\\x{30FF}"),
+    ("CJKUnifiedIdeographs", "\\x{4E00}-\\x{9FFF}"),
+    ("HangulSyllables", "\\x{AC00}-\\x{D7AF}"),
+    ("PrivateUseArea", "\\x{E000}-\\x{F8FF}"),
+    ("AlphabeticPresentationForms", "\\x{FB00}-\\x{FB4F}"),
+    ("HalfwidthandFullwidthForms", "\\x{FF00}-\\x{FFEF}"),
+    ("Specials", "\\x{FFF0}-\\x{FFFF}"),
+];
+
+/// Compiles an `sh:pattern` with its `sh:flags`.
+pub(crate) fn compile(pattern: &str, flags: Option<&str>) -> Result<Regex, String> {
+    let mut dot_all = false;
+    let mut multi_line = false;
+    let mut case_insensitive = false;
+    let mut extended = false;
+    let mut literal = false;
+    for flag in flags.unwrap_or("").chars() {
+        match flag {
+            's' => dot_all = true,
+            'm' => multi_line = true,
+            'i' => case_insensitive = true,
+            'x' => extended = true,
+            'q' => literal = true,
+            other => {
+                return Err(format!(
+                    "unknown flag '{}' (expected s, m, i, x or q)",
+                    other
+                ))
+            }
+        }
+    }
+    // With `q` every character stands for itself and only `i` still applies.
+    let translated = if literal {
+        regex::escape(pattern)
+    } else {
+        Translator::new(pattern, dot_all, extended).translate()?
+    };
+    RegexBuilder::new(&translated)
+        .multi_line(multi_line && !literal)
+        .case_insensitive(case_insensitive)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+struct Translator {
+    chars: Vec<char>,
+    pos: usize,
+    dot_all: bool,
+    extended: bool,
+}
+
+impl Translator {
+    fn new(pattern: &str, dot_all: bool, extended: bool) -> Self {
+        Self {
+            chars: pattern.chars().collect(),
+            pos: 0,
+            dot_all,
+            extended,
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.get(self.pos).copied();
+        self.pos += 1;
+        c
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn translate(mut self) -> Result<String, String> {
+        let mut out = String::new();
+        while let Some(c) = self.next() {
+            match c {
+                // `x` removes whitespace, except inside character classes.
+                ' ' | '\t' | '\n' | '\r' if self.extended => {}
+                '\\' => out.push_str(&self.escape(false)?),
+                '[' => out.push_str(&self.class()?),
+                '.' if self.dot_all => out.push_str("(?s:.)"),
+                '.' => out.push_str("[^\\n\\r]"),
+                other => out.push(other),
+            }
+        }
+        Ok(out)
+    }
+
+    /// Translates the escape following a `\`.
+    fn escape(&mut self, in_class: bool) -> Result<String, String> {
+        let c = self
+            .next()
+            .ok_or_else(|| "pattern ends with an unescaped '\\'".to_string())?;
+        Ok(match c {
+            'i' => set(NAME_START_CHARS, false, in_class),
+            'I' => set(NAME_START_CHARS, true, in_class),
+            'c' | 'C' => {
+                let name_chars = format!("{}{}", NAME_START_CHARS, NAME_CHARS);
+                set(&name_chars, c == 'C', in_class)
+            }
+            'w' => set(NON_WORD_CHARS, true, in_class),
+            'W' => set(NON_WORD_CHARS, false, in_class),
+            's' => set(WHITESPACE_CHARS, false, in_class),
+            'S' => set(WHITESPACE_CHARS, true, in_class),
+            'd' | 'D' | 'n' | 'r' | 't' => format!("\\{}", c),
+            'p' | 'P' => {
+                let name = self.property_name()?;
+                match name.strip_prefix("Is") {
+                    Some(block) => {
+                        let ranges = BLOCKS
+                            .iter()
+                            .find(|(known, _)| *known == block)
+                            .map(|(_, ranges)| *ranges)
+                            .ok_or_else(|| format!("unsupported Unicode block '{}'", name))?;
+                        set(ranges, c == 'P', in_class)
+                    }
+                    None => format!("\\{}{{{}}}", c, name),
+                }
+            }
+            '1'..='9' => {
+                return Err(format!(
+                    "back-reference \\{} is not supported in sh:pattern",
+                    c
+                ))
+            }
+            '\\' | '|' | '.' | '?' | '*' | '+' | '(' | ')' | '{' | '}' | '-' | '[' | ']' | '^'
+            | '$' => format!("\\{}", c),
+            other => return Err(format!("invalid escape '\\{}'", other)),
+        })
+    }
+
+    /// Reads the `{Name}` of a `\p` or `\P` escape.
+    fn property_name(&mut self) -> Result<String, String> {
+        if self.next() != Some('{') {
+            return Err("expected '{' after \\p".to_string());
+        }
+        let mut name = String::new();
+        loop {
+            match self.next() {
+                Some('}') => return Ok(name),
+                Some(c) => name.push(c),
+                None => return Err("unterminated \\p{...} escape".to_string()),
+            }
+        }
+    }
+
+    /// Translates a character class whose `[` was just read, including a trailing
+    /// subtraction `-[...]`.
+    fn class(&mut self) -> Result<String, String> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.pos += 1;
+        }
+        let mut body = String::new();
+        loop {
+            match self.next() {
+                None => return Err("unterminated character class".to_string()),
+                Some(']') => break,
+                Some('-') if self.peek() == Some('[') => {
+                    self.pos += 1;
+                    let subtracted = self.class()?;
+                    if self.next() != Some(']') {
+                        return Err("a class subtraction must end its character class".to_string());
+                    }
+                    let base = if negated {
+                        format!("[^{}]", body)
+                    } else {
+                        format!("[{}]", body)
+                    };
+                    return Ok(format!("[{}--{}]", base, subtracted));
+                }
+                Some('\\') => body.push_str(&self.escape(true)?),
+                // Set operators and nested classes in `regex`, plain characters in XSD.
+                Some(c @ ('&' | '~' | '[')) => {
+                    body.push('\\');
+                    body.push(c);
+                }
+                Some(c) => body.push(c),
+            }
+        }
+        Ok(if negated {
+            format!("[^{}]", body)
+        } else {
+            format!("[{}]", body)
+        })
+    }
+}
+
+/// A class matching `ranges`, or everything else when `negated`. Inside a character class
+/// the ranges are added to it directly; a negated set becomes a nested class.
+fn set(ranges: &str, negated: bool, in_class: bool) -> String {
+    match (negated, in_class) {
+        (false, true) => ranges.to_string(),
+        (false, false) => format!("[{}]", ranges),
+        (true, _) => format!("[^{}]", ranges),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, flags: Option<&str>, value: &str) -> bool {
+        compile(pattern, flags)
+            .unwrap_or_else(|e| panic!("{} failed to compile: {}", pattern, e))
+            .is_match(value)
+    }
+
+    #[test]
+    fn xml_name_escapes_and_class_subtraction() {
+        assert!(matches("^\\i\\c*$", None, "_rdf-name.1"));
+        assert!(!matches("^\\i\\c*$", None, "1name"));
+        assert!(matches("^[a-z-[aeiou]]+$", None, "rhythm"));
+        assert!(!matches("^[a-z-[aeiou]]+$", None, "vowel"));
+        assert!(matches("^[^a-z-[0-9]]$", None, "A"));
+        assert!(!matches("^[^a-z-[0-9]]$", None, "5"));
+        assert!(matches("^\\p{IsBasicLatin}+$", None, "plain"));
+        assert!(!matches("^\\p{IsBasicLatin}+$", None, "caf\u{e9}"));
+    }
+
+    #[test]
+    fn whitespace_word_and_dot_keep_their_xsd_meaning() {
+        assert!(!matches("^\\s$", None, "\u{a0}"));
+        assert!(matches("^\\w+$", None, "abc1"));
+        // Unlike Perl-style \w, XSD's excludes punctuation such as the underscore.
+        assert!(!matches("^\\w+$", None, "a_b"));
+        assert!(!matches("^\\w+$", None, "a-b"));
+        assert!(!matches("^a.b$", None, "a\rb"));
+        assert!(matches("^a.b$", Some("s"), "a\nb"));
+    }
+
+    #[test]
+    fn flags_are_honoured() {
+        assert!(matches("^abc$", Some("i"), "ABC"));
+        assert!(matches("^b$", Some("m"), "a\nb"));
+        assert!(!matches("^b$", None, "a\nb"));
+        assert!(matches("^a b c$", Some("x"), "abc"));
+        assert!(matches("^[ ]$", Some("x"), " "));
+        assert!(matches("a.b*", Some("q"), "xa.b*y"));
+        assert!(!matches("a.b*", Some("q"), "aXb"));
+        assert!(matches("A.B", Some("qi"), "a.b"));
+        assert!(compile("a", Some("U")).is_err());
+    }
+
+    #[test]
+    fn unsupported_constructs_are_rejected() {
+        assert!(compile("(a)\\1", None).is_err());
+        assert!(compile("\\bword", None).is_err());
+        assert!(compile("\\p{IsNoSuchBlock}", None).is_err());
+    }
+}
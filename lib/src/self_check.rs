@@ -0,0 +1,111 @@
+//! Checks emitted validation reports against bundled SHACL report shapes.
+//!
+//! The shapes in `report_shapes.ttl` encode the structure the SHACL specification
+//! requires of a validation report (one boolean `sh:conforms` that agrees with the
+//! results, one focus node, severity and source component per result, ...). Running
+//! them over our own reports guards `report.rs` against emitting malformed output.
+
+use crate::named_nodes::SHACL;
+use crate::{Source, Validator, ValidatorBuilder};
+use oxigraph::model::vocab::rdf;
+use oxigraph::model::{Graph, NamedOrBlankNodeRef, Term, TermRef};
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const REPORT_SHAPES: &str = include_str!("report_shapes.ttl");
+
+static CHECKER_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A structural problem found in a validation report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportIssue {
+    /// The report or result node that is malformed.
+    pub focus_node: Term,
+    /// The report property involved, when the problem concerns one.
+    pub path: Option<Term>,
+    /// The constraint component that flagged the problem.
+    pub component: Term,
+    pub message: Option<String>,
+}
+
+impl fmt::Display for ReportIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.focus_node)?;
+        if let Some(path) = &self.path {
+            write!(f, " {}", path)?;
+        }
+        write!(f, ": violates {}", self.component)?;
+        if let Some(message) = &self.message {
+            write!(f, " ({})", message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Validates report graphs against the bundled report shapes.
+///
+/// Building a checker compiles the report shapes once; [`ReportChecker::check`] can then
+/// be called for any number of reports.
+pub struct ReportChecker {
+    validator: Validator,
+}
+
+impl ReportChecker {
+    pub fn new() -> Result<Self, Box<dyn Error>> {
+        // Sources are loaded from disk, so stage the bundled shapes in a private file.
+        let dir = std::env::temp_dir().join(format!(
+            "shacl-rs-report-shapes-{}-{}",
+            std::process::id(),
+            CHECKER_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&dir)?;
+        let shapes_path: PathBuf = dir.join("report_shapes.ttl");
+        let built = std::fs::write(&shapes_path, REPORT_SHAPES)
+            .map_err(|e| Box::new(e) as Box<dyn Error>)
+            .and_then(|()| {
+                ValidatorBuilder::new()
+                    .with_shapes_source(Source::File(shapes_path.clone()))
+                    .with_data_source(Source::File(shapes_path.clone()))
+                    .build()
+            });
+        let _ = std::fs::remove_dir_all(&dir);
+        Ok(ReportChecker { validator: built? })
+    }
+
+    /// Returns the problems found in `report`; an empty list means it is well-formed.
+    pub fn check(&self, report: &Graph) -> Result<Vec<ReportIssue>, String> {
+        let mut issues = Vec::new();
+        self.validator
+            .validate_each(std::iter::once(report.clone()), |_, check| {
+                issues = report_issues(&check.to_graph());
+            })?;
+        Ok(issues)
+    }
+}
+
+fn report_issues(check_report: &Graph) -> Vec<ReportIssue> {
+    let sh = SHACL::get();
+    let object = |subject: NamedOrBlankNodeRef<'_>, predicate| {
+        check_report
+            .object_for_subject_predicate(subject, predicate)
+            .map(TermRef::into_owned)
+    };
+    let mut issues: Vec<ReportIssue> = check_report
+        .subjects_for_predicate_object(rdf::TYPE, TermRef::from(sh.validation_result))
+        .filter_map(|result| {
+            Some(ReportIssue {
+                focus_node: object(result, sh.focus_node)?,
+                path: object(result, sh.result_path),
+                component: object(result, sh.source_constraint_component)?,
+                message: match object(result, sh.result_message) {
+                    Some(Term::Literal(literal)) => Some(literal.value().to_string()),
+                    _ => None,
+                },
+            })
+        })
+        .collect();
+    issues.sort_by_key(|issue| issue.to_string());
+    issues
+}
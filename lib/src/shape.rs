@@ -1,2 +1,193 @@
 pub use crate::model::shapes::{NodeShape, PropertyShape};
 pub(crate) use crate::runtime::ValidateShape;
+
+use crate::context::ShapesModel;
+use crate::model::components::ComponentDescriptor;
+use crate::types::{ComponentID, Path, PropShapeID, Severity, Target, ID};
+use oxigraph::model::Term;
+
+/// The parsed form of a shape, as seen through a [`ShapeView`].
+#[derive(Debug, Clone, Copy)]
+pub enum ShapeKind<'a> {
+    Node(&'a NodeShape),
+    Property(&'a PropertyShape),
+}
+
+/// A read-only view of a parsed shape within a validator's shapes model.
+///
+/// Obtained from [`crate::Validator::shapes`] and friends. Identifiers carried by
+/// [`ComponentDescriptor`]s (e.g. the `ID` in `sh:node`) can be resolved back to views
+/// with [`ShapeView::node_shape`] and [`ShapeView::property_shape`].
+#[derive(Clone, Copy)]
+pub struct ShapeView<'a> {
+    model: &'a ShapesModel,
+    kind: ShapeKind<'a>,
+}
+
+impl<'a> ShapeView<'a> {
+    pub(crate) fn node(model: &'a ShapesModel, id: &ID) -> Option<Self> {
+        model.get_node_shape_by_id(id).map(|shape| ShapeView {
+            model,
+            kind: ShapeKind::Node(shape),
+        })
+    }
+
+    pub(crate) fn property(model: &'a ShapesModel, id: &PropShapeID) -> Option<Self> {
+        model.get_prop_shape_by_id(id).map(|shape| ShapeView {
+            model,
+            kind: ShapeKind::Property(shape),
+        })
+    }
+
+    /// Returns views of every node and property shape, ordered by shape term.
+    pub(crate) fn all(model: &'a ShapesModel) -> Vec<Self> {
+        let mut shapes: Vec<(String, ShapeView<'a>)> = model
+            .node_shapes
+            .values()
+            .map(ShapeKind::Node)
+            .chain(model.prop_shapes.values().map(ShapeKind::Property))
+            .map(|kind| {
+                let view = ShapeView { model, kind };
+                (view.term().to_string(), view)
+            })
+            .collect();
+        shapes.sort_by(|a, b| a.0.cmp(&b.0));
+        shapes.into_iter().map(|(_, view)| view).collect()
+    }
+
+    pub fn kind(&self) -> ShapeKind<'a> {
+        self.kind
+    }
+
+    /// The IRI or blank node naming the shape in the shapes graph.
+    pub fn term(&self) -> Term {
+        let term = match self.kind {
+            ShapeKind::Node(shape) => self
+                .model
+                .nodeshape_id_lookup()
+                .borrow()
+                .get_term(*shape.identifier())
+                .cloned(),
+            ShapeKind::Property(shape) => self
+                .model
+                .propshape_id_lookup()
+                .borrow()
+                .get_term(*shape.identifier())
+                .cloned(),
+        };
+        term.expect("parsed shapes are always registered in the ID lookup")
+    }
+
+    pub fn is_property_shape(&self) -> bool {
+        matches!(self.kind, ShapeKind::Property(_))
+    }
+
+    pub fn targets(&self) -> &'a [Target] {
+        match self.kind {
+            ShapeKind::Node(shape) => &shape.targets,
+            ShapeKind::Property(shape) => &shape.targets,
+        }
+    }
+
+    /// The `sh:path` of a property shape; `None` for node shapes.
+    pub fn path(&self) -> Option<&'a Path> {
+        match self.kind {
+            ShapeKind::Node(_) => None,
+            ShapeKind::Property(shape) => Some(shape.path()),
+        }
+    }
+
+    /// The `sh:order` of a property shape; `None` for node shapes and unordered shapes.
+    pub fn order(&self) -> Option<f64> {
+        match self.kind {
+            ShapeKind::Node(_) => None,
+            ShapeKind::Property(shape) => shape.order(),
+        }
+    }
+
+    pub fn severity(&self) -> &'a Severity {
+        match self.kind {
+            ShapeKind::Node(shape) => shape.severity(),
+            ShapeKind::Property(shape) => shape.severity(),
+        }
+    }
+
+    pub fn is_deactivated(&self) -> bool {
+        match self.kind {
+            ShapeKind::Node(shape) => shape.is_deactivated(),
+            ShapeKind::Property(shape) => shape.is_deactivated(),
+        }
+    }
+
+    /// The shape's constraints in declaration order, paired with their component IDs.
+    pub fn constraints(&self) -> impl Iterator<Item = (ComponentID, &'a ComponentDescriptor)> + 'a {
+        let model = self.model;
+        let ids = match self.kind {
+            ShapeKind::Node(shape) => shape.constraints(),
+            ShapeKind::Property(shape) => shape.constraints(),
+        };
+        ids.iter().filter_map(move |id| {
+            model
+                .get_component_descriptor(id)
+                .map(|descriptor| (*id, descriptor))
+        })
+    }
+
+    /// Resolves a node shape referenced by a descriptor (`sh:node`, `sh:and`, ...).
+    pub fn node_shape(&self, id: &ID) -> Option<ShapeView<'a>> {
+        ShapeView::node(self.model, id)
+    }
+
+    /// Resolves a property shape referenced by an `sh:property` descriptor.
+    pub fn property_shape(&self, id: &PropShapeID) -> Option<ShapeView<'a>> {
+        ShapeView::property(self.model, id)
+    }
+
+    /// The property shapes the shape declares with `sh:property`, in declaration order.
+    pub fn property_shapes(&self) -> Vec<ShapeView<'a>> {
+        self.constraints()
+            .filter_map(|(_, descriptor)| match descriptor {
+                ComponentDescriptor::Property { shape } => self.property_shape(shape),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Resolves the shapes `descriptor` refers to: the property shape of `sh:property`, the
+    /// node shapes of `sh:node`, `sh:not`, `sh:qualifiedValueShape` and the logical lists.
+    pub(crate) fn referenced_shapes(&self, descriptor: &ComponentDescriptor) -> Vec<ShapeView<'a>> {
+        match descriptor {
+            ComponentDescriptor::Node { shape: id }
+            | ComponentDescriptor::Not { shape: id }
+            | ComponentDescriptor::QualifiedValueShape { shape: id, .. } => {
+                self.node_shape(id).into_iter().collect()
+            }
+            ComponentDescriptor::Property { shape: id } => {
+                self.property_shape(id).into_iter().collect()
+            }
+            ComponentDescriptor::And { shapes: ids }
+            | ComponentDescriptor::Or { shapes: ids }
+            | ComponentDescriptor::Xone { shapes: ids } => {
+                ids.iter().filter_map(|id| self.node_shape(id)).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The shape's node identifier in Graphviz output.
+    pub(crate) fn graphviz_id(&self) -> String {
+        match self.kind {
+            ShapeKind::Node(shape) => shape.identifier().to_graphviz_id(),
+            ShapeKind::Property(shape) => shape.identifier().to_graphviz_id(),
+        }
+    }
+}
+
+impl std::fmt::Debug for ShapeView<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShapeView")
+            .field("term", &self.term())
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
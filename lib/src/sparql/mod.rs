@@ -4,8 +4,9 @@ use crate::context::ParsingContext;
 use crate::model::components::sparql::{
     CustomConstraintComponentDefinition, Parameter, SPARQLValidator,
 };
-use crate::named_nodes::{RDF, SHACL};
+use crate::named_nodes::{OWL, RDF, SHACL};
 use crate::types::Severity;
+use log::warn;
 use ontoenv::api::{OntoEnv, ResolveTarget};
 use oxigraph::model::{
     GraphNameRef, Literal, NamedNode, NamedNodeRef, NamedOrBlankNodeRef as SubjectRef, Term,
@@ -16,7 +17,7 @@ use spargebra::algebra::{AggregateExpression, Expression, GraphPattern, OrderExp
 use spargebra::term::GroundTerm;
 use spargebra::{Query as AlgebraQuery, SparqlParser};
 use std::cell::RefCell;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
 type CustomComponentMaps = (
     HashMap<NamedNode, CustomConstraintComponentDefinition>,
@@ -96,9 +97,11 @@ impl SparqlExecutor for SparqlServices {
         }
 
         let subject_ref = to_subject_ref(node)?;
-        let shacl = SHACL::new();
+        let shacl = SHACL::get();
 
-        let mut prefixes_subjects: HashSet<Term> = store
+        // Only the nodes named by `sh:prefixes` contribute declarations (plus a node that
+        // declares prefixes itself). Each of them is followed through owl:imports.
+        let mut pending: VecDeque<Term> = store
             .quads_for_pattern(
                 Some(subject_ref),
                 Some(shacl.prefixes),
@@ -108,24 +111,30 @@ impl SparqlExecutor for SparqlServices {
             .filter_map(Result::ok)
             .map(|q| q.object)
             .collect();
+        let declares_itself = store
+            .quads_for_pattern(
+                Some(subject_ref),
+                Some(shacl.declare),
+                None,
+                Some(shape_graph_iri_ref),
+            )
+            .next()
+            .is_some();
+        if declares_itself {
+            pending.push_front(node.clone());
+        }
 
-        prefixes_subjects.extend(
-            store
-                .quads_for_pattern(None, Some(shacl.declare), None, None)
-                .filter_map(Result::ok)
-                .map(|q| q.subject.into()),
-        );
-
+        let owl = OWL::get();
+        let mut visited: HashSet<Term> = HashSet::new();
         let mut collected_prefixes: HashMap<String, String> = HashMap::new();
 
-        for prefixes_subject in prefixes_subjects {
+        while let Some(prefixes_subject) = pending.pop_front() {
+            if !visited.insert(prefixes_subject.clone()) {
+                continue;
+            }
+            let prefixes_subject_ref = to_subject_ref(&prefixes_subject)?;
             let declarations: Vec<Term> = store
-                .quads_for_pattern(
-                    Some(to_subject_ref(&prefixes_subject)?),
-                    Some(shacl.declare),
-                    None,
-                    None,
-                )
+                .quads_for_pattern(Some(prefixes_subject_ref), Some(shacl.declare), None, None)
                 .filter_map(Result::ok)
                 .map(|q| q.object)
                 .collect();
@@ -153,18 +162,11 @@ impl SparqlExecutor for SparqlServices {
                 if let (Some(Term::Literal(prefix_lit)), Some(Term::Literal(namespace_lit))) =
                     (prefix_val, namespace_val)
                 {
-                    let prefix = prefix_lit.value().to_string();
-                    let namespace = namespace_lit.value().to_string();
-                    if let Some(existing_namespace) = collected_prefixes.get(&prefix) {
-                        if existing_namespace != &namespace {
-                            return Err(format!(
-                                "Duplicate prefix '{}' with different namespaces: '{}' and '{}'",
-                                prefix, existing_namespace, namespace
-                            ));
-                        }
-                    } else {
-                        collected_prefixes.insert(prefix, namespace);
-                    }
+                    insert_prefix(
+                        &mut collected_prefixes,
+                        prefix_lit.value(),
+                        namespace_lit.value(),
+                    )?;
                 } else {
                     return Err(format!(
                         "Ill-formed prefix declaration: {}. Missing sh:prefix or sh:namespace.",
@@ -177,21 +179,22 @@ impl SparqlExecutor for SparqlServices {
                 if let Some(graphid) = env.resolve(ResolveTarget::Graph(ontology_iri.clone())) {
                     if let Ok(ont) = env.get_ontology(&graphid) {
                         for (prefix, namespace) in ont.namespace_map().iter() {
-                            if let Some(existing_namespace) =
-                                collected_prefixes.get(prefix.as_str())
-                            {
-                                if existing_namespace != namespace {
-                                    return Err(format!(
-                                        "Duplicate prefix '{}' with different namespaces: '{}' and '{}'",
-                                        prefix, existing_namespace, namespace
-                                    ));
-                                }
-                            } else {
-                                collected_prefixes.insert(prefix.clone(), namespace.clone());
-                            }
+                            insert_prefix(&mut collected_prefixes, prefix, namespace)?;
                         }
                     }
                 }
+
+                pending.extend(
+                    store
+                        .quads_for_pattern(
+                            Some(prefixes_subject_ref),
+                            Some(owl.imports),
+                            None,
+                            None,
+                        )
+                        .filter_map(Result::ok)
+                        .map(|q| q.object),
+                );
             }
         }
 
@@ -288,6 +291,27 @@ impl MessageTemplater for SparqlServices {
     }
 }
 
+/// Combines two `PREFIX` blocks, keeping the first declaration seen for each prefix.
+fn merge_prefix_blocks(primary: &str, secondary: &str) -> String {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut lines = Vec::new();
+    for line in primary.lines().chain(secondary.lines()) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let prefix = trimmed
+            .strip_prefix("PREFIX")
+            .and_then(|rest| rest.split(':').next())
+            .map(|p| p.trim().to_string())
+            .unwrap_or_else(|| trimmed.to_string());
+        if seen.insert(prefix) {
+            lines.push(trimmed.to_string());
+        }
+    }
+    lines.join("\n")
+}
+
 fn to_subject_ref(term: &Term) -> Result<SubjectRef<'_>, String> {
     match term {
         Term::NamedNode(n) => Ok(n.as_ref().into()),
@@ -331,7 +355,7 @@ fn collect_template_extras(
         .iter()
         .map(|pred| pred.as_str().to_string())
         .collect();
-    let rdf = RDF::new();
+    let rdf = RDF::get();
     for quad in context
         .store
         .quads_for_pattern(
@@ -794,13 +818,94 @@ fn check_expression(
     }
 }
 
+/// Picks the validator a component declares through `predicate`.
+///
+/// SHACL lets a component declare several validators of the same kind without saying
+/// which one applies, so the choice is made deterministically (by query text, then by
+/// term) and a warning is logged. Precedence between kinds is applied at validation
+/// time: `sh:nodeValidator` or `sh:propertyValidator` win over `sh:validator`.
+fn select_validator_term(
+    context: &ParsingContext,
+    cc_iri: &NamedNode,
+    predicate: NamedNodeRef<'_>,
+    label: &str,
+) -> Option<Term> {
+    let shacl = SHACL::get();
+    let mut candidates: Vec<(String, Term)> = context
+        .store
+        .quads_for_pattern(
+            Some(cc_iri.as_ref().into()),
+            Some(predicate),
+            None,
+            Some(context.shape_graph_iri_ref()),
+        )
+        .filter_map(Result::ok)
+        .map(|q| {
+            let query = to_subject_ref(&q.object)
+                .ok()
+                .and_then(|subject| {
+                    context
+                        .store
+                        .quads_for_pattern(
+                            Some(subject),
+                            None,
+                            None,
+                            Some(context.shape_graph_iri_ref()),
+                        )
+                        .filter_map(Result::ok)
+                        .find(|quad| quad.predicate == shacl.select || quad.predicate == shacl.ask)
+                        .map(|quad| match quad.object {
+                            Term::Literal(lit) => lit.value().to_string(),
+                            other => other.to_string(),
+                        })
+                })
+                .unwrap_or_default();
+            (query, q.object)
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| a.1.to_string().cmp(&b.1.to_string()))
+    });
+    if candidates.len() > 1 {
+        warn!(
+            "Custom constraint component {} declares {} {} validators; using {}",
+            cc_iri,
+            candidates.len(),
+            label,
+            candidates[0].1
+        );
+    }
+    candidates.into_iter().next().map(|(_, term)| term)
+}
+
+/// Records a prefix declaration, rejecting a prefix bound to two different namespaces.
+fn insert_prefix(
+    collected: &mut HashMap<String, String>,
+    prefix: &str,
+    namespace: &str,
+) -> Result<(), String> {
+    match collected.get(prefix) {
+        Some(existing) if existing != namespace => Err(format!(
+            "Duplicate prefix '{}' with different namespaces: '{}' and '{}'",
+            prefix, existing, namespace
+        )),
+        Some(_) => Ok(()),
+        None => {
+            collected.insert(prefix.to_string(), namespace.to_string());
+            Ok(())
+        }
+    }
+}
+
 pub fn parse_custom_constraint_components<E: SparqlExecutor>(
     context: &ParsingContext,
     services: &E,
 ) -> Result<CustomComponentMaps, String> {
     let mut definitions = HashMap::new();
     let mut param_to_component: HashMap<NamedNode, Vec<NamedNode>> = HashMap::new();
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
 
     let shapes_graph_iri = context.shape_graph_iri.as_str();
     let query = format!(
@@ -1020,6 +1125,20 @@ pub fn parse_custom_constraint_components<E: SparqlExecutor>(
 
                             let ask_pred =
                                 NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#ask");
+                            // Node and property validators may also be ASK validators; these
+                            // are evaluated per value node through $value rather than $this.
+                            let is_ask = is_ask
+                                || context
+                                    .store
+                                    .quads_for_pattern(
+                                        Some(subject),
+                                        Some(ask_pred),
+                                        None,
+                                        Some(context.shape_graph_iri_ref()),
+                                    )
+                                    .next()
+                                    .is_some();
+                            let require_this = require_this && !is_ask;
                             let query_pred = if is_ask { ask_pred } else { shacl.select };
 
                             let query_object = context
@@ -1058,12 +1177,22 @@ pub fn parse_custom_constraint_components<E: SparqlExecutor>(
                                 require_path,
                             )?;
 
-                            let prefixes = services.prefixes_for_node(
+                            let validator_prefixes = services.prefixes_for_node(
                                 v_term,
                                 &context.store,
                                 &context.env,
                                 context.shape_graph_iri_ref(),
                             )?;
+                            // Declarations made on the component (template) itself apply to
+                            // all of its validators; the validator's own declarations win.
+                            let component_prefixes = services.prefixes_for_node(
+                                &Term::NamedNode(cc_iri.clone()),
+                                &context.store,
+                                &context.env,
+                                context.shape_graph_iri_ref(),
+                            )?;
+                            let prefixes =
+                                merge_prefix_blocks(&validator_prefixes, &component_prefixes);
 
                             let full_query = if prefixes.is_empty() {
                                 query_str.clone()
@@ -1142,56 +1271,29 @@ pub fn parse_custom_constraint_components<E: SparqlExecutor>(
                             }))
                         };
 
-                    let validator_prop =
-                        NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#validator");
-                    if let Some(v_term) = context
-                        .store
-                        .quads_for_pattern(
-                            Some(cc_iri.as_ref().into()),
-                            Some(validator_prop),
-                            None,
-                            Some(context.shape_graph_iri_ref()),
-                        )
-                        .filter_map(Result::ok)
-                        .map(|q| q.object)
-                        .next()
+                    if let Some(v_term) =
+                        select_validator_term(context, cc_iri, shacl.validator, "sh:validator")
                     {
                         validator =
                             parse_validator(&v_term, true, context, services, false, false)?;
                     }
 
-                    let node_validator_prop =
-                        NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#nodeValidator");
-                    if let Some(v_term) = context
-                        .store
-                        .quads_for_pattern(
-                            Some(cc_iri.as_ref().into()),
-                            Some(node_validator_prop),
-                            None,
-                            Some(context.shape_graph_iri_ref()),
-                        )
-                        .filter_map(Result::ok)
-                        .map(|q| q.object)
-                        .next()
-                    {
+                    if let Some(v_term) = select_validator_term(
+                        context,
+                        cc_iri,
+                        shacl.node_validator,
+                        "sh:nodeValidator",
+                    ) {
                         node_validator =
                             parse_validator(&v_term, false, context, services, false, true)?;
                     }
 
-                    let property_validator_prop =
-                        NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#propertyValidator");
-                    if let Some(v_term) = context
-                        .store
-                        .quads_for_pattern(
-                            Some(cc_iri.as_ref().into()),
-                            Some(property_validator_prop),
-                            None,
-                            Some(context.shape_graph_iri_ref()),
-                        )
-                        .filter_map(Result::ok)
-                        .map(|q| q.object)
-                        .next()
-                    {
+                    if let Some(v_term) = select_validator_term(
+                        context,
+                        cc_iri,
+                        shacl.property_validator,
+                        "sh:propertyValidator",
+                    ) {
                         property_validator =
                             parse_validator(&v_term, false, context, services, true, true)?;
                     }
@@ -1221,7 +1323,7 @@ fn substitute_placeholders(message: &str, substitutions: &[(String, String)]) ->
     let mut text = message.to_string();
     for (name, value) in substitutions {
         let placeholder_q = format!("{{?{}}}", name);
-        let placeholder_dollar = format!("{{$${}}}", name);
+        let placeholder_dollar = format!("{{${}}}", name);
         text = text.replace(&placeholder_q, value);
         text = text.replace(&placeholder_dollar, value);
     }
@@ -1280,6 +1382,18 @@ pub fn instantiate_message_terms(
 mod tests {
     use super::*;
 
+    #[test]
+    fn merged_prefix_blocks_prefer_primary_declarations() {
+        let merged = merge_prefix_blocks(
+            "PREFIX ex: <http://example.org/a#>",
+            "PREFIX ex: <http://example.org/b#>\nPREFIX foo: <http://example.org/foo#>",
+        );
+        assert_eq!(
+            merged,
+            "PREFIX ex: <http://example.org/a#>\nPREFIX foo: <http://example.org/foo#>"
+        );
+    }
+
     #[test]
     fn message_instantiation_handles_multiple_templates() {
         let templates = vec![
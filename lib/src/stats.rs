@@ -0,0 +1,269 @@
+//! Aggregate statistics about the values found along property shape paths.
+//!
+//! For every property shape that declares `sh:datatype` or `sh:class`, the value nodes of
+//! its path are resolved over the whole data graph (rather than per focus node) and
+//! grouped by their datatype or class. Comparing that distribution with what the shape
+//! expects shows whether a shape or the data is more likely to need fixing.
+
+use crate::context::ValidationContext;
+use crate::model::components::ComponentDescriptor;
+use crate::shape::PropertyShape;
+use crate::sparql::SparqlExecutor;
+use oxigraph::model::{NamedNode, Term};
+use oxigraph::sparql::{QueryResults, Variable};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+/// What a value node was observed to be.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ObservedKind {
+    /// A literal with the given datatype.
+    Datatype(NamedNode),
+    /// An IRI or blank node with the given `rdf:type`.
+    Class(NamedNode),
+    /// An IRI or blank node without any `rdf:type`.
+    Untyped,
+}
+
+impl fmt::Display for ObservedKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObservedKind::Datatype(datatype) => write!(f, "{}", datatype),
+            ObservedKind::Class(class) => write!(f, "instances of {}", class),
+            ObservedKind::Untyped => write!(f, "untyped nodes"),
+        }
+    }
+}
+
+/// How many value nodes of a path were observed as a given kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObservedType {
+    pub kind: ObservedKind,
+    pub count: usize,
+}
+
+/// The observed datatype/class distribution of one property shape's path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathTypeStatistics {
+    /// The property shape the path belongs to.
+    pub shape: Term,
+    /// The path, rendered as a SPARQL property path.
+    pub path: String,
+    /// Datatypes required through `sh:datatype`.
+    pub expected_datatypes: Vec<NamedNode>,
+    /// Classes required through `sh:class`.
+    pub expected_classes: Vec<NamedNode>,
+    /// Number of (focus node, value node) pairs found along the path.
+    pub value_count: usize,
+    /// Observed kinds, most frequent first. A node with several classes counts once per class.
+    pub observed: Vec<ObservedType>,
+}
+
+impl PathTypeStatistics {
+    /// Fraction of the path's value nodes observed as `kind`, between 0 and 1.
+    pub fn share(&self, kind: &ObservedKind) -> f64 {
+        if self.value_count == 0 {
+            return 0.0;
+        }
+        let count = self
+            .observed
+            .iter()
+            .find(|observed| &observed.kind == kind)
+            .map_or(0, |observed| observed.count);
+        count as f64 / self.value_count as f64
+    }
+
+    fn is_expected(&self, kind: &ObservedKind) -> bool {
+        match kind {
+            ObservedKind::Datatype(datatype) => self.expected_datatypes.contains(datatype),
+            ObservedKind::Class(class) => self.expected_classes.contains(class),
+            ObservedKind::Untyped => false,
+        }
+    }
+}
+
+impl fmt::Display for PathTypeStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expected: Vec<String> = self
+            .expected_datatypes
+            .iter()
+            .map(|datatype| datatype.to_string())
+            .chain(
+                self.expected_classes
+                    .iter()
+                    .map(|class| format!("instances of {}", class)),
+            )
+            .collect();
+        write!(
+            f,
+            "{} (shape {}): {} value(s), shape expects {}",
+            self.path,
+            self.shape,
+            self.value_count,
+            expected.join(" and ")
+        )?;
+        for observed in &self.observed {
+            write!(
+                f,
+                "\n  {:5.1}% ({}) are {}{}",
+                self.share(&observed.kind) * 100.0,
+                observed.count,
+                observed.kind,
+                if self.is_expected(&observed.kind) {
+                    ""
+                } else {
+                    " [unexpected]"
+                }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects statistics for every active property shape with a datatype or class expectation,
+/// ordered by path.
+pub(crate) fn path_type_statistics(
+    context: &ValidationContext,
+) -> Result<Vec<PathTypeStatistics>, String> {
+    let mut statistics = Vec::new();
+    for shape in context.model.prop_shapes.values() {
+        if shape.is_deactivated() {
+            continue;
+        }
+        let (expected_datatypes, expected_classes) = expected_types(context, shape);
+        if expected_datatypes.is_empty() && expected_classes.is_empty() {
+            continue;
+        }
+        let Some(shape_term) = context
+            .model
+            .propshape_id_lookup()
+            .borrow()
+            .get_term(*shape.identifier())
+            .cloned()
+        else {
+            continue;
+        };
+
+        let (value_count, observed) = observe_path(context, shape)?;
+        statistics.push(PathTypeStatistics {
+            shape: shape_term,
+            path: shape.sparql_path(),
+            expected_datatypes,
+            expected_classes,
+            value_count,
+            observed,
+        });
+    }
+    statistics.sort_by(|a, b| {
+        a.path
+            .cmp(&b.path)
+            .then_with(|| a.shape.to_string().cmp(&b.shape.to_string()))
+    });
+    Ok(statistics)
+}
+
+fn expected_types(
+    context: &ValidationContext,
+    shape: &PropertyShape,
+) -> (Vec<NamedNode>, Vec<NamedNode>) {
+    let mut datatypes = Vec::new();
+    let mut classes = Vec::new();
+    for constraint_id in shape.constraints() {
+        match context.model.get_component_descriptor(constraint_id) {
+            Some(ComponentDescriptor::Datatype {
+                datatype: Term::NamedNode(datatype),
+            }) => datatypes.push(datatype.clone()),
+            Some(ComponentDescriptor::DatatypeIn { datatypes: allowed }) => {
+                datatypes.extend(allowed.iter().filter_map(|datatype| match datatype {
+                    Term::NamedNode(datatype) => Some(datatype.clone()),
+                    _ => None,
+                }))
+            }
+            Some(ComponentDescriptor::Class {
+                class: Term::NamedNode(class),
+            }) => classes.push(class.clone()),
+            _ => {}
+        }
+    }
+    datatypes.sort();
+    datatypes.dedup();
+    classes.sort();
+    classes.dedup();
+    (datatypes, classes)
+}
+
+/// Resolves every value node of the shape's path and tallies its datatype or classes.
+fn observe_path(
+    context: &ValidationContext,
+    shape: &PropertyShape,
+) -> Result<(usize, Vec<ObservedType>), String> {
+    let query_str = format!(
+        "SELECT ?focus ?value ?type WHERE {{ ?focus {} ?value . \
+         OPTIONAL {{ ?value <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> ?type }} }}",
+        shape.sparql_path()
+    );
+    let prepared = context.model.sparql.prepared_query(&query_str)?;
+    let results = context
+        .model
+        .sparql
+        .execute_with_substitutions(&query_str, &prepared, context.model.store(), &[], false)
+        .map_err(|e| {
+            format!(
+                "Failed to execute statistics query for PropertyShape {}: {}",
+                shape.identifier(),
+                e
+            )
+        })?;
+    let QueryResults::Solutions(solutions) = results else {
+        return Err(format!(
+            "Unexpected result for PropertyShape {} statistics query",
+            shape.identifier()
+        ));
+    };
+
+    let focus_var = Variable::new_unchecked("focus");
+    let value_var = Variable::new_unchecked("value");
+    let type_var = Variable::new_unchecked("type");
+
+    let mut pairs: HashSet<(Term, Term)> = HashSet::new();
+    let mut typed_nodes: HashSet<Term> = HashSet::new();
+    let mut counts: BTreeMap<ObservedKind, HashSet<(Term, Term)>> = BTreeMap::new();
+    for solution in solutions {
+        let solution = solution.map_err(|e| e.to_string())?;
+        let (Some(focus), Some(value)) = (solution.get(&focus_var), solution.get(&value_var))
+        else {
+            continue;
+        };
+        let pair = (focus.clone(), value.clone());
+        pairs.insert(pair.clone());
+        let kind = match (value, solution.get(&type_var)) {
+            (Term::Literal(literal), _) => ObservedKind::Datatype(literal.datatype().into_owned()),
+            (_, Some(Term::NamedNode(class))) => {
+                typed_nodes.insert(value.clone());
+                ObservedKind::Class(class.clone())
+            }
+            _ => continue,
+        };
+        counts.entry(kind).or_default().insert(pair);
+    }
+
+    // Nodes are untyped only when no solution carried a named rdf:type for them.
+    let untyped: HashSet<(Term, Term)> = pairs
+        .iter()
+        .filter(|(_, value)| !matches!(value, Term::Literal(_)) && !typed_nodes.contains(value))
+        .cloned()
+        .collect();
+    if !untyped.is_empty() {
+        counts.insert(ObservedKind::Untyped, untyped);
+    }
+
+    let mut observed: Vec<ObservedType> = counts
+        .into_iter()
+        .map(|(kind, pairs)| ObservedType {
+            kind,
+            count: pairs.len(),
+        })
+        .collect();
+    observed.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.kind.cmp(&b.kind)));
+    Ok((pairs.len(), observed))
+}
@@ -2,6 +2,7 @@ use crate::named_nodes::{MF, RDF, RDFS, SHACL, SHT};
 use crate::runtime::ToSubjectRef;
 use oxigraph::io::{RdfFormat, RdfParser};
 use oxigraph::model::{vocab::xsd, Graph, NamedOrBlankNodeRef as SubjectRef, TermRef, TripleRef};
+use std::collections::{HashSet, VecDeque};
 use std::fs;
 use std::io::Cursor;
 use std::path::{Path, PathBuf};
@@ -58,8 +59,8 @@ fn read_manifest_content(path: &Path) -> Result<String, String> {
 }
 
 fn extract_path_graph(manifest_graph: &Graph, path_node: SubjectRef, report_graph: &mut Graph) {
-    let sh = SHACL::new();
-    let rdf = RDF::new();
+    let sh = SHACL::get();
+    let rdf = RDF::get();
     for triple in manifest_graph.triples_for_subject(path_node) {
         report_graph.insert(triple);
         // Recurse for nested paths
@@ -84,7 +85,7 @@ fn extract_path_graph(manifest_graph: &Graph, path_node: SubjectRef, report_grap
 
 fn extract_report_graph(manifest_graph: &Graph, result_node: SubjectRef) -> Graph {
     let mut report_graph = Graph::new();
-    let sh = SHACL::new();
+    let sh = SHACL::get();
 
     // Add triples where result_node is the subject
     for triple in manifest_graph.triples_for_subject(result_node) {
@@ -163,11 +164,11 @@ pub fn load_manifest(path: &Path) -> Result<Manifest, String> {
         manifest_graph.insert(TripleRef::new(&quad.subject, &quad.predicate, &quad.object));
     }
 
-    let mf = MF::new();
-    let sht = SHT::new();
-    let rdf = RDF::new();
-    let rdfs = RDFS::new();
-    let sh = SHACL::new();
+    let mf = MF::get();
+    let sht = SHT::get();
+    let rdf = RDF::get();
+    let rdfs = RDFS::get();
+    let sh = SHACL::get();
 
     let manifest_node = manifest_graph
         .subjects_for_predicate_object(rdf.type_, mf.manifest)
@@ -224,12 +225,12 @@ pub fn load_manifest(path: &Path) -> Result<Manifest, String> {
 
                     // Optional explicit data/shapes graph paths
                     if let Some(dg) =
-                        manifest_graph.object_for_subject_predicate(action_s, SHT::new().data_graph)
+                        manifest_graph.object_for_subject_predicate(action_s, SHT::get().data_graph)
                     {
                         data_graph_path = resolve_graph_path(path, &manifest_url, dg)?;
                     }
                     if let Some(sg) = manifest_graph
-                        .object_for_subject_predicate(action_s, SHT::new().shapes_graph)
+                        .object_for_subject_predicate(action_s, SHT::get().shapes_graph)
                     {
                         shapes_graph_path = resolve_graph_path(path, &manifest_url, sg)?;
                     }
@@ -315,8 +316,8 @@ pub fn list_includes(path: &Path) -> Result<Vec<PathBuf>, String> {
         manifest_graph.insert(TripleRef::new(&quad.subject, &quad.predicate, &quad.object));
     }
 
-    let mf = MF::new();
-    let rdf = RDF::new();
+    let mf = MF::get();
+    let rdf = RDF::get();
 
     let manifest_node = manifest_graph
         .subjects_for_predicate_object(rdf.type_, mf.manifest)
@@ -336,3 +337,55 @@ pub fn list_includes(path: &Path) -> Result<Vec<PathBuf>, String> {
     }
     Ok(includes)
 }
+
+/// Loads the test cases of a manifest and of every manifest it includes, transitively.
+///
+/// Each test case is paired with the manifest that defines it; the list is sorted by
+/// manifest path, then test name.
+pub fn collect_test_cases(manifest_path: &Path) -> Result<Vec<(PathBuf, TestCase)>, String> {
+    let mut all_tests = Vec::new();
+    let mut to_visit = VecDeque::new();
+    let mut visited = HashSet::new();
+    to_visit.push_back(manifest_path.to_path_buf());
+
+    while let Some(current_manifest) = to_visit.pop_front() {
+        let canonical = current_manifest
+            .canonicalize()
+            .unwrap_or_else(|_| current_manifest.clone());
+        if !visited.insert(canonical.clone()) {
+            continue;
+        }
+
+        let manifest = load_manifest(&canonical).map_err(|e| {
+            format!(
+                "Failed to load manifest from {}: {}",
+                canonical.display(),
+                e
+            )
+        })?;
+        for case in manifest.test_cases {
+            all_tests.push((canonical.clone(), case));
+        }
+
+        let includes = list_includes(&canonical).map_err(|e| {
+            format!(
+                "Failed to list mf:include targets for {}: {}",
+                canonical.display(),
+                e
+            )
+        })?;
+        for include in includes {
+            let include_canonical = include.canonicalize().unwrap_or_else(|_| include.clone());
+            if !visited.contains(&include_canonical) {
+                to_visit.push_back(include_canonical);
+            }
+        }
+    }
+
+    all_tests.sort_by(|(path_a, case_a), (path_b, case_b)| {
+        path_a
+            .cmp(path_b)
+            .then_with(|| case_a.name.cmp(&case_b.name))
+    });
+    Ok(all_tests)
+}
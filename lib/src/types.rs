@@ -227,10 +227,134 @@ pub enum Severity {
     Custom(NamedNode),
 }
 
+/// How results whose severity is a custom IRI (not `sh:Info`, `sh:Warning` or
+/// `sh:Violation`) affect a report's conformance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CustomSeverityConformance {
+    /// Any result makes the report non-conforming, as the SHACL specification requires.
+    #[default]
+    NonConforming,
+    /// Results with a custom severity are still reported but do not affect `sh:conforms`.
+    Ignored,
+}
+
+/// What to do when an imported graph defines a shape differently from the shapes graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateShapePolicy {
+    /// Log a warning and use the shapes graph's definition.
+    #[default]
+    Warn,
+    /// Fail validator construction.
+    Error,
+}
+
+/// The default graph that SPARQL-based constraints (`sh:sparql` and SPARQL constraint
+/// components) query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueryDataset {
+    /// The data graph alone, as the SHACL specification requires; the shapes graph is
+    /// only reachable through `GRAPH $shapesGraph`.
+    #[default]
+    DataGraph,
+    /// The union of every graph in the store, including the shapes graph and any loaded
+    /// imports.
+    Union,
+}
+
+/// A value of `sh:nodeKind`: one kind of RDF node, or a combination of two. The kind of a
+/// single node is always one of `BlankNode`, `Iri` and `Literal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// `sh:BlankNode`.
+    BlankNode,
+    /// `sh:IRI`.
+    Iri,
+    /// `sh:Literal`.
+    Literal,
+    /// `sh:BlankNodeOrIRI`.
+    BlankNodeOrIri,
+    /// `sh:BlankNodeOrLiteral`.
+    BlankNodeOrLiteral,
+    /// `sh:IRIOrLiteral`.
+    IriOrLiteral,
+}
+
+impl NodeKind {
+    const ALL: [NodeKind; 6] = [
+        NodeKind::BlankNode,
+        NodeKind::Iri,
+        NodeKind::Literal,
+        NodeKind::BlankNodeOrIri,
+        NodeKind::BlankNodeOrLiteral,
+        NodeKind::IriOrLiteral,
+    ];
+
+    /// Creates a `NodeKind` from a `Term` if it is one of the six SHACL node kind IRIs.
+    pub(crate) fn from_term(term: &Term) -> Option<Self> {
+        match term {
+            Term::NamedNode(nn) => Self::ALL.into_iter().find(|kind| kind.iri() == nn.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// The kind of `term`, one of `BlankNode`, `Iri` and `Literal`.
+    pub fn of(term: &Term) -> Self {
+        match term {
+            Term::NamedNode(_) => NodeKind::Iri,
+            Term::BlankNode(_) => NodeKind::BlankNode,
+            _ => NodeKind::Literal,
+        }
+    }
+
+    /// The SHACL IRI of this node kind, e.g. `sh:BlankNodeOrIRI`.
+    pub fn iri(self) -> NamedNodeRef<'static> {
+        let shacl = SHACL::get();
+        match self {
+            NodeKind::BlankNode => shacl.blank_node,
+            NodeKind::Iri => shacl.iri,
+            NodeKind::Literal => shacl.literal,
+            NodeKind::BlankNodeOrIri => shacl.blank_node_or_iri,
+            NodeKind::BlankNodeOrLiteral => shacl.blank_node_or_literal,
+            NodeKind::IriOrLiteral => shacl.iri_or_literal,
+        }
+    }
+
+    /// Returns `true` if nodes of `kind` satisfy this node kind; a combined kind allows
+    /// both of the kinds it names.
+    pub fn allows(self, kind: NodeKind) -> bool {
+        self == kind
+            || matches!(
+                (self, kind),
+                (
+                    NodeKind::BlankNodeOrIri,
+                    NodeKind::BlankNode | NodeKind::Iri
+                ) | (
+                    NodeKind::BlankNodeOrLiteral,
+                    NodeKind::BlankNode | NodeKind::Literal
+                ) | (NodeKind::IriOrLiteral, NodeKind::Iri | NodeKind::Literal)
+            )
+    }
+}
+
+/// Describes the node kind in words, e.g. "a blank node or an IRI".
+impl fmt::Display for NodeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            NodeKind::BlankNode => "a blank node",
+            NodeKind::Iri => "an IRI",
+            NodeKind::Literal => "a literal",
+            NodeKind::BlankNodeOrIri => "a blank node or an IRI",
+            NodeKind::BlankNodeOrLiteral => "a blank node or a literal",
+            NodeKind::IriOrLiteral => "an IRI or a literal",
+        };
+        f.write_str(description)
+    }
+}
+
 impl Severity {
     /// Creates a `Severity` from a `Term` if it matches a SHACL severity IRI.
     pub(crate) fn from_term(term: &Term) -> Option<Self> {
-        let shacl = SHACL::new();
+        let shacl = SHACL::get();
         match term {
             Term::NamedNode(nn) if *nn == shacl.info => Some(Severity::Info),
             Term::NamedNode(nn) if *nn == shacl.warning => Some(Severity::Warning),
@@ -239,12 +363,26 @@ impl Severity {
             _ => None,
         }
     }
+
+    /// Returns `true` if this severity is at least as severe as `threshold`, in the order
+    /// `sh:Info` < `sh:Warning` < `sh:Violation`. Custom severities rank as violations.
+    pub fn is_at_least(&self, threshold: &Severity) -> bool {
+        self.rank() >= threshold.rank()
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Severity::Info => 0,
+            Severity::Warning => 1,
+            Severity::Violation | Severity::Custom(_) => 2,
+        }
+    }
 }
 
 impl Target {
     /// Creates a `Target` from a predicate and object `TermRef` if they correspond to a known SHACL target property.
     pub(crate) fn from_predicate_object(predicate: NamedNodeRef, object: TermRef) -> Option<Self> {
-        let shacl = SHACL::new();
+        let shacl = SHACL::get();
         if predicate == shacl.target_class {
             Some(Target::Class(object.into_owned()))
         } else if predicate == shacl.target_node {
@@ -441,6 +579,87 @@ impl Target {
             Target::Advanced(selector) => evaluate_advanced_target(context, selector, source_shape),
         }
     }
+
+    /// Returns the contexts of the `candidates` this target selects, checking each candidate
+    /// on its own instead of resolving every focus node of the target. Advanced targets have
+    /// no such check and are resolved in full before filtering.
+    pub(crate) fn get_target_nodes_among(
+        &self,
+        context: &ValidationContext,
+        source_shape: SourceShape,
+        candidates: &HashSet<Term>,
+    ) -> Result<Vec<Context>, String> {
+        if let Target::Advanced(_) = self {
+            return Ok(self
+                .get_target_nodes(context, source_shape)?
+                .into_iter()
+                .filter(|ctx| candidates.contains(ctx.focus_node()))
+                .collect());
+        }
+        let mut selected = Vec::new();
+        for candidate in candidates {
+            if self.selects(context, candidate)? {
+                selected.push(candidate.clone());
+            }
+        }
+        Ok(contexts_from_terms(context, selected, source_shape))
+    }
+
+    /// Returns `true` if `node` is one of the focus nodes of this (non-advanced) target.
+    fn selects(&self, context: &ValidationContext, node: &Term) -> Result<bool, String> {
+        let store = context.model.store();
+        match self {
+            Target::Node(t) => Ok(t == node),
+            Target::Class(c) => {
+                let query_str = "PREFIX rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#>
+                PREFIX rdfs: <http://www.w3.org/2000/01/rdf-schema#>
+                    ASK { ?inst rdf:type ?c . ?c rdfs:subClassOf* ?target_class }";
+                let prepared = context
+                    .model
+                    .sparql
+                    .prepared_query(query_str)
+                    .map_err(|e| {
+                        format!(
+                            "SPARQL parse error for Target::Class: {} {:?}",
+                            query_str, e
+                        )
+                    })?;
+                let substitutions = [
+                    (Variable::new_unchecked("inst"), node.clone()),
+                    (Variable::new_unchecked("target_class"), c.clone()),
+                ];
+                match context
+                    .model
+                    .sparql
+                    .execute_with_substitutions(query_str, &prepared, store, &substitutions, false)
+                    .map_err(|e| {
+                        format!("SPARQL query error for Target::Class: {} {}", query_str, e)
+                    })? {
+                    QueryResults::Boolean(selected) => Ok(selected),
+                    _ => Err(format!(
+                        "Unexpected result type for Target::Class: {}",
+                        query_str
+                    )),
+                }
+            }
+            Target::SubjectsOf(Term::NamedNode(predicate)) => {
+                let Ok(subject) = term_to_subject_ref(node) else {
+                    return Ok(false);
+                };
+                Ok(store
+                    .quads_for_pattern(Some(subject), Some(predicate.as_ref()), None, None)
+                    .next()
+                    .is_some())
+            }
+            Target::ObjectsOf(Term::NamedNode(predicate)) => Ok(store
+                .quads_for_pattern(None, Some(predicate.as_ref()), Some(node.as_ref()), None)
+                .next()
+                .is_some()),
+            // Predicates of SubjectsOf/ObjectsOf must be IRIs.
+            Target::SubjectsOf(_) | Target::ObjectsOf(_) => Ok(false),
+            Target::Advanced(_) => Err("Advanced targets cannot check single nodes".to_string()),
+        }
+    }
 }
 
 fn contexts_from_terms(
@@ -475,7 +694,7 @@ fn evaluate_advanced_target(
     }
 
     let selector_ref = term_to_subject_ref(selector)?;
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     let store = context.model.store();
     let shape_graph = context.model.shape_graph_iri_ref();
 
@@ -568,7 +787,7 @@ fn collect_target_shape_nodes(
     selector_ref: NamedOrBlankNodeRef<'_>,
     shape_graph: oxigraph::model::GraphNameRef<'_>,
 ) -> Result<Vec<Term>, String> {
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     let shape_ids = collect_shape_ids(context, selector_ref, shacl.target_shape, shape_graph)?;
     let mut terms = Vec::new();
     for shape_id in shape_ids {
@@ -625,7 +844,7 @@ fn apply_filter_shapes(
     focus_terms: Vec<Term>,
     shape_graph: oxigraph::model::GraphNameRef<'_>,
 ) -> Result<Vec<Term>, String> {
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     let filter_ids = collect_shape_ids(context, selector_ref, shacl.filter_shape, shape_graph)?;
     if filter_ids.is_empty() {
         return Ok(focus_terms);
@@ -654,7 +873,7 @@ fn apply_ask_validators(
     focus_terms: Vec<Term>,
     shape_graph: oxigraph::model::GraphNameRef<'_>,
 ) -> Result<Vec<Term>, String> {
-    let shacl = SHACL::new();
+    let shacl = SHACL::get();
     let store = context.model.store();
     let mut current_terms = focus_terms;
     for quad in store
@@ -799,7 +1018,7 @@ pub(crate) fn node_conforms_to_shape(
     let mut temp_trace = Vec::new();
     match check_conformance_for_node(&mut node_context, shape, context, &mut temp_trace)? {
         ConformanceReport::Conforms => Ok(true),
-        ConformanceReport::NonConforms(_) => Ok(false),
+        ConformanceReport::NonConforms(..) => Ok(false),
     }
 }
 
@@ -0,0 +1,159 @@
+//! SHACL features that appear in the shapes graph but that the engine does not implement.
+//!
+//! Such constructs would otherwise be ignored silently, so a shapes graph could seem to
+//! pass while part of it never ran. The parser records each one; validators expose them
+//! through `Validator::unsupported_features` and reports list them on the
+//! `sh:ValidationReport` node with [`UNSUPPORTED_FEATURE`].
+
+use oxigraph::model::{NamedNode, Term};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Predicate linking the `sh:ValidationReport` node to a description of each unsupported
+/// feature found in the shapes graph.
+pub const UNSUPPORTED_FEATURE: &str = "urn:shacl-rs:unsupportedFeature";
+
+const SHACL_NAMESPACE: &str = "http://www.w3.org/ns/shacl#";
+
+/// SHACL-JS predicates, none of which are implemented.
+const JAVASCRIPT_PREDICATES: &[&str] = &["js", "jsFunctionName", "jsLibrary", "jsLibraryURL"];
+
+/// `sh:` predicates that may appear on a shape without being constraint parameters: they
+/// are read by the target, path, rule, SPARQL and reporting code rather than by the
+/// component registry.
+const NON_COMPONENT_PREDICATES: &[&str] = &[
+    "path",
+    "target",
+    "targetNode",
+    "targetClass",
+    "targetSubjectsOf",
+    "targetObjectsOf",
+    "severity",
+    "message",
+    "deactivated",
+    "name",
+    "description",
+    "order",
+    "group",
+    "defaultValue",
+    "property",
+    "rule",
+    "sparql",
+    "prefixes",
+    "declare",
+    "ignoredProperties",
+    "flags",
+    "qualifiedValueShapesDisjoint",
+    "qualifiedMinCount",
+    "qualifiedMaxCount",
+    "labelTemplate",
+    "parameter",
+    "validator",
+    "nodeValidator",
+    "propertyValidator",
+    "shapesGraph",
+    "suggestedShapesGraph",
+    "entailment",
+];
+
+/// Why a feature is not supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum UnsupportedKind {
+    /// SHACL-JS constraints and libraries.
+    JavaScript,
+    /// A predicate in the SHACL namespace that no implemented component, target or
+    /// shape property uses.
+    UnrecognizedPredicate,
+}
+
+/// One unsupported construct on one shape.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnsupportedFeature {
+    /// The shape carrying the construct.
+    pub shape: Term,
+    /// The predicate that introduces it, e.g. `sh:js`.
+    pub predicate: NamedNode,
+    pub kind: UnsupportedKind,
+}
+
+impl fmt::Display for UnsupportedFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self.kind {
+            UnsupportedKind::JavaScript => "SHACL-JavaScript is not supported",
+            UnsupportedKind::UnrecognizedPredicate => "not a supported SHACL constraint",
+        };
+        write!(
+            f,
+            "{} on shape {}: {}; it was ignored",
+            self.predicate, self.shape, reason
+        )
+    }
+}
+
+/// Returns the unsupported features among the predicates of `shape` that the component
+/// registry did not `process`, sorted by predicate.
+pub(crate) fn detect<'a>(
+    shape: &Term,
+    predicates: impl IntoIterator<Item = &'a NamedNode>,
+    processed: &HashSet<NamedNode>,
+) -> Vec<UnsupportedFeature> {
+    let mut features: Vec<UnsupportedFeature> = predicates
+        .into_iter()
+        .filter(|predicate| !processed.contains(*predicate))
+        .filter_map(|predicate| {
+            let local = predicate.as_str().strip_prefix(SHACL_NAMESPACE)?;
+            let kind = if JAVASCRIPT_PREDICATES.contains(&local) {
+                UnsupportedKind::JavaScript
+            } else if NON_COMPONENT_PREDICATES.contains(&local) {
+                return None;
+            } else {
+                UnsupportedKind::UnrecognizedPredicate
+            };
+            Some(UnsupportedFeature {
+                shape: shape.clone(),
+                predicate: predicate.clone(),
+                kind,
+            })
+        })
+        .collect();
+    features.sort_by(|a, b| a.predicate.cmp(&b.predicate));
+    features
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sh(local: &str) -> NamedNode {
+        NamedNode::new_unchecked(format!("{}{}", SHACL_NAMESPACE, local))
+    }
+
+    #[test]
+    fn reports_javascript_and_unknown_shacl_predicates_only() {
+        let shape: Term = NamedNode::new_unchecked("http://example.org/S").into();
+        let predicates = [
+            sh("js"),
+            sh("minCount"),
+            sh("targetClass"),
+            sh("maxCuont"),
+            NamedNode::new_unchecked("http://example.org/note"),
+        ];
+        let processed: HashSet<NamedNode> = [sh("minCount")].into_iter().collect();
+
+        let features = detect(&shape, &predicates, &processed);
+        let found: Vec<(&str, UnsupportedKind)> = features
+            .iter()
+            .map(|feature| (feature.predicate.as_str(), feature.kind))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("http://www.w3.org/ns/shacl#js", UnsupportedKind::JavaScript),
+                (
+                    "http://www.w3.org/ns/shacl#maxCuont",
+                    UnsupportedKind::UnrecognizedPredicate
+                ),
+            ]
+        );
+    }
+}
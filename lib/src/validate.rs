@@ -1,23 +1,369 @@
+use crate::audit::AuditLog;
 use crate::context::{Context, SourceShape, ValidationContext};
-use crate::report::ValidationReportBuilder;
-use crate::runtime::{ComponentValidationResult, ToSubjectRef};
-use crate::shape::{NodeShape, PropertyShape, ValidateShape};
+use crate::options::{StopReason, ValidationOptions};
+use crate::persistent_cache::PersistentConformanceCache;
+use crate::reachability::dependency_order;
+use crate::report::{ReportShard, ShardKey, ValidationReportBuilder};
+use crate::runtime::{
+    store_target_conformance, ComponentValidationResult, ToSubjectRef, ValidationFailure,
+    ValueNodeUse,
+};
+use crate::shape::{NodeShape, PropertyShape, ShapeKind, ShapeView, ValidateShape};
 use crate::sparql::SparqlExecutor;
-use crate::types::{PropShapeID, TraceItem};
-use log::{debug, info};
-use oxigraph::model::{Literal, Term};
+use crate::types::{ComponentID, PropShapeID, TraceItem};
+use log::{debug, info, warn};
+use oxigraph::model::{Literal, QuadRef, Term};
 use oxigraph::sparql::{QueryResults, Variable};
+use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Instant;
 
-pub(crate) fn validate(context: &ValidationContext) -> Result<ValidationReportBuilder, String> {
-    let mut report_builder = ValidationReportBuilder::new();
-    // Validate all node shapes
-    for shape in context.model.node_shapes.values() {
-        shape.process_targets(context, &mut report_builder)?;
+/// Component ID reported for focus nodes whose value nodes exceed the configured cap.
+/// No component is registered under it; results name
+/// [`VALUE_NODE_LIMIT_COMPONENT`](crate::report::VALUE_NODE_LIMIT_COMPONENT) as their
+/// `sh:sourceConstraintComponent`.
+pub(crate) const VALUE_NODE_LIMIT_COMPONENT_ID: ComponentID = ComponentID(u64::MAX);
+
+/// Number of value nodes evaluated at a time for property shapes whose constraints check
+/// value nodes one by one.
+const VALUE_NODE_CHUNK_SIZE: usize = 1024;
+
+/// What a run does when a file it keeps besides the report, the audit log or the persistent
+/// cache, cannot be read or written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OutputErrors {
+    /// The run fails with the error.
+    Fail,
+    /// The error is logged and the run carries on without the file.
+    Warn,
+}
+
+impl OutputErrors {
+    fn handle(self, result: Result<(), String>) -> Result<(), String> {
+        match (self, result) {
+            (OutputErrors::Warn, Err(e)) => {
+                warn!("{}", e);
+                Ok(())
+            }
+            (_, result) => result,
+        }
+    }
+}
+
+pub(crate) fn validate(
+    context: &ValidationContext,
+    options: &ValidationOptions,
+) -> Result<ValidationReportBuilder, String> {
+    validate_shapes(context, options, None, true, OutputErrors::Fail)
+}
+
+/// Validates like [`validate`], but only logs failures to read or write the audit log and
+/// the persistent cache.
+pub(crate) fn validate_logging_output_errors(
+    context: &ValidationContext,
+    options: &ValidationOptions,
+) -> Result<ValidationReportBuilder, String> {
+    validate_shapes(context, options, None, true, OutputErrors::Warn)
+}
+
+/// Validates a scratch data graph that holds only part of the data, such as one payload of
+/// `Validator::validate_each`.
+pub(crate) fn validate_scratch(
+    context: &ValidationContext,
+    options: &ValidationOptions,
+) -> Result<ValidationReportBuilder, String> {
+    validate_shapes(context, options, None, false, OutputErrors::Fail)
+}
+
+/// Validates only the focus nodes in `focus_nodes`, against every shape that targets them.
+pub(crate) fn validate_focus_nodes(
+    context: &ValidationContext,
+    options: &ValidationOptions,
+    focus_nodes: &HashSet<Term>,
+) -> Result<ValidationReportBuilder, String> {
+    validate_shapes(
+        context,
+        options,
+        Some(focus_nodes),
+        false,
+        OutputErrors::Fail,
+    )
+}
+
+/// Checks that every shape `options` restricts validation to exists.
+pub(crate) fn check_options(
+    context: &ValidationContext,
+    options: &ValidationOptions,
+) -> Result<(), String> {
+    for shape in options.shapes().into_iter().flatten() {
+        let known = context
+            .model
+            .nodeshape_id_lookup()
+            .borrow()
+            .get(shape)
+            .is_some()
+            || context
+                .model
+                .propshape_id_lookup()
+                .borrow()
+                .get(shape)
+                .is_some();
+        if !known {
+            return Err(format!("Shape {} not found", shape));
+        }
+    }
+    Ok(())
+}
+
+fn validate_shapes(
+    context: &ValidationContext,
+    options: &ValidationOptions,
+    focus_filter: Option<&HashSet<Term>>,
+    whole_data_graph: bool,
+    output_errors: OutputErrors,
+) -> Result<ValidationReportBuilder, String> {
+    check_options(context, options)?;
+    // Cached custom component and shape conformance results are only valid while the data
+    // is unchanged.
+    context.clear_result_caches();
+    let run = ValidationRun::new(options, focus_filter);
+    // Open the output files before changing any context state, so a failure leaves nothing
+    // behind.
+    if let Some(path) = options.persistent_cache() {
+        match PersistentConformanceCache::open(path, context) {
+            Ok(cache) => *run.persistent_cache.borrow_mut() = Some(cache),
+            Err(e) => output_errors.handle(Err(e))?,
+        }
+    }
+    if let Some(path) = options.audit_log() {
+        match AuditLog::open(path) {
+            Ok(audit_log) => *context.audit_log.borrow_mut() = Some(audit_log),
+            Err(e) => output_errors.handle(Err(e))?,
+        }
+    }
+    context
+        .sparql_constraints_enabled
+        .set(options.sparql_constraints());
+    context.result_details_enabled.set(options.result_details());
+    let shards = validate_shards(context, &run);
+    context.sparql_constraints_enabled.set(true);
+    context.result_details_enabled.set(false);
+    let audit_log = context.audit_log.borrow_mut().take();
+    let audit_result = audit_log.map_or(Ok(()), AuditLog::finish);
+    let cache_result = match run.persistent_cache.take() {
+        Some(cache) => {
+            info!("skipped {} focus nodes known to conform", cache.hits);
+            // Entries of nodes this run did not reach are only stale after a complete run.
+            let complete = whole_data_graph
+                && options.shapes().is_none()
+                && run.stop_reason.get().is_none()
+                && shards.is_ok();
+            cache.finish(complete)
+        }
+        None => Ok(()),
+    };
+    output_errors.handle(audit_result)?;
+    output_errors.handle(cache_result)?;
+    Ok(ValidationReportBuilder::from_shards(shards?).with_stop_reason(run.stop_reason.get()))
+}
+
+fn validate_shards(
+    context: &ValidationContext,
+    run: &ValidationRun<'_>,
+) -> Result<Vec<ReportShard>, String> {
+    // Each shape fills its own shard and shards are merged in shape order, so the report
+    // does not depend on the order shapes are validated in. Referenced shapes go first, so
+    // their conformance is cached by the time a referencing shape checks it.
+    let mut shards = Vec::new();
+    for view in dependency_order(&ShapeView::all(&context.model)) {
+        let (source, shape): (SourceShape, &dyn ValidateShape) = match view.kind() {
+            ShapeKind::Node(shape) => (SourceShape::NodeShape(*shape.identifier()), shape),
+            ShapeKind::Property(shape) => (SourceShape::PropertyShape(*shape.identifier()), shape),
+        };
+        if run.should_stop() || !run.includes_shape(context, &source) {
+            continue;
+        }
+        let mut shard = shape_shard(context, source);
+        shape.process_targets(context, run, &mut shard)?;
+        shards.push(shard);
+    }
+    Ok(shards)
+}
+
+/// State shared by the shapes of one validation run: which focus nodes to validate, and
+/// whether the run has hit one of the limits of its [`ValidationOptions`].
+pub(crate) struct ValidationRun<'a> {
+    options: &'a ValidationOptions,
+    focus_filter: Option<&'a HashSet<Term>>,
+    deadline: Option<Instant>,
+    reported: Cell<usize>,
+    stop_reason: Cell<Option<StopReason>>,
+    /// Focus nodes known to conform from earlier runs, when the options name a cache file.
+    persistent_cache: RefCell<Option<PersistentConformanceCache>>,
+}
+
+impl<'a> ValidationRun<'a> {
+    fn new(options: &'a ValidationOptions, focus_filter: Option<&'a HashSet<Term>>) -> Self {
+        ValidationRun {
+            options,
+            focus_filter,
+            deadline: options.timeout().map(|timeout| Instant::now() + timeout),
+            reported: Cell::new(0),
+            stop_reason: Cell::new(None),
+            persistent_cache: RefCell::new(None),
+        }
+    }
+
+    /// Returns the persistent cache entry for validating `focus_node` against `source`, or
+    /// `None` when the run keeps no cache or the pair cannot be cached.
+    fn persistent_cache_key(
+        &self,
+        context: &ValidationContext,
+        source: &SourceShape,
+        focus_node: &Term,
+    ) -> Result<Option<String>, String> {
+        match self.persistent_cache.borrow_mut().as_mut() {
+            Some(cache) => cache.key(context, source, focus_node),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns whether an earlier run found the pair behind `key` conforming.
+    fn known_to_conform(&self, key: Option<&str>) -> bool {
+        match (self.persistent_cache.borrow_mut().as_mut(), key) {
+            (Some(cache), Some(key)) => cache.conforms(key),
+            _ => false,
+        }
     }
-    // Validate all property shapes
-    for shape in context.model.prop_shapes.values() {
-        shape.process_targets(context, &mut report_builder)?;
+
+    /// Records in the persistent cache that the pair behind `key` conforms.
+    fn record_conforming(&self, key: Option<String>) {
+        if let (Some(cache), Some(key)) = (self.persistent_cache.borrow_mut().as_mut(), key) {
+            cache.insert(key);
+        }
+    }
+
+    fn includes_shape(&self, context: &ValidationContext, source: &SourceShape) -> bool {
+        match self.options.shapes() {
+            Some(shapes) => source
+                .get_term(context)
+                .is_some_and(|term| shapes.contains(&term)),
+            None => true,
+        }
+    }
+
+    /// Returns `true` once the run has reached its result limit or deadline.
+    fn should_stop(&self) -> bool {
+        if self.stop_reason.get().is_some() {
+            return true;
+        }
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            self.stop_reason.set(Some(StopReason::Timeout));
+            return true;
+        }
+        false
+    }
+
+    /// Adds `failure` to `shard` unless its severity is ignored or the result limit has
+    /// been reached.
+    fn add_failure(
+        &self,
+        context: &ValidationContext,
+        shard: &mut ReportShard,
+        ctx: &Context,
+        failure: ValidationFailure,
+    ) {
+        if self.stop_reason.get() == Some(StopReason::MaxErrors) {
+            return;
+        }
+        let severity = ValidationReportBuilder::severity_for_result(ctx, &failure, context)
+            .cloned()
+            .unwrap_or_default();
+        if self.options.ignores_severity(&severity) {
+            return;
+        }
+        shard.add_failure(ctx, failure);
+        self.reported.set(self.reported.get() + 1);
+        if self
+            .options
+            .max_errors()
+            .is_some_and(|limit| self.reported.get() >= limit)
+        {
+            self.stop_reason.set(Some(StopReason::MaxErrors));
+        }
+    }
+}
+
+fn shape_shard(context: &ValidationContext, source: SourceShape) -> ReportShard {
+    let shape = source
+        .get_term(context)
+        .map(|term| term.to_string())
+        .unwrap_or_else(|| source.to_string());
+    let is_property_shape = matches!(source, SourceShape::PropertyShape(_));
+    ReportShard::new(ShardKey::shape(shape, is_property_shape))
+}
+
+/// Orders a shape's focus nodes so that results within a shard are deterministic, keeping
+/// only those in `focus_filter` when given.
+fn ordered_target_contexts(
+    target_contexts: HashSet<Context>,
+    focus_filter: Option<&HashSet<Term>>,
+) -> Vec<Context> {
+    let mut ordered: Vec<(String, Context)> = target_contexts
+        .into_iter()
+        .filter(|ctx| focus_filter.is_none_or(|nodes| nodes.contains(ctx.focus_node())))
+        .map(|ctx| (ctx.focus_node().to_string(), ctx))
+        .collect();
+    ordered.sort_by(|a, b| a.0.cmp(&b.0));
+    ordered.into_iter().map(|(_, ctx)| ctx).collect()
+}
+
+/// Validates `focus_nodes` against a single property shape, ignoring its declared targets.
+///
+/// Value nodes are resolved from each focus node through the shape's path, exactly as for
+/// a targeted focus node.
+pub(crate) fn validate_property_shape(
+    context: &ValidationContext,
+    shape: &Term,
+    focus_nodes: &[Term],
+) -> Result<ValidationReportBuilder, String> {
+    let shape_id = context
+        .model
+        .propshape_id_lookup()
+        .borrow()
+        .get(shape)
+        .ok_or_else(|| format!("Property shape {} not found", shape))?;
+    let property_shape = context
+        .model
+        .get_prop_shape_by_id(&shape_id)
+        .ok_or_else(|| format!("Property shape {} not found", shape))?;
+    context.clear_result_caches();
+
+    let mut report_builder = ValidationReportBuilder::new();
+    for focus_node in focus_nodes {
+        let trace_index = {
+            let mut traces = context.execution_traces.borrow_mut();
+            traces.push(Vec::new());
+            traces.len() - 1
+        };
+        let mut focus_context = Context::new(
+            focus_node.clone(),
+            None,
+            None,
+            SourceShape::PropertyShape(shape_id),
+            trace_index,
+        );
+
+        let mut traces = context.execution_traces.borrow_mut();
+        let trace = &mut traces[trace_index];
+        for result in property_shape.validate(&mut focus_context, context, trace)? {
+            if let ComponentValidationResult::Fail(ctx, failure) = result {
+                report_builder.add_failure(&ctx, failure);
+            }
+        }
     }
     Ok(report_builder)
 }
@@ -128,16 +474,82 @@ fn lookup_by_signature(
     None
 }
 
+/// Canonicalizes one chunk of the value nodes of a focus node the way
+/// [`canonicalize_value_nodes`] does, without collecting every object of the focus node:
+/// only literals that are not stored exactly are looked up among the stored objects.
+fn canonicalize_value_node_chunk(
+    validation_context: &ValidationContext,
+    shape: &PropertyShape,
+    focus_node: &Term,
+    mut nodes: Vec<Term>,
+) -> Vec<Term> {
+    let predicate = match shape.path_term() {
+        Term::NamedNode(nn) => nn,
+        _ => return nodes,
+    };
+    let subject = match focus_node.try_to_subject_ref() {
+        Ok(subject) => subject,
+        Err(_) => return nodes,
+    };
+    let store = validation_context.model.store();
+    let data_graph = validation_context.data_graph_iri_ref();
+    let original_index = validation_context.model.original_values.as_ref();
+
+    for node in &mut nodes {
+        let Term::Literal(lit) = node.clone() else {
+            continue;
+        };
+        if let Some(original) =
+            original_index.and_then(|index| index.resolve_literal(focus_node, predicate, &lit))
+        {
+            *node = original;
+            continue;
+        }
+        let stored = store
+            .contains(QuadRef::new(
+                subject,
+                predicate.as_ref(),
+                lit.as_ref(),
+                data_graph,
+            ))
+            .unwrap_or(true);
+        if stored {
+            continue;
+        }
+        let signature = literal_signature(&lit);
+        let matching = store
+            .quads_for_pattern(
+                Some(subject),
+                Some(predicate.as_ref()),
+                None,
+                Some(data_graph),
+            )
+            .filter_map(Result::ok)
+            .map(|q| q.object)
+            .find(|object| match object {
+                Term::Literal(stored) => literal_signature(stored) == signature,
+                _ => false,
+            });
+        if let Some(term) = matching {
+            *node = term;
+        }
+    }
+
+    nodes
+}
+
 impl ValidateShape for NodeShape {
     fn process_targets(
         &self,
         context: &ValidationContext,
-        report_builder: &mut ValidationReportBuilder,
+        run: &ValidationRun<'_>,
+        report_shard: &mut ReportShard,
     ) -> Result<(), String> {
         if self.is_deactivated() {
             return Ok(());
         }
         // first gather all of the targets
+        let source = SourceShape::NodeShape(*self.identifier());
         let mut target_contexts = HashSet::new();
         for target in self.targets.iter() {
             info!(
@@ -145,12 +557,21 @@ impl ValidateShape for NodeShape {
                 target,
                 self.identifier()
             );
-            target_contexts.extend(
-                target.get_target_nodes(context, SourceShape::NodeShape(*self.identifier()))?,
-            );
+            target_contexts.extend(match run.focus_filter {
+                Some(nodes) => target.get_target_nodes_among(context, source.clone(), nodes)?,
+                None => target.get_target_nodes(context, source.clone())?,
+            });
         }
 
-        for mut target_context in target_contexts.into_iter() {
+        for mut target_context in ordered_target_contexts(target_contexts, run.focus_filter) {
+            if run.should_stop() {
+                break;
+            }
+            let cache_key =
+                run.persistent_cache_key(context, &source, target_context.focus_node())?;
+            if run.known_to_conform(cache_key.as_deref()) {
+                continue;
+            }
             let trace_index = {
                 let mut traces = context.execution_traces.borrow_mut();
                 traces.push(Vec::new());
@@ -162,6 +583,7 @@ impl ValidateShape for NodeShape {
                 let mut traces = context.execution_traces.borrow_mut();
                 let trace = &mut traces[trace_index];
                 trace.push(TraceItem::NodeShape(*self.identifier())); // Record NodeShape visit
+                let started = Instant::now();
 
                 // for each target, validate the constraints
                 let constraints = self.constraints();
@@ -170,6 +592,7 @@ impl ValidateShape for NodeShape {
                     self.identifier(),
                     constraints.len()
                 );
+                let mut first_failure = None;
                 for constraint_id in constraints {
                     debug!(
                         "Evaluating node shape constraint {} for shape {}",
@@ -186,7 +609,10 @@ impl ValidateShape for NodeShape {
                         Ok(validation_results) => {
                             for result in validation_results {
                                 if let ComponentValidationResult::Fail(ctx, failure) = result {
-                                    report_builder.add_failure(&ctx, failure);
+                                    if first_failure.is_none() {
+                                        first_failure = Some((ctx.clone(), failure.clone()));
+                                    }
+                                    run.add_failure(context, report_shard, &ctx, failure);
                                 }
                             }
                         }
@@ -196,6 +622,17 @@ impl ValidateShape for NodeShape {
                         }
                     }
                 }
+                if first_failure.is_none() {
+                    run.record_conforming(cache_key);
+                }
+                store_target_conformance(
+                    context,
+                    target_context.focus_node().clone(),
+                    *self.identifier(),
+                    first_failure,
+                );
+                context
+                    .record_duration(TraceItem::NodeShape(*self.identifier()), started.elapsed());
             }
         }
         Ok(())
@@ -206,12 +643,14 @@ impl ValidateShape for PropertyShape {
     fn process_targets(
         &self,
         context: &ValidationContext,
-        report_builder: &mut ValidationReportBuilder,
+        run: &ValidationRun<'_>,
+        report_shard: &mut ReportShard,
     ) -> Result<(), String> {
         if self.is_deactivated() {
             return Ok(());
         }
         // first gather all of the targets
+        let source = SourceShape::PropertyShape(*self.identifier());
         let mut target_contexts = HashSet::new();
         for target in self.targets.iter() {
             info!(
@@ -219,12 +658,21 @@ impl ValidateShape for PropertyShape {
                 target,
                 self.identifier()
             );
-            target_contexts.extend(
-                target.get_target_nodes(context, SourceShape::PropertyShape(*self.identifier()))?,
-            );
+            target_contexts.extend(match run.focus_filter {
+                Some(nodes) => target.get_target_nodes_among(context, source.clone(), nodes)?,
+                None => target.get_target_nodes(context, source.clone())?,
+            });
         }
 
-        for mut target_context in target_contexts.into_iter() {
+        for mut target_context in ordered_target_contexts(target_contexts, run.focus_filter) {
+            if run.should_stop() {
+                break;
+            }
+            let cache_key =
+                run.persistent_cache_key(context, &source, target_context.focus_node())?;
+            if run.known_to_conform(cache_key.as_deref()) {
+                continue;
+            }
             let trace_index = {
                 let mut traces = context.execution_traces.borrow_mut();
                 traces.push(Vec::new());
@@ -238,9 +686,15 @@ impl ValidateShape for PropertyShape {
 
                 match self.validate(&mut target_context, context, trace) {
                     Ok(validation_results) => {
+                        if !validation_results
+                            .iter()
+                            .any(|result| matches!(result, ComponentValidationResult::Fail(..)))
+                        {
+                            run.record_conforming(cache_key);
+                        }
                         for result in validation_results {
                             if let ComponentValidationResult::Fail(ctx, failure) = result {
-                                report_builder.add_failure(&ctx, failure);
+                                run.add_failure(context, report_shard, &ctx, failure);
                             }
                         }
                     }
@@ -271,7 +725,21 @@ impl PropertyShape {
             return Ok(vec![]);
         }
         trace.push(TraceItem::PropertyShape(*self.identifier()));
+        let started = Instant::now();
+        let results = self.validate_focus_nodes(focus_context, context, trace);
+        context.record_duration(
+            TraceItem::PropertyShape(*self.identifier()),
+            started.elapsed(),
+        );
+        results
+    }
 
+    fn validate_focus_nodes(
+        &self,
+        focus_context: &mut Context,
+        context: &ValidationContext,
+        trace: &mut Vec<TraceItem>,
+    ) -> Result<Vec<ComponentValidationResult>, String> {
         let mut all_results: Vec<ComponentValidationResult> = Vec::new();
 
         // If the incoming context has value nodes, those are our focus nodes (for nested property shapes).
@@ -319,25 +787,8 @@ impl PropertyShape {
                     )
                 })?;
 
-            let value_nodes_vec: Vec<Term> = match results {
-                QueryResults::Solutions(solutions) => {
-                    let value_node_var = Variable::new("valueNode")
-                        .map_err(|e| format!("Internal error creating SPARQL variable: {}", e))?;
-
-                    let mut nodes = Vec::new();
-                    for solution_res in solutions {
-                        let solution = solution_res.map_err(|e| e.to_string())?;
-                        if let Some(term) = solution.get(&value_node_var) {
-                            nodes.push(term.clone());
-                        } else {
-                            return Err(format!(
-                                "Missing valueNode in solution for PropertyShape {}",
-                                self.identifier()
-                            ));
-                        }
-                    }
-                    canonicalize_value_nodes(context, self, &focus_node, nodes)
-                }
+            let solutions = match results {
+                QueryResults::Solutions(solutions) => solutions,
                 QueryResults::Boolean(_) => {
                     return Err(format!(
                         "Unexpected boolean result for PropertyShape {} query",
@@ -351,53 +802,242 @@ impl PropertyShape {
                     ));
                 }
             };
+            let value_node_var = Variable::new("valueNode")
+                .map_err(|e| format!("Internal error creating SPARQL variable: {}", e))?;
+            let value_nodes = solutions.map(|solution_res| {
+                let solution = solution_res.map_err(|e| e.to_string())?;
+                solution.get(&value_node_var).cloned().ok_or_else(|| {
+                    format!(
+                        "Missing valueNode in solution for PropertyShape {}",
+                        self.identifier()
+                    )
+                })
+            });
 
-            let value_nodes_opt = if value_nodes_vec.is_empty() {
-                None
-            } else {
-                Some(value_nodes_vec)
-            };
-
-            let mut constraint_validation_context = Context::new(
-                focus_node.clone(),
-                Some(self.path().clone()),
-                value_nodes_opt,
-                SourceShape::PropertyShape(PropShapeID(self.identifier().0)),
-                focus_context.trace_index(),
-            );
-
-            let constraints = self.constraints();
             debug!(
                 "Property shape {} has {} constraints",
                 self.identifier(),
-                constraints.len()
+                self.constraints().len()
             );
-            for constraint_id in constraints {
-                debug!(
-                    "Evaluating property shape constraint {} for shape {}",
-                    constraint_id,
-                    self.identifier()
-                );
-                let component = context
-                    .get_component(constraint_id)
-                    .ok_or_else(|| format!("Component not found: {}", constraint_id))?;
-
-                match component.validate(
-                    *constraint_id,
-                    &mut constraint_validation_context,
+            let stopped = if self.evaluates_value_nodes_in_chunks(context)? {
+                self.validate_value_node_chunks(
+                    &focus_node,
+                    value_nodes,
+                    focus_context,
                     context,
                     trace,
-                ) {
-                    Ok(results) => {
-                        all_results.extend(results);
-                    }
-                    Err(e) => {
-                        return Err(e);
-                    }
-                }
+                    &mut all_results,
+                )?
+            } else {
+                self.validate_collected_value_nodes(
+                    &focus_node,
+                    value_nodes,
+                    focus_context,
+                    context,
+                    trace,
+                    &mut all_results,
+                )?
+            };
+            if stopped {
+                return Ok(all_results);
             }
         }
 
         Ok(all_results)
     }
+
+    /// Returns `true` when every constraint of this shape checks value nodes one at a time
+    /// or only counts them, so the value nodes never have to be held at once.
+    fn evaluates_value_nodes_in_chunks(&self, context: &ValidationContext) -> Result<bool, String> {
+        for constraint_id in self.constraints() {
+            let component = context
+                .get_component(constraint_id)
+                .ok_or_else(|| format!("Component not found: {}", constraint_id))?;
+            if component.value_node_use() == ValueNodeUse::All {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Evaluates the constraints against the value nodes as they are produced, a chunk at
+    /// a time, then evaluates the cardinality constraints against their number. A focus
+    /// node with fewer value nodes than one chunk is evaluated exactly as if they were
+    /// collected. Returns `true` when a first-failure pass should stop.
+    fn validate_value_node_chunks(
+        &self,
+        focus_node: &Term,
+        value_nodes: impl Iterator<Item = Result<Term, String>>,
+        focus_context: &Context,
+        context: &ValidationContext,
+        trace: &mut Vec<TraceItem>,
+        all_results: &mut Vec<ComponentValidationResult>,
+    ) -> Result<bool, String> {
+        let mut per_value = Vec::new();
+        let mut counting = Vec::new();
+        for constraint_id in self.constraints() {
+            let component = context
+                .get_component(constraint_id)
+                .ok_or_else(|| format!("Component not found: {}", constraint_id))?;
+            match component.value_node_use() {
+                ValueNodeUse::Count => counting.push(*constraint_id),
+                _ => per_value.push(*constraint_id),
+            }
+        }
+
+        let mut count = 0;
+        let mut evaluated_chunk = false;
+        let mut chunk = Vec::new();
+        for value_node in value_nodes {
+            chunk.push(value_node?);
+            count += 1;
+            if chunk.len() < VALUE_NODE_CHUNK_SIZE {
+                continue;
+            }
+            let nodes = canonicalize_value_node_chunk(
+                context,
+                self,
+                focus_node,
+                std::mem::take(&mut chunk),
+            );
+            evaluated_chunk = true;
+            let mut chunk_context = self.value_node_context(focus_node, Some(nodes), focus_context);
+            if self.evaluate_constraints(
+                &per_value,
+                &mut chunk_context,
+                context,
+                trace,
+                all_results,
+            )? {
+                return Ok(true);
+            }
+        }
+        if !chunk.is_empty() || !evaluated_chunk {
+            let nodes = if evaluated_chunk {
+                canonicalize_value_node_chunk(context, self, focus_node, chunk)
+            } else {
+                canonicalize_value_nodes(context, self, focus_node, chunk)
+            };
+            let nodes = (!nodes.is_empty()).then_some(nodes);
+            let mut chunk_context = self.value_node_context(focus_node, nodes, focus_context);
+            if self.evaluate_constraints(
+                &per_value,
+                &mut chunk_context,
+                context,
+                trace,
+                all_results,
+            )? {
+                return Ok(true);
+            }
+        }
+
+        let mut count_context = self.value_node_context(focus_node, None, focus_context);
+        count_context.set_value_node_count(count);
+        self.evaluate_constraints(&counting, &mut count_context, context, trace, all_results)
+    }
+
+    /// Collects the value nodes and evaluates every constraint against them, reporting the
+    /// focus node instead when it has more value nodes than the configured cap. Returns
+    /// `true` when a first-failure pass should stop.
+    fn validate_collected_value_nodes(
+        &self,
+        focus_node: &Term,
+        value_nodes: impl Iterator<Item = Result<Term, String>>,
+        focus_context: &Context,
+        context: &ValidationContext,
+        trace: &mut Vec<TraceItem>,
+        all_results: &mut Vec<ComponentValidationResult>,
+    ) -> Result<bool, String> {
+        let value_node_limit = context.model.features.max_value_nodes;
+        let mut nodes = Vec::new();
+        for value_node in value_nodes {
+            // Stop pulling solutions as soon as the cap is exceeded so huge fan-outs are
+            // never materialized.
+            if value_node_limit.is_some_and(|limit| nodes.len() >= limit) {
+                let message = format!(
+                    "Path {} has more than {} value nodes for focus node {}; its constraints were not evaluated",
+                    self.sparql_path(),
+                    value_node_limit.unwrap_or_default(),
+                    focus_node
+                );
+                let failure = ValidationFailure {
+                    component_id: VALUE_NODE_LIMIT_COMPONENT_ID,
+                    failed_value_node: None,
+                    message_terms: vec![Literal::new_simple_literal(&message).into()],
+                    details: Vec::new(),
+                    message,
+                    result_path: None,
+                    source_constraint: None,
+                    severity: None,
+                };
+                let limit_context = self.value_node_context(focus_node, None, focus_context);
+                all_results.push(ComponentValidationResult::Fail(limit_context, failure));
+                return Ok(focus_context.conformance_mode().stops_at_first_failure());
+            }
+            nodes.push(value_node?);
+        }
+
+        let nodes = canonicalize_value_nodes(context, self, focus_node, nodes);
+        let nodes = (!nodes.is_empty()).then_some(nodes);
+        let mut constraint_validation_context =
+            self.value_node_context(focus_node, nodes, focus_context);
+        self.evaluate_constraints(
+            self.constraints(),
+            &mut constraint_validation_context,
+            context,
+            trace,
+            all_results,
+        )
+    }
+
+    /// Builds the context the constraints of this shape are evaluated in.
+    fn value_node_context(
+        &self,
+        focus_node: &Term,
+        value_nodes: Option<Vec<Term>>,
+        focus_context: &Context,
+    ) -> Context {
+        let mut constraint_context = Context::new(
+            focus_node.clone(),
+            Some(self.path().clone()),
+            value_nodes,
+            SourceShape::PropertyShape(PropShapeID(self.identifier().0)),
+            focus_context.trace_index(),
+        );
+        constraint_context.set_conformance_mode(focus_context.conformance_mode());
+        constraint_context
+    }
+
+    /// Evaluates `constraints` in `constraint_context` and appends their results. Returns
+    /// `true` when a first-failure pass should stop.
+    fn evaluate_constraints(
+        &self,
+        constraints: &[ComponentID],
+        constraint_context: &mut Context,
+        context: &ValidationContext,
+        trace: &mut Vec<TraceItem>,
+        all_results: &mut Vec<ComponentValidationResult>,
+    ) -> Result<bool, String> {
+        let mode = constraint_context.conformance_mode();
+        for constraint_id in constraints {
+            debug!(
+                "Evaluating property shape constraint {} for shape {}",
+                constraint_id,
+                self.identifier()
+            );
+            let component = context
+                .get_component(constraint_id)
+                .ok_or_else(|| format!("Component not found: {}", constraint_id))?;
+
+            let results = component.validate(*constraint_id, constraint_context, context, trace)?;
+            let failed = results
+                .iter()
+                .any(|r| matches!(r, ComponentValidationResult::Fail(_, _)));
+            all_results.extend(results);
+            if failed && mode.stops_at_first_failure() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
 }
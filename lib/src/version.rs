@@ -0,0 +1,225 @@
+//! Version pinning for shapes graphs.
+//!
+//! A shapes graph declares its version on its `owl:Ontology` node through
+//! `owl:versionInfo` and/or `owl:versionIRI`. Services that validate against cached
+//! shapes can require a version (or range) at construction time, so a stale shapes
+//! graph fails `ValidatorBuilder::build` instead of silently producing reports.
+
+use crate::named_nodes::OWL;
+use oxigraph::model::vocab::rdf;
+use oxigraph::model::{GraphNameRef, NamedNode, NamedOrBlankNode, Term};
+use oxigraph::store::Store;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// The version a shapes graph declares.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShapesVersion {
+    /// Lexical value of `owl:versionInfo`.
+    pub version_info: Option<String>,
+    /// Value of `owl:versionIRI`.
+    pub version_iri: Option<NamedNode>,
+}
+
+impl fmt::Display for ShapesVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.version_info, &self.version_iri) {
+            (Some(info), Some(iri)) => write!(f, "version {} ({})", info, iri),
+            (Some(info), None) => write!(f, "version {}", info),
+            (None, Some(iri)) => write!(f, "version IRI {}", iri),
+            (None, None) => write!(f, "no version"),
+        }
+    }
+}
+
+/// A version the shapes graph must declare.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapesVersionRequirement {
+    /// `owl:versionInfo` must equal this string.
+    Exactly(String),
+    /// `owl:versionInfo` must be at least `min` (inclusive) and below `max` (exclusive).
+    /// Versions are compared component-wise on `.`, numerically where both components
+    /// are numbers.
+    Range {
+        min: Option<String>,
+        max: Option<String>,
+    },
+    /// `owl:versionIRI` must equal this IRI.
+    VersionIri(NamedNode),
+}
+
+impl ShapesVersionRequirement {
+    /// Returns whether `version` satisfies the requirement.
+    pub fn matches(&self, version: &ShapesVersion) -> bool {
+        match self {
+            ShapesVersionRequirement::Exactly(expected) => {
+                version.version_info.as_deref() == Some(expected.as_str())
+            }
+            ShapesVersionRequirement::Range { min, max } => {
+                let Some(info) = version.version_info.as_deref() else {
+                    return false;
+                };
+                min.as_deref()
+                    .is_none_or(|min| compare_versions(info, min) != Ordering::Less)
+                    && max
+                        .as_deref()
+                        .is_none_or(|max| compare_versions(info, max) == Ordering::Less)
+            }
+            ShapesVersionRequirement::VersionIri(expected) => {
+                version.version_iri.as_ref() == Some(expected)
+            }
+        }
+    }
+}
+
+impl fmt::Display for ShapesVersionRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShapesVersionRequirement::Exactly(version) => write!(f, "version {}", version),
+            ShapesVersionRequirement::Range { min, max } => {
+                let bounds: Vec<String> = min
+                    .iter()
+                    .map(|min| format!(">={}", min))
+                    .chain(max.iter().map(|max| format!("<{}", max)))
+                    .collect();
+                write!(f, "version {}", bounds.join(","))
+            }
+            ShapesVersionRequirement::VersionIri(iri) => write!(f, "version IRI {}", iri),
+        }
+    }
+}
+
+impl FromStr for ShapesVersionRequirement {
+    type Err = String;
+
+    /// Parses `1.2.0` (exact), `>=1.2`, `<2`, `>=1.2,<2` (range) or `<http://...>`
+    /// (version IRI).
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let raw = raw.trim();
+        if let Some(iri) = raw
+            .strip_prefix('<')
+            .and_then(|rest| rest.strip_suffix('>'))
+        {
+            return NamedNode::new(iri)
+                .map(ShapesVersionRequirement::VersionIri)
+                .map_err(|e| format!("Invalid version IRI '{}': {}", iri, e));
+        }
+        if !raw.starts_with(">=") && !raw.starts_with('<') {
+            if raw.is_empty() {
+                return Err("Empty shapes version requirement".to_string());
+            }
+            return Ok(ShapesVersionRequirement::Exactly(raw.to_string()));
+        }
+        let mut min = None;
+        let mut max = None;
+        for bound in raw.split(',').map(str::trim) {
+            if let Some(version) = bound.strip_prefix(">=") {
+                min = Some(version.trim().to_string());
+            } else if let Some(version) = bound.strip_prefix('<') {
+                max = Some(version.trim().to_string());
+            } else {
+                return Err(format!(
+                    "Invalid version bound '{}': expected >=VERSION or <VERSION",
+                    bound
+                ));
+            }
+        }
+        Ok(ShapesVersionRequirement::Range { min, max })
+    }
+}
+
+/// Reads the version declared by the `owl:Ontology` node(s) of `graph`.
+pub(crate) fn read_shapes_version(store: &Store, graph: &NamedNode) -> ShapesVersion {
+    let owl = OWL::get();
+    let graph_name = GraphNameRef::NamedNode(graph.as_ref());
+    let mut version = ShapesVersion::default();
+    let ontologies: Vec<NamedOrBlankNode> = store
+        .quads_for_pattern(
+            None,
+            Some(rdf::TYPE),
+            Some(owl.ontology.into()),
+            Some(graph_name),
+        )
+        .filter_map(Result::ok)
+        .map(|quad| quad.subject)
+        .collect();
+    for ontology in &ontologies {
+        for quad in store
+            .quads_for_pattern(Some(ontology.as_ref()), None, None, Some(graph_name))
+            .filter_map(Result::ok)
+        {
+            match quad.object {
+                Term::Literal(literal) if quad.predicate == owl.version_info => {
+                    version
+                        .version_info
+                        .get_or_insert_with(|| literal.value().to_string());
+                }
+                Term::NamedNode(iri) if quad.predicate == owl.version_iri => {
+                    version.version_iri.get_or_insert(iri);
+                }
+                _ => {}
+            }
+        }
+    }
+    version
+}
+
+/// Compares dot-separated versions component by component; missing components count as
+/// zero, so `1.2` equals `1.2.0`.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let a: Vec<&str> = a.trim_start_matches('v').split('.').collect();
+    let b: Vec<&str> = b.trim_start_matches('v').split('.').collect();
+    for index in 0..a.len().max(b.len()) {
+        let left = a.get(index).copied().unwrap_or("0");
+        let right = b.get(index).copied().unwrap_or("0");
+        let ordering = match (left.parse::<u64>(), right.parse::<u64>()) {
+            (Ok(left), Ok(right)) => left.cmp(&right),
+            _ => left.cmp(right),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(version: &str) -> ShapesVersion {
+        ShapesVersion {
+            version_info: Some(version.to_string()),
+            version_iri: None,
+        }
+    }
+
+    #[test]
+    fn compares_numeric_components() {
+        assert_eq!(compare_versions("1.10", "1.9"), Ordering::Greater);
+        assert_eq!(compare_versions("1.2", "1.2.0"), Ordering::Equal);
+        assert_eq!(compare_versions("v2.0", "2"), Ordering::Equal);
+        assert_eq!(compare_versions("1.2.0-rc1", "1.2.0"), Ordering::Greater);
+    }
+
+    #[test]
+    fn parses_and_matches_requirements() {
+        let range: ShapesVersionRequirement = ">=1.2, <2".parse().unwrap();
+        assert!(range.matches(&info("1.2.0")));
+        assert!(range.matches(&info("1.10")));
+        assert!(!range.matches(&info("2.0")));
+        assert!(!range.matches(&ShapesVersion::default()));
+
+        let exact: ShapesVersionRequirement = "1.2.0".parse().unwrap();
+        assert!(exact.matches(&info("1.2.0")));
+        assert!(!exact.matches(&info("1.2")));
+
+        let iri: ShapesVersionRequirement = "<http://example.org/shapes/1.2>".parse().unwrap();
+        assert!(iri.matches(&ShapesVersion {
+            version_info: None,
+            version_iri: Some(NamedNode::new_unchecked("http://example.org/shapes/1.2")),
+        }));
+        assert!(">=1,~2".parse::<ShapesVersionRequirement>().is_err());
+    }
+}
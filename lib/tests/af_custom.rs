@@ -33,3 +33,347 @@ fn custom_component_uses_default_parameter_value() {
         report_ttl
     );
 }
+
+#[test]
+fn component_severity_and_message_apply_unless_shape_overrides() {
+    use oxigraph::model::{NamedNodeRef, TermRef};
+
+    let shapes = fixture_path("af_severity_shapes.ttl");
+    let data = fixture_path("af_severity_data.ttl");
+
+    let validator = Validator::from_files(&shapes, &data).expect("validator should build");
+    let report = validator.validate();
+    assert!(!report.conforms(), "both focus nodes should fail");
+
+    let graph = report.to_graph();
+    let sh = |local: &str| format!("http://www.w3.org/ns/shacl#{}", local);
+    let focus_node = NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#focusNode");
+    let severity = NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#resultSeverity");
+    let message = NamedNodeRef::new_unchecked("http://www.w3.org/ns/shacl#resultMessage");
+
+    let result_for = |focus: &str| {
+        let focus = NamedNodeRef::new_unchecked(focus);
+        let result = graph
+            .subjects_for_predicate_object(focus_node, focus)
+            .next()
+            .expect("expected a result for focus node");
+        let severity = graph
+            .object_for_subject_predicate(result, severity)
+            .map(|t| t.to_string())
+            .expect("result should carry a severity");
+        let messages: Vec<String> = graph
+            .objects_for_subject_predicate(result, message)
+            .filter_map(|t| match t {
+                TermRef::Literal(lit) => Some(lit.value().to_string()),
+                _ => None,
+            })
+            .collect();
+        (severity, messages)
+    };
+
+    let (alpha_severity, alpha_messages) = result_for("http://example.org/Alpha");
+    assert_eq!(alpha_severity, format!("<{}>", sh("Info")));
+    assert_eq!(alpha_messages, vec!["Value bad is forbidden.".to_string()]);
+
+    let (beta_severity, beta_messages) = result_for("http://example.org/Beta");
+    assert_eq!(beta_severity, format!("<{}>", sh("Warning")));
+    assert_eq!(beta_messages, vec!["Shape-level message.".to_string()]);
+}
+
+#[test]
+fn component_var_name_and_declared_prefixes_apply_to_validators() {
+    let shapes = fixture_path("af_template_shapes.ttl");
+    let data = fixture_path("af_template_data.ttl");
+
+    let validator = Validator::from_files(&shapes, &data)
+        .expect("validator should build with component-level prefix declarations");
+    let report = validator.validate();
+    assert!(!report.conforms(), "long code should violate the component");
+
+    let report_ttl = report
+        .to_turtle()
+        .expect("failed to serialize validation report");
+
+    assert!(
+        report_ttl.contains("Code longer than 3 characters."),
+        "sh:varName should drive message substitution: {}",
+        report_ttl
+    );
+    assert!(
+        report_ttl.contains("http://example.org/Long"),
+        "long code should be reported: {}",
+        report_ttl
+    );
+    assert!(
+        !report_ttl.contains("http://example.org/Exempt"),
+        "exempt item should be filtered through the declared prefix: {}",
+        report_ttl
+    );
+}
+
+#[test]
+fn shape_template_instantiated_per_referencing_shape() {
+    let shapes = fixture_path("af_shape_template_shapes.ttl");
+    let data = fixture_path("af_shape_template_data.ttl");
+
+    let validator = Validator::from_files(&shapes, &data)
+        .expect("validator should build with shape template references");
+    let report = validator.validate();
+    assert!(
+        !report.conforms(),
+        "missing values should violate the template"
+    );
+
+    let report_ttl = report
+        .to_turtle()
+        .expect("failed to serialize validation report");
+
+    for expected in ["http://example.org/Nameless", "http://example.org/Solo"] {
+        assert!(
+            report_ttl.contains(expected),
+            "{} should be reported: {}",
+            expected,
+            report_ttl
+        );
+    }
+    for unexpected in ["http://example.org/Alice", "http://example.org/Pair"] {
+        assert!(
+            !report_ttl.contains(&format!("sh:focusNode <{}>", unexpected)),
+            "{} should conform: {}",
+            unexpected,
+            report_ttl
+        );
+    }
+}
+
+#[test]
+fn node_validator_ask_binds_value_to_focus_node() {
+    let shapes = fixture_path("af_node_ask_shapes.ttl");
+    let data = fixture_path("af_node_ask_data.ttl");
+
+    let validator = Validator::from_files(&shapes, &data)
+        .expect("validator should build with a node-level ASK validator");
+    let report = validator.validate();
+    assert!(
+        !report.conforms(),
+        "uncoded item should violate the component"
+    );
+
+    let report_ttl = report
+        .to_turtle()
+        .expect("failed to serialize validation report");
+
+    assert!(
+        report_ttl.contains("sh:focusNode <http://example.org/Uncoded>"),
+        "uncoded item should be reported: {}",
+        report_ttl
+    );
+    assert!(
+        !report_ttl.contains("sh:focusNode <http://example.org/Coded>"),
+        "coded item should conform: {}",
+        report_ttl
+    );
+    assert!(
+        report_ttl.contains("Uncoded has no value for code."),
+        "$value should be bound to the focus node in messages: {}",
+        report_ttl
+    );
+}
+
+#[test]
+fn validator_selection_is_deterministic_and_follows_precedence() {
+    let shapes = fixture_path("af_validator_selection_shapes.ttl");
+    let data = fixture_path("af_validator_selection_data.ttl");
+
+    for _ in 0..3 {
+        let validator = Validator::from_files(&shapes, &data)
+            .expect("validator should build with several validators per component");
+        let report = validator.validate();
+        assert!(!report.conforms(), "every validator flags the subject");
+
+        let report_ttl = report
+            .to_turtle()
+            .expect("failed to serialize validation report");
+
+        assert!(
+            report_ttl.contains("node validator"),
+            "node shapes should prefer sh:nodeValidator: {}",
+            report_ttl
+        );
+        assert!(
+            report_ttl.contains("property validator"),
+            "property shapes should prefer sh:propertyValidator: {}",
+            report_ttl
+        );
+        assert!(
+            !report_ttl.contains("general validator"),
+            "sh:validator should only be a fallback: {}",
+            report_ttl
+        );
+        assert!(
+            report_ttl.contains("first validator") && !report_ttl.contains("second validator"),
+            "duplicate validators should resolve by query text: {}",
+            report_ttl
+        );
+    }
+}
+
+#[test]
+fn multi_valued_parameters_instantiate_one_constraint_per_value() {
+    let shapes = fixture_path("af_multi_param_shapes.ttl");
+    let data = fixture_path("af_multi_param_data.ttl");
+
+    let validator = Validator::from_files(&shapes, &data)
+        .expect("validator should build with a multi-valued parameter");
+    let report = validator.validate();
+    assert!(
+        !report.conforms(),
+        "forbidden properties should be reported"
+    );
+
+    let report_ttl = report
+        .to_turtle()
+        .expect("failed to serialize validation report");
+
+    for expected in [
+        "Legacy must not use legacyId.",
+        "Drafted must not use draftNote.",
+    ] {
+        assert!(
+            report_ttl.contains(expected),
+            "every parameter value should be checked ({}): {}",
+            expected,
+            report_ttl
+        );
+    }
+    assert!(
+        !report_ttl.contains("sh:focusNode <http://example.org/Clean>"),
+        "record without forbidden properties should conform: {}",
+        report_ttl
+    );
+}
+
+#[test]
+fn optional_parameter_default_is_bound_when_absent() {
+    let shapes = fixture_path("af_optional_default_shapes.ttl");
+    let data = fixture_path("af_optional_default_data.ttl");
+
+    let validator = Validator::from_files(&shapes, &data)
+        .expect("validator should build when an optional parameter is omitted");
+    let report = validator.validate();
+    assert!(!report.conforms(), "the defaulted limit should be enforced");
+
+    let report_ttl = report
+        .to_turtle()
+        .expect("failed to serialize validation report");
+
+    assert!(
+        report_ttl.contains("TwoTags has more than 1 values for tag."),
+        "sh:defaultValue should be bound in the query and message: {}",
+        report_ttl
+    );
+    assert!(
+        !report_ttl.contains("sh:focusNode <http://example.org/OneTag>"),
+        "a single tag is within the default limit: {}",
+        report_ttl
+    );
+    assert!(
+        !report_ttl.contains("more than 3 values"),
+        "an explicit value should override the default: {}",
+        report_ttl
+    );
+}
+
+#[test]
+fn select_validator_path_binding_becomes_complex_result_path() {
+    use oxigraph::model::{NamedNode, Term};
+    use shacl::types::Path;
+
+    let shapes = fixture_path("af_result_path_shapes.ttl");
+    let data = fixture_path("af_result_path_data.ttl");
+
+    let validator = Validator::from_files(&shapes, &data)
+        .expect("validator should build with a SELECT validator binding ?path");
+    let report = validator.validate();
+
+    let parent: Term = NamedNode::new_unchecked("http://example.org/parent").into();
+    let results: Vec<_> = report.results().collect();
+    assert_eq!(results.len(), 1, "only the flagged child should fail");
+    assert_eq!(
+        results[0].path,
+        Some(Path::Inverse(Box::new(Path::Simple(parent))))
+    );
+
+    let report_ttl = report
+        .to_turtle()
+        .expect("failed to serialize validation report");
+    assert!(
+        report_ttl.contains("sh:inversePath <http://example.org/parent>"),
+        "the blank-node path should be serialized as sh:resultPath: {}",
+        report_ttl
+    );
+}
+
+#[test]
+fn property_ask_validator_messages_substitute_parameters_and_value() {
+    let shapes = fixture_path("af_property_ask_shapes.ttl");
+    let data = fixture_path("af_property_ask_data.ttl");
+
+    let validator = Validator::from_files(&shapes, &data)
+        .expect("validator should build with a property-level ASK validator");
+    let report = validator.validate();
+    assert!(
+        !report.conforms(),
+        "the long code should violate the component"
+    );
+
+    let report_ttl = report
+        .to_turtle()
+        .expect("failed to serialize validation report");
+    assert!(
+        report_ttl.contains("abcdef of ")
+            && report_ttl.contains("Long is longer than 3 characters"),
+        "{{$value}}, {{$this}} and {{?maxChars}} should be substituted: {}",
+        report_ttl
+    );
+    assert!(
+        report_ttl.contains("Long ist länger als 3 Zeichen\"@de"),
+        "every language variant should be instantiated: {}",
+        report_ttl
+    );
+    assert!(
+        !report_ttl.contains('{'),
+        "no placeholder should be left unexpanded: {}",
+        report_ttl
+    );
+}
+
+#[test]
+fn shared_component_invocations_report_for_every_shape() {
+    use std::collections::HashSet;
+
+    let shapes = fixture_path("af_mixin_shapes.ttl");
+    let data = fixture_path("af_mixin_data.ttl");
+
+    let validator =
+        Validator::from_files(&shapes, &data).expect("validator should build with mixin shapes");
+    // The second run must not be affected by results cached in the first.
+    for _ in 0..2 {
+        let report = validator.validate();
+        let results: Vec<_> = report.results().collect();
+        assert_eq!(results.len(), 2, "each mixin should report the short code");
+        assert!(results
+            .iter()
+            .all(|result| result.focus_node.to_string() == "<http://example.org/Short>"));
+
+        // The query runs once, but each result keeps its own property shape.
+        let sources: HashSet<_> = results
+            .iter()
+            .filter_map(|result| result.source_shape.clone())
+            .collect();
+        assert_eq!(sources.len(), 2);
+        assert!(results
+            .iter()
+            .all(|result| result.engine_message == "ab is shorter than 4 characters"));
+    }
+}
@@ -0,0 +1,111 @@
+use shacl::{Source, ValidationOptions, Validator, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn log_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "shacl-rs-audit-{}-{}.jsonl",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn validator() -> Validator {
+    Validator::from_files(
+        &fixture_path("audit_log_shapes.ttl"),
+        &fixture_path("audit_log_data.ttl"),
+    )
+    .expect("failed to load fixtures")
+}
+
+#[test]
+fn every_evaluation_is_logged_with_its_outcome() {
+    let path = log_path("outcomes");
+    let validator = validator();
+    let report = validator
+        .validate_with_options(&ValidationOptions::new().with_audit_log(&path))
+        .expect("validation failed");
+    assert!(!report.conforms());
+
+    let log = std::fs::read_to_string(&path).expect("audit log was not written");
+    let lines: Vec<&str> = log.lines().collect();
+    let min_count: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|line| line.contains("#MinCountConstraintComponent>"))
+        .collect();
+    assert_eq!(min_count.len(), 2, "{}", log);
+    assert!(min_count.iter().any(|line| line
+        .contains("\"focus_node\":\"<http://example.org/alice>\"")
+        && line.contains("\"outcome\":\"pass\"")
+        && line.contains("\"value_nodes\":[\"\\\"Alice\\\"\"]")));
+    assert!(min_count.iter().any(|line| line
+        .contains("\"focus_node\":\"<http://example.org/bob>\"")
+        && line.contains("\"outcome\":\"fail\"")
+        && line.contains("\"path\":\"<http://example.org/name>\"")));
+
+    for (seq, line) in lines.iter().enumerate() {
+        assert!(line.starts_with("{\"run\":\""), "{}", line);
+        assert!(line.contains(&format!("\"seq\":{},", seq)), "{}", line);
+        assert!(line.ends_with('}'), "{}", line);
+    }
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn runs_append_to_the_same_log() {
+    let path = log_path("append");
+    let validator = validator();
+    let options = ValidationOptions::new().with_audit_log(&path);
+    validator.validate_with_options(&options).unwrap();
+    let first = std::fs::read_to_string(&path).unwrap().lines().count();
+    validator.validate_with_options(&options).unwrap();
+    let second = std::fs::read_to_string(&path).unwrap().lines().count();
+    assert!(first > 0);
+    assert_eq!(second, 2 * first);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn unwritable_logs_fail_the_run() {
+    let path = std::env::temp_dir()
+        .join("shacl-rs-audit-missing-dir")
+        .join("log.jsonl");
+    let validator = validator();
+    let err = validator
+        .validate_with_options(&ValidationOptions::new().with_audit_log(&path))
+        .err()
+        .expect("an unwritable audit log should fail the run");
+    assert!(err.contains("audit log"), "{}", err);
+}
+
+#[test]
+fn unwritable_logs_are_reported_as_errors() {
+    let path = std::env::temp_dir()
+        .join(format!("shacl-rs-audit-missing-{}", std::process::id()))
+        .join("audit.jsonl");
+    let options = ValidationOptions::new().with_audit_log(&path);
+
+    let err = validator()
+        .validate_with_options(&options)
+        .err()
+        .expect("a log in a missing directory cannot be opened");
+    assert!(err.contains("Failed to open audit log"), "{}", err);
+
+    let build = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("audit_log_shapes.ttl").into()))
+        .with_data_source(Source::File(fixture_path("audit_log_data.ttl").into()))
+        .with_validation_options(options)
+        .build();
+    let err = build.err().expect("build should check the audit log");
+    assert!(
+        err.to_string().contains("Failed to open audit log"),
+        "{}",
+        err
+    );
+}
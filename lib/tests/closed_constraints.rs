@@ -0,0 +1,54 @@
+use oxigraph::model::{Literal, NamedNode, Term};
+use shacl::types::Path;
+use shacl::Validator;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+#[test]
+fn closed_shapes_report_each_unexpected_property() {
+    let validator = Validator::from_files(
+        &fixture_path("closed_shapes.ttl"),
+        &fixture_path("closed_data.ttl"),
+    )
+    .expect("failed to load fixtures");
+    let report = validator.validate();
+
+    let mut results: Vec<(Term, Option<Path>, Option<Term>)> = report
+        .results()
+        .map(|result| {
+            assert_eq!(
+                result
+                    .source_constraint_component
+                    .as_ref()
+                    .map(|c| c.as_str()),
+                Some("http://www.w3.org/ns/shacl#ClosedConstraintComponent")
+            );
+            (result.focus_node, result.path, result.value)
+        })
+        .collect();
+    results.sort_by_key(|(_, path, _)| format!("{:?}", path));
+
+    // rdf:type is ignored on people and ex:city on addresses; alice only uses those and
+    // the declared paths, so only bob's extra properties are reported.
+    assert_eq!(
+        results,
+        vec![
+            (
+                ex("bob"),
+                Some(Path::Simple(ex("nickname"))),
+                Some(Literal::new_simple_literal("B").into()),
+            ),
+            (
+                ex("bob"),
+                Some(Path::Simple(ex("zip"))),
+                Some(Literal::new_simple_literal("00100").into()),
+            ),
+        ]
+    );
+}
@@ -0,0 +1,51 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::types::TraceItem;
+use shacl::{HeatEntryKind, Validator};
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn iri(value: &str) -> Term {
+    NamedNode::new_unchecked(value).into()
+}
+
+#[test]
+fn typed_frequencies_match_string_keyed_frequencies() {
+    let shapes = fixture_path("node_message_shapes.ttl");
+    let data = fixture_path("node_message_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+    let report = validator.validate();
+    assert!(!report.conforms());
+
+    let typed = report.get_component_frequencies_by_id();
+    let untyped = report.get_component_frequencies();
+    assert_eq!(typed.len(), untyped.len());
+    for ((id, label, _), count) in &untyped {
+        let entry = typed
+            .values()
+            .find(|entry| &entry.item.to_string() == id)
+            .unwrap_or_else(|| panic!("missing typed entry for {}", id));
+        assert_eq!(&entry.label, label);
+        assert_eq!(entry.count, *count);
+    }
+
+    for (item, entry) in &typed {
+        assert_eq!(item, &entry.item);
+        let expected_kind = match item {
+            TraceItem::NodeShape(_) => HeatEntryKind::NodeShape,
+            TraceItem::PropertyShape(_) => HeatEntryKind::PropertyShape,
+            TraceItem::Component(_) => HeatEntryKind::Component,
+        };
+        assert_eq!(entry.kind, expected_kind);
+    }
+
+    let team = typed
+        .values()
+        .find(|entry| entry.iri == iri("http://example.org/TeamShape"))
+        .expect("targeted node shape should be counted");
+    assert!(matches!(team.item, TraceItem::NodeShape(_)));
+    assert!(typed
+        .values()
+        .any(|entry| entry.iri == iri("http://www.w3.org/ns/shacl#NodeConstraintComponent")));
+}
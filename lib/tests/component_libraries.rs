@@ -0,0 +1,63 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::{Source, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(format!(
+        "{}/tests/fixtures/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    ))
+}
+
+#[test]
+fn components_are_discovered_in_library_graphs() {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("component_library_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("component_library_data.ttl")))
+        .with_component_library_sources([Source::File(fixture_path("component_library.ttl"))])
+        .build()
+        .expect("failed to build validator with a component library");
+
+    let components: Vec<&str> = validator
+        .custom_components()
+        .iter()
+        .map(|component| component.iri.as_str())
+        .collect();
+    assert_eq!(
+        components,
+        vec!["http://example.org/lib#MinLengthComponent"]
+    );
+
+    let report = validator.validate();
+    let results: Vec<_> = report.results().collect();
+    assert_eq!(results.len(), 1, "{:?}", results);
+    assert_eq!(
+        results[0].focus_node,
+        Term::from(NamedNode::new_unchecked("http://example.org/Al"))
+    );
+    assert_eq!(
+        results[0]
+            .source_constraint_component
+            .as_ref()
+            .map(|component| component.as_str()),
+        Some("http://example.org/lib#MinLengthComponent")
+    );
+}
+
+#[test]
+fn a_library_without_components_is_rejected() {
+    let err = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("component_library_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("component_library_data.ttl")))
+        .with_component_library_sources([Source::File(fixture_path("conformance_cache_data.ttl"))])
+        .build()
+        .err()
+        .expect("a library without components should fail to build");
+    assert!(
+        err.to_string()
+            .contains("declares no sh:ConstraintComponent"),
+        "{}",
+        err
+    );
+}
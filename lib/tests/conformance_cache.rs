@@ -0,0 +1,57 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::{ConformanceCacheStats, Validator};
+use std::collections::HashSet;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+#[test]
+fn shared_value_nodes_are_checked_against_a_shape_once() {
+    let shapes = fixture_path("conformance_cache_shapes.ttl");
+    let data = fixture_path("conformance_cache_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+    let report = validator.validate();
+
+    assert_eq!(
+        report.conformance_cache_stats(),
+        ConformanceCacheStats { hits: 2, misses: 2 }
+    );
+    assert_eq!(report.conformance_cache_stats().hit_rate(), 0.5);
+
+    // A cached non-conformance still produces a result for every focus node.
+    let focus_nodes: HashSet<Term> = report.results().map(|result| result.focus_node).collect();
+    assert_eq!(focus_nodes, HashSet::from([ex("Carol"), ex("Dave")]));
+}
+
+#[test]
+fn cache_statistics_cover_a_single_run() {
+    let shapes = fixture_path("conformance_cache_shapes.ttl");
+    let data = fixture_path("conformance_cache_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+
+    let first = validator.validate().conformance_cache_stats();
+    let second = validator.validate().conformance_cache_stats();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn referenced_shapes_are_validated_first() {
+    let shapes = fixture_path("conformance_order_shapes.ttl");
+    let data = fixture_path("conformance_order_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+    let report = validator.validate();
+
+    // Both addresses were validated as targets of ex:ZAddressShape before ex:APersonShape
+    // checked them with sh:node. Shapes on the cycle between ex:TeamShape and ex:LeadShape
+    // are still validated, in some order.
+    let stats = report.conformance_cache_stats();
+    assert_eq!(stats.hits, 2, "{:?}", stats);
+
+    let focus_nodes: HashSet<Term> = report.results().map(|result| result.focus_node).collect();
+    assert_eq!(focus_nodes, HashSet::from([ex("Bob"), ex("Flat2")]));
+}
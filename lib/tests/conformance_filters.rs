@@ -0,0 +1,37 @@
+use shacl::conformance::run_test_suite_filtered;
+use shacl::{TestFilter, TestStatusKind};
+use std::path::PathBuf;
+
+fn suite_manifest() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("test-suite")
+        .join("manifest.ttl")
+}
+
+#[test]
+fn filtered_runs_only_include_selected_tests() {
+    let filter = TestFilter::new()
+        .with_section("core/node")
+        .with_name("minInclusive");
+    let report = run_test_suite_filtered(&suite_manifest(), &filter).expect("suite should load");
+    assert!(!report.outcomes.is_empty());
+    for outcome in &report.outcomes {
+        assert_eq!(outcome.section, "core/node");
+        assert!(outcome.name.contains("minInclusive"), "{}", outcome.name);
+    }
+    assert_eq!(report.totals().total(), report.outcomes.len());
+}
+
+#[test]
+fn status_filters_keep_only_matching_outcomes() {
+    let filter = TestFilter::new()
+        .with_section("core/node")
+        .with_status(TestStatusKind::Passed);
+    let report = run_test_suite_filtered(&suite_manifest(), &filter).expect("suite should load");
+    assert!(report
+        .outcomes
+        .iter()
+        .all(|outcome| outcome.status.kind() == TestStatusKind::Passed));
+    assert_eq!(report.failures().count(), 0);
+}
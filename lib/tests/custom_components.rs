@@ -0,0 +1,63 @@
+use shacl::Validator;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn declared_components_list_parameters_and_selected_validators() {
+    let shapes = fixture_path("af_validator_selection_shapes.ttl");
+    let data = fixture_path("af_validator_selection_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+
+    let components = validator.custom_components();
+    let iris: Vec<&str> = components
+        .iter()
+        .map(|component| component.iri.as_str())
+        .collect();
+    assert_eq!(
+        iris,
+        vec![
+            "http://example.org/DuplicateComponent",
+            "http://example.org/PrecedenceComponent"
+        ]
+    );
+
+    let precedence = &components[1];
+    assert_eq!(precedence.parameters.len(), 1);
+    assert!(!precedence.parameters[0].optional);
+    assert!(!precedence.node_shape_validator().unwrap().is_ask);
+    assert!(precedence
+        .property_shape_validator()
+        .unwrap()
+        .query
+        .contains("$PATH"));
+
+    let duplicate = &components[0];
+    assert!(duplicate.node_shape_validator().unwrap().is_ask);
+    assert!(duplicate.property_shape_validator().unwrap().is_ask);
+    assert_eq!(
+        duplicate.to_string(),
+        "<http://example.org/DuplicateComponent>\n  \
+         parameter <http://example.org/flagTwice> (required)\n  \
+         node shapes: sh:validator (ASK)\n  \
+         property shapes: sh:validator (ASK)"
+    );
+}
+
+#[test]
+fn optional_parameters_and_defaults_are_reported() {
+    let shapes = fixture_path("af_optional_default_shapes.ttl");
+    let validator = Validator::from_files(&shapes, &shapes).expect("failed to load fixtures");
+
+    let components = validator.custom_components();
+    assert_eq!(components.len(), 1);
+    let listing = components[0].to_string();
+    assert!(
+        listing.contains("parameter <http://example.org/maxAllowed> (optional, default \"1\""),
+        "{}",
+        listing
+    );
+    assert!(listing.contains("node shapes: sh:nodeValidator (SELECT)"));
+    assert!(listing.contains("property shapes: none, the component is ignored"));
+}
@@ -0,0 +1,97 @@
+use oxigraph::model::{Graph, Literal, NamedNode, Term, Triple};
+use shacl::types::CustomSeverityConformance;
+use shacl::{Source, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn iri(value: &str) -> Term {
+    NamedNode::new_unchecked(value).into()
+}
+
+fn build(data: &str, policy: CustomSeverityConformance) -> shacl::Validator {
+    ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("custom_severity_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path(data)))
+        .with_custom_severity_conformance(policy)
+        .build()
+        .expect("failed to load fixtures")
+}
+
+fn result_severities(graph: &Graph) -> Vec<Term> {
+    let severity = NamedNode::new_unchecked("http://www.w3.org/ns/shacl#resultSeverity");
+    let mut severities: Vec<Term> = graph
+        .triples_for_predicate(&severity)
+        .map(|triple| triple.object.into_owned())
+        .collect();
+    severities.sort_by_key(|term| term.to_string());
+    severities
+}
+
+fn conforms_value(graph: &Graph) -> Term {
+    let conforms = NamedNode::new_unchecked("http://www.w3.org/ns/shacl#conforms");
+    graph
+        .triples_for_predicate(&conforms)
+        .next()
+        .expect("report should state sh:conforms")
+        .object
+        .into_owned()
+}
+
+#[test]
+fn custom_severities_are_reported_and_fail_conformance_by_default() {
+    let validator = build(
+        "custom_severity_data.ttl",
+        CustomSeverityConformance::NonConforming,
+    );
+    let report = validator.validate();
+    assert!(!report.conforms());
+
+    let graph = report.to_graph();
+    assert_eq!(
+        result_severities(&graph),
+        vec![
+            iri("http://example.org/Advisory"),
+            iri("http://example.org/Advisory"),
+            iri("http://www.w3.org/ns/shacl#Violation"),
+        ]
+    );
+}
+
+#[test]
+fn ignored_custom_severities_do_not_affect_conformance() {
+    let validator = build(
+        "custom_severity_data.ttl",
+        CustomSeverityConformance::Ignored,
+    );
+    let report = validator.validate();
+    assert!(!report.conforms(), "ex:bob still lacks a name");
+
+    let mut named = Graph::new();
+    let ex = |local: &str| NamedNode::new_unchecked(format!("http://example.org/{}", local));
+    let rdf_type = NamedNode::new_unchecked("http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
+    named.insert(&Triple::new(ex("carol"), rdf_type, ex("Person")));
+    named.insert(&Triple::new(
+        ex("carol"),
+        ex("name"),
+        Literal::new_simple_literal("Carol"),
+    ));
+    validator
+        .replace_data_graph(&named)
+        .expect("failed to replace data graph");
+
+    let report = validator.validate();
+    assert!(report.conforms());
+    let graph = report.to_graph();
+    assert_eq!(conforms_value(&graph), Literal::from(true).into());
+    assert_eq!(
+        result_severities(&graph),
+        vec![iri("http://example.org/Advisory")]
+    );
+    assert!(report.self_check().unwrap().is_empty());
+}
@@ -0,0 +1,130 @@
+use oxigraph::io::{RdfFormat, RdfParser};
+use oxigraph::model::{Graph, GraphNameRef, NamedNode, NamedNodeRef, Term};
+use oxigraph::store::Store;
+use shacl::{Source, Validator, ValidatorBuilder};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+const SHAPES: NamedNodeRef<'_> = NamedNodeRef::new_unchecked("urn:app:shapes");
+const DATA: NamedNodeRef<'_> = NamedNodeRef::new_unchecked("urn:app:data");
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn app_store() -> Store {
+    let store = Store::new().expect("store");
+    for (file, graph) in [
+        ("datatype_in_shapes.ttl", SHAPES),
+        ("datatype_in_data.ttl", DATA),
+    ] {
+        let turtle = fs::read_to_string(fixture_path(file)).expect("read fixture");
+        store
+            .load_from_reader(
+                RdfParser::from_format(RdfFormat::Turtle).with_default_graph(graph),
+                turtle.as_bytes(),
+            )
+            .expect("load fixture");
+    }
+    store
+        .load_from_reader(
+            RdfParser::from_format(RdfFormat::Turtle).with_default_graph(DATA),
+            "<http://example.org/Meetup> a <http://example.org/Event> ; \
+             <http://example.org/when> [ <http://example.org/day> 3 ] ."
+                .as_bytes(),
+        )
+        .expect("load blank node");
+    store
+}
+
+/// Hashes the quads of `graph` in `store`, independently of their order.
+fn graph_hash(store: &Store, graph: NamedNodeRef<'_>) -> u64 {
+    let mut quads: Vec<String> = store
+        .quads_for_pattern(None, None, None, Some(graph.into()))
+        .map(|quad| quad.expect("quad").to_string())
+        .collect();
+    quads.sort();
+    let mut hasher = DefaultHasher::new();
+    quads.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn exercise(validator: &Validator) {
+    assert!(!validator.validate().conforms());
+    let shape = NamedNode::new_unchecked("http://example.org/EventShape");
+    validator.validate_shape(&shape).expect("shape validation");
+    let node: Term = NamedNode::new_unchecked("http://example.org/Party").into();
+    validator.validate_node(&node).expect("node validation");
+}
+
+fn validate_batch(validator: &Validator) -> Result<(), String> {
+    validator.validate_each(vec![Graph::new()], |_, _| {})
+}
+
+#[test]
+fn skolemizing_validators_never_rewrite_an_applications_store() {
+    let store = app_store();
+    let data_before = graph_hash(&store, DATA);
+    let shapes_before = graph_hash(&store, SHAPES);
+    let graphs_before = store.named_graphs().count();
+
+    // Skolemization is on by default, so the graphs are copied before it runs.
+    let validator = ValidatorBuilder::new()
+        .with_store(store.clone(), SHAPES.into(), DATA.into())
+        .expect("named graphs")
+        .build()
+        .expect("validator");
+    exercise(&validator);
+    validate_batch(&validator).expect("batch validation");
+
+    assert_eq!(graph_hash(&store, DATA), data_before);
+    assert_eq!(graph_hash(&store, SHAPES), shapes_before);
+    assert_eq!(store.named_graphs().count(), graphs_before);
+    assert!(store
+        .quads_for_pattern(None, None, None, Some(GraphNameRef::from(DATA)))
+        .filter_map(Result::ok)
+        .any(|quad| quad.object.is_blank_node()));
+}
+
+#[test]
+fn validating_a_shared_store_only_reads_the_data_graph() {
+    let store = app_store();
+    let data_before = graph_hash(&store, DATA);
+    let graphs_before = store.named_graphs().count();
+    let validator =
+        Validator::from_store(store.clone(), SHAPES.into(), DATA.into()).expect("validator");
+    exercise(&validator);
+    // Batch validation would need a scratch graph in the application's store.
+    let err = validate_batch(&validator).expect_err("batches need a private store");
+    assert!(err.contains("scratch graph"), "{}", err);
+    assert_eq!(graph_hash(&store, DATA), data_before);
+    assert_eq!(store.named_graphs().count(), graphs_before);
+}
+
+#[test]
+fn validating_loaded_sources_leaves_the_data_graph_unchanged() {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("datatype_in_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("datatype_in_data.ttl")))
+        .build()
+        .expect("validator");
+    let snapshot = |validator: &Validator| {
+        let mut quads: Vec<String> = validator
+            .data_graph_quads()
+            .expect("data graph")
+            .iter()
+            .map(|quad| quad.to_string())
+            .collect();
+        quads.sort();
+        quads
+    };
+    let before = snapshot(&validator);
+    exercise(&validator);
+    validate_batch(&validator).expect("batch validation");
+    assert_eq!(snapshot(&validator), before);
+}
@@ -0,0 +1,96 @@
+use oxigraph::io::{RdfFormat, RdfParser};
+use oxigraph::model::{NamedNode, NamedNodeRef, Term};
+use oxigraph::store::Store;
+use shacl::{Source, ValidatorBuilder};
+use std::fs;
+use std::path::PathBuf;
+
+const PEOPLE: &str = "PREFIX ex: <http://example.org/>
+CONSTRUCT { ?person ?p ?o } WHERE { ?person a ex:Person ; ?p ?o }";
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+fn builder() -> ValidatorBuilder {
+    ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("data_subset_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("data_subset_data.ttl")))
+}
+
+fn focus_nodes(builder: ValidatorBuilder) -> Vec<Term> {
+    let validator = builder.build().expect("failed to build validator");
+    let mut focus_nodes: Vec<Term> = validator
+        .validate()
+        .results()
+        .map(|result| result.focus_node)
+        .collect();
+    focus_nodes.sort_by_key(|term| term.to_string());
+    focus_nodes
+}
+
+#[test]
+fn only_the_constructed_subset_is_validated() {
+    assert_eq!(focus_nodes(builder()), vec![ex("bob"), ex("gadget")]);
+    assert_eq!(
+        focus_nodes(builder().with_data_subset_query(PEOPLE)),
+        vec![ex("bob")]
+    );
+}
+
+#[test]
+fn describe_queries_are_accepted() {
+    let describe = "PREFIX ex: <http://example.org/> DESCRIBE ex:gadget";
+    assert_eq!(
+        focus_nodes(builder().with_data_subset_query(describe)),
+        vec![ex("gadget")]
+    );
+}
+
+#[test]
+fn queries_without_a_graph_result_are_rejected() {
+    let error = builder()
+        .with_data_subset_query("SELECT * WHERE { ?s ?p ?o }")
+        .build()
+        .err()
+        .expect("a SELECT query is rejected");
+    assert!(
+        error.to_string().contains("CONSTRUCT or DESCRIBE"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn a_subset_of_an_existing_store_leaves_the_store_untouched() {
+    let shapes = NamedNodeRef::new_unchecked("urn:app:shapes");
+    let data = NamedNodeRef::new_unchecked("urn:app:data");
+    let store = Store::new().expect("store");
+    for (file, graph) in [
+        ("data_subset_shapes.ttl", shapes),
+        ("data_subset_data.ttl", data),
+    ] {
+        let turtle = fs::read_to_string(fixture_path(file)).expect("read fixture");
+        store
+            .load_from_reader(
+                RdfParser::from_format(RdfFormat::Turtle).with_default_graph(graph),
+                turtle.as_bytes(),
+            )
+            .expect("load fixture");
+    }
+    let before = store.len().expect("len");
+
+    let builder = ValidatorBuilder::new()
+        .with_store(store.clone(), shapes.into(), data.into())
+        .expect("named graphs")
+        .with_data_subset_query(PEOPLE);
+    assert_eq!(focus_nodes(builder), vec![ex("bob")]);
+    assert_eq!(store.len().expect("len"), before);
+}
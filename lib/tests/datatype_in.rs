@@ -0,0 +1,69 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::{Source, ValidatorBuilder};
+use std::fs;
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+#[test]
+fn values_must_match_one_of_the_listed_datatypes() {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("datatype_in_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("datatype_in_data.ttl")))
+        .build()
+        .expect("failed to load fixtures");
+    let report = validator.validate();
+    assert!(!report.conforms());
+
+    let results = report.result_codes();
+    let mut failing: Vec<Term> = results.iter().map(|r| r.focus_node.clone()).collect();
+    failing.sort_by_key(|term| term.to_string());
+    assert_eq!(failing, vec![ex("Party"), ex("Pointer"), ex("Typo")]);
+    for result in &results {
+        assert_eq!(
+            result.component.as_str(),
+            "urn:shacl-rs:DatatypeInConstraintComponent"
+        );
+        assert_eq!(result.code.as_str(), "DatatypeInViolation");
+    }
+}
+
+#[test]
+fn non_iri_members_are_rejected_at_parse_time() {
+    let dir = std::env::temp_dir().join(format!("shacl_datatype_in_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let shapes = dir.join("shapes.ttl");
+    fs::write(
+        &shapes,
+        r#"@prefix sh: <http://www.w3.org/ns/shacl#> .
+@prefix shrs: <urn:shacl-rs:> .
+@prefix ex: <http://example.org/> .
+ex:S a sh:NodeShape ; sh:targetClass ex:Event ;
+    sh:property [ sh:path ex:when ; shrs:datatypeIn ( "date" ) ] .
+"#,
+    )
+    .unwrap();
+
+    let Err(err) = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(shapes))
+        .with_data_source(Source::File(fixture_path("datatype_in_data.ttl")))
+        .build()
+    else {
+        panic!("literal datatype should be rejected");
+    };
+    assert!(
+        err.to_string().contains("datatypes must be IRIs"),
+        "{}",
+        err
+    );
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,72 @@
+use shacl::{Source, Validator};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+#[test]
+fn shapes_graph_is_loaded_from_the_data_graph_declaration() {
+    let shapes = format!("file://{}", fixture_path("declared_shapes.ttl").display());
+    let data = format!(
+        r#"
+@prefix sh: <http://www.w3.org/ns/shacl#> .
+@prefix owl: <http://www.w3.org/2002/07/owl#> .
+@prefix ex: <http://example.org/> .
+
+<http://example.org/data> a owl:Ontology ; sh:shapesGraph <{}> .
+ex:alice a ex:Person ; ex:name "Alice" .
+ex:bob a ex:Person .
+"#,
+        shapes
+    );
+    let validator =
+        Validator::from_data_only(Source::InlineTurtle(data)).expect("declared shapes load");
+    assert_eq!(
+        validator.data_graph_iri().as_str(),
+        "http://example.org/data"
+    );
+    assert_ne!(validator.shapes_graph_iri(), validator.data_graph_iri());
+
+    let report = validator.validate();
+    let focus_nodes: Vec<String> = report
+        .results()
+        .map(|result| result.focus_node.to_string())
+        .collect();
+    assert_eq!(focus_nodes, vec!["<http://example.org/bob>".to_string()]);
+}
+
+#[test]
+fn a_data_graph_can_declare_itself_as_shapes_graph() {
+    let data = r#"
+@prefix sh: <http://www.w3.org/ns/shacl#> .
+@prefix owl: <http://www.w3.org/2002/07/owl#> .
+@prefix ex: <http://example.org/> .
+
+<http://example.org/self> a owl:Ontology ; sh:shapesGraph <http://example.org/self> .
+ex:PersonShape a sh:NodeShape ;
+    sh:targetClass ex:Person ;
+    sh:property [ sh:path ex:name ; sh:minCount 1 ] .
+ex:bob a ex:Person .
+"#;
+    let validator = Validator::from_data_only(Source::InlineTurtle(data.to_string()))
+        .expect("self-declared shapes load");
+    assert_eq!(validator.shapes_graph_iri(), validator.data_graph_iri());
+    assert!(!validator.validate().conforms());
+}
+
+#[test]
+fn data_without_declaration_is_an_error() {
+    let data = "@prefix ex: <http://example.org/> .\nex:bob a ex:Person .\n";
+    let err = Validator::from_data_only(Source::InlineTurtle(data.to_string()))
+        .err()
+        .expect("no shapes graph is declared");
+    assert!(
+        err.to_string().contains("declares no shapes graph"),
+        "unexpected error: {}",
+        err
+    );
+}
@@ -0,0 +1,81 @@
+use ontoenv::config::Config;
+use shacl::types::DuplicateShapePolicy;
+use shacl::{Source, Validator, ValidatorBuilder};
+use std::error::Error;
+use std::path::PathBuf;
+
+fn fixture_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("duplicate_shapes")
+}
+
+/// Builds a validator in an environment that also loads the imported shape modules.
+fn build(policy: DuplicateShapePolicy) -> Result<Validator, Box<dyn Error>> {
+    build_with_skolem_base(policy, None)
+}
+
+fn build_with_skolem_base(
+    policy: DuplicateShapePolicy,
+    shapes_skolem_base: Option<&str>,
+) -> Result<Validator, Box<dyn Error>> {
+    let dir = fixture_dir();
+    let config = Config::builder()
+        .root(dir.clone())
+        .locations(vec![dir.clone()])
+        .offline(true)
+        .temporary(true)
+        .build()?;
+    ValidatorBuilder::new()
+        .with_env_config(config)
+        .with_shapes_source(Source::File(dir.join("shapes.ttl")))
+        .with_data_source(Source::File(dir.join("data.ttl")))
+        .with_duplicate_shape_policy(policy)
+        .with_skolem_bases(shapes_skolem_base.map(str::to_string), None)
+        .build()
+}
+
+#[test]
+fn conflicting_imported_definitions_warn_by_default() {
+    let validator = build(DuplicateShapePolicy::Warn).expect("conflicts only warn by default");
+    // The shapes graph's definition is the one in effect: alice has a name but no email.
+    assert!(validator.validate().conforms());
+}
+
+#[test]
+fn conflicting_imported_definitions_can_fail_the_build() {
+    let err = build(DuplicateShapePolicy::Error)
+        .err()
+        .expect("conflicting definitions should fail the build");
+    let message = err.to_string();
+    assert!(
+        message.contains("<http://example.org/PersonShape> is defined differently")
+            && message.contains("imported graph <http://example.org/layer-conflict>"),
+        "unexpected error: {}",
+        message
+    );
+    assert!(
+        !message.contains("AddressShape"),
+        "identical copies are not conflicts: {}",
+        message
+    );
+}
+
+#[test]
+fn identical_nested_shapes_match_under_a_custom_skolem_base() {
+    let err = build_with_skolem_base(DuplicateShapePolicy::Error, Some("urn:shapes:skolem/"))
+        .err()
+        .expect("the conflicting PersonShape should still fail the build");
+    let message = err.to_string();
+    assert!(
+        message.contains("<http://example.org/PersonShape> is defined differently"),
+        "unexpected error: {}",
+        message
+    );
+    assert!(
+        !message.contains("AddressShape"),
+        "nested blank-node shapes under a custom base are not conflicts: {}",
+        message
+    );
+}
@@ -0,0 +1,51 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::{ErrorCode, Source, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+#[test]
+fn results_carry_stable_codes_independent_of_messages() {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("error_code_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("error_code_data.ttl")))
+        .build()
+        .expect("failed to load fixtures");
+    let report = validator.validate();
+
+    let mut codes: Vec<(Term, String)> = report
+        .result_codes()
+        .into_iter()
+        .map(|result| (result.focus_node, result.code.to_string()))
+        .collect();
+    codes.sort_by(|a, b| a.1.cmp(&b.1));
+
+    let alice: Term = NamedNode::new_unchecked("http://example.org/Alice").into();
+    assert_eq!(
+        codes,
+        vec![
+            (alice.clone(), "DatatypeViolation".to_string()),
+            (alice, "MinCountViolation".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn codes_follow_the_source_constraint_component() {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("error_code_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("error_code_data.ttl")))
+        .build()
+        .expect("failed to load fixtures");
+    for result in validator.validate().result_codes() {
+        assert_eq!(
+            result.code,
+            ErrorCode::for_component(result.component.as_ref())
+        );
+    }
+}
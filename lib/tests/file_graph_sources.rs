@@ -0,0 +1,69 @@
+use shacl::{Source, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn file_graph(graph_iri: &str) -> Source {
+    Source::FileGraph {
+        path: fixture_path("file_graph_dataset.trig"),
+        graph_iri: graph_iri.to_string(),
+    }
+}
+
+#[test]
+fn validates_one_named_graph_of_a_dataset() {
+    for (data_graph, conforms) in [("urn:example:valid", true), ("urn:example:invalid", false)] {
+        let validator = ValidatorBuilder::new()
+            .with_shapes_source(file_graph("urn:example:shapes"))
+            .with_data_source(file_graph(data_graph))
+            .build()
+            .expect("dataset graphs should load");
+        assert_eq!(validator.shapes_graph_iri().as_str(), "urn:example:shapes");
+        assert_eq!(validator.data_graph_iri().as_str(), data_graph);
+
+        let report = validator.validate();
+        assert_eq!(report.conforms(), conforms, "data graph {}", data_graph);
+        // Only the selected graph is data: bob is not seen when validating the valid graph.
+        assert_eq!(report.results().count(), usize::from(!conforms));
+    }
+}
+
+#[test]
+fn missing_named_graph_is_an_error() {
+    let err = ValidatorBuilder::new()
+        .with_shapes_source(file_graph("urn:example:shapes"))
+        .with_data_source(file_graph("urn:example:absent"))
+        .build()
+        .err()
+        .expect("an absent graph should not load");
+    assert!(
+        err.to_string()
+            .contains("has no named graph <urn:example:absent>"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn graphs_can_only_be_selected_from_dataset_files() {
+    let err = ValidatorBuilder::new()
+        .with_shapes_source(Source::FileGraph {
+            path: fixture_path("result_ordering_shapes.ttl"),
+            graph_iri: "urn:example:shapes".to_string(),
+        })
+        .with_data_source(file_graph("urn:example:valid"))
+        .build()
+        .err()
+        .expect("a Turtle file has no named graphs to select");
+    assert!(
+        err.to_string()
+            .contains("not a TriG, N-Quads or JSON-LD file"),
+        "unexpected error: {}",
+        err
+    );
+}
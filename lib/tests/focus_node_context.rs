@@ -0,0 +1,56 @@
+use oxigraph::model::vocab::xsd;
+use oxigraph::model::{Literal, NamedNode, Term};
+use shacl::{FocusNodeContext, ValidationResult, Validator};
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+fn result_for(results: &[ValidationResult], focus_node: &str) -> ValidationResult {
+    results
+        .iter()
+        .find(|result| result.focus_node == ex(focus_node))
+        .cloned()
+        .unwrap_or_else(|| panic!("no result for {}: {:?}", focus_node, results))
+}
+
+#[test]
+fn context_lists_types_and_values_along_the_result_path() {
+    let validator = Validator::from_files(
+        &fixture_path("shape_conformance_shapes.ttl"),
+        &fixture_path("shape_conformance_data.ttl"),
+    )
+    .expect("failed to load fixtures");
+    let report = validator.validate();
+    let results: Vec<ValidationResult> = report.results().collect();
+
+    // ex:bob has no ex:name, so only his type gives context.
+    let bob = result_for(&results, "bob");
+    assert_eq!(
+        report.focus_node_context(&bob, 5).unwrap(),
+        FocusNodeContext {
+            types: vec![ex("Person")],
+            values: Vec::new(),
+            truncated: false,
+        }
+    );
+
+    // The closed-shape result points at the disallowed ex:area value.
+    let kitchen = result_for(&results, "kitchen");
+    assert_eq!(
+        report.focus_node_context(&kitchen, 5).unwrap(),
+        FocusNodeContext {
+            types: vec![ex("Room")],
+            values: vec![Literal::new_typed_literal("12", xsd::INTEGER).into()],
+            truncated: false,
+        }
+    );
+
+    let limited = report.focus_node_context(&kitchen, 0).unwrap();
+    assert!(limited.types.is_empty() && limited.values.is_empty());
+    assert!(limited.truncated);
+}
@@ -0,0 +1,43 @@
+use shacl::{GraphLoadError, Validator};
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn invalid_data_turtle_reports_structured_syntax_errors() {
+    let shapes = fixture_path("validate_each_shapes.ttl");
+    let data = fixture_path("load_error_bad_data.ttl");
+
+    let err = match Validator::from_files(&shapes, &data) {
+        Ok(_) => panic!("malformed data graph should not load"),
+        Err(err) => err,
+    };
+    let load_error = err
+        .downcast_ref::<GraphLoadError>()
+        .unwrap_or_else(|| panic!("expected a GraphLoadError, got: {}", err));
+
+    assert_eq!(load_error.role, "data");
+    assert!(load_error.path.ends_with("load_error_bad_data.ttl"));
+    let first = load_error
+        .issues
+        .first()
+        .expect("at least one syntax issue should be reported");
+    // ex:Bob's statement is missing the ';' before ex:name on line 8.
+    assert_eq!(first.line, Some(8), "unexpected issue: {:?}", first);
+    assert!(first.column.is_some());
+    assert!(
+        !first.message.starts_with("Parser error"),
+        "position should not be repeated in the message: {}",
+        first.message
+    );
+}
+
+#[test]
+fn well_formed_graphs_load_and_validate() {
+    let shapes = fixture_path("validate_each_shapes.ttl");
+    let data = fixture_path("validate_each_data.ttl");
+
+    let validator = Validator::from_files(&shapes, &data).expect("well-formed graphs should load");
+    assert!(validator.validate().conforms());
+}
@@ -0,0 +1,31 @@
+use shacl::Validator;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn shapes_and_data_resolving_to_one_graph_are_rejected() {
+    // Both files declare <http://example.org/onto> as their owl:Ontology.
+    let shapes = fixture_path("graph_collision_shapes.ttl");
+    let data = fixture_path("graph_collision_data.ttl");
+
+    let err = match Validator::from_files(&shapes, &data) {
+        Ok(_) => panic!("colliding graph names should not load"),
+        Err(err) => err.to_string(),
+    };
+    assert!(
+        err.contains("both resolve to graph <http://example.org/onto>"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn the_same_file_can_be_used_for_shapes_and_data() {
+    let shapes = fixture_path("graph_collision_shapes.ttl");
+
+    let validator =
+        Validator::from_files(&shapes, &shapes).expect("a graph may be validated against itself");
+    assert!(validator.validate().conforms());
+}
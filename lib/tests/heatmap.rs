@@ -0,0 +1,85 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::{HeatEntryKind, Validator};
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn iri(value: &str) -> Term {
+    NamedNode::new_unchecked(value).into()
+}
+
+#[test]
+fn heatmap_lists_invocations_per_shape_and_component() {
+    let shapes = fixture_path("node_message_shapes.ttl");
+    let data = fixture_path("node_message_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+    let report = validator.validate();
+
+    let heatmap = report.heatmap();
+    assert!(!heatmap.is_empty());
+    assert!(
+        heatmap
+            .windows(2)
+            .all(|pair| pair[0].invocations >= pair[1].invocations),
+        "entries should be ordered by invocations: {:?}",
+        heatmap
+    );
+
+    let team = heatmap
+        .iter()
+        .find(|entry| entry.iri == iri("http://example.org/TeamShape"))
+        .expect("targeted node shape should be listed");
+    assert_eq!(team.kind, HeatEntryKind::NodeShape);
+    assert_eq!(team.invocations, 1);
+
+    let member_property = heatmap
+        .iter()
+        .find(|entry| entry.label == "<http://example.org/member>")
+        .expect("property shape should be listed under its path");
+    assert_eq!(member_property.kind, HeatEntryKind::PropertyShape);
+    assert_eq!(member_property.invocations, 1);
+
+    let node_constraint = heatmap
+        .iter()
+        .find(|entry| entry.iri == iri("http://www.w3.org/ns/shacl#NodeConstraintComponent"))
+        .expect("sh:node component should be listed");
+    assert_eq!(node_constraint.kind, HeatEntryKind::Component);
+    assert!(
+        member_property.duration >= node_constraint.duration,
+        "property shape time includes its components"
+    );
+}
+
+#[test]
+fn component_timings_are_sorted_slowest_first() {
+    let shapes = fixture_path("node_message_shapes.ttl");
+    let data = fixture_path("node_message_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+    let report = validator.validate();
+
+    let timings = report.get_component_timings();
+    assert_eq!(timings.len(), report.heatmap().len());
+    assert!(
+        timings
+            .windows(2)
+            .all(|pair| pair[0].total >= pair[1].total),
+        "timings should be ordered by total time: {:?}",
+        timings
+    );
+
+    let team = timings
+        .iter()
+        .find(|timing| timing.iri == iri("http://example.org/TeamShape"))
+        .expect("targeted node shape should be timed");
+    assert_eq!(team.kind, HeatEntryKind::NodeShape);
+    assert_eq!(team.invocations, 1);
+    assert_eq!(team.mean(), team.total);
+    assert!(
+        timings
+            .iter()
+            .filter(|timing| timing.kind == HeatEntryKind::Component)
+            .all(|timing| timing.total <= team.total),
+        "the targeted shape's time includes every component"
+    );
+}
@@ -0,0 +1,80 @@
+use oxigraph::io::RdfFormat;
+use shacl::{Source, ValidatorBuilder};
+
+const SHAPES: &str = r#"
+@prefix ex: <http://example.org/> .
+@prefix owl: <http://www.w3.org/2002/07/owl#> .
+@prefix sh: <http://www.w3.org/ns/shacl#> .
+
+<http://example.org/shapes> a owl:Ontology .
+
+ex:PersonShape
+    a sh:NodeShape ;
+    sh:targetClass ex:Person ;
+    sh:property [ sh:path ex:name ; sh:minCount 1 ] .
+"#;
+
+const DATA: &str = r#"
+@prefix ex: <http://example.org/> .
+
+ex:alice a ex:Person ; ex:name "Alice" .
+ex:bob a ex:Person .
+"#;
+
+#[test]
+fn validators_can_be_built_from_inline_turtle() {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::InlineTurtle(SHAPES.to_string()))
+        .with_data_source(Source::InlineTurtle(DATA.to_string()))
+        .build()
+        .expect("inline sources should load");
+
+    // A declared ontology names the graph; otherwise the content does.
+    assert_eq!(
+        validator.shapes_graph_iri().as_str(),
+        "http://example.org/shapes"
+    );
+    assert!(validator
+        .data_graph_iri()
+        .as_str()
+        .starts_with("urn:shacl-rs:source:"));
+
+    let report = validator.validate();
+    let focus_nodes: Vec<String> = report
+        .results()
+        .map(|result| result.focus_node.to_string())
+        .collect();
+    assert_eq!(focus_nodes, vec!["<http://example.org/bob>"]);
+}
+
+#[test]
+fn inline_turtle_and_bytes_sources_can_be_mixed() {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::Bytes {
+            data: SHAPES.as_bytes().to_vec(),
+            format: RdfFormat::Turtle,
+        })
+        .with_data_sources([
+            Source::InlineTurtle(DATA.to_string()),
+            Source::InlineTurtle(
+                "<http://example.org/bob> <http://example.org/name> \"Bob\" .".to_string(),
+            ),
+        ])
+        .build()
+        .expect("in-memory sources should load");
+    assert!(validator.validate().conforms());
+}
+
+#[test]
+fn malformed_inline_turtle_is_reported_as_a_load_error() {
+    let err = ValidatorBuilder::new()
+        .with_shapes_source(Source::InlineTurtle(SHAPES.to_string()))
+        .with_data_source(Source::InlineTurtle("ex:bob a ".to_string()))
+        .build()
+        .err()
+        .expect("malformed Turtle should fail the build");
+    let load_error = err
+        .downcast_ref::<shacl::GraphLoadError>()
+        .expect("the error should be a GraphLoadError");
+    assert_eq!(load_error.role, "data");
+}
@@ -0,0 +1,51 @@
+//! `sh:pattern` matches IRI value nodes against their string form (SPARQL `str()`), as
+//! SHACL Core specifies, so IRI naming conventions need no extension component.
+
+use oxigraph::model::{NamedNode, Term};
+use shacl::{Source, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn iri(value: &str) -> Term {
+    NamedNode::new_unchecked(value).into()
+}
+
+#[test]
+fn pattern_applies_to_iri_focus_and_value_nodes() {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("iri_pattern_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("iri_pattern_data.ttl")))
+        .build()
+        .expect("failed to load fixtures");
+    let report = validator.validate();
+
+    let mut failing: Vec<(String, String)> = report
+        .result_codes()
+        .into_iter()
+        .map(|result| (result.focus_node.to_string(), result.code.to_string()))
+        .collect();
+    failing.sort();
+    assert_eq!(
+        failing,
+        vec![
+            (
+                iri("http://example.org/Depot").to_string(),
+                "PatternViolation".to_string()
+            ),
+            (
+                iri("http://example.org/building/Annex_2").to_string(),
+                "PatternViolation".to_string()
+            ),
+            (
+                iri("http://example.org/building/Annex_2").to_string(),
+                "PatternViolation".to_string()
+            ),
+        ]
+    );
+}
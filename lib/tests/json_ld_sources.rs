@@ -0,0 +1,86 @@
+use oxigraph::io::{JsonLdProfileSet, RdfFormat};
+use oxigraph::model::{NamedNode, Term};
+use shacl::{GraphLoadError, Source, Validator, ValidatorBuilder};
+use std::error::Error;
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn json_ld() -> RdfFormat {
+    RdfFormat::JsonLd {
+        profile: JsonLdProfileSet::empty(),
+    }
+}
+
+fn build(data: Source) -> Result<Validator, Box<dyn Error>> {
+    ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("jsonld_shapes.ttl")))
+        .with_data_source(data)
+        .build()
+}
+
+fn failing_focus_nodes(validator: &Validator) -> Vec<Term> {
+    validator
+        .validate()
+        .results()
+        .map(|result| result.focus_node)
+        .collect()
+}
+
+#[test]
+fn json_ld_files_are_loaded_as_data() {
+    let validator =
+        build(Source::File(fixture_path("jsonld_data.jsonld"))).expect("JSON-LD data should load");
+    assert_eq!(
+        failing_focus_nodes(&validator),
+        vec![Term::from(NamedNode::new_unchecked(
+            "http://example.org/s2"
+        ))]
+    );
+}
+
+#[test]
+fn in_memory_sources_use_the_given_format() {
+    let data = std::fs::read(fixture_path("jsonld_data.jsonld")).expect("fixture should exist");
+    let validator = build(Source::Bytes {
+        data,
+        format: json_ld(),
+    })
+    .expect("in-memory JSON-LD should load");
+    assert_eq!(failing_focus_nodes(&validator).len(), 1);
+    assert!(validator
+        .data_graph_iri()
+        .as_str()
+        .starts_with("urn:shacl-rs:source:"));
+
+    let shapes = std::fs::read(fixture_path("jsonld_shapes.ttl")).expect("fixture should exist");
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::Bytes {
+            data: shapes,
+            format: RdfFormat::Turtle,
+        })
+        .with_data_source(Source::File(fixture_path("jsonld_data.jsonld")))
+        .build()
+        .expect("in-memory Turtle shapes should load");
+    assert_eq!(failing_focus_nodes(&validator).len(), 1);
+}
+
+#[test]
+fn malformed_in_memory_sources_report_syntax_errors() {
+    let err = build(Source::Bytes {
+        data: b"{ \"@id\": ".to_vec(),
+        format: json_ld(),
+    })
+    .err()
+    .expect("malformed JSON-LD should fail the build");
+    let load_error = err
+        .downcast_ref::<GraphLoadError>()
+        .expect("the error should be a GraphLoadError");
+    assert_eq!(load_error.role, "data");
+    assert!(!load_error.issues.is_empty());
+}
@@ -0,0 +1,36 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::{Source, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+#[test]
+fn language_tags_differing_only_in_case_are_equal() {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("language_tag_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("language_tag_data.ttl")))
+        .build()
+        .expect("failed to load fixtures");
+    let report = validator.validate();
+
+    let results = report.result_codes();
+    assert!(results.iter().all(|r| r.focus_node != ex("Mixed")));
+
+    // A different tag (not just a different case) is still a different term.
+    let mut codes: Vec<&str> = results
+        .iter()
+        .filter(|r| r.focus_node == ex("Other"))
+        .map(|r| r.code.as_str())
+        .collect();
+    codes.sort();
+    assert_eq!(codes, vec!["HasValueViolation", "InViolation"]);
+}
@@ -0,0 +1,79 @@
+use oxigraph::model::NamedNode;
+use shacl::{MessageCatalog, Source, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(format!(
+        "{}/tests/fixtures/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    ))
+}
+
+fn engine_messages(catalog: MessageCatalog) -> Vec<(String, String)> {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("shape_conformance_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("shape_conformance_data.ttl")))
+        .with_message_catalog(catalog)
+        .build()
+        .expect("failed to build validator");
+    let mut messages: Vec<(String, String)> = validator
+        .validate()
+        .results()
+        .map(|result| {
+            let component = result
+                .source_constraint_component
+                .as_ref()
+                .map(NamedNode::to_string)
+                .unwrap_or_default();
+            (component, result.engine_message)
+        })
+        .collect();
+    messages.sort();
+    messages
+}
+
+#[test]
+fn regional_locale_falls_back_to_language_catalog() {
+    let catalog = MessageCatalog::for_locale(&fixture_path("messages"), "de-CH")
+        .expect("failed to load the de catalog");
+    assert_eq!(catalog.locale(), "de");
+
+    let messages = engine_messages(catalog);
+    assert_eq!(
+        messages,
+        vec![
+            (
+                "<http://www.w3.org/ns/shacl#ClosedConstraintComponent>".to_string(),
+                "Knoten <http://example.org/kitchen> hat einen Wert für <http://example.org/area>, \
+                 den die geschlossene Shape nicht erlaubt"
+                    .to_string()
+            ),
+            (
+                "<http://www.w3.org/ns/shacl#MinCountConstraintComponent>".to_string(),
+                "Anzahl der Werte (0) unter dem Minimum 1".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn english_is_built_in() {
+    let catalog = MessageCatalog::for_locale(&fixture_path("messages"), "en-GB")
+        .expect("English needs no catalog file");
+    assert_eq!(catalog, MessageCatalog::english());
+
+    let messages = engine_messages(catalog);
+    assert!(messages
+        .iter()
+        .any(|(_, message)| message == "Value count (0) does not meet minimum requirement: 1"));
+}
+
+#[test]
+fn missing_catalogs_are_reported() {
+    let error = MessageCatalog::for_locale(&fixture_path("messages"), "fr")
+        .expect_err("there is no French catalog");
+    assert!(error
+        .to_string()
+        .contains("No message catalog for locale fr"));
+}
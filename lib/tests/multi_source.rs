@@ -0,0 +1,71 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::{Source, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+fn sources(names: &[&str]) -> Vec<Source> {
+    names
+        .iter()
+        .map(|name| Source::File(fixture_path(name)))
+        .collect()
+}
+
+#[test]
+fn shapes_and_data_from_several_files_are_merged() {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_sources(sources(&[
+            "multi_source_person_shapes.ttl",
+            "multi_source_address_shapes.ttl",
+        ]))
+        .with_data_sources(sources(&[
+            "multi_source_alice_data.ttl",
+            "multi_source_bob_data.ttl",
+        ]))
+        .build()
+        .expect("failed to load fixtures");
+
+    let report = validator.validate();
+    let focus_nodes: Vec<Term> = report.results().map(|result| result.focus_node).collect();
+    // Both files label a blank node `_:a`; had they been merged as one node, Bob's address
+    // would have Alice's city. Likewise both shapes files use `_:p` for different shapes.
+    assert_eq!(focus_nodes, vec![ex("bob")]);
+    assert!(validator.shape(&ex("AddressShape")).is_some());
+}
+
+#[test]
+fn sources_that_share_a_graph_are_rejected() {
+    let shapes = sources(&["multi_source_person_shapes.ttl"]);
+    let error = ValidatorBuilder::new()
+        .with_shapes_sources(shapes.clone())
+        .with_data_sources(sources(&[
+            "multi_source_alice_data.ttl",
+            "multi_source_person_shapes.ttl",
+        ]))
+        .build()
+        .err()
+        .expect("data that overwrites the shapes graph is rejected");
+    assert!(
+        error.to_string().contains("both resolve to graph"),
+        "{}",
+        error
+    );
+
+    // Listing the same file twice is harmless.
+    let validator = ValidatorBuilder::new()
+        .with_shapes_sources([shapes[0].clone(), shapes[0].clone()])
+        .with_data_sources(sources(&["multi_source_bob_data.ttl"]))
+        .build()
+        .expect("duplicate sources are loaded once");
+    assert!(validator.shape(&ex("PersonShape")).is_some());
+    assert!(validator.validate().conforms());
+}
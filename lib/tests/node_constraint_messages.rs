@@ -0,0 +1,32 @@
+use shacl::Validator;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn sh_node_result_message_names_inner_failing_constraint() {
+    let shapes = fixture_path("node_message_shapes.ttl");
+    let data = fixture_path("node_message_data.ttl");
+
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+    let report = validator.validate();
+    assert!(!report.conforms(), "both members violate ex:MemberShape");
+
+    let report_ttl = report
+        .to_turtle()
+        .expect("failed to serialize validation report");
+    assert!(
+        report_ttl.contains(
+            "Value <http://example.org/X> does not conform to sh:node shape <http://example.org/MemberShape>: \
+             sh:minCount on path <http://example.org/name>"
+        ),
+        "missing name should be reported through sh:node: {}",
+        report_ttl
+    );
+    assert!(
+        report_ttl.contains("sh:datatype on path <http://example.org/age> for value"),
+        "wrong datatype should be reported through sh:node: {}",
+        report_ttl
+    );
+}
@@ -0,0 +1,63 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::types::{NodeKind, Path};
+use shacl::{ValidationResult, Validator};
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn result_on(results: &[ValidationResult], property: &str) -> ValidationResult {
+    let path = Path::Simple(Term::NamedNode(NamedNode::new_unchecked(format!(
+        "http://example.org/{}",
+        property
+    ))));
+    results
+        .iter()
+        .find(|result| result.path.as_ref() == Some(&path))
+        .cloned()
+        .unwrap_or_else(|| panic!("no result on {}: {:?}", property, results))
+}
+
+#[test]
+fn node_kind_results_name_expected_and_actual_kinds() {
+    let validator = Validator::from_files(
+        &fixture_path("node_kind_shapes.ttl"),
+        &fixture_path("node_kind_data.ttl"),
+    )
+    .expect("failed to load fixtures");
+    let report = validator.validate();
+    let results: Vec<ValidationResult> = report.results().collect();
+    assert_eq!(results.len(), 2, "{:?}", results);
+
+    let knows = result_on(&results, "knows");
+    assert_eq!(knows.expected_node_kind, Some(NodeKind::BlankNodeOrIri));
+    assert_eq!(knows.actual_node_kind, Some(NodeKind::Literal));
+    assert!(
+        knows.engine_message.ends_with(
+            "is a literal, but sh:nodeKind <http://www.w3.org/ns/shacl#BlankNodeOrIRI> \
+             requires a blank node or an IRI"
+        ),
+        "{}",
+        knows.engine_message
+    );
+
+    let name = result_on(&results, "name");
+    assert_eq!(name.expected_node_kind, Some(NodeKind::Literal));
+    assert_eq!(name.actual_node_kind, Some(NodeKind::Iri));
+    assert!(
+        name.engine_message.ends_with(
+            "is an IRI, but sh:nodeKind <http://www.w3.org/ns/shacl#Literal> requires a literal"
+        ),
+        "{}",
+        name.engine_message
+    );
+}
+
+#[test]
+fn combined_node_kinds_allow_both_kinds() {
+    assert!(NodeKind::BlankNodeOrIri.allows(NodeKind::BlankNode));
+    assert!(NodeKind::BlankNodeOrIri.allows(NodeKind::Iri));
+    assert!(!NodeKind::BlankNodeOrIri.allows(NodeKind::Literal));
+    assert!(NodeKind::IriOrLiteral.allows(NodeKind::Literal));
+    assert!(!NodeKind::Iri.allows(NodeKind::BlankNode));
+}
@@ -0,0 +1,36 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::{Source, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+#[test]
+fn parameters_and_values_are_compared_in_canonical_form() {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("normalization_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("normalization_data.ttl")))
+        .build()
+        .expect("failed to load fixtures");
+    let report = validator.validate();
+    let results = report.result_codes();
+
+    // "01" and "002" denote the same integers as 1 and 2; "1.500" is the decimal 1.5.
+    assert!(results.iter().all(|r| r.focus_node != ex("Good")));
+
+    // "05" and 5 normalize to the same minimum, so only one component reports it.
+    let slow: Vec<&str> = results
+        .iter()
+        .filter(|r| r.focus_node == ex("Slow"))
+        .map(|r| r.code.as_str())
+        .collect();
+    assert_eq!(slow, vec!["MinInclusiveViolation"]);
+}
@@ -0,0 +1,51 @@
+use oxigraph::model::NamedNode;
+use shacl::{ObservedKind, Validator};
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn observed_datatypes_and_classes_are_compared_with_expectations() {
+    let shapes = fixture_path("path_stats_shapes.ttl");
+    let data = fixture_path("path_stats_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+
+    let statistics = validator
+        .path_type_statistics()
+        .expect("statistics should be computed");
+    // ex:note has no datatype/class expectation and is left out.
+    let paths: Vec<&str> = statistics.iter().map(|s| s.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "<http://example.org/sensor>",
+            "<http://example.org/timestamp>"
+        ]
+    );
+
+    let timestamp = &statistics[1];
+    assert_eq!(timestamp.value_count, 4);
+    assert_eq!(
+        timestamp.expected_datatypes,
+        vec![NamedNode::new_unchecked(
+            "http://www.w3.org/2001/XMLSchema#dateTime"
+        )]
+    );
+    let string = ObservedKind::Datatype(NamedNode::new_unchecked(
+        "http://www.w3.org/2001/XMLSchema#string",
+    ));
+    assert_eq!(timestamp.observed[0].kind, string);
+    assert_eq!(timestamp.observed[0].count, 3);
+    assert!((timestamp.share(&string) - 0.75).abs() < f64::EPSILON);
+
+    let sensor = &statistics[0];
+    assert_eq!(sensor.value_count, 3);
+    let sensor_class = ObservedKind::Class(NamedNode::new_unchecked("http://example.org/Sensor"));
+    assert_eq!(sensor.observed[0].kind, sensor_class);
+    assert_eq!(sensor.observed[0].count, 2);
+    assert!(sensor
+        .observed
+        .iter()
+        .any(|observed| observed.kind == ObservedKind::Untyped && observed.count == 1));
+}
@@ -0,0 +1,149 @@
+use oxigraph::model::{GraphName, Literal, NamedNode, Quad, Term};
+use shacl::{QuadChange, Source, ValidationOptions, Validator, ValidatorBuilder};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn cache_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "shacl-rs-persistent-cache-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+fn validator(data: Source) -> Validator {
+    ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("persistent_cache_shapes.ttl")))
+        .with_data_source(data)
+        .build()
+        .expect("failed to load fixtures")
+}
+
+fn focus_nodes(validator: &Validator, options: &ValidationOptions) -> HashSet<Term> {
+    validator
+        .validate_with_options(options)
+        .expect("validation failed")
+        .results()
+        .map(|result| result.focus_node)
+        .collect()
+}
+
+#[test]
+fn conforming_nodes_are_cached_across_validators() {
+    let path = cache_path("entries");
+    let options = ValidationOptions::new().with_persistent_cache(&path);
+    let data = Source::File(fixture_path("persistent_cache_data.ttl"));
+
+    let first = focus_nodes(&validator(data.clone()), &options);
+    assert_eq!(first, HashSet::from([ex("carol")]));
+    // Alice and Bob conform to ex:PersonShape; ex:EmployeeShape is not cacheable.
+    let entries = std::fs::read_to_string(&path).expect("cache should be written");
+    assert_eq!(entries.lines().count(), 2, "{}", entries);
+
+    // A fresh validator over the same files reuses the entries and reports the same.
+    let second = focus_nodes(&validator(data), &options);
+    assert_eq!(second, first);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), entries);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn changed_nodes_are_validated_again() {
+    let path = cache_path("changes");
+    let options = ValidationOptions::new().with_persistent_cache(&path);
+    focus_nodes(
+        &validator(Source::File(fixture_path("persistent_cache_data.ttl"))),
+        &options,
+    );
+
+    // Bob's name is now a number; the cached entry no longer matches his triples.
+    let changed = r#"
+        @prefix ex: <http://example.org/> .
+        ex:alice a ex:Person ; ex:name "Alice" ; ex:employer ex:acme .
+        ex:bob a ex:Person ; ex:name 42 .
+        ex:carol a ex:Person .
+        ex:acme a ex:Company .
+    "#;
+    let results = focus_nodes(
+        &validator(Source::InlineTurtle(changed.to_string())),
+        &options,
+    );
+    assert_eq!(results, HashSet::from([ex("bob"), ex("carol")]));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn partial_runs_keep_entries_they_did_not_revisit() {
+    let path = cache_path("partial");
+    let options = ValidationOptions::new().with_persistent_cache(&path);
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("persistent_cache_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("persistent_cache_data.ttl")))
+        .with_validation_options(options.clone())
+        .build()
+        .expect("failed to load fixtures");
+    validator.validate();
+    let entries = std::fs::read_to_string(&path).expect("cache should be written");
+    assert_eq!(entries.lines().count(), 2, "{}", entries);
+
+    // Giving Carol a name only re-validates her; Alice's and Bob's entries survive.
+    let name = Quad::new(
+        NamedNode::new_unchecked("http://example.org/carol"),
+        NamedNode::new_unchecked("http://example.org/name"),
+        Literal::new_simple_literal("Carol"),
+        GraphName::DefaultGraph,
+    );
+    validator
+        .revalidate(&[QuadChange::Added(name)])
+        .expect("revalidate");
+    let after = std::fs::read_to_string(&path).expect("cache should be written");
+    for entry in entries.lines() {
+        assert!(after.lines().any(|line| line == entry), "{} lost", entry);
+    }
+    assert_eq!(after.lines().count(), 3, "{}", after);
+
+    validator
+        .validate_node(&ex("alice"))
+        .expect("validate node");
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), after);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn unreadable_caches_are_reported_as_errors() {
+    // A directory cannot be read as a cache file.
+    let path = std::env::temp_dir();
+    let options = ValidationOptions::new().with_persistent_cache(&path);
+    let data = Source::File(fixture_path("persistent_cache_data.ttl"));
+
+    let err = validator(data.clone())
+        .validate_with_options(&options)
+        .err()
+        .expect("the cache cannot be read");
+    assert!(err.contains("Failed to read conformance cache"), "{}", err);
+
+    let build = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("persistent_cache_shapes.ttl")))
+        .with_data_source(data)
+        .with_validation_options(options)
+        .build();
+    let err = build.err().expect("build should check the cache");
+    assert!(
+        err.to_string().contains("Failed to read conformance cache"),
+        "{}",
+        err
+    );
+}
@@ -0,0 +1,52 @@
+use shacl::{PrefixMap, Source, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+#[test]
+fn declared_prefixes_shorten_iris_in_messages_and_graphviz() {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("prefix_map_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("prefix_map_data.ttl")))
+        .build()
+        .expect("failed to load fixtures");
+
+    let prefixes = validator.prefix_map();
+    assert_eq!(prefixes.namespace("ex"), Some("http://example.org/people#"));
+    assert_eq!(
+        prefixes.namespace("sh"),
+        Some("http://www.w3.org/ns/shacl#")
+    );
+    assert_eq!(
+        prefixes
+            .shorten("http://example.org/people#Alice")
+            .as_deref(),
+        Some("ex:Alice")
+    );
+
+    let report = validator.validate();
+    let messages: Vec<String> = report.results().map(|r| r.engine_message).collect();
+    assert_eq!(messages, vec!["ex:Alice has no ex:name".to_string()]);
+
+    let dot = validator.to_graphviz().expect("graphviz");
+    assert!(dot.contains("ex:PersonShape"), "{}", dot);
+}
+
+#[test]
+fn prefix_maps_can_be_built_by_hand() {
+    let mut prefixes = PrefixMap::new();
+    assert!(prefixes.is_empty());
+    prefixes.insert("brick", "https://brickschema.org/schema/Brick#");
+    assert_eq!(
+        prefixes
+            .expand("brick:Air_Temperature_Sensor")
+            .map(|iri| iri.into_string()),
+        Some("https://brickschema.org/schema/Brick#Air_Temperature_Sensor".to_string())
+    );
+    assert_eq!(prefixes.iter().count(), 1);
+}
@@ -0,0 +1,59 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::model::components::ComponentDescriptor;
+use shacl::Validator;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn iri(value: &str) -> Term {
+    NamedNode::new_unchecked(value).into()
+}
+
+#[test]
+fn property_shapes_are_evaluated_in_sh_order() {
+    let validator = Validator::from_files(
+        &fixture_path("property_order_shapes.ttl"),
+        &fixture_path("property_order_data.ttl"),
+    )
+    .expect("failed to load fixtures");
+    let person = validator
+        .shape(&iri("http://example.org/PersonShape"))
+        .expect("PersonShape should be parsed");
+
+    let properties: Vec<(Term, Option<f64>)> = person
+        .constraints()
+        .filter_map(|(_, descriptor)| match descriptor {
+            ComponentDescriptor::Property { shape } => person.property_shape(shape),
+            _ => None,
+        })
+        .map(|property| (property.term(), property.order()))
+        .collect();
+    assert_eq!(
+        properties,
+        vec![
+            (iri("http://example.org/NameProperty"), Some(1.0)),
+            (iri("http://example.org/BirthDateProperty"), Some(2.5)),
+            (iri("http://example.org/EmailProperty"), Some(10.0)),
+            (iri("http://example.org/NicknameProperty"), None),
+        ]
+    );
+
+    let report = validator.validate();
+    assert!(!report.conforms());
+}
+
+#[test]
+fn non_numeric_order_is_rejected() {
+    let err = Validator::from_files(
+        &fixture_path("property_order_invalid_shapes.ttl"),
+        &fixture_path("property_order_data.ttl"),
+    )
+    .err()
+    .expect("a non-numeric sh:order should fail to parse");
+    assert!(
+        err.to_string().contains("sh:order first"),
+        "unexpected error: {}",
+        err
+    );
+}
@@ -0,0 +1,36 @@
+use shacl::Validator;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn property_shapes_with_their_own_targets_are_validated_standalone() {
+    let shapes = fixture_path("standalone_property_shapes.ttl");
+    let data = fixture_path("standalone_property_data.ttl");
+
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+    let report = validator.validate();
+    assert!(!report.conforms());
+
+    let report_ttl = report
+        .to_turtle()
+        .expect("failed to serialize validation report");
+    // ex:NameShape is typed sh:PropertyShape and targets subjects of ex:name.
+    assert!(
+        report_ttl.contains("sh:focusNode <http://example.org/A>"),
+        "typed property shape should apply its sh:targetSubjectsOf: {}",
+        report_ttl
+    );
+    // ex:ManagerShape is untyped; its sh:path and sh:targetObjectsOf make it a property shape.
+    assert!(
+        report_ttl.contains("sh:focusNode <http://example.org/C>"),
+        "untyped property shape should apply its sh:targetObjectsOf: {}",
+        report_ttl
+    );
+    assert!(
+        !report_ttl.contains("sh:focusNode <http://example.org/B>"),
+        "ex:B has exactly one name: {}",
+        report_ttl
+    );
+}
@@ -0,0 +1,57 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::types::QueryDataset;
+use shacl::{Source, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+/// Returns the focus nodes of the results produced by `shape`, sorted.
+fn focus_nodes_of(dataset: QueryDataset, shape: &str) -> Vec<String> {
+    let shape: Term = NamedNode::new_unchecked(shape).into();
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("query_dataset_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("query_dataset_data.ttl")))
+        .with_query_dataset(dataset)
+        .build()
+        .expect("validator should build");
+    let report = validator.validate();
+    let mut nodes: Vec<String> = report
+        .results()
+        .filter(|result| result.source_shape.as_ref() == Some(&shape))
+        .map(|result| result.focus_node.to_string())
+        .collect();
+    nodes.sort();
+    nodes
+}
+
+#[test]
+fn sparql_constraints_do_not_see_the_shapes_graph_by_default() {
+    // ex:alice is only labelled in the shapes graph, which the query must not match.
+    assert_eq!(
+        focus_nodes_of(QueryDataset::DataGraph, "http://example.org/LabelShape"),
+        vec!["<http://example.org/alice>", "<http://example.org/bob>"]
+    );
+}
+
+#[test]
+fn union_default_graph_is_an_explicit_opt_in() {
+    assert_eq!(
+        focus_nodes_of(QueryDataset::Union, "http://example.org/LabelShape"),
+        vec!["<http://example.org/bob>"]
+    );
+}
+
+#[test]
+fn shapes_graph_stays_reachable_through_graph_patterns() {
+    for dataset in [QueryDataset::DataGraph, QueryDataset::Union] {
+        assert_eq!(
+            focus_nodes_of(dataset, "http://example.org/MaxAgeShape"),
+            vec!["<http://example.org/bob>"]
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use shacl::{FetchPolicy, Source, ValidatorBuilder};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+const DATA: &str = "@prefix ex: <http://example.org/> .\nex:bob a ex:Person .\n";
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("shacl-rs-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+/// Serves one canned response per connection, reporting each request's header lines, then
+/// closes the listener.
+fn serve(responses: Vec<String>) -> (String, mpsc::Receiver<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+    let url = format!("http://{}/people", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for response in responses {
+            let (mut stream, _) = listener.accept().expect("failed to accept");
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                headers.push(line.trim().to_ascii_lowercase());
+            }
+            sender.send(headers).unwrap();
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (url, receiver)
+}
+
+fn ok_response() -> String {
+    format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/turtle\r\nETag: \"v1\"\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        DATA.len(),
+        DATA
+    )
+}
+
+fn status_response(status: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status
+    )
+}
+
+fn build(url: &str, policy: FetchPolicy) -> Result<shacl::Validator, Box<dyn std::error::Error>> {
+    ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("multi_source_person_shapes.ttl")))
+        .with_data_source(Source::Graph(url.to_string()))
+        .with_fetch_policy(policy)
+        .build()
+}
+
+fn quick_policy() -> FetchPolicy {
+    FetchPolicy::new()
+        .with_max_attempts(3)
+        .with_backoff(Duration::from_millis(1), Duration::from_millis(5))
+        .with_timeout(Duration::from_secs(5))
+}
+
+#[test]
+fn failed_fetches_are_retried_then_served_from_the_cache() {
+    let cache = scratch_dir("fetch-cache");
+    let policy = quick_policy().with_cache_dir(&cache);
+
+    let (url, requests) = serve(vec![
+        status_response("503 Service Unavailable"),
+        ok_response(),
+    ]);
+    let validator = build(&url, policy.clone()).expect("the second attempt succeeds");
+    assert_eq!(validator.data_graph_iri().as_str(), url);
+    // Bob has no address.
+    assert!(!validator.validate().conforms());
+    assert_eq!(
+        requests.iter().take(2).count(),
+        2,
+        "one failed and one successful request"
+    );
+
+    let _ = std::fs::remove_dir_all(&cache);
+}
+
+#[test]
+fn cached_copies_are_revalidated_and_used_when_the_server_is_down() {
+    let cache = scratch_dir("fetch-revalidate");
+    let policy = quick_policy().with_cache_dir(&cache);
+    let (url, requests) = serve(vec![ok_response(), status_response("304 Not Modified")]);
+
+    build(&url, policy.clone()).expect("the first fetch succeeds");
+    requests.recv().unwrap();
+
+    let validator = build(&url, policy.clone()).expect("a 304 reuses the cached copy");
+    let headers = requests.recv().unwrap();
+    assert!(
+        headers.contains(&"if-none-match: \"v1\"".to_string()),
+        "{:?}",
+        headers
+    );
+    assert!(!validator.validate().conforms());
+
+    // The server has answered both requests and closed its listener, so every attempt fails and the cached copy is used.
+    let validator = build(&url, policy).expect("falls back to the cached copy");
+    assert_eq!(validator.data_graph_iri().as_str(), url);
+    assert!(!validator.validate().conforms());
+
+    let _ = std::fs::remove_dir_all(&cache);
+}
+
+#[test]
+fn without_a_cache_the_last_error_is_reported() {
+    let (url, _requests) = serve(vec![
+        status_response("503 Service Unavailable"),
+        status_response("503 Service Unavailable"),
+    ]);
+    let error = build(&url, quick_policy().with_max_attempts(2))
+        .err()
+        .expect("both attempts fail");
+    assert!(
+        error.to_string().contains("after 2 attempt(s)"),
+        "{}",
+        error
+    );
+    assert!(error.to_string().contains("503"), "{}", error);
+
+    let (url, _requests) = serve(vec![status_response("404 Not Found")]);
+    let error = build(&url, quick_policy())
+        .err()
+        .expect("client errors are not retried");
+    assert!(error.to_string().contains("404"), "{}", error);
+}
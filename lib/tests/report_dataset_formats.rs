@@ -0,0 +1,71 @@
+use oxigraph::io::{RdfFormat, RdfParser};
+use oxigraph::model::{GraphName, NamedNode, Quad};
+use shacl::{Source, ValidatorBuilder, DEFAULT_REPORT_GRAPH};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn build() -> shacl::Validator {
+    ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("validate_each_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("validate_each_data.ttl")))
+        .build()
+        .expect("failed to load fixtures")
+}
+
+fn parse_quads(serialized: &str, format: RdfFormat) -> Vec<Quad> {
+    RdfParser::from_format(format)
+        .for_slice(serialized.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .expect("report should parse as a dataset")
+}
+
+#[test]
+fn dataset_formats_place_report_in_default_graph() {
+    let validator = build();
+    let report = validator.validate();
+    let triple_count = report.to_graph().len();
+    let expected = GraphName::NamedNode(NamedNode::new_unchecked(DEFAULT_REPORT_GRAPH));
+
+    for format in [RdfFormat::TriG, RdfFormat::NQuads] {
+        let quads = parse_quads(&report.to_rdf(format).expect("serialize"), format);
+        assert_eq!(quads.len(), triple_count, "{format}");
+        assert!(
+            quads.iter().all(|quad| quad.graph_name == expected),
+            "{format} output should only use the default report graph"
+        );
+    }
+}
+
+#[test]
+fn report_graph_is_configurable() {
+    let validator = build();
+    let graph = NamedNode::new_unchecked("http://example.org/reports/run-1");
+    let report = validator.validate().with_report_graph(graph.clone());
+    assert_eq!(report.report_graph(), graph);
+
+    let quads = parse_quads(
+        &report.to_rdf(RdfFormat::NQuads).expect("serialize"),
+        RdfFormat::NQuads,
+    );
+    assert!(!quads.is_empty());
+    assert!(quads
+        .iter()
+        .all(|quad| quad.graph_name == GraphName::NamedNode(graph.clone())));
+    assert_eq!(report.to_quads().len(), quads.len());
+}
+
+#[test]
+fn triple_formats_are_unchanged() {
+    let validator = build();
+    let report = validator
+        .validate()
+        .with_report_graph(NamedNode::new_unchecked("http://example.org/ignored"));
+    let turtle = report.to_rdf(RdfFormat::Turtle).expect("serialize");
+    assert!(!turtle.contains("http://example.org/ignored"));
+}
@@ -0,0 +1,78 @@
+use oxigraph::model::Term;
+use shacl::{ValidationResult, Validator};
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn validator() -> Validator {
+    Validator::from_files(
+        &fixture_path("deskolemization_shapes.ttl"),
+        &fixture_path("deskolemization_data.ttl"),
+    )
+    .expect("failed to load fixtures")
+}
+
+fn is_skolem_iri(term: &Term) -> bool {
+    matches!(term, Term::NamedNode(node) if node.as_str().contains("/.well-known/skolem/"))
+}
+
+#[test]
+fn reports_show_blank_nodes_instead_of_skolem_iris() {
+    let validator = validator();
+    let report = validator.validate();
+    let results: Vec<ValidationResult> = report.results().collect();
+    assert_eq!(results.len(), 2, "{:?}", results);
+
+    // The child is the focus node of the sh:minLength result and the value of the
+    // sh:nodeKind result; both show the same blank node.
+    let focus_node = results
+        .iter()
+        .map(|result| &result.focus_node)
+        .find(|term| matches!(term, Term::BlankNode(_)))
+        .expect("the child should be a blank focus node");
+    assert!(results
+        .iter()
+        .any(|result| result.value.as_ref() == Some(focus_node)));
+
+    let turtle = report.to_turtle().unwrap();
+    assert!(!turtle.contains(".well-known/skolem"), "{}", turtle);
+
+    // Blank focus nodes from the data graph still have context.
+    let result = results
+        .iter()
+        .find(|result| &result.focus_node == focus_node)
+        .unwrap();
+    let context = report.focus_node_context(result, 5).unwrap();
+    assert_eq!(context.types.len(), 1, "{:?}", context);
+}
+
+#[test]
+fn skolem_iris_can_be_kept_for_debugging() {
+    let validator = validator();
+    let report = validator.validate().with_skolem_iris(true);
+    let results: Vec<ValidationResult> = report.results().collect();
+    assert!(results
+        .iter()
+        .any(|result| is_skolem_iri(&result.focus_node)));
+
+    let skolem_iri = results
+        .iter()
+        .find(|result| is_skolem_iri(&result.focus_node))
+        .map(|result| result.focus_node.to_string())
+        .unwrap();
+    let turtle = report.to_turtle().unwrap();
+    assert!(turtle.contains(&skolem_iri), "{}", turtle);
+
+    // The blank node shown by default is labelled after its skolem IRI.
+    let label = skolem_iri
+        .trim_end_matches('>')
+        .rsplit('/')
+        .next()
+        .unwrap()
+        .to_string();
+    let deskolemized: Vec<ValidationResult> = validator.validate().results().collect();
+    assert!(deskolemized
+        .iter()
+        .any(|result| result.focus_node.to_string() == format!("_:{}", label)));
+}
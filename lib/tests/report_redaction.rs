@@ -0,0 +1,78 @@
+use oxigraph::model::{Literal, NamedNode, Term};
+use shacl::{Redaction, ValidationResult, Validator};
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn validator() -> Validator {
+    Validator::from_files(
+        &fixture_path("redaction_shapes.ttl"),
+        &fixture_path("redaction_data.ttl"),
+    )
+    .expect("failed to load fixtures")
+}
+
+#[test]
+fn masked_reports_hide_values_and_keep_structure() {
+    let validator = validator();
+    let report = validator.validate().with_redaction(Redaction::mask());
+    let results: Vec<ValidationResult> = report.results().collect();
+    assert_eq!(results.len(), 2, "{:?}", results);
+
+    let alice: Term = NamedNode::new_unchecked("http://example.org/alice").into();
+    for result in &results {
+        assert_eq!(result.focus_node, alice);
+        assert!(!result.engine_message.contains("private"), "{:?}", result);
+    }
+    let values: Vec<Term> = results.iter().filter_map(|r| r.value.clone()).collect();
+    assert!(values.contains(&Literal::new_simple_literal("[redacted]").into()));
+    assert!(
+        values.contains(&Literal::new_language_tagged_literal_unchecked("[redacted]", "en").into())
+    );
+    assert!(results.iter().any(|result| result.messages
+        == vec![Term::from(Literal::new_simple_literal(
+            "Nickname [redacted] is not allowed"
+        ))]));
+
+    let turtle = report.to_turtle().unwrap();
+    assert!(
+        !turtle.contains("private") && !turtle.contains("the-great"),
+        "{}",
+        turtle
+    );
+    assert!(turtle.contains("example.org/alice"), "{}", turtle);
+    assert!(!report.conforms());
+}
+
+#[test]
+fn hashed_reports_are_stable_and_can_hide_iris() {
+    let validator = validator();
+    let redaction = Redaction::hash().with_iris(true).with_salt("team");
+    let first: Vec<ValidationResult> = validator
+        .validate()
+        .with_redaction(redaction.clone())
+        .results()
+        .collect();
+    let second: Vec<ValidationResult> = validator
+        .validate()
+        .with_redaction(redaction)
+        .results()
+        .collect();
+    assert_eq!(first, second);
+
+    let focus_node = first[0].focus_node.to_string();
+    assert!(
+        focus_node.starts_with("<urn:shacl-rs:redacted:"),
+        "{}",
+        focus_node
+    );
+    for result in &first {
+        let value = result
+            .value
+            .as_ref()
+            .expect("results have values")
+            .to_string();
+        assert!(value.contains("sha256:"), "{}", value);
+    }
+}
@@ -0,0 +1,86 @@
+use oxigraph::io::{RdfFormat, RdfParser};
+use oxigraph::model::{Graph, NamedNode, Term};
+use shacl::{ReportChecker, Validator};
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn iri(value: &str) -> Term {
+    NamedNode::new_unchecked(value).into()
+}
+
+fn parse_turtle(turtle: &str) -> Graph {
+    let mut graph = Graph::new();
+    for triple in RdfParser::from_format(RdfFormat::Turtle).for_slice(turtle.as_bytes()) {
+        graph.insert(triple.expect("fixture should be valid Turtle").as_ref());
+    }
+    graph
+}
+
+#[test]
+fn emitted_reports_pass_the_self_check() {
+    let checker = ReportChecker::new().expect("report shapes should compile");
+    for (shapes, data) in [
+        ("node_message_shapes.ttl", "node_message_data.ttl"),
+        ("reachability_shapes.ttl", "reachability_data.ttl"),
+    ] {
+        let validator = Validator::from_files(&fixture_path(shapes), &fixture_path(data))
+            .expect("failed to load fixtures");
+        let report = validator.validate();
+        let issues = checker
+            .check(&report.to_graph())
+            .expect("self-check should run");
+        assert!(
+            issues.is_empty(),
+            "{} produced a malformed report: {:?}",
+            shapes,
+            issues
+        );
+    }
+
+    let validator = Validator::from_files(
+        &fixture_path("node_message_shapes.ttl"),
+        &fixture_path("node_message_data.ttl"),
+    )
+    .expect("failed to load fixtures");
+    assert!(!validator.validate().conforms());
+    assert!(validator.validate().self_check().unwrap().is_empty());
+}
+
+#[test]
+fn malformed_reports_are_flagged() {
+    let report = parse_turtle(
+        r#"
+        @prefix sh: <http://www.w3.org/ns/shacl#> .
+        @prefix ex: <http://example.org/> .
+        ex:report a sh:ValidationReport ;
+            sh:conforms true ;
+            sh:result ex:result .
+        ex:result a sh:ValidationResult ;
+            sh:focusNode ex:a, ex:b ;
+            sh:resultSeverity sh:Violation .
+        "#,
+    );
+    let checker = ReportChecker::new().expect("report shapes should compile");
+    let issues = checker.check(&report).expect("self-check should run");
+
+    let components: Vec<&Term> = issues.iter().map(|issue| &issue.component).collect();
+    assert!(
+        components.contains(&&iri("http://www.w3.org/ns/shacl#OrConstraintComponent")),
+        "conforms true with results should be flagged: {:?}",
+        issues
+    );
+    let result_issues: Vec<_> = issues
+        .iter()
+        .filter(|issue| issue.focus_node == iri("http://example.org/result"))
+        .collect();
+    assert!(result_issues.iter().any(|issue| {
+        issue.path == Some(iri("http://www.w3.org/ns/shacl#focusNode"))
+            && issue.component == iri("http://www.w3.org/ns/shacl#MaxCountConstraintComponent")
+    }));
+    assert!(result_issues.iter().any(|issue| {
+        issue.path == Some(iri("http://www.w3.org/ns/shacl#sourceConstraintComponent"))
+            && issue.component == iri("http://www.w3.org/ns/shacl#MinCountConstraintComponent")
+    }));
+}
@@ -0,0 +1,84 @@
+use oxigraph::model::{GraphNameRef, NamedNode};
+use oxigraph::sparql::{QueryResults, SparqlEvaluator};
+use oxigraph::store::Store;
+use shacl::{Source, ValidatorBuilder, DEFAULT_REPORT_GRAPH};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn build() -> shacl::Validator {
+    ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("validate_each_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("validate_each_data.ttl")))
+        .build()
+        .expect("failed to load fixtures")
+}
+
+fn graph_len(store: &Store, graph: &NamedNode) -> usize {
+    store
+        .quads_for_pattern(
+            None,
+            None,
+            None,
+            Some(GraphNameRef::NamedNode(graph.as_ref())),
+        )
+        .count()
+}
+
+#[test]
+fn insert_into_caller_store_uses_timestamped_graph() {
+    let validator = build();
+    let report = validator.validate();
+    let store = Store::new().expect("store");
+
+    let graph = report.insert_into(&store).expect("insert report");
+    assert!(graph
+        .as_str()
+        .starts_with(&format!("{}/", DEFAULT_REPORT_GRAPH)));
+    // Every report triple plus the prov:generatedAtTime stamp.
+    assert_eq!(graph_len(&store, &graph), report.to_graph().len() + 1);
+
+    let query = "PREFIX sh: <http://www.w3.org/ns/shacl#>
+        PREFIX prov: <http://www.w3.org/ns/prov#>
+        SELECT ?g ?time WHERE { GRAPH ?g { ?r a sh:ValidationReport ; prov:generatedAtTime ?time } }";
+    let QueryResults::Solutions(solutions) = SparqlEvaluator::new()
+        .parse_query(query)
+        .expect("query")
+        .on_store(&store)
+        .execute()
+        .expect("execute")
+    else {
+        panic!("expected solutions");
+    };
+    let rows: Vec<_> = solutions.collect::<Result<_, _>>().expect("rows");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get("g"), Some(&graph.clone().into()));
+}
+
+#[test]
+fn insert_into_validator_store_keeps_history_and_results() {
+    let validator = build();
+    let base = NamedNode::new_unchecked("http://example.org/reports");
+    let before = validator.validate().to_graph().len();
+
+    let first = validator
+        .validate()
+        .with_report_graph(base.clone())
+        .insert_into_store()
+        .expect("insert first report");
+    let second = validator
+        .validate()
+        .with_report_graph(base)
+        .insert_into_store()
+        .expect("insert second report");
+
+    assert_ne!(first, second, "each insertion gets its own graph");
+    assert!(first.as_str().starts_with("http://example.org/reports/"));
+    // Stored reports live in their own graphs and do not leak into later validation runs.
+    assert_eq!(validator.validate().to_graph().len(), before);
+}
@@ -0,0 +1,90 @@
+use oxigraph::model::{Graph, Literal, NamedNode, NamedNodeRef, Term};
+use shacl::{
+    Source, ValidatorBuilder, AGGREGATED_RESULTS_COMPONENT, RESULT_COUNT, VIOLATED_COMPONENT,
+};
+use std::path::PathBuf;
+
+const SH: &str = "http://www.w3.org/ns/shacl#";
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn build() -> shacl::Validator {
+    ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("aggregation_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("aggregation_data.ttl")))
+        .build()
+        .expect("failed to load fixtures")
+}
+
+fn sh(local: &str) -> NamedNode {
+    NamedNode::new_unchecked(format!("{}{}", SH, local))
+}
+
+fn results_for<'a>(graph: &'a Graph, focus: &str) -> Vec<oxigraph::model::NamedOrBlankNodeRef<'a>> {
+    let focus = NamedNodeRef::new_unchecked(focus);
+    graph
+        .subjects_for_predicate_object(&sh("focusNode"), focus)
+        .collect()
+}
+
+#[test]
+fn focus_nodes_over_threshold_collapse_into_one_result() {
+    let validator = build();
+    let unaggregated = validator.validate();
+    let plain = unaggregated.to_graph();
+    assert_eq!(results_for(&plain, "http://example.org/Garbage").len(), 5);
+
+    let report = validator.validate().with_focus_node_aggregation(2);
+    assert!(!report.conforms());
+    let graph = report.to_graph();
+
+    let garbage = results_for(&graph, "http://example.org/Garbage");
+    assert_eq!(garbage.len(), 1);
+    let result = garbage[0];
+    assert_eq!(
+        graph.object_for_subject_predicate(result, &sh("sourceConstraintComponent")),
+        Some(NamedNodeRef::new_unchecked(AGGREGATED_RESULTS_COMPONENT).into())
+    );
+    assert_eq!(
+        graph.object_for_subject_predicate(result, &sh("resultSeverity")),
+        Some(sh("Violation").as_ref().into())
+    );
+    assert_eq!(
+        graph
+            .object_for_subject_predicate(result, NamedNodeRef::new_unchecked(RESULT_COUNT))
+            .map(|term| term.into_owned()),
+        Some(Term::from(Literal::from(5_i64)))
+    );
+    let mut components: Vec<String> = graph
+        .objects_for_subject_predicate(result, NamedNodeRef::new_unchecked(VIOLATED_COMPONENT))
+        .map(|term| term.to_string())
+        .collect();
+    components.sort();
+    assert_eq!(
+        components,
+        vec![
+            format!("<{}DatatypeConstraintComponent>", SH),
+            format!("<{}MaxCountConstraintComponent>", SH),
+            format!("<{}MinCountConstraintComponent>", SH),
+        ]
+    );
+
+    // Focus nodes at or under the threshold keep their individual results.
+    assert_eq!(
+        results_for(&graph, "http://example.org/Thermometer").len(),
+        1
+    );
+}
+
+#[test]
+fn aggregated_report_passes_self_check() {
+    let validator = build();
+    let report = validator.validate().with_focus_node_aggregation(1);
+    let issues = report.self_check().expect("self-check runs");
+    assert!(issues.is_empty(), "unexpected issues: {:?}", issues);
+}
@@ -0,0 +1,103 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::types::Path;
+use shacl::{ValidationOptions, ValidationResult, Validator};
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn ex(local: &str) -> NamedNode {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local))
+}
+
+fn sh(local: &str) -> NamedNode {
+    NamedNode::new_unchecked(format!("http://www.w3.org/ns/shacl#{}", local))
+}
+
+fn result_for(results: &[ValidationResult], focus_node: &str) -> ValidationResult {
+    results
+        .iter()
+        .find(|result| result.focus_node == Term::from(ex(focus_node)))
+        .cloned()
+        .unwrap_or_else(|| panic!("no result for {}: {:?}", focus_node, results))
+}
+
+fn validator() -> Validator {
+    Validator::from_files(
+        &fixture_path("result_details_shapes.ttl"),
+        &fixture_path("result_details_data.ttl"),
+    )
+    .expect("failed to load fixtures")
+}
+
+#[test]
+fn details_are_off_by_default() {
+    let validator = validator();
+    let report = validator.validate();
+    let results: Vec<ValidationResult> = report.results().collect();
+    assert_eq!(results.len(), 2, "{:?}", results);
+    assert!(results.iter().all(|result| result.details.is_empty()));
+    assert!(!report.to_turtle().unwrap().contains("sh:detail"));
+}
+
+#[test]
+fn node_constraint_failure_explains_the_nested_failure() {
+    let validator = validator();
+    let options = ValidationOptions::new().with_result_details(true);
+    let report = validator
+        .validate_with_options(&options)
+        .expect("validation failed");
+    let results: Vec<ValidationResult> = report.results().collect();
+
+    let team = result_for(&results, "red");
+    assert_eq!(
+        team.source_constraint_component,
+        Some(sh("NodeConstraintComponent"))
+    );
+    assert_eq!(team.details.len(), 1, "{:?}", team.details);
+    let detail = &team.details[0];
+    assert_eq!(detail.focus_node, Term::from(ex("ann")));
+    assert_eq!(detail.path, Some(Path::Simple(ex("name").into())));
+    assert_eq!(
+        detail.source_constraint_component,
+        Some(sh("MinCountConstraintComponent"))
+    );
+}
+
+#[test]
+fn or_constraint_failure_explains_every_member() {
+    let validator = validator();
+    let options = ValidationOptions::new().with_result_details(true);
+    let report = validator
+        .validate_with_options(&options)
+        .expect("validation failed");
+    let results: Vec<ValidationResult> = report.results().collect();
+
+    let contact = result_for(&results, "bob");
+    assert_eq!(
+        contact.source_constraint_component,
+        Some(sh("OrConstraintComponent"))
+    );
+    let paths: Vec<Option<Path>> = contact
+        .details
+        .iter()
+        .map(|detail| detail.path.clone())
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            Some(Path::Simple(ex("email").into())),
+            Some(Path::Simple(ex("phone").into())),
+        ]
+    );
+
+    let graph = report.to_graph();
+    let detail = sh("detail");
+    assert_eq!(
+        graph
+            .iter()
+            .filter(|triple| triple.predicate == detail.as_ref())
+            .count(),
+        3
+    );
+}
@@ -0,0 +1,96 @@
+use shacl::{Source, Validator, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn build() -> Validator {
+    ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("result_ordering_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("result_ordering_data.ttl")))
+        .build()
+        .expect("failed to load fixtures")
+}
+
+#[test]
+fn results_are_sorted_by_focus_node_then_path() {
+    let validator = build();
+    let report = validator.validate();
+    assert!(!report.conforms());
+
+    let order: Vec<(String, String)> = report
+        .results()
+        .map(|result| {
+            let path = result
+                .path
+                .map(|path| path.to_sparql_path().expect("paths are IRIs"))
+                .unwrap_or_default();
+            (result.focus_node.to_string(), path)
+        })
+        .collect();
+    let mut expected = Vec::new();
+    for focus in ["a", "b", "c"] {
+        for path in [
+            "(<http://example.org/address> / <http://example.org/city>)",
+            "<http://example.org/name>",
+        ] {
+            expected.push((format!("<http://example.org/{}>", focus), path.to_string()));
+        }
+    }
+    assert_eq!(order, expected);
+}
+
+#[test]
+fn identical_results_are_reported_once() {
+    let validator = build();
+    let report = validator.validate();
+    let name_results = report
+        .results()
+        .filter(|result| result.focus_node.to_string() == "<http://example.org/a>")
+        .filter(|result| {
+            result.source_shape.as_ref().map(|shape| shape.to_string())
+                == Some("<http://example.org/NameProperty>".to_string())
+        })
+        .count();
+    assert_eq!(name_results, 1);
+    assert_eq!(report.result_codes().len(), 6);
+}
+
+#[test]
+fn serializations_are_identical_across_runs() {
+    let first = build().validate().to_turtle().expect("failed to serialize");
+    let second = build().validate().to_turtle().expect("failed to serialize");
+    assert_eq!(first, second);
+}
+
+#[test]
+fn seeded_blank_node_labels_are_stable_and_distinct_per_seed() {
+    let validator = build();
+    let first = validator
+        .validate()
+        .with_blank_node_seed(7)
+        .to_turtle()
+        .expect("failed to serialize");
+    let second = build()
+        .validate()
+        .with_blank_node_seed(7)
+        .to_turtle()
+        .expect("failed to serialize");
+    assert_eq!(first, second);
+    assert!(
+        !first.contains("_:report") && !first.contains("_:r0"),
+        "{}",
+        first
+    );
+
+    let other = validator
+        .validate()
+        .with_blank_node_seed(8)
+        .to_turtle()
+        .expect("failed to serialize");
+    assert_ne!(first, other);
+}
@@ -0,0 +1,224 @@
+use oxigraph::io::{RdfFormat, RdfParser};
+use oxigraph::model::vocab::rdf;
+use oxigraph::model::{
+    BlankNode, Graph, GraphName, Literal, NamedNode, NamedNodeRef, NamedOrBlankNode, Quad, Term,
+    Triple,
+};
+use oxigraph::store::Store;
+use shacl::{QuadChange, Source, ValidationReport, Validator, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn ex(local: &str) -> NamedNode {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local))
+}
+
+fn quad(subject: &str, predicate: &str, object: impl Into<Term>) -> Quad {
+    Quad::new(ex(subject), ex(predicate), object, GraphName::DefaultGraph)
+}
+
+fn validator(shapes: &str) -> Validator {
+    ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path(shapes)))
+        .with_data_source(Source::File(fixture_path("revalidate_data.ttl")))
+        .build()
+        .expect("failed to load fixtures")
+}
+
+fn failing(report: &ValidationReport<'_>) -> Vec<Term> {
+    let mut nodes: Vec<Term> = report
+        .result_codes()
+        .into_iter()
+        .map(|r| r.focus_node)
+        .collect();
+    nodes.sort_by_key(|term| term.to_string());
+    nodes.dedup();
+    nodes
+}
+
+#[test]
+fn revalidates_only_focus_nodes_connected_to_the_change() {
+    let validator = validator("revalidate_shapes.ttl");
+    let mut report = validator.validate();
+    assert_eq!(failing(&report), vec![ex("Bob").into(), ex("Carol").into()]);
+
+    // Fixing Carol's address re-validates Carol (who reaches it) but not Alice or Bob.
+    let partial = validator
+        .revalidate(&[QuadChange::Added(quad(
+            "CarolHome",
+            "city",
+            Literal::new_simple_literal("Springfield"),
+        ))])
+        .expect("revalidate");
+    let covered = partial.revalidated_focus_nodes().expect("partial report");
+    assert!(covered.contains(&ex("Carol").into()));
+    assert!(!covered.contains(&ex("Alice").into()));
+    assert!(!covered.contains(&ex("Bob").into()));
+    assert!(failing(&partial).is_empty());
+    report.merge_revalidation(partial);
+    assert_eq!(failing(&report), vec![ex("Bob").into()]);
+
+    // Breaking Alice adds her result and keeps Bob's.
+    let partial = validator
+        .revalidate(&[
+            QuadChange::Removed(quad("Alice", "age", Literal::from(30))),
+            QuadChange::Added(quad("Alice", "age", Literal::new_simple_literal("old"))),
+        ])
+        .expect("revalidate");
+    report.merge_revalidation(partial);
+    assert_eq!(failing(&report), vec![ex("Alice").into(), ex("Bob").into()]);
+    assert_eq!(failing(&report), failing(&validator.validate()));
+    assert!(report.revalidated_focus_nodes().is_none());
+}
+
+#[test]
+fn revalidating_changed_subjects_merges_into_the_previous_report() {
+    let validator = validator("revalidate_shapes.ttl");
+    let report = validator.validate();
+    assert_eq!(failing(&report), vec![ex("Bob").into(), ex("Carol").into()]);
+
+    // Fix Bob's age and Carol's address behind the validator's back.
+    let mut graph = Graph::new();
+    for quad in validator.data_graph_quads().expect("data graph") {
+        let stale =
+            quad.subject == NamedOrBlankNode::from(ex("Bob")) && quad.predicate == ex("age");
+        if !stale {
+            graph.insert(&Triple::new(quad.subject, quad.predicate, quad.object));
+        }
+    }
+    graph.insert(&Triple::new(ex("Bob"), ex("age"), Literal::from(25)));
+    graph.insert(&Triple::new(
+        ex("CarolHome"),
+        ex("city"),
+        Literal::new_simple_literal("Springfield"),
+    ));
+    validator.replace_data_graph(&graph).expect("replace");
+
+    // Only Bob is named, so Carol's stale result survives the merge.
+    let report = validator
+        .revalidate_subjects(report, &[ex("Bob").into()])
+        .expect("revalidate");
+    assert!(report.revalidated_focus_nodes().is_none());
+    assert_eq!(failing(&report), vec![ex("Carol").into()]);
+
+    let report = validator
+        .revalidate_subjects(report, &[ex("CarolHome").into()])
+        .expect("revalidate");
+    assert!(report.conforms());
+    assert_eq!(failing(&report), failing(&validator.validate()));
+}
+
+#[test]
+fn sparql_constraints_revalidate_every_focus_node() {
+    let validator = validator("revalidate_sparql_shapes.ttl");
+    let mut report = validator.validate();
+    assert!(report.conforms());
+
+    // Bob's new age clashes with Alice's, which only a full re-validation can notice.
+    let partial = validator
+        .revalidate(&[
+            QuadChange::Removed(quad("Bob", "age", Literal::new_simple_literal("thirty"))),
+            QuadChange::Added(quad("Bob", "age", Literal::from(30))),
+        ])
+        .expect("revalidate");
+    assert!(partial.revalidated_focus_nodes().is_none());
+    report.merge_revalidation(partial);
+    assert_eq!(failing(&report), vec![ex("Alice").into(), ex("Bob").into()]);
+}
+
+#[test]
+fn changes_to_other_graphs_are_rejected() {
+    let validator = validator("revalidate_shapes.ttl");
+    let change = Quad::new(ex("Alice"), ex("age"), Literal::from(31), ex("elsewhere"));
+    let Err(err) = validator.revalidate(&[QuadChange::Added(change)]) else {
+        panic!("a change outside the data graph must be rejected");
+    };
+    assert!(err.contains("not the data graph"), "{}", err);
+}
+
+#[test]
+fn changes_to_a_borrowed_store_keep_its_blank_nodes() {
+    let shapes_graph = NamedNodeRef::new_unchecked("urn:app:shapes");
+    let data_graph = NamedNode::new_unchecked("urn:app:data");
+    let store = Store::new().expect("store");
+    let shapes = std::fs::read_to_string(fixture_path("revalidate_shapes.ttl")).expect("shapes");
+    store
+        .load_from_reader(
+            RdfParser::from_format(RdfFormat::Turtle).with_default_graph(shapes_graph),
+            shapes.as_bytes(),
+        )
+        .expect("load shapes");
+    let home = BlankNode::new_unchecked("home");
+    let in_data = |quad: Quad| {
+        Quad::new(
+            quad.subject,
+            quad.predicate,
+            quad.object,
+            data_graph.clone(),
+        )
+    };
+    store
+        .insert(&in_data(quad("Carol", "address", home.clone())))
+        .expect("insert");
+    store
+        .insert(&Quad::new(
+            home.clone(),
+            ex("street"),
+            Literal::new_simple_literal("Main St"),
+            data_graph.clone(),
+        ))
+        .expect("insert");
+    store
+        .insert(&Quad::new(
+            ex("Carol"),
+            rdf::TYPE,
+            ex("Person"),
+            data_graph.clone(),
+        ))
+        .expect("insert");
+    let validator = Validator::from_store(
+        store.clone(),
+        shapes_graph.into(),
+        data_graph.as_ref().into(),
+    )
+    .expect("validator");
+    let report = validator.validate();
+    assert_eq!(failing(&report), vec![ex("Carol").into()]);
+
+    let city = Quad::new(
+        home.clone(),
+        ex("city"),
+        Literal::new_simple_literal("Springfield"),
+        GraphName::DefaultGraph,
+    );
+    let partial = validator
+        .revalidate(&[QuadChange::Added(city.clone())])
+        .expect("revalidate");
+    assert!(failing(&partial).is_empty());
+    assert!(store.contains(&in_data(city.clone())).expect("store"));
+    assert!(
+        store
+            .iter()
+            .filter_map(Result::ok)
+            .all(|quad| !quad.to_string().contains("/.well-known/skolem/")),
+        "no skolem IRIs may be written to the caller's store"
+    );
+
+    validator
+        .revalidate(&[QuadChange::Removed(city.clone())])
+        .expect("revalidate");
+    assert!(!store.contains(&in_data(city.clone())).expect("store"));
+
+    // The caller restores the city directly and names the blank node that changed.
+    store.insert(&in_data(city)).expect("insert");
+    let report = validator
+        .revalidate_subjects(report, &[home.into()])
+        .expect("revalidate");
+    assert!(report.conforms());
+}
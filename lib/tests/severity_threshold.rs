@@ -0,0 +1,49 @@
+use shacl::types::Severity;
+use shacl::{Source, Validator, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn build(data: &str) -> Validator {
+    ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("severity_threshold_shapes.ttl")))
+        .with_data_source(Source::InlineTurtle(format!(
+            "@prefix ex: <http://example.org/> .\n{}",
+            data
+        )))
+        .build()
+        .expect("validator should build")
+}
+
+#[test]
+fn thresholds_only_count_results_at_or_above_their_severity() {
+    // Missing email (warning) and nickname (info), but the name is there.
+    let validator = build("ex:alice a ex:Person ; ex:name \"Alice\" .");
+    let report = validator.validate();
+    assert!(!report.conforms());
+    assert!(!report.conforms_with(&Severity::Info));
+    assert!(!report.conforms_with(&Severity::Warning));
+    assert!(report.conforms_with(&Severity::Violation));
+
+    // Only the info result remains.
+    let validator =
+        build("ex:alice a ex:Person ; ex:name \"Alice\" ; ex:email \"alice@example.org\" .");
+    let report = validator.validate();
+    assert!(!report.conforms());
+    assert!(report.conforms_with(&Severity::Warning));
+}
+
+#[test]
+fn violations_fail_every_threshold() {
+    let validator =
+        build("ex:bob a ex:Person ; ex:email \"bob@example.org\" ; ex:nickname \"B\" .");
+    let report = validator.validate();
+    for threshold in [Severity::Info, Severity::Warning, Severity::Violation] {
+        assert!(!report.conforms_with(&threshold));
+    }
+}
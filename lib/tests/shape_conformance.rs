@@ -0,0 +1,52 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::Validator;
+use std::collections::HashSet;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+#[test]
+fn shapes_are_split_by_whether_they_produced_results() {
+    let shapes = fixture_path("shape_conformance_shapes.ttl");
+    let data = fixture_path("shape_conformance_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+    let report = validator.validate();
+
+    let violating = report.violating_shapes();
+    assert_eq!(
+        violating,
+        HashSet::from([ex("PersonNameShape"), ex("RoomShape")])
+    );
+
+    let conforming = report.conforming_shapes();
+    assert!(conforming.contains(&ex("PersonShape")));
+    assert!(conforming.contains(&ex("PersonAgeShape")));
+    assert!(
+        !conforming.contains(&ex("RetiredShape")),
+        "deactivated shapes are neither conforming nor violating"
+    );
+    assert!(conforming.is_disjoint(&violating));
+}
+
+#[test]
+fn every_active_shape_conforms_when_the_report_is_empty() {
+    let shapes = fixture_path("shape_conformance_shapes.ttl");
+    let validator = Validator::from_files(&shapes, &shapes).expect("failed to load fixtures");
+    let report = validator.validate();
+
+    assert!(report.conforms());
+    assert!(report.violating_shapes().is_empty());
+    for shape in [
+        "PersonShape",
+        "PersonNameShape",
+        "PersonAgeShape",
+        "RoomShape",
+    ] {
+        assert!(report.conforming_shapes().contains(&ex(shape)), "{}", shape);
+    }
+}
@@ -0,0 +1,73 @@
+use shacl::Validator;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn validator() -> Validator {
+    Validator::from_files(
+        &fixture_path("documentation_shapes.ttl"),
+        &fixture_path("documentation_data.ttl"),
+    )
+    .expect("failed to load fixtures")
+}
+
+#[test]
+fn markdown_documents_node_shapes_and_their_properties() {
+    let markdown = validator().to_markdown();
+
+    let address = markdown
+        .find("## `ex:AddressShape`")
+        .expect("AddressShape has a section");
+    let person = markdown
+        .find("## `ex:PersonShape` (Person)")
+        .expect("PersonShape has a section");
+    assert!(address < person, "{}", markdown);
+
+    assert!(
+        markdown.contains("A person known to the \\| registry."),
+        "{}",
+        markdown
+    );
+    assert!(
+        markdown.contains("Targets: instances of ex:Person"),
+        "{}",
+        markdown
+    );
+    assert!(
+        markdown.contains("| `ex:name` | name | 1..1 | xsd:string | length >= 2 | Full name. |"),
+        "{}",
+        markdown
+    );
+    assert!(
+        markdown.contains("| `ex:address` |  | 0..* | conforms to ex:AddressShape |  |  |"),
+        "{}",
+        markdown
+    );
+    assert!(
+        markdown.contains("- closed, ignoring ex:note"),
+        "{}",
+        markdown
+    );
+    assert!(
+        markdown.contains("one of \"Berlin\", \"Paris\""),
+        "{}",
+        markdown
+    );
+}
+
+#[test]
+fn html_escapes_shapes_graph_text() {
+    let html = validator().to_html();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(
+        html.contains("<h2><code>ex:PersonShape</code> (Person)</h2>"),
+        "{}",
+        html
+    );
+    assert!(
+        html.contains("one of &quot;Berlin&quot;, &quot;Paris&quot;"),
+        "{}",
+        html
+    );
+}
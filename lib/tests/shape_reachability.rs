@@ -0,0 +1,128 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::{ShapeReachability, Validator};
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn iri(value: &str) -> Term {
+    NamedNode::new_unchecked(value).into()
+}
+
+fn find<'a>(entries: &'a [ShapeReachability], shape: &str) -> &'a ShapeReachability {
+    entries
+        .iter()
+        .find(|entry| entry.shape == iri(shape))
+        .unwrap_or_else(|| panic!("{} should be listed", shape))
+}
+
+#[test]
+fn reports_reference_chains_and_unreachable_shapes() {
+    let shapes = fixture_path("reachability_shapes.ttl");
+    let data = fixture_path("reachability_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+    let entries = validator.shape_reachability();
+    assert_eq!(entries.len(), validator.shapes().len());
+
+    let person = find(&entries, "http://example.org/PersonShape");
+    assert!(person.targeted);
+    assert_eq!(
+        person.chain,
+        Some(vec![iri("http://example.org/PersonShape")])
+    );
+
+    let po_box = find(&entries, "http://example.org/PoBoxShape");
+    assert!(!po_box.targeted);
+    assert_eq!(
+        po_box.chain,
+        Some(vec![
+            iri("http://example.org/PersonShape"),
+            iri("http://example.org/AddressProperty"),
+            iri("http://example.org/AddressShape"),
+            iri("http://example.org/PoBoxShape"),
+        ])
+    );
+    assert!(po_box
+        .to_string()
+        .contains("reachable via <http://example.org/PersonShape> -> "));
+
+    for orphan in [
+        "http://example.org/OrphanShape",
+        "http://example.org/OrphanChildShape",
+    ] {
+        let entry = find(&entries, orphan);
+        assert!(!entry.is_reachable(), "{} should be unreachable", orphan);
+        assert!(entry
+            .to_string()
+            .ends_with("unreachable from any targeted shape"));
+    }
+
+    let unreachable_property_shapes = entries
+        .iter()
+        .filter(|entry| entry.is_property_shape && !entry.is_reachable())
+        .count();
+    assert_eq!(unreachable_property_shapes, 1);
+}
+
+#[test]
+fn shape_graphviz_follows_references_to_the_requested_depth() {
+    let shapes = fixture_path("reachability_shapes.ttl");
+    let data = fixture_path("reachability_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+
+    let dot = validator
+        .to_graphviz_for_shape(&iri("http://example.org/PersonShape"), 1)
+        .expect("graphviz");
+    assert!(
+        dot.contains("Path: <http://example.org/address>"),
+        "{}",
+        dot
+    );
+    let address = dot
+        .lines()
+        .find(|line| line.contains("AddressShape"))
+        .expect("the sh:node reference is one level deep");
+    assert!(!address.contains("dashed"), "{}", dot);
+    let street = dot
+        .lines()
+        .find(|line| line.contains("StreetShape"))
+        .expect("shapes past the depth are still drawn");
+    assert!(street.contains("style=dashed"), "{}", dot);
+    assert!(
+        !dot.contains("Path: <http://example.org/street>"),
+        "{}",
+        dot
+    );
+    assert!(!dot.contains("Orphan"), "{}", dot);
+
+    let err = validator
+        .to_graphviz_for_shape(&iri("http://example.org/MissingShape"), 1)
+        .expect_err("unknown shapes cannot be rendered");
+    assert!(err.contains("MissingShape"), "{}", err);
+}
+
+#[test]
+fn graphviz_output_shows_targets() {
+    let shapes = fixture_path("reachability_shapes.ttl");
+    let data = fixture_path("reachability_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+
+    for dot in [
+        validator.to_graphviz().expect("graphviz"),
+        validator
+            .to_graphviz_for_shape(&iri("http://example.org/PersonShape"), 0)
+            .expect("graphviz"),
+    ] {
+        let edges: Vec<&str> = dot
+            .lines()
+            .filter(|line| line.contains("label=\"sh:targetClass\""))
+            .collect();
+        assert_eq!(edges.len(), 1, "{}", dot);
+        let target_id = edges[0].split_whitespace().next().unwrap();
+        let target = dot
+            .lines()
+            .find(|line| line.trim_start().starts_with(&format!("{} [", target_id)))
+            .expect("the target is drawn as a node");
+        assert!(target.contains("Person"), "{}", dot);
+    }
+}
@@ -0,0 +1,68 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::model::components::ComponentDescriptor;
+use shacl::shape::ShapeKind;
+use shacl::types::{Path, Target};
+use shacl::Validator;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn iri(value: &str) -> Term {
+    NamedNode::new_unchecked(value).into()
+}
+
+#[test]
+fn shapes_expose_targets_paths_and_descriptors() {
+    let shapes = fixture_path("node_message_shapes.ttl");
+    let data = fixture_path("node_message_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+
+    let all = validator.shapes();
+    assert!(all
+        .windows(2)
+        .all(|pair| pair[0].term().to_string() <= pair[1].term().to_string()));
+    assert_eq!(
+        all.iter().filter(|shape| shape.is_property_shape()).count(),
+        3
+    );
+
+    let team = validator
+        .shape(&iri("http://example.org/TeamShape"))
+        .expect("TeamShape should be parsed");
+    assert!(matches!(team.kind(), ShapeKind::Node(_)));
+    assert!(team.path().is_none());
+    assert!(matches!(
+        team.targets(),
+        [Target::Class(class)] if class == &iri("http://example.org/Team")
+    ));
+
+    let member_property = team
+        .constraints()
+        .find_map(|(_, descriptor)| match descriptor {
+            ComponentDescriptor::Property { shape } => team.property_shape(shape),
+            _ => None,
+        })
+        .expect("TeamShape should declare a property shape");
+    assert_eq!(
+        team.property_shapes()
+            .iter()
+            .map(|shape| shape.term())
+            .collect::<Vec<_>>(),
+        vec![member_property.term()]
+    );
+    assert!(matches!(
+        member_property.path(),
+        Some(Path::Simple(predicate)) if predicate == &iri("http://example.org/member")
+    ));
+
+    let member_shape = member_property
+        .constraints()
+        .find_map(|(_, descriptor)| match descriptor {
+            ComponentDescriptor::Node { shape } => member_property.node_shape(shape),
+            _ => None,
+        })
+        .expect("member property should reference MemberShape through sh:node");
+    assert_eq!(member_shape.term(), iri("http://example.org/MemberShape"));
+    assert_eq!(member_shape.constraints().count(), 2);
+}
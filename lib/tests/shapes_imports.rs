@@ -0,0 +1,76 @@
+use ontoenv::config::Config;
+use oxigraph::model::{NamedNode, Term};
+use shacl::{Source, Validator, ValidatorBuilder};
+use std::error::Error;
+use std::path::PathBuf;
+
+fn fixture_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+/// Builds a validator for the layered shapes of `duplicate_shapes`, whose imported modules
+/// OntoEnv finds in the fixture directory.
+fn build(imports: bool) -> Result<Validator, Box<dyn Error>> {
+    let dir = fixture_dir().join("duplicate_shapes");
+    let config = Config::builder()
+        .root(dir.clone())
+        .locations(vec![dir.clone()])
+        .offline(true)
+        .temporary(true)
+        .build()?;
+    ValidatorBuilder::new()
+        .with_env_config(config)
+        .with_shapes_source(Source::File(dir.join("shapes.ttl")))
+        .with_data_source(Source::File(dir.join("data.ttl")))
+        .with_shapes_imports(imports)
+        .build()
+}
+
+#[test]
+fn imported_shapes_are_ignored_by_default() {
+    let validator = build(false).expect("failed to build validator");
+    assert!(validator.validate().conforms());
+}
+
+#[test]
+fn imported_shapes_are_validated_when_enabled() {
+    let validator = build(true).expect("failed to build validator");
+    let report = validator.validate();
+    // layer-conflict adds an sh:minCount 1 on ex:email, which alice lacks.
+    let focus_nodes: Vec<Term> = report.results().map(|result| result.focus_node).collect();
+    assert_eq!(focus_nodes, vec![ex("alice")]);
+
+    // layer-copy repeats AddressShape verbatim; it is not merged a second time.
+    let address = validator
+        .shape(&ex("AddressShape"))
+        .expect("AddressShape is parsed");
+    assert_eq!(address.constraints().count(), 1);
+}
+
+#[test]
+fn unresolvable_imports_fail_the_build() {
+    let error = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(
+            fixture_dir().join("missing_import_shapes.ttl"),
+        ))
+        .with_data_source(Source::File(
+            fixture_dir().join("duplicate_shapes/data.ttl"),
+        ))
+        .with_shapes_imports(true)
+        .build()
+        .err()
+        .expect("a missing import fails the build");
+    assert!(
+        error
+            .to_string()
+            .contains("<http://example.invalid/no-such-module> imported by the shapes graph"),
+        "{}",
+        error
+    );
+}
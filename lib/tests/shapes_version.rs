@@ -0,0 +1,68 @@
+use oxigraph::model::NamedNode;
+use shacl::{ShapesVersion, ShapesVersionRequirement, Source, Validator, ValidatorBuilder};
+use std::error::Error;
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn build(shapes: &str, requirement: &str) -> Result<Validator, Box<dyn Error>> {
+    ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path(shapes)))
+        .with_data_source(Source::File(fixture_path("custom_severity_data.ttl")))
+        .with_expected_shapes_version(requirement.parse::<ShapesVersionRequirement>()?)
+        .build()
+}
+
+#[test]
+fn declared_version_is_exposed() {
+    let validator = build("versioned_shapes.ttl", ">=1.4").expect("version should match");
+    assert_eq!(
+        validator.shapes_version(),
+        ShapesVersion {
+            version_info: Some("1.4.2".to_string()),
+            version_iri: Some(NamedNode::new_unchecked(
+                "http://example.org/versioned-shapes/1.4.2"
+            )),
+        }
+    );
+}
+
+#[test]
+fn matching_requirements_build() {
+    for requirement in [
+        "1.4.2",
+        ">=1.4,<2",
+        "<1.10",
+        "<http://example.org/versioned-shapes/1.4.2>",
+    ] {
+        build("versioned_shapes.ttl", requirement)
+            .unwrap_or_else(|e| panic!("{} should match: {}", requirement, e));
+    }
+}
+
+#[test]
+fn mismatched_or_missing_versions_fail_fast() {
+    let err = build("versioned_shapes.ttl", ">=2.0")
+        .err()
+        .expect("stale shapes should be rejected");
+    let message = err.to_string();
+    assert!(
+        message.contains("declares version 1.4.2") && message.contains("version >=2.0 is required"),
+        "unexpected error: {}",
+        message
+    );
+
+    let err = build("custom_severity_shapes.ttl", "1.0")
+        .err()
+        .expect("unversioned shapes should be rejected");
+    assert!(
+        err.to_string().contains("declares no version"),
+        "unexpected error: {}",
+        err
+    );
+}
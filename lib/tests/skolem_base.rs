@@ -0,0 +1,57 @@
+use shacl::{Source, Validator, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(format!(
+        "{}/tests/fixtures/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    ))
+}
+
+fn build(data_base: &str) -> Validator {
+    ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("skolem_base_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("skolem_base_data.ttl")))
+        .with_skolem_bases(None, Some(data_base.to_string()))
+        .build()
+        .expect("failed to load fixtures")
+}
+
+#[test]
+fn blank_nodes_are_skolemized_under_the_configured_base() {
+    let validator = build("urn:local:skolem/");
+
+    let quads = validator
+        .data_graph_quads()
+        .expect("data graph should be readable");
+    assert!(
+        quads
+            .iter()
+            .any(|quad| quad.object.to_string().starts_with("<urn:local:skolem/")),
+        "blank node should use the configured base: {:?}",
+        quads
+    );
+
+    let report = validator.validate();
+    let report_ttl = report
+        .to_turtle()
+        .expect("failed to serialize validation report");
+    assert!(
+        !report_ttl.contains("sh:focusNode <http://example.org/A>"),
+        "skolemized blank node should still count as a blank node: {}",
+        report_ttl
+    );
+    assert!(
+        report_ttl.contains("sh:focusNode <http://example.org/B>"),
+        "upstream skolem IRI is an IRI under a different base: {}",
+        report_ttl
+    );
+}
+
+#[test]
+fn configured_base_decides_which_iris_are_blank_nodes() {
+    // Sharing the upstream store's base makes its skolem IRIs blank nodes again.
+    let validator = build("urn:upstream:skolem/");
+    assert!(validator.validate().conforms());
+}
@@ -0,0 +1,43 @@
+use shacl::Validator;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn sh_prefixes_follow_declared_graph_and_imports_only() {
+    let shapes = fixture_path("sparql_prefixes_shapes.ttl");
+    let data = fixture_path("sparql_prefixes_data.ttl");
+
+    // ex:UnrelatedDeclarations binds `voc` to another namespace; it must not be consulted.
+    let validator = Validator::from_files(&shapes, &data)
+        .expect("unrelated sh:declare blocks should not collide with scoped prefixes");
+    let report = validator.validate();
+    assert!(!report.conforms(), "retired item should be reported");
+
+    let report_ttl = report
+        .to_turtle()
+        .expect("failed to serialize validation report");
+    assert!(
+        report_ttl.contains("sh:focusNode <http://example.org/Retired>"),
+        "imported voc prefix should resolve to the vocabulary namespace: {}",
+        report_ttl
+    );
+    assert!(
+        !report_ttl.contains("sh:focusNode <http://example.org/Active>"),
+        "active item should conform: {}",
+        report_ttl
+    );
+}
+
+#[test]
+#[should_panic(expected = "Duplicate prefix 'voc'")]
+fn conflicting_prefixes_across_imports_are_rejected() {
+    let shapes = fixture_path("sparql_prefixes_collision_shapes.ttl");
+    let data = fixture_path("sparql_prefixes_data.ttl");
+
+    // Prefixes are resolved when the constraint runs, so the conflict surfaces from
+    // validate(); `voc` is bound differently by the declared graph and its import.
+    let validator = Validator::from_files(&shapes, &data).expect("validator should build");
+    let _ = validator.validate();
+}
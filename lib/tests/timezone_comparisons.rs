@@ -0,0 +1,56 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::{Source, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+#[test]
+fn date_times_compare_on_the_timeline_and_explain_indeterminate_pairs() {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("timezone_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("timezone_data.ttl")))
+        .build()
+        .expect("failed to load fixtures");
+    let report = validator.validate();
+    let results: Vec<_> = report.results().collect();
+
+    // 01:00+02:00 is 23:00Z the previous day: below the minimum, but before the 00:30Z end.
+    let zoned: Vec<_> = results
+        .iter()
+        .filter(|r| r.focus_node == ex("zoned"))
+        .collect();
+    assert_eq!(zoned.len(), 1);
+    assert_eq!(
+        zoned[0].code.as_ref().map(|c| c.as_str()),
+        Some("MinInclusiveViolation")
+    );
+    assert!(!zoned[0].engine_message.contains("time zone"));
+
+    // An unzoned value more than 14 hours from the zoned bounds is still ordered.
+    assert!(results.iter().all(|r| r.focus_node != ex("unzonedFar")));
+
+    // Within 14 hours, the order is indeterminate: both comparisons fail and say why.
+    let near: Vec<_> = results
+        .iter()
+        .filter(|r| r.focus_node == ex("unzonedNear"))
+        .collect();
+    assert_eq!(near.len(), 2);
+    for result in near {
+        assert!(
+            result
+                .engine_message
+                .contains("2024-01-01T10:00:00 has no time zone and is within 14 hours"),
+            "{}",
+            result.engine_message
+        );
+    }
+}
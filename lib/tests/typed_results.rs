@@ -0,0 +1,73 @@
+use oxigraph::model::{Literal, NamedNode, Term};
+use shacl::types::{Path, Severity};
+use shacl::{Source, ValidatorBuilder};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+fn sh(local: &str) -> NamedNode {
+    NamedNode::new_unchecked(format!("http://www.w3.org/ns/shacl#{}", local))
+}
+
+#[test]
+fn results_expose_the_fields_of_the_report_graph() {
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("aggregation_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("aggregation_data.ttl")))
+        .build()
+        .expect("failed to load fixtures");
+    let report = validator.validate();
+    let results: Vec<_> = report.results().collect();
+
+    let result_predicate = sh("result");
+    assert_eq!(
+        results.len(),
+        report
+            .to_graph()
+            .triples_for_predicate(&result_predicate)
+            .count()
+    );
+
+    let thermometer: Vec<_> = results
+        .iter()
+        .filter(|result| result.focus_node == ex("Thermometer"))
+        .collect();
+    assert_eq!(thermometer.len(), 1);
+    let missing_unit = thermometer[0];
+    assert_eq!(missing_unit.path, Some(Path::Simple(ex("unit"))));
+    assert_eq!(missing_unit.severity, Severity::Warning);
+    assert_eq!(missing_unit.value, None);
+    assert_eq!(
+        missing_unit.source_constraint_component,
+        Some(sh("MinCountConstraintComponent"))
+    );
+    assert_eq!(
+        missing_unit.code.as_ref().map(|code| code.as_str()),
+        Some("MinCountViolation")
+    );
+    assert!(missing_unit.source_shape.is_some());
+    assert!(!missing_unit.engine_message.is_empty());
+
+    let bad_readings: Vec<Term> = results
+        .iter()
+        .filter(|result| {
+            result.source_constraint_component == Some(sh("DatatypeConstraintComponent"))
+        })
+        .filter_map(|result| result.value.clone())
+        .collect();
+    assert_eq!(bad_readings.len(), 2);
+    assert!(bad_readings.contains(&Literal::new_simple_literal("a").into()));
+    assert!(results
+        .iter()
+        .filter(|result| result.focus_node == ex("Garbage"))
+        .all(|result| result.path.is_some()));
+}
@@ -0,0 +1,41 @@
+use shacl::{synthetic_graph_name, Validator};
+use std::path::Path;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn files_without_ontology_iri_get_path_derived_graph_names() {
+    let shapes = fixture_path("unnamed_shapes.ttl");
+    let data = fixture_path("unnamed_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+
+    assert_eq!(
+        validator.shapes_graph_iri(),
+        &synthetic_graph_name(Path::new(&shapes))
+    );
+    assert_eq!(
+        validator.data_graph_iri(),
+        &synthetic_graph_name(Path::new(&data))
+    );
+    assert!(validator
+        .shapes_graph_iri()
+        .as_str()
+        .ends_with("/tests/fixtures/unnamed_shapes.ttl"));
+
+    let report = validator.validate();
+    let report_ttl = report
+        .to_turtle()
+        .expect("failed to serialize validation report");
+    assert!(
+        report_ttl.contains("sh:focusNode <http://example.org/Unlabelled>"),
+        "shapes should still be parsed from the renamed graph: {}",
+        report_ttl
+    );
+    assert!(
+        !report_ttl.contains("sh:focusNode <http://example.org/Labelled>"),
+        "labelled item conforms: {}",
+        report_ttl
+    );
+}
@@ -0,0 +1,45 @@
+use shacl::{UnsupportedKind, Validator, UNSUPPORTED_FEATURE};
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn unsupported_features_are_recorded_and_reported() {
+    let validator = Validator::from_files(
+        &fixture_path("unsupported_shapes.ttl"),
+        &fixture_path("unsupported_data.ttl"),
+    )
+    .expect("failed to load fixtures");
+
+    let mut found: Vec<(&str, UnsupportedKind)> = validator
+        .unsupported_features()
+        .iter()
+        .map(|feature| (feature.predicate.as_str(), feature.kind))
+        .collect();
+    found.sort();
+    assert_eq!(
+        found,
+        vec![
+            ("http://www.w3.org/ns/shacl#js", UnsupportedKind::JavaScript),
+            (
+                "http://www.w3.org/ns/shacl#maxCuont",
+                UnsupportedKind::UnrecognizedPredicate
+            ),
+        ]
+    );
+
+    // The ignored constraints do not fail validation, but the report says they were ignored.
+    let report = validator.validate();
+    assert!(report.conforms());
+    let graph = report.to_graph();
+    let descriptions: Vec<String> = graph
+        .iter()
+        .filter(|triple| triple.predicate.as_str() == UNSUPPORTED_FEATURE)
+        .map(|triple| triple.object.to_string())
+        .collect();
+    assert_eq!(descriptions.len(), 2);
+    assert!(descriptions
+        .iter()
+        .any(|description| description.contains("SHACL-JavaScript is not supported")));
+}
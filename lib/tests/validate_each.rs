@@ -0,0 +1,91 @@
+use oxigraph::io::{RdfFormat, RdfParser};
+use oxigraph::model::Graph;
+use shacl::Validator;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn parse_graph(turtle: &str) -> Graph {
+    let mut graph = Graph::new();
+    for triple in RdfParser::from_format(RdfFormat::Turtle).for_slice(turtle.as_bytes()) {
+        graph.insert(&triple.expect("fixture triple should parse").into());
+    }
+    graph
+}
+
+#[test]
+fn validate_each_reports_every_graph_independently() {
+    let shapes = fixture_path("validate_each_shapes.ttl");
+    let data = fixture_path("validate_each_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("validator should build");
+
+    let payloads = vec![
+        parse_graph(
+            "@prefix ex: <http://example.org/> .\nex:r1 a ex:Reading ; ex:value 3 .",
+        ),
+        parse_graph("@prefix ex: <http://example.org/> .\nex:r2 a ex:Reading ."),
+        parse_graph(
+            "@prefix ex: <http://example.org/> .\nex:r3 a ex:Reading ; ex:value 4 ; ex:sensor [ ex:model \"x\" ] .",
+        ),
+        parse_graph(
+            "@prefix ex: <http://example.org/> .\nex:r4 a ex:Reading ; ex:value 5 ; ex:sensor [ ex:unit \"C\" ] .",
+        ),
+    ];
+
+    let mut outcomes = Vec::new();
+    validator
+        .validate_each(payloads, |index, report| {
+            let report_ttl = report.to_turtle().expect("report should serialize");
+            outcomes.push((index, report.conforms(), report_ttl));
+        })
+        .expect("batch validation should succeed");
+
+    let conforms: Vec<(usize, bool)> = outcomes.iter().map(|(i, c, _)| (*i, *c)).collect();
+    assert_eq!(conforms, vec![(0, true), (1, false), (2, false), (3, true)]);
+
+    // Reports only mention the payload they were produced for.
+    assert!(outcomes[1].2.contains("http://example.org/r2"));
+    assert!(!outcomes[1].2.contains("http://example.org/r1"));
+    assert!(outcomes[2].2.contains("http://example.org/r3"));
+    assert!(!outcomes[2].2.contains("http://example.org/r2"));
+
+    // The validator's own data graph is untouched by the batch.
+    assert!(validator.validate().conforms());
+}
+
+#[test]
+fn data_graph_can_be_replaced_and_cleared_in_place() {
+    let shapes = fixture_path("validate_each_shapes.ttl");
+    let data = fixture_path("validate_each_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("validator should build");
+    assert!(validator.validate().conforms());
+
+    let invalid = parse_graph(
+        "@prefix ex: <http://example.org/> .\nex:r5 a ex:Reading ; ex:value 1 ; ex:sensor [ ex:model \"y\" ] .",
+    );
+    validator
+        .replace_data_graph(&invalid)
+        .expect("data graph should be replaced");
+    let report = validator.validate();
+    assert!(!report.conforms(), "replaced data should be validated");
+    let report_ttl = report.to_turtle().expect("report should serialize");
+    assert!(report_ttl.contains("http://example.org/r5"));
+    assert!(
+        !report_ttl.contains("http://example.org/Baseline"),
+        "stale data should be gone: {}",
+        report_ttl
+    );
+
+    let fix = parse_graph("@prefix ex: <http://example.org/> .\nex:r6 a ex:Reading ; ex:value 2 .");
+    validator
+        .extend_data_graph(&fix)
+        .expect("data graph should be extended");
+    assert!(!validator.validate().conforms(), "ex:r5 is still present");
+
+    validator
+        .clear_data_graph()
+        .expect("data graph should be cleared");
+    assert!(validator.data_graph_quads().unwrap().is_empty());
+    assert!(validator.validate().conforms());
+}
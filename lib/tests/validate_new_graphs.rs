@@ -0,0 +1,124 @@
+use oxigraph::io::RdfFormat;
+use oxigraph::model::{GraphNameRef, NamedNode, NamedNodeRef};
+use oxigraph::store::Store;
+use shacl::{GraphTracker, Validator};
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn load_batch(store: &Store, graph: &str, turtle: &str) {
+    store
+        .load_from_reader(
+            oxigraph::io::RdfParser::from_format(RdfFormat::Turtle)
+                .with_default_graph(NamedNodeRef::new_unchecked(graph)),
+            turtle.as_bytes(),
+        )
+        .expect("batch should load");
+}
+
+fn validator() -> Validator {
+    Validator::from_files(
+        &fixture_path("validate_each_shapes.ttl"),
+        &fixture_path("validate_each_data.ttl"),
+    )
+    .expect("validator should build")
+}
+
+#[test]
+fn only_new_or_changed_graphs_are_validated() {
+    let validator = validator();
+    let store = Store::new().expect("store");
+    let mut tracker = GraphTracker::new();
+    load_batch(
+        &store,
+        "urn:batch:1",
+        "@prefix ex: <http://example.org/> .\nex:r1 a ex:Reading ; ex:value 1 .",
+    );
+    load_batch(
+        &store,
+        "urn:batch:2",
+        "@prefix ex: <http://example.org/> .\nex:r2 a ex:Reading .",
+    );
+
+    let mut conforms = None;
+    let validated = validator
+        .validate_new_graphs(&store, &mut tracker, |report| {
+            conforms = Some(report.conforms());
+            let turtle = report.to_turtle().expect("serialize");
+            assert!(turtle.contains("http://example.org/r2"));
+        })
+        .expect("validation should succeed");
+    assert_eq!(
+        validated,
+        vec![
+            NamedNode::new_unchecked("urn:batch:1"),
+            NamedNode::new_unchecked("urn:batch:2")
+        ]
+    );
+    assert_eq!(conforms, Some(false));
+    assert_eq!(tracker.len(), 2);
+
+    // Nothing changed: no report.
+    let validated = validator
+        .validate_new_graphs(&store, &mut tracker, |_| panic!("nothing to validate"))
+        .expect("validation should succeed");
+    assert!(validated.is_empty());
+
+    // A new batch and a fix to an old one are validated; the untouched batch is not.
+    load_batch(
+        &store,
+        "urn:batch:2",
+        "@prefix ex: <http://example.org/> .\nex:r2 ex:value 2 .",
+    );
+    load_batch(
+        &store,
+        "urn:batch:3",
+        "@prefix ex: <http://example.org/> .\nex:r3 a ex:Reading ; ex:value 3 .",
+    );
+    let mut conforms = None;
+    let validated = validator
+        .validate_new_graphs(&store, &mut tracker, |report| {
+            conforms = Some(report.conforms())
+        })
+        .expect("validation should succeed");
+    assert_eq!(
+        validated,
+        vec![
+            NamedNode::new_unchecked("urn:batch:2"),
+            NamedNode::new_unchecked("urn:batch:3")
+        ]
+    );
+    assert_eq!(conforms, Some(true));
+}
+
+#[test]
+fn dropped_graphs_are_forgotten() {
+    let validator = validator();
+    let store = Store::new().expect("store");
+    let mut tracker = GraphTracker::new();
+    let batch = "@prefix ex: <http://example.org/> .\nex:r1 a ex:Reading ; ex:value 1 .";
+    load_batch(&store, "urn:batch:1", batch);
+    validator
+        .validate_new_graphs(&store, &mut tracker, |_| {})
+        .expect("validation should succeed");
+
+    store
+        .clear_graph(GraphNameRef::NamedNode(NamedNodeRef::new_unchecked(
+            "urn:batch:1",
+        )))
+        .expect("clear");
+    store
+        .remove_named_graph(NamedNodeRef::new_unchecked("urn:batch:1"))
+        .expect("remove");
+    validator
+        .validate_new_graphs(&store, &mut tracker, |_| {})
+        .expect("validation should succeed");
+    assert!(tracker.is_empty());
+
+    load_batch(&store, "urn:batch:1", batch);
+    let validated = validator
+        .validate_new_graphs(&store, &mut tracker, |_| {})
+        .expect("validation should succeed");
+    assert_eq!(validated.len(), 1);
+}
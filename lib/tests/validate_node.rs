@@ -0,0 +1,57 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::Validator;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+fn load() -> Validator {
+    Validator::from_files(
+        &fixture_path("validate_node_shapes.ttl"),
+        &fixture_path("validate_node_data.ttl"),
+    )
+    .expect("failed to load fixtures")
+}
+
+fn focus_nodes_with_results(report: &shacl::ValidationReport<'_>) -> Vec<Term> {
+    let mut nodes: Vec<Term> = report.results().map(|result| result.focus_node).collect();
+    nodes.sort_by_key(|node| node.to_string());
+    nodes.dedup();
+    nodes
+}
+
+#[test]
+fn validate_node_reports_only_the_given_node() {
+    let validator = load();
+
+    // ex:alice is an ex:Person through rdfs:subClassOf.
+    let report = validator
+        .validate_node(&ex("alice"))
+        .expect("validation failed");
+    assert!(!report.conforms());
+    assert_eq!(focus_nodes_with_results(&report), vec![ex("alice")]);
+
+    // ex:carol is targeted as a person and as the subject of ex:wrote, ex:pamphlet as the
+    // object of ex:wrote.
+    let report = validator
+        .validate_nodes(&[ex("carol"), ex("pamphlet")])
+        .expect("validation failed");
+    assert_eq!(
+        focus_nodes_with_results(&report),
+        vec![ex("carol"), ex("pamphlet")]
+    );
+}
+
+#[test]
+fn nodes_no_shape_targets_conform() {
+    let validator = load();
+    let report = validator
+        .validate_node(&ex("dave"))
+        .expect("validation failed");
+    assert!(report.conforms());
+    assert_eq!(report.results().count(), 0);
+}
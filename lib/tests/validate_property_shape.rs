@@ -0,0 +1,63 @@
+use oxigraph::model::{NamedNode, NamedNodeRef, Term};
+use shacl::Validator;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new(format!("http://example.org/{}", local))
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn validates_only_the_requested_property_shape_for_given_focus_nodes() {
+    let shapes = fixture_path("property_spot_check_shapes.ttl");
+    let data = fixture_path("property_spot_check_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+
+    let report = validator
+        .validate_property_shape(
+            NamedNodeRef::new("http://example.org/NameProperty").unwrap(),
+            &[ex("Named"), ex("Unnamed")],
+        )
+        .expect("property shape should be found");
+    assert!(!report.conforms());
+
+    let report_ttl = report
+        .to_turtle()
+        .expect("failed to serialize validation report");
+    assert!(
+        report_ttl.contains("sh:focusNode <http://example.org/Unnamed>"),
+        "missing ex:name should be reported: {}",
+        report_ttl
+    );
+    assert!(
+        !report_ttl.contains("sh:focusNode <http://example.org/Named>"),
+        "ex:Named has a name: {}",
+        report_ttl
+    );
+    // ex:Person1 lacks ex:age, but neither its target nor ex:AgeProperty is evaluated.
+    assert!(
+        !report_ttl.contains("http://example.org/Person1"),
+        "targets and other shapes should not run: {}",
+        report_ttl
+    );
+}
+
+#[test]
+fn unknown_property_shape_is_an_error() {
+    let shapes = fixture_path("property_spot_check_shapes.ttl");
+    let data = fixture_path("property_spot_check_data.ttl");
+    let validator = Validator::from_files(&shapes, &data).expect("failed to load fixtures");
+
+    let err = validator
+        .validate_property_shape(
+            NamedNodeRef::new("http://example.org/PersonShape").unwrap(),
+            &[ex("Named")],
+        )
+        .err()
+        .expect("a node shape is not a property shape");
+    assert!(err.contains("not found"), "unexpected error: {}", err);
+}
@@ -0,0 +1,127 @@
+use oxigraph::io::{RdfFormat, RdfParser};
+use oxigraph::model::vocab::xsd;
+use oxigraph::model::{GraphNameRef, LiteralRef, NamedNode, NamedNodeRef, QuadRef, Term};
+use oxigraph::store::Store;
+use shacl::{Validator, ValidatorBuilder};
+use std::fs;
+use std::path::PathBuf;
+
+const SHAPES: NamedNodeRef<'_> = NamedNodeRef::new_unchecked("urn:app:shapes");
+const DATA: NamedNodeRef<'_> = NamedNodeRef::new_unchecked("urn:app:data");
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+fn app_store() -> Store {
+    let store = Store::new().expect("store");
+    for (file, graph) in [
+        ("datatype_in_shapes.ttl", SHAPES),
+        ("datatype_in_data.ttl", DATA),
+    ] {
+        let turtle = fs::read_to_string(fixture_path(file)).expect("read fixture");
+        store
+            .load_from_reader(
+                RdfParser::from_format(RdfFormat::Turtle).with_default_graph(graph),
+                turtle.as_bytes(),
+            )
+            .expect("load fixture");
+    }
+    store
+        .load_from_reader(
+            RdfParser::from_format(RdfFormat::Turtle).with_default_graph(DATA),
+            "<http://example.org/Meetup> a <http://example.org/Event> ; \
+             <http://example.org/when> [ <http://example.org/day> 3 ] ."
+                .as_bytes(),
+        )
+        .expect("load blank node");
+    store
+}
+
+fn failing_focus_nodes(validator: &Validator) -> Vec<Term> {
+    let report = validator.validate();
+    let mut failing: Vec<Term> = report
+        .result_codes()
+        .into_iter()
+        .map(|r| r.focus_node)
+        .collect();
+    failing.sort_by_key(|term| term.to_string());
+    failing
+}
+
+#[test]
+fn validates_graphs_of_an_existing_store_in_place() {
+    let store = app_store();
+    let before = store.len().expect("len");
+    let validator =
+        Validator::from_store(store.clone(), SHAPES.into(), DATA.into()).expect("validator");
+    assert_eq!(validator.shapes_graph_iri().as_ref(), SHAPES);
+    assert_eq!(validator.data_graph_iri().as_ref(), DATA);
+    assert_eq!(
+        failing_focus_nodes(&validator),
+        vec![ex("Meetup"), ex("Party"), ex("Pointer"), ex("Typo")]
+    );
+    // Nothing was copied into or rewritten in the application's graphs.
+    assert_eq!(store.len().expect("len"), before);
+    assert!(store
+        .quads_for_pattern(None, None, None, Some(DATA.into()))
+        .filter_map(Result::ok)
+        .any(|quad| quad.object.is_blank_node()));
+
+    // Later changes to the store are seen by the next validation.
+    let party = NamedNode::new_unchecked("http://example.org/Party");
+    let when = NamedNodeRef::new_unchecked("http://example.org/when");
+    let bad = store
+        .quads_for_pattern(
+            Some(party.as_ref().into()),
+            Some(when),
+            None,
+            Some(DATA.into()),
+        )
+        .next()
+        .expect("party date")
+        .expect("quad");
+    store.remove(&bad).expect("remove");
+    store
+        .insert(QuadRef::new(
+            party.as_ref(),
+            when,
+            LiteralRef::new_typed_literal("2024-05-02", xsd::DATE),
+            DATA,
+        ))
+        .expect("insert");
+    assert!(!failing_focus_nodes(&validator).contains(&ex("Party")));
+}
+
+#[test]
+fn existing_store_graphs_must_be_named_and_present() {
+    let store = app_store();
+    let Err(err) = Validator::from_store(store.clone(), SHAPES.into(), GraphNameRef::DefaultGraph)
+    else {
+        panic!("the default graph is not supported");
+    };
+    assert!(err.to_string().contains("named graph"), "{}", err);
+
+    let missing = NamedNodeRef::new_unchecked("urn:app:missing");
+    let Err(err) = Validator::from_store(store.clone(), SHAPES.into(), missing.into()) else {
+        panic!("a missing graph must be reported");
+    };
+    assert!(err.to_string().contains("no data graph"), "{}", err);
+
+    let Err(err) = ValidatorBuilder::new()
+        .with_store(store, SHAPES.into(), DATA.into())
+        .expect("named graphs")
+        .with_data_source(shacl::Source::File(fixture_path("datatype_in_data.ttl")))
+        .build()
+    else {
+        panic!("sources and a store are exclusive");
+    };
+    assert!(err.to_string().contains("cannot be combined"), "{}", err);
+}
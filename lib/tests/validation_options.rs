@@ -0,0 +1,133 @@
+use oxigraph::model::{NamedNode, Term};
+use shacl::types::Severity;
+use shacl::{Source, StopReason, ValidationOptions, Validator, ValidatorBuilder};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn ex(local: &str) -> Term {
+    NamedNode::new_unchecked(format!("http://example.org/{}", local)).into()
+}
+
+fn build_validator(options: ValidationOptions) -> Result<Validator, Box<dyn std::error::Error>> {
+    ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("validation_options_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("validation_options_data.ttl")))
+        .with_validation_options(options)
+        .build()
+}
+
+#[test]
+fn default_options_report_every_result() {
+    let validator = build_validator(ValidationOptions::default()).expect("failed to load fixtures");
+    let report = validator.validate();
+    // Three sensors, each missing a unit, a label and an id.
+    assert_eq!(report.results().count(), 9);
+    assert_eq!(report.stop_reason(), None);
+}
+
+#[test]
+fn result_limit_and_fail_fast_stop_the_run() {
+    let validator = build_validator(ValidationOptions::new().with_max_errors(4))
+        .expect("failed to load fixtures");
+    let report = validator.validate();
+    assert_eq!(report.results().count(), 4);
+    assert_eq!(report.stop_reason(), Some(StopReason::MaxErrors));
+    assert!(!report.conforms());
+
+    let report = validator
+        .validate_with_options(&ValidationOptions::new().with_fail_fast(true))
+        .expect("options are valid");
+    assert_eq!(report.results().count(), 1);
+    assert_eq!(report.stop_reason(), Some(StopReason::MaxErrors));
+}
+
+#[test]
+fn ignored_severities_are_dropped_from_the_report() {
+    let validator = build_validator(ValidationOptions::new().with_ignored_severity(Severity::Info))
+        .expect("failed to load fixtures");
+    let report = validator.validate();
+    assert_eq!(report.results().count(), 6);
+    assert!(report
+        .results()
+        .all(|result| result.severity == Severity::Violation));
+}
+
+#[test]
+fn only_the_selected_shapes_run() {
+    let validator = build_validator(ValidationOptions::default()).expect("failed to load fixtures");
+    let report = validator
+        .validate_with_options(&ValidationOptions::new().with_shapes([ex("SensorIdShape")]))
+        .expect("options are valid");
+    let shapes: HashSet<Term> = report
+        .results()
+        .filter_map(|result| result.source_shape)
+        .collect();
+    assert_eq!(shapes, HashSet::from([ex("SensorIdShape")]));
+    assert_eq!(report.results().count(), 3);
+
+    let error = validator
+        .validate_with_options(&ValidationOptions::new().with_shapes([ex("MissingShape")]))
+        .err()
+        .expect("unknown shapes are rejected");
+    assert!(error.contains("MissingShape"), "{}", error);
+    assert!(build_validator(ValidationOptions::new().with_shapes([ex("MissingShape")])).is_err());
+}
+
+#[test]
+fn sparql_constraints_can_be_switched_off() {
+    let validator = build_validator(ValidationOptions::new().with_sparql_constraints(false))
+        .expect("failed to load fixtures");
+    let report = validator.validate();
+    assert_eq!(report.results().count(), 6);
+    assert!(report
+        .results()
+        .all(|result| result.source_shape != Some(ex("SensorIdShape"))));
+
+    // The switch only applies to the run it was given to.
+    let report = validator
+        .validate_with_options(&ValidationOptions::default())
+        .expect("options are valid");
+    assert_eq!(report.results().count(), 9);
+}
+
+#[test]
+fn an_elapsed_timeout_stops_before_any_focus_node() {
+    let validator = build_validator(ValidationOptions::new().with_timeout(Duration::ZERO))
+        .expect("failed to load fixtures");
+    let report = validator.validate();
+    assert_eq!(report.stop_reason(), Some(StopReason::Timeout));
+    assert_eq!(report.results().count(), 0);
+}
+
+#[test]
+fn validate_shape_runs_one_shape_with_the_validator_options() {
+    let validator = build_validator(ValidationOptions::default()).expect("failed to load fixtures");
+    let sensor_shape = NamedNode::new_unchecked("http://example.org/SensorShape");
+    let report = validator
+        .validate_shape(&sensor_shape)
+        .expect("the shape exists");
+    // Only the ex:unit property shape referenced by ex:SensorShape reports.
+    assert_eq!(report.results().count(), 3);
+    assert!(report
+        .results()
+        .all(|result| result.path == Some(shacl::types::Path::Simple(ex("unit")))));
+
+    let limited =
+        build_validator(ValidationOptions::new().with_max_errors(1)).expect("failed to load");
+    let report = limited
+        .validate_shape(&sensor_shape)
+        .expect("the shape exists");
+    assert_eq!(report.results().count(), 1);
+
+    assert!(validator
+        .validate_shape(&NamedNode::new_unchecked("http://example.org/MissingShape"))
+        .is_err());
+}
@@ -0,0 +1,141 @@
+use oxigraph::model::NamedNode;
+use shacl::{Source, ValidatorBuilder, VALUE_NODE_LIMIT_COMPONENT};
+use std::path::PathBuf;
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(format!(
+        "{}/tests/fixtures/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    ))
+}
+
+fn build(limit: Option<usize>) -> shacl::Validator {
+    let mut builder = ValidatorBuilder::new()
+        .with_shapes_source(Source::File(fixture_path("value_node_limit_shapes.ttl")))
+        .with_data_source(Source::File(fixture_path("value_node_limit_data.ttl")));
+    if let Some(limit) = limit {
+        builder = builder.with_max_value_nodes(limit);
+    }
+    builder.build().expect("failed to load fixtures")
+}
+
+#[test]
+fn focus_nodes_over_the_value_node_cap_are_reported() {
+    let validator = build(Some(3));
+    let report = validator.validate();
+    assert!(!report.conforms());
+
+    let report_ttl = report
+        .to_turtle()
+        .expect("failed to serialize validation report");
+    // ex:Dense reaches six nodes through ex:link*, ex:Sparse only two.
+    assert!(
+        report_ttl.contains("sh:focusNode <http://example.org/Dense>"),
+        "dense focus node should be reported: {}",
+        report_ttl
+    );
+    assert!(
+        report_ttl.contains("has more than 3 value nodes"),
+        "result should explain the cap: {}",
+        report_ttl
+    );
+    assert!(
+        !report_ttl.contains("sh:focusNode <http://example.org/Sparse>"),
+        "focus node under the cap should validate normally: {}",
+        report_ttl
+    );
+
+    let results: Vec<_> = report.results().collect();
+    assert_eq!(results.len(), 1, "{:?}", results);
+    assert_eq!(
+        results[0].source_constraint_component,
+        Some(NamedNode::new_unchecked(VALUE_NODE_LIMIT_COMPONENT))
+    );
+    assert_eq!(
+        results[0].code.as_ref().map(|code| code.as_str()),
+        Some("ValueNodeLimitViolation")
+    );
+    let issues = report.self_check().expect("report shapes should load");
+    assert!(
+        issues.is_empty(),
+        "capped report is malformed: {:?}",
+        issues
+    );
+}
+
+#[test]
+fn value_nodes_are_unlimited_by_default() {
+    let validator = build(None);
+    assert!(validator.validate().conforms());
+}
+
+#[test]
+fn value_nodes_checked_one_at_a_time_are_evaluated_in_chunks_past_the_cap() {
+    // 2500 values span several chunks; the malformed one sits in the last.
+    let mut data = String::from("@prefix ex: <http://example.org/> .\nex:Big a ex:Bag ;\n");
+    for i in 0..2500 {
+        data.push_str(&format!("    ex:item {} ;\n", i));
+    }
+    data.push_str("    ex:item \"oops\" .\n");
+    let shapes = r#"
+@prefix sh: <http://www.w3.org/ns/shacl#> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+@prefix ex: <http://example.org/> .
+
+ex:BagShape
+    a sh:NodeShape ;
+    sh:targetClass ex:Bag ;
+    sh:property [
+        sh:path ex:item ;
+        sh:datatype xsd:integer ;
+        sh:maxCount 2000 ;
+    ] .
+"#;
+    let validator = ValidatorBuilder::new()
+        .with_shapes_source(Source::InlineTurtle(shapes.to_string()))
+        .with_data_source(Source::InlineTurtle(data))
+        .with_max_value_nodes(3)
+        .build()
+        .expect("failed to load graphs");
+    let report = validator.validate();
+
+    let mut components: Vec<String> = report
+        .results()
+        .map(|result| {
+            result
+                .source_constraint_component
+                .as_ref()
+                .map(|component| component.as_str().to_string())
+                .unwrap_or_default()
+        })
+        .collect();
+    components.sort();
+    assert_eq!(
+        components,
+        vec![
+            "http://www.w3.org/ns/shacl#DatatypeConstraintComponent",
+            "http://www.w3.org/ns/shacl#MaxCountConstraintComponent",
+        ]
+    );
+    let datatype_result = report
+        .results()
+        .find(|result| result.value.is_some())
+        .expect("the malformed value should be reported");
+    assert_eq!(
+        datatype_result
+            .value
+            .as_ref()
+            .map(|value| value.to_string()),
+        Some("\"oops\"".to_string())
+    );
+    let max_count_result = report
+        .results()
+        .find(|result| result.value.is_none())
+        .expect("the value count should be reported");
+    assert!(
+        max_count_result.engine_message.contains("2501"),
+        "{}",
+        max_count_result.engine_message
+    );
+}